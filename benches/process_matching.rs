@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Benchmarks of the matching done on every process refresh, on the recorded desktop session
+//! in `tests/fixtures`. `just bench` runs them with the `bench` feature, and criterion writes
+//! its reports to `target/criterion`.
+//!
+//! All of it runs each second on the UI thread, so together it has to stay far below the
+//! refresh interval. The budgets, for the 2000-process snapshot and 300 desktop apps, are:
+//!
+//! | Benchmark                  | Budget |
+//! |----------------------------|--------|
+//! | `process_candidate_keys`   | 10 ms  |
+//! | `exec_candidate_keys`      | 5 ms   |
+//! | `desktop_map_construction` | 10 ms  |
+//! | `snapshot_aggregation`     | 20 ms  |
+//!
+//! They are not enforced, as timings vary between machines; a change that moves a
+//! benchmark towards its budget should say why in its description.
+
+use cosmic_task_monitor::benches::Session;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn process_matching(criterion: &mut Criterion) {
+    let session = Session::load();
+    criterion.bench_function("process_candidate_keys", |bencher| {
+        bencher.iter(|| session.candidate_keys());
+    });
+    criterion.bench_function("exec_candidate_keys", |bencher| {
+        bencher.iter(|| session.exec_keys());
+    });
+    criterion.bench_function("desktop_map_construction", |bencher| {
+        bencher.iter(|| session.desktop_map());
+    });
+    criterion.bench_function("snapshot_aggregation", |bencher| {
+        bencher.iter(|| session.aggregate());
+    });
+}
+
+criterion_group!(matching, process_matching);
+criterion_main!(matching);
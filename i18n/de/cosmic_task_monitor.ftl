@@ -10,6 +10,7 @@ page-id = Seite { $num }
 nav-apps = Apps
 nav-autostart = Autostart
 nav-performance = Leistung
+nav-settings = Einstellungen
 git-description = Git commit {$hash} on {$date}
 apps-title = Apps ({ $count })
 autostart-title = Autostart ({ $count })
@@ -100,3 +101,394 @@ process-action-open-path = Dateipfad öffnen
 process-action-copy-info = App-ID / PID kopieren
 process-none-selected = Keine Anwendung ausgewählt.
 steam-app-fallback = Steam App { $app_id }
+settings-title = Einstellungen
+settings-integrations = Integrationen
+show-tray-icon = Tray-Symbol anzeigen
+tray-tooltip = CPU: { $cpu } · RAM: { $ram }
+tray-open = Task Monitor öffnen
+tray-top-app = { $name } — { $cpu } · { $ram }
+tray-no-apps = Keine laufenden Apps.
+tray-quit-app = App beenden
+background-collector = Im Hintergrund weiter aufzeichnen
+remote-mode = Fernüberwachung
+remote-lan = Zugriff aus dem lokalen Netzwerk erlauben
+remote-lan-address = Adresse für andere Geräte
+bytes-exact = { $bytes } Bytes
+threads-exact = { $count } Threads
+group-by-app = Nach App
+group-by-user = Nach Benutzer
+group-by-category = Nach Kategorie
+group-user-fallback = Benutzer { $uid }
+category-game = Spiele
+category-development = Entwicklung
+category-graphics = Grafik
+category-multimedia = Multimedia
+category-office = Büro
+category-education = Bildung
+category-science = Wissenschaft
+category-network = Internet
+category-system = System
+category-utility = Zubehör
+category-other = Sonstige
+category-all = Alle
+baseline-mark = Ausgangswert setzen
+baseline-clear = Ausgangswert entfernen
+baseline-title = Änderungen seit Ausgangswert (vor { $elapsed })
+baseline-summary = ΔCPU { $cpu } · ΔRAM { $ram }
+baseline-new-app = { $name } (neu)
+baseline-exited = Beendet: { $names }
+threshold-alerts = Bei anhaltend hoher Auslastung warnen
+alert-history = Warnungsverlauf
+alert-history-empty = Bisher wurden keine Warnungen ausgelöst.
+alert-history-disabled = Schwellwert-Warnungen sind in den Einstellungen ausgeschaltet.
+alert-history-entry = { $name } — { $metric } { $value }
+alert-history-age = vor { $age }
+alert-history-clear = Leeren
+alert-history-export = Als CSV exportieren
+alert-export-success = Exportiert nach { $path }
+alert-export-failed = Export fehlgeschlagen: { $error }
+alert-notification-summary = { $name } belegt viel { $metric }
+alert-notification-body = { $metric } ist anhaltend hoch, aktuell { $value }.
+alert-metric-cpu = CPU
+alert-metric-ram = RAM
+audio-title = Audio
+pressure-title = Blockiert (10 s):
+pressure-cpu = CPU
+pressure-memory = Speicher
+pressure-io = E/A
+pressure-reading = { $resource } { $some } %
+pressure-reading-full = { $resource } { $some } % · vollständig { $full } %
+gamemode-title = GameMode
+settings-appearance = Darstellung
+density = Dichte
+density-compact = Kompakt
+density-comfortable = Angenehm
+density-spacious = Großzügig
+search-placeholder = Suchen, oder file: port: title:
+install-size = Installationsgröße: { $size }
+install-size-pending = wird berechnet …
+origin-distro = Paket
+origin-flatpak = Flatpak
+origin-snap = Snap
+origin-appimage = AppImage
+origin-steam = Steam
+origin-manual = Manuell
+process-action-update = Im Store aktualisieren
+custom-columns = Eigene Spalten
+custom-column-name = Spaltenname
+custom-column-expression = Ausdruck, z. B. rss / threads
+custom-column-add = Spalte hinzufügen
+custom-column-fields = Felder: { $fields }
+custom-column-name-missing = Bitte einen Spaltennamen eingeben.
+custom-column-invalid = Ungültiger Ausdruck: { $error }
+highlight-rules = Hervorhebungsregeln
+highlight-pattern = Regex, z. B. wine|proton
+highlight-badge = Abzeichen (optional)
+highlight-add = Regel hinzufügen
+highlight-target-name = Name
+highlight-target-cmdline = Befehlszeile
+highlight-color-purple = Lila
+highlight-color-red = Rot
+highlight-color-orange = Orange
+highlight-color-yellow = Gelb
+highlight-color-green = Grün
+highlight-color-blue = Blau
+highlight-hint = Groß- und Kleinschreibung wird ignoriert. Die erste passende Regel färbt die App ein.
+highlight-pattern-missing = Bitte ein Muster eingeben.
+highlight-pattern-invalid = Ungültiges Muster: { $error }
+config-load-failed = Einige Einstellungen konnten nicht gelesen werden und wurden auf die Standardwerte zurückgesetzt.
+profiles = Ansichtsprofile
+profiles-empty = Speichere die aktuelle Ansicht über Ansicht → Ansichtsprofile.
+profile-save = Aktuelle Ansicht speichern…
+profile-save-title = Ansichtsprofil speichern
+profile-save-description = Speichert Ansichtsmodus, Gruppierung, Sortierung, Kategoriefilter und eigene Spalten. Ein Profil mit gleichem Namen wird ersetzt.
+profile-name = Profilname
+profile-save-confirm = Speichern
+profile-apply = Anwenden
+split-view = Geteilte Ansicht
+split-view-empty = Wähle eine App aus, um ihre Details zu sehen.
+table-cpu-per-core = CPU(% je Kern)
+cpu-percent-mode = CPU-Prozent
+cpu-percent-machine = Anteil am ganzen System
+cpu-percent-core = Anteil an einem Kern
+cpu-smoothing = CPU-Glättung
+cpu-smoothing-off = Aus
+cpu-smoothing-light = Leicht (etwa 3 Sekunden)
+cpu-smoothing-strong = Stark (etwa 5 Sekunden)
+sort-damping = Nach CPU oder Speicher sortierte Zeilen umordnen
+sort-damping-off = Bei jeder Änderung
+sort-damping-light = Bei Änderungen über 1 %
+sort-damping-strong = Bei Änderungen über 5 %
+nav-apps-count = Apps ({ $count })
+nav-autostart-count = Autostart ({ $count })
+nav-cgroups = Kontrollgruppen
+cgroups-title = Kontrollgruppen
+cgroups-unavailable = Unter /sys/fs/cgroup wurde keine cgroup-v2-Hierarchie gefunden.
+table-encoder = Encoder
+show-encoder-column = Video-Encoder-Last pro App anzeigen (NVENC, VCN, Quick Sync)
+double-click-action = Doppelklick auf eine App
+double-click-details = Öffnet ihre Details
+double-click-focus = Fokussiert ihr Fenster
+double-click-open-location = Öffnet ihren Dateiort
+double-click-nothing = Bewirkt nichts
+tools = Werkzeuge
+free-port = Port freigeben…
+free-port-title = Port freigeben
+free-port-description = Findet die Apps, die auf einem TCP- oder UDP-Port lauschen, damit sie beendet werden können.
+free-port-placeholder = Port, z. B. 8080
+free-port-find = Suchen
+free-port-none = Keine App lauscht auf diesem Port.
+free-port-stop = Beenden
+protected-app = Vor versehentlichem Beenden schützen
+protected-stop-title = { $name } beenden?
+protected-kill-title = { $name } zwangsweise beenden?
+protected-restart-title = { $name } neu starten?
+protected-confirm-description = { $name } ist geschützt. Gib zur Bestätigung ihren Namen ein.
+startup-page = Beim Start öffnen
+process-states = Zustände: { $states }
+process-state-zombie = Ein Prozess wurde beendet, aber von seinem Elternprozess nicht eingesammelt (Zombie).
+process-state-uninterruptible = Ein Prozess hängt im nicht unterbrechbaren Schlaf, meist beim Warten auf eine Festplatte oder ein Netzwerk-Dateisystem.
+container-enter = Container betreten
+container-stop = Container stoppen
+table-swap = Swap
+memory-breakdown = Speicher
+memory-resident = Resident: { $size }
+memory-swap = Ausgelagert: { $size }
+memory-shm = Gemeinsamer Speicher (/dev/shm): { $size }
+memory-memfd = Speicherdateien (memfd): { $size }
+history-retention = Verlauf der Diagramme
+history-retention-minute = 1 Minute
+history-retention-five-minutes = 5 Minuten
+history-retention-fifteen-minutes = 15 Minuten
+history-retention-hour = 1 Stunde
+table-wakeups = Aufwachen/s
+show-wakeups-column = Aufwachvorgänge pro Sekunde pro App anzeigen
+table-fds = FDs
+show-fd-column = Offene Dateideskriptoren pro App anzeigen
+fd-near-limit = Ein Prozess hat { $count } von höchstens { $limit } Dateien geöffnet. Möglicherweise gibt er Dateideskriptoren nicht frei.
+process-action-open-terminal = Terminal im Arbeitsverzeichnis öffnen
+loaded-libraries = Geladene Bibliotheken
+loaded-libraries-count = { $shown } von { $total } Bibliotheken
+steam-shader-precache = Shader-Vorkompilierung
+steam-downloads = Wird heruntergeladen
+proton = Proton
+proton-unknown = Unbekannte Proton-Version
+proton-prefix-build = Präfix eingerichtet von { $build }
+proton-open-prefix = Präfix öffnen
+playtime = Spielzeit
+playtime-session = Diese Sitzung
+playtime-today = Heute
+playtime-total = Insgesamt
+playtime-duration = { $hours } Std. { $minutes } Min.
+nav-games = Spiele
+nav-games-count = Spiele ({ $count })
+games-playtime-today = Spielzeit heute: { $playtime }
+games-none-running = Es laufen keine Spiele.
+games-recently-played = Zuletzt gespielt
+games-total-playtime = { $playtime } gespielt
+games-launch = Starten
+sort-state-ascending = Aufsteigend sortiert
+sort-state-descending = Absteigend sortiert
+sort-state-none = Nicht sortiert, aktivieren, um nach dieser Spalte zu sortieren
+nav-events = Ereignisse
+events-clear = Leeren
+events-filter = Nach App filtern
+events-empty = Bisher wurden keine Apps gestartet oder beendet.
+events-started = Gestartet
+events-exited = Beendet
+cpu-architecture = Architektur
+cpu-cluster = { $kind } ({ $count })
+core-type-uniform = Kerne
+core-type-prime = Prime-Kerne
+core-type-performance = P-Kerne
+core-type-efficiency = E-Kerne
+core-type-short-prime = Prime
+core-type-short-performance = P
+core-type-short-efficiency = E
+cpu-core-typed = Kern { $index } · { $kind }
+cores-last-ran-on = Zuletzt auf Kernen ausgeführt
+process-action-profile = { $seconds } s lang profilieren
+profile-title = Profil
+profile-running = { $name } wird { $seconds } Sekunden lang profiliert…
+profile-source-perf = Häufigste Symbole, mit perf erfasst
+profile-source-stacks = Häufigste Kernel-Stacks, aus /proc erfasst (perf installieren für Symbole im Userspace)
+profile-perf-failed = perf konnte sich nicht anhängen: { $error }
+profile-no-samples = Es wurden keine Stichproben erfasst. Die App wurde möglicherweise beendet.
+process-action-strace = Systemaufrufe verfolgen
+process-action-strace-relaunch = Unter strace neu starten
+debug-press-enter = Zum Schließen Enter drücken.
+process-action-debugger = Debugger anhängen
+debugger-command = Debugger-Befehl
+ptrace-title = Anhängen erfordert Administratorrechte
+ptrace-sudo-description = Der Kernel erlaubt Programmen nur, ihre eigenen Kindprozesse zu verfolgen (ptrace_scope ist { $scope }). Das Werkzeug kann stattdessen über sudo laufen, das im Terminal nach deinem Passwort fragt.
+ptrace-blocked-description = Der Kernel erlaubt bis zum nächsten Neustart überhaupt kein Anhängen an laufende Prozesse (ptrace_scope ist 3).
+ptrace-sysctl-hint = Um das Anhängen an eigene Prozesse bis zum Neustart zu erlauben, führe aus: sudo sysctl kernel.yama.ptrace_scope=0
+ptrace-use-sudo = sudo verwenden
+filter = Filter
+filter-show-services = Hintergrunddienste anzeigen
+filter-show-helpers = Hilfsprogramme und Applets anzeigen
+filter-show-shells = Shells anzeigen
+filter-show-portals = Desktop-Portale anzeigen
+always-visible = Immer angezeigte Apps
+always-visible-placeholder = Prozessname oder App-ID, z. B. syncthing
+always-visible-add = Hinzufügen
+always-visible-hint = Aufgeführte Apps bleiben auf der Apps-Seite, auch wenn ihr Name nach einem Hintergrunddienst oder Hilfsprogramm aussieht.
+process-backend = Quelle der Prozessdaten
+process-backend-sysinfo = Standard
+process-backend-procfs = Direkt aus /proc (weniger Last)
+proc-access-hidepid = Prozesse anderer Benutzer sind ausgeblendet, weil /proc mit hidepid eingehängt ist. Systemdienste fehlen daher, und manche Apps werden nur über den Prozessnamen erkannt. Füge deinen Benutzer der Gruppe aus der Option gid= des /proc-Mounts hinzu, um sie zu sehen.
+proc-access-sandbox = Der Task-Monitor läuft in einer Flatpak-Sandbox und sieht nur die Prozesse darin. Erlaube ihm, Befehle auf dem Host auszuführen, z. B. mit flatpak override --user --talk-name=org.freedesktop.Flatpak com.github.exepta.cosmic-task-monitor, und starte ihn neu, um das ganze System zu überwachen.
+proc-access-unreadable = Die Befehlszeilen der meisten Prozesse können nicht gelesen werden. Apps werden daher nur über den Prozessnamen erkannt und manche womöglich falsch benannt. Ein gehärteter Kernel oder ein Sicherheitsmodul schränkt /proc ein; die Capability CAP_SYS_PTRACE für den Task-Monitor hebt das auf.
+settings-export = Einstellungen exportieren…
+settings-import = Einstellungen importieren…
+settings-transfer-title = Einstellungen
+settings-transfer-close = Schließen
+settings-export-success = Einstellungen nach { $path } exportiert.
+settings-export-failed = Die Einstellungen konnten nicht exportiert werden: { $error }
+settings-import-title = Einstellungen importieren
+settings-import-description = Der Import von { $file } ändert diese Einstellungen:
+settings-import-unchanged = Die Datei enthält dieselben Einstellungen wie dieser Task-Monitor.
+settings-import-skipped = Ausgelassen, weil unbekannt oder ungültig: { $keys }
+settings-import-confirm = Importieren
+settings-import-failed = Die Einstellungen konnten nicht importiert werden: { $error }
+settings-import-not-settings = Die Datei ist kein Einstellungsexport dieses Task-Monitors
+settings-import-newer = Die Datei wurde von einer neueren Version exportiert (Format { $version })
+snapshot-interval = Prozess-Schnappschüsse speichern
+snapshot-interval-off = Nie
+snapshot-interval-five-minutes = Alle 5 Minuten
+snapshot-interval-fifteen-minutes = Alle 15 Minuten
+snapshot-interval-hour = Jede Stunde
+process-restarts = Neustarts, seit der Task-Monitor läuft: { $count }
+process-restart-loop = In den letzten zwei Minuten { $count }-mal neu gestartet; womöglich stürzt es beim Start ab.
+helpers-title = Hilfsprozesse
+helpers-total = { $count } ausgeblendete Hilfsprozesse mit { $size }
+helpers-row = { $name } ({ $pid }): { $size }, CPU { $cpu }
+include-helpers = Ausgeblendete Hilfsprozesse in App-Summen einrechnen
+helpers-total-included = { $count } ausgeblendete Hilfsprozesse mit { $size }, in die Summen dieser App eingerechnet
+alert-metric-memory-growth = Speicherwachstum
+memory-growth-rate = Wächst um { $size } pro Stunde
+memory-growth-per-hour = { $size } pro Stunde
+memory-growth-notification-summary = { $name } belegt immer mehr Speicher
+memory-growth-notification-body = Wächst um { $rate }, ohne zu sinken. Möglicherweise hat es ein Speicherleck.
+memory-growth-alert = Warnen, wenn der Speicher einer App stetig wächst
+memory-growth-alert-off = Nie
+memory-growth-alert-100mb = Schneller als 100 MB pro Stunde
+memory-growth-alert-500mb = Schneller als 500 MB pro Stunde
+memory-growth-alert-1gb = Schneller als 1 GB pro Stunde
+quick-actions-title = Schnellaktionen
+quick-end-top-cpu = App mit der höchsten CPU-Last beenden
+quick-end-top-cpu-title = { $name } beenden?
+quick-end-top-cpu-body = { $name } belegt gerade die meiste CPU, { $cpu }. Ungespeicherte Arbeit darin kann verloren gehen.
+quick-end-top-cpu-none = Es läuft keine App, die beendet werden könnte.
+quick-drop-caches = Dateisystem-Caches leeren
+quick-drop-caches-done = Die Dateisystem-Caches wurden geleert, { $size } Seitencache wurden freigegeben.
+quick-drop-caches-failed = Die Dateisystem-Caches konnten nicht geleert werden: { $error }
+quick-drop-caches-denied = Administratorrechte wurden nicht erteilt
+load-title = Last:
+load-average = { $one } · { $five } · { $fifteen } (1, 5, 15 min)
+load-average-queued = Mehr Aufgaben wollen laufen, als die { $cores } Kerne aufnehmen können, daher warten einige.
+load-runnable = { $runnable } lauffähig von { $total } Aufgaben
+nav-info = Info
+info-system = System
+info-distribution = Distribution
+info-kernel = Kernel
+info-host-name = Rechnername
+info-uptime = Laufzeit
+info-boot-time = Gestartet
+info-hardware = Hardware
+info-cpu = Prozessor
+info-architecture = Architektur
+info-gpu = Grafik
+info-gpu-value = { $name } ({ $driver })
+info-memory = Arbeitsspeicher
+info-memory-modules = Speichermodule
+info-memory-modules-not-read = Mit dmidecode aus den Firmware-Tabellen lesen
+info-memory-modules-read = Lesen
+info-memory-modules-reading = Speichermodule werden gelesen …
+info-memory-modules-none = Die Firmware listet keine Speichermodule.
+info-memory-modules-denied = Das Lesen der Firmware-Tabellen erfordert Administratorrechte
+info-memory-modules-read-privileged = Als Administrator lesen
+info-memory-modules-failed = Die Speichermodule konnten nicht gelesen werden: { $error }
+info-dmidecode-missing = dmidecode ist nicht installiert
+global-shortcut = Mit Strg+Umschalt+Esc öffnen
+global-shortcut-taken = Strg+Umschalt+Esc führt in den COSMIC-Tastenkürzeln bereits { $action } aus
+global-shortcut-failed = Das Tastenkürzel konnte nicht geändert werden: { $error }
+source-retry = Erneut versuchen
+source-steam-library-unreadable = Steam-Bibliothek nicht lesbar: { $path } ({ $error }). Steam-Spiele werden eventuell ohne Namen angezeigt.
+source-steam-library-missing = Steam-Bibliothek { $path } ist nicht verfügbar. Ist das Laufwerk eingehängt?
+source-gpu-nvidia-smi-missing = GPU-Messwerte nicht verfügbar: nvidia-smi ist nicht installiert.
+source-gpu-nvidia-smi-failed = GPU-Messwerte nicht verfügbar: nvidia-smi erreicht den NVIDIA-Treiber nicht.
+source-gpu-permission = GPU-Messwerte nicht verfügbar: keine Berechtigung zum Lesen von { $path }.
+steam-app-loading = Steam App { $app_id } …
+reduce-motion = Bewegungen reduzieren
+compare-with = Vergleichen mit …
+comparison-picking = Wähle die App, mit der { $name } verglichen werden soll.
+comparison-title = Vergleich
+comparison-close = Vergleich schließen
+comparison-not-running = Läuft nicht
+comparison-average-cpu = CPU im Mittel
+comparison-peak-cpu = CPU-Spitze
+comparison-peak-ram = RAM-Spitze
+comparison-processes = Prozesse
+comparison-processes-value = { $processes } ({ $threads } Threads)
+launch-override = Startanpassungen
+launch-override-env = Umgebungsvariablen beim Neustart
+launch-override-env-placeholder = MANGOHUD=1 PROTON_LOG=1
+launch-override-args = Zusätzliche Argumente beim Neustart
+launch-override-args-placeholder = --fullscreen
+launch-override-save = Anpassungen speichern
+launch-override-invalid-env = „{ $word }“ ist kein SCHLÜSSEL=Wert-Paar.
+launch-override-launcher = Diese App startet über ihren Launcher neu, der diese Angaben nicht weitergibt. Nutze stattdessen die Startoptionen des Launchers.
+log-capture-restart = Neu starten und Ausgaben mitschreiben
+log-capture-show = Mitgeschriebenes Protokoll anzeigen
+log-capture-title = Mitgeschriebenes Protokoll
+log-capture-open-file = Protokolldatei öffnen
+log-capture-empty = Noch keine Ausgaben.
+log-capture-failed = Neustart fehlgeschlagen: { $error }
+log-capture-no-command = die App hat keine Befehlszeile, mit der sie gestartet werden kann
+journal-title = Journal-Meldungen
+journal-follow = Neuen Meldungen folgen
+journal-matches = Von { $processes } laufenden Prozessen und der Kennung „{ $identifier }“
+journal-loading = Journal wird gelesen…
+journal-empty = Keine Meldungen gefunden.
+journal-failed = journalctl ist fehlgeschlagen: { $error }
+screen-recording-start = Bildschirm aufnehmen
+screen-recording-stop = Aufnahme beenden
+screen-recording-running = Aufnahme läuft. Wähle das Fenster der App im Dialog zur Bildschirmfreigabe, falls er erscheint.
+screen-recording-saved = Gespeichert unter { $path }
+screen-recording-open = Aufnahme öffnen
+screen-recording-failed = Aufnahme fehlgeschlagen: { $error }
+screen-recording-not-saved = es wurde kein Video gespeichert (Aufnahmeprogramm { $status }); der Dialog wurde vielleicht abgebrochen
+browser-processes = Prozesse nach Typ
+browser-process-row = { $kind }: { $count } · { $size } · { $cpu }
+browser-process-main = Hauptprozess
+browser-process-gpu = GPU-Prozess
+browser-process-renderer = Renderer (Tabs und Erweiterungen)
+browser-process-network = Netzwerkdienst
+browser-process-utility = Andere Dienste
+browser-process-other = Zygoten und Hilfsprozesse
+memory-trim = Speicher freigeben
+memory-trim-proc-reclaim = Gibt die Seiten der App über /proc/<pid>/reclaim frei.
+memory-trim-cgroup-reclaim = Gibt Speicher der eigenen cgroup der App über memory.reclaim frei.
+memory-trim-jvm = Bittet die Java-Laufzeit mit jcmd, freien Heap-Speicher zurückzugeben.
+memory-trim-unsupported = Nicht verfügbar: Der Kernel hat kein /proc/<pid>/reclaim, die App hat keine eigene cgroup mit beschreibbarem memory.reclaim (ab Linux 5.19) und ist keine Java-App mit installiertem jcmd.
+memory-trim-freed = Der belegte Speicher ist um { $size } gesunken.
+memory-trim-failed = Freigeben fehlgeschlagen: { $error }
+process-action-pause = Anhalten
+process-action-resume = Fortsetzen
+pause-method-freezer = Über die cgroup der App eingefroren.
+pause-method-signals = Mit SIGSTOP angehalten. Seitdem gestartete Prozesse laufen weiter.
+pause-unavailable = Der Task-Monitor und die Desktop-Shell können nicht pausiert werden.
+automation-hooks = Automatisierungen
+automation-trigger-start = Startet
+automation-trigger-cpu = CPU über
+automation-trigger-ram = RAM über
+automation-threshold = Prozent
+automation-command = Befehl, z. B. renice 10 -p {"{"}pid{"}"}
+automation-add = Hinzufügen
+automation-hint = Führt den Befehl mit sh aus, wenn eine passende App startet oder den Schwellwert erreicht. {"{"}app_id{"}"}, {"{"}pid{"}"} und {"{"}name{"}"} werden ersetzt. Jede Automatisierung läuft höchstens einmal pro Minute.
+automation-command-missing = Gib einen Befehl ein.
+automation-threshold-invalid = Gib einen Prozentwert von 1 bis 100 ein.
+automation-on-start = Beim Start
+automation-on-cpu = CPU ≥ { $percent }%
+automation-on-ram = RAM ≥ { $percent }%
@@ -10,6 +10,7 @@ page-id = Page { $num }
 nav-apps = Apps
 nav-autostart = Autostart
 nav-performance = Performance
+nav-settings = Settings
 git-description = Git commit {$hash} on {$date}
 apps-title = Apps ({ $count })
 autostart-title = Autostart ({ $count })
@@ -100,3 +101,394 @@ process-action-open-path = Open file path
 process-action-copy-info = Copy app ID / PID
 process-none-selected = No application selected.
 steam-app-fallback = Steam App { $app_id }
+settings-title = Settings
+settings-integrations = Integrations
+show-tray-icon = Show tray icon
+tray-tooltip = CPU: { $cpu } · RAM: { $ram }
+tray-open = Open Task Monitor
+tray-top-app = { $name } — { $cpu } · { $ram }
+tray-no-apps = No running apps.
+tray-quit-app = Quit app
+background-collector = Keep collecting in background
+remote-mode = Remote monitoring
+remote-lan = Allow access from the local network
+remote-lan-address = Address for other devices
+bytes-exact = { $bytes } bytes
+threads-exact = { $count } threads
+group-by-app = By app
+group-by-user = By user
+group-by-category = By category
+group-user-fallback = User { $uid }
+category-game = Games
+category-development = Development
+category-graphics = Graphics
+category-multimedia = Multimedia
+category-office = Office
+category-education = Education
+category-science = Science
+category-network = Internet
+category-system = System
+category-utility = Utilities
+category-other = Other
+category-all = All
+baseline-mark = Mark baseline
+baseline-clear = Clear baseline
+baseline-title = Changes since baseline ({ $elapsed } ago)
+baseline-summary = ΔCPU { $cpu } · ΔRAM { $ram }
+baseline-new-app = { $name } (new)
+baseline-exited = Exited: { $names }
+threshold-alerts = Alert on sustained high usage
+alert-history = Alert history
+alert-history-empty = No alerts fired yet.
+alert-history-disabled = Threshold alerts are turned off in Settings.
+alert-history-entry = { $name } — { $metric } { $value }
+alert-history-age = { $age } ago
+alert-history-clear = Clear
+alert-history-export = Export CSV
+alert-export-success = Exported to { $path }
+alert-export-failed = Export failed: { $error }
+alert-notification-summary = { $name } is using a lot of { $metric }
+alert-notification-body = { $metric } has stayed high, currently at { $value }.
+alert-metric-cpu = CPU
+alert-metric-ram = RAM
+audio-title = Audio
+pressure-title = Stalled (10 s):
+pressure-cpu = CPU
+pressure-memory = Memory
+pressure-io = I/O
+pressure-reading = { $resource } { $some }%
+pressure-reading-full = { $resource } { $some }% · full { $full }%
+gamemode-title = GameMode
+settings-appearance = Appearance
+density = Density
+density-compact = Compact
+density-comfortable = Comfortable
+density-spacious = Spacious
+search-placeholder = Search, or file: port: title:
+install-size = Install size: { $size }
+install-size-pending = calculating…
+origin-distro = Package
+origin-flatpak = Flatpak
+origin-snap = Snap
+origin-appimage = AppImage
+origin-steam = Steam
+origin-manual = Manual
+process-action-update = Update via store
+custom-columns = Custom columns
+custom-column-name = Column name
+custom-column-expression = Expression, e.g. rss / threads
+custom-column-add = Add column
+custom-column-fields = Fields: { $fields }
+custom-column-name-missing = Enter a column name.
+custom-column-invalid = Invalid expression: { $error }
+highlight-rules = Highlight rules
+highlight-pattern = Regex, e.g. wine|proton
+highlight-badge = Badge (optional)
+highlight-add = Add rule
+highlight-target-name = Name
+highlight-target-cmdline = Command line
+highlight-color-purple = Purple
+highlight-color-red = Red
+highlight-color-orange = Orange
+highlight-color-yellow = Yellow
+highlight-color-green = Green
+highlight-color-blue = Blue
+highlight-hint = Patterns ignore case. The first matching rule tints the app.
+highlight-pattern-missing = Enter a pattern.
+highlight-pattern-invalid = Invalid pattern: { $error }
+config-load-failed = Some settings could not be read and were reset to their defaults.
+profiles = View profiles
+profiles-empty = Save the current layout from View → View profiles.
+profile-save = Save current view…
+profile-save-title = Save view profile
+profile-save-description = Saves the view mode, grouping, sorting, category filter, and custom columns. A profile with the same name is replaced.
+profile-name = Profile name
+profile-save-confirm = Save
+profile-apply = Apply
+split-view = Split view
+split-view-empty = Select an app to see its details.
+table-cpu-per-core = CPU(% of core)
+cpu-percent-mode = CPU percentage
+cpu-percent-machine = Share of the whole machine
+cpu-percent-core = Share of one core
+cpu-smoothing = CPU smoothing
+cpu-smoothing-off = Off
+cpu-smoothing-light = Light (about 3 seconds)
+cpu-smoothing-strong = Strong (about 5 seconds)
+sort-damping = Reorder rows sorted by CPU or memory
+sort-damping-off = On every change
+sort-damping-light = On changes over 1%
+sort-damping-strong = On changes over 5%
+nav-apps-count = Apps ({ $count })
+nav-autostart-count = Autostart ({ $count })
+nav-cgroups = Control groups
+cgroups-title = Control groups
+cgroups-unavailable = No cgroup v2 hierarchy found under /sys/fs/cgroup.
+table-encoder = Encoder
+show-encoder-column = Show video encoder load per app (NVENC, VCN, Quick Sync)
+double-click-action = Double-clicking an app
+double-click-details = Opens its details
+double-click-focus = Focuses its window
+double-click-open-location = Opens its file location
+double-click-nothing = Does nothing
+tools = Tools
+free-port = Free a port…
+free-port-title = Free a port
+free-port-description = Finds the apps listening on a TCP or UDP port so they can be stopped.
+free-port-placeholder = Port, e.g. 8080
+free-port-find = Find
+free-port-none = No app is listening on this port.
+free-port-stop = Stop
+protected-app = Protect from accidental stops
+protected-stop-title = Stop { $name }?
+protected-kill-title = Force kill { $name }?
+protected-restart-title = Restart { $name }?
+protected-confirm-description = { $name } is protected. Type its name to confirm.
+startup-page = Open on
+process-states = States: { $states }
+process-state-zombie = A process has exited but was not reaped by its parent (zombie).
+process-state-uninterruptible = A process is stuck in uninterruptible sleep, usually waiting on a disk or network file system.
+container-enter = Enter container
+container-stop = Stop container
+table-swap = Swap
+memory-breakdown = Memory
+memory-resident = Resident: { $size }
+memory-swap = Swapped out: { $size }
+memory-shm = Shared memory (/dev/shm): { $size }
+memory-memfd = Memory files (memfd): { $size }
+history-retention = Graph history
+history-retention-minute = 1 minute
+history-retention-five-minutes = 5 minutes
+history-retention-fifteen-minutes = 15 minutes
+history-retention-hour = 1 hour
+table-wakeups = Wakeups/s
+show-wakeups-column = Show wakeups per second per app
+table-fds = FDs
+show-fd-column = Show open file descriptors per app
+fd-near-limit = A process has { $count } of at most { $limit } files open. It may be leaking file descriptors.
+process-action-open-terminal = Open terminal at working directory
+loaded-libraries = Loaded libraries
+loaded-libraries-count = { $shown } of { $total } libraries
+steam-shader-precache = Shader pre-caching
+steam-downloads = Downloading
+proton = Proton
+proton-unknown = Unknown Proton version
+proton-prefix-build = Prefix set up by { $build }
+proton-open-prefix = Open prefix
+playtime = Playtime
+playtime-session = This session
+playtime-today = Today
+playtime-total = Total
+playtime-duration = { $hours } h { $minutes } min
+nav-games = Games
+nav-games-count = Games ({ $count })
+games-playtime-today = Playtime today: { $playtime }
+games-none-running = No games are running.
+games-recently-played = Recently played
+games-total-playtime = { $playtime } played
+games-launch = Launch
+sort-state-ascending = Sorted ascending
+sort-state-descending = Sorted descending
+sort-state-none = Not sorted, activate to sort by this column
+nav-events = Events
+events-clear = Clear
+events-filter = Filter by app
+events-empty = No apps have started or exited yet.
+events-started = Started
+events-exited = Exited
+cpu-architecture = Architecture
+cpu-cluster = { $kind } ({ $count })
+core-type-uniform = Cores
+core-type-prime = Prime cores
+core-type-performance = P-cores
+core-type-efficiency = E-cores
+core-type-short-prime = Prime
+core-type-short-performance = P
+core-type-short-efficiency = E
+cpu-core-typed = Core { $index } · { $kind }
+cores-last-ran-on = Last ran on cores
+process-action-profile = Profile for { $seconds } s
+profile-title = Profile
+profile-running = Profiling { $name } for { $seconds } seconds…
+profile-source-perf = Hottest symbols sampled with perf
+profile-source-stacks = Hottest kernel stacks sampled from /proc (install perf for user-space symbols)
+profile-perf-failed = perf could not attach: { $error }
+profile-no-samples = No samples were recorded. The app may have exited.
+process-action-strace = Trace system calls
+process-action-strace-relaunch = Relaunch under strace
+debug-press-enter = Press Enter to close.
+process-action-debugger = Attach debugger
+debugger-command = Debugger command
+ptrace-title = Attaching needs administrator rights
+ptrace-sudo-description = The kernel only lets programs trace their own child processes (ptrace_scope is { $scope }). The tool can run through sudo instead, which asks for your password in the terminal.
+ptrace-blocked-description = The kernel does not allow attaching to running processes at all (ptrace_scope is 3) until the next reboot.
+ptrace-sysctl-hint = To allow attaching to your own processes until reboot, run: sudo sysctl kernel.yama.ptrace_scope=0
+ptrace-use-sudo = Use sudo
+filter = Filter
+filter-show-services = Show background services
+filter-show-helpers = Show helpers and applets
+filter-show-shells = Show shells
+filter-show-portals = Show desktop portals
+always-visible = Always shown apps
+always-visible-placeholder = Process name or app id, e.g. syncthing
+always-visible-add = Add
+always-visible-hint = Listed apps stay on the Apps page even when their name looks like a background service or helper.
+process-backend = Process data source
+process-backend-sysinfo = Standard
+process-backend-procfs = Direct /proc (lower overhead)
+proc-access-hidepid = Other users' processes are hidden because /proc is mounted with hidepid, so system services are missing and some apps are matched by process name only. Add your user to the group named by the gid= option of the /proc mount to see them.
+proc-access-sandbox = The task monitor runs in a Flatpak sandbox and only sees the processes inside it. Allow it to run commands on the host, e.g. with flatpak override --user --talk-name=org.freedesktop.Flatpak com.github.exepta.cosmic-task-monitor, and restart it to monitor the whole system.
+proc-access-unreadable = The command lines of most processes cannot be read, so apps are matched by process name only and some may be named wrongly. A hardened kernel or security module restricts /proc; granting the task monitor the CAP_SYS_PTRACE capability lifts this.
+settings-export = Export settings…
+settings-import = Import settings…
+settings-transfer-title = Settings
+settings-transfer-close = Close
+settings-export-success = Settings exported to { $path }.
+settings-export-failed = Could not export the settings: { $error }
+settings-import-title = Import settings
+settings-import-description = Importing { $file } changes these settings:
+settings-import-unchanged = The file has the same settings as this task monitor.
+settings-import-skipped = Left out because they are unknown or invalid: { $keys }
+settings-import-confirm = Import
+settings-import-failed = Could not import the settings: { $error }
+settings-import-not-settings = the file is not a settings export of this task monitor
+settings-import-newer = the file was exported by a newer version (format { $version })
+snapshot-interval = Save process snapshots to disk
+snapshot-interval-off = Never
+snapshot-interval-five-minutes = Every 5 minutes
+snapshot-interval-fifteen-minutes = Every 15 minutes
+snapshot-interval-hour = Every hour
+process-restarts = Restarts since the task monitor started: { $count }
+process-restart-loop = Restarted { $count } times in the last two minutes; it may be crashing on start.
+helpers-title = Helpers
+helpers-total = { $count } hidden helper processes using { $size }
+helpers-row = { $name } ({ $pid }): { $size }, CPU { $cpu }
+include-helpers = Count hidden helpers in app totals
+helpers-total-included = { $count } hidden helper processes using { $size }, counted in this app's totals
+alert-metric-memory-growth = Memory growth
+memory-growth-rate = Growing by { $size } per hour
+memory-growth-per-hour = { $size } per hour
+memory-growth-notification-summary = { $name } keeps using more memory
+memory-growth-notification-body = Growing by { $rate } without dropping. It may be leaking memory.
+memory-growth-alert = Alert when an app's memory keeps growing
+memory-growth-alert-off = Never
+memory-growth-alert-100mb = Faster than 100 MB per hour
+memory-growth-alert-500mb = Faster than 500 MB per hour
+memory-growth-alert-1gb = Faster than 1 GB per hour
+quick-actions-title = Quick actions
+quick-end-top-cpu = End the app using the most CPU
+quick-end-top-cpu-title = End { $name }?
+quick-end-top-cpu-body = { $name } uses the most CPU right now, { $cpu }. Unsaved work in it may be lost.
+quick-end-top-cpu-none = No app is running that could be ended.
+quick-drop-caches = Drop filesystem caches
+quick-drop-caches-done = Dropped the filesystem caches, freeing { $size } of page cache.
+quick-drop-caches-failed = Could not drop the filesystem caches: { $error }
+quick-drop-caches-denied = administrator rights were not granted
+load-title = Load:
+load-average = { $one } · { $five } · { $fifteen } (1, 5, 15 min)
+load-average-queued = More tasks want to run than the { $cores } cores can take, so some are waiting.
+load-runnable = { $runnable } runnable of { $total } tasks
+nav-info = Info
+info-system = System
+info-distribution = Distribution
+info-kernel = Kernel
+info-host-name = Host name
+info-uptime = Uptime
+info-boot-time = Booted
+info-hardware = Hardware
+info-cpu = Processor
+info-architecture = Architecture
+info-gpu = Graphics
+info-gpu-value = { $name } ({ $driver })
+info-memory = Memory
+info-memory-modules = Memory modules
+info-memory-modules-not-read = Read from the firmware tables with dmidecode
+info-memory-modules-read = Read
+info-memory-modules-reading = Reading memory modules…
+info-memory-modules-none = The firmware lists no memory modules.
+info-memory-modules-denied = Reading the firmware tables needs administrator rights
+info-memory-modules-read-privileged = Read as administrator
+info-memory-modules-failed = Could not read the memory modules: { $error }
+info-dmidecode-missing = dmidecode is not installed
+global-shortcut = Open with Ctrl+Shift+Esc
+global-shortcut-taken = Ctrl+Shift+Esc already runs { $action } in the COSMIC keyboard shortcuts
+global-shortcut-failed = Could not change the shortcut: { $error }
+source-retry = Retry
+source-steam-library-unreadable = Steam library unreadable: { $path } ({ $error }). Steam games may show without names.
+source-steam-library-missing = Steam library { $path } is not available. Is its drive mounted?
+source-gpu-nvidia-smi-missing = GPU metrics unavailable: nvidia-smi is not installed.
+source-gpu-nvidia-smi-failed = GPU metrics unavailable: nvidia-smi cannot talk to the NVIDIA driver.
+source-gpu-permission = GPU metrics unavailable: missing permission to read { $path }.
+steam-app-loading = Steam App { $app_id }…
+reduce-motion = Reduce motion
+compare-with = Compare with…
+comparison-picking = Pick the app to compare { $name } with.
+comparison-title = Comparison
+comparison-close = Close comparison
+comparison-not-running = Not running
+comparison-average-cpu = Average CPU
+comparison-peak-cpu = Peak CPU
+comparison-peak-ram = Peak RAM
+comparison-processes = Processes
+comparison-processes-value = { $processes } ({ $threads } threads)
+launch-override = Launch overrides
+launch-override-env = Environment variables for Restart
+launch-override-env-placeholder = MANGOHUD=1 PROTON_LOG=1
+launch-override-args = Extra arguments for Restart
+launch-override-args-placeholder = --fullscreen
+launch-override-save = Save overrides
+launch-override-invalid-env = “{ $word }” is not a KEY=value pair.
+launch-override-launcher = This app restarts through its launcher, which does not pass these on. Use the launcher's own launch options instead.
+log-capture-restart = Restart and capture logs
+log-capture-show = Show captured log
+log-capture-title = Captured log
+log-capture-open-file = Open log file
+log-capture-empty = Nothing written yet.
+log-capture-failed = Restart failed: { $error }
+log-capture-no-command = the app has no command line to launch it with
+journal-title = Journal messages
+journal-follow = Follow new messages
+journal-matches = From { $processes } running processes and the identifier “{ $identifier }”
+journal-loading = Reading the journal…
+journal-empty = No messages found.
+journal-failed = journalctl failed: { $error }
+screen-recording-start = Record screen
+screen-recording-stop = Stop recording
+screen-recording-running = Recording. Pick the app's window in the screen sharing dialog if it asks.
+screen-recording-saved = Saved to { $path }
+screen-recording-open = Open recording
+screen-recording-failed = Recording failed: { $error }
+screen-recording-not-saved = no video was saved (recorder { $status }); the dialog may have been cancelled
+browser-processes = Processes by type
+browser-process-row = { $kind }: { $count } · { $size } · { $cpu }
+browser-process-main = Main process
+browser-process-gpu = GPU process
+browser-process-renderer = Renderers (tabs and extensions)
+browser-process-network = Network service
+browser-process-utility = Other services
+browser-process-other = Zygotes and helpers
+memory-trim = Trim memory
+memory-trim-proc-reclaim = Reclaims the app's pages through /proc/<pid>/reclaim.
+memory-trim-cgroup-reclaim = Reclaims memory of the app's own cgroup through memory.reclaim.
+memory-trim-jvm = Asks the Java runtime to return freed heap memory with jcmd.
+memory-trim-unsupported = Not available: the kernel has no /proc/<pid>/reclaim, the app has no cgroup of its own with a writable memory.reclaim (Linux 5.19 or later), and it is not a Java app with jcmd installed.
+memory-trim-freed = Resident memory went down by { $size }.
+memory-trim-failed = Trimming failed: { $error }
+process-action-pause = Pause
+process-action-resume = Resume
+pause-method-freezer = Frozen through the app's cgroup.
+pause-method-signals = Stopped with SIGSTOP. Processes started since are not paused.
+pause-unavailable = The task monitor and the desktop shell cannot be paused.
+automation-hooks = Automation hooks
+automation-trigger-start = Starts
+automation-trigger-cpu = CPU above
+automation-trigger-ram = RAM above
+automation-threshold = Percent
+automation-command = Command, e.g. renice 10 -p {"{"}pid{"}"}
+automation-add = Add hook
+automation-hint = Runs the command with sh when a matching app starts or reaches the threshold. {"{"}app_id{"}"}, {"{"}pid{"}"}, and {"{"}name{"}"} are replaced. Each hook runs at most once a minute.
+automation-command-missing = Enter a command.
+automation-threshold-invalid = Enter a percentage from 1 to 100.
+automation-on-start = On start
+automation-on-cpu = CPU ≥ { $percent }%
+automation-on-ram = RAM ≥ { $percent }%
@@ -12,13 +12,15 @@ BIN_SRC="${CARGO_TARGET_DIR}/release/${APP_NAME}"
 DESKTOP_SRC="resources/app.desktop"
 APPDATA_SRC="resources/app.metainfo.xml"
 ICON_SRC="resources/icons/hicolor/scalable/apps/${APP_ID}.svg"
+SERVICE_SRC="resources/collector.service"
 
 BIN_DST="${BASE_DIR}/bin/${APP_NAME}"
 DESKTOP_DST="${BASE_DIR}/share/applications/${APP_ID}.desktop"
 APPDATA_DST="${BASE_DIR}/share/appdata/${APP_ID}.metainfo.xml"
 ICON_DST="${BASE_DIR}/share/icons/hicolor/scalable/apps/${APP_ID}.svg"
+SERVICE_DST="${BASE_DIR}/lib/systemd/user/${APP_NAME}-collector.service"
 
-for src in "${BIN_SRC}" "${DESKTOP_SRC}" "${APPDATA_SRC}" "${ICON_SRC}"; do
+for src in "${BIN_SRC}" "${DESKTOP_SRC}" "${APPDATA_SRC}" "${ICON_SRC}" "${SERVICE_SRC}"; do
   if [ ! -f "${src}" ]; then
     echo "Missing required file: ${src}" >&2
     exit 1
@@ -36,7 +38,7 @@ first_existing_parent() {
 }
 
 needs_privilege=0
-for dst in "${BIN_DST}" "${DESKTOP_DST}" "${APPDATA_DST}" "${ICON_DST}"; do
+for dst in "${BIN_DST}" "${DESKTOP_DST}" "${APPDATA_DST}" "${ICON_DST}" "${SERVICE_DST}"; do
   parent="$(first_existing_parent "${dst}")"
   if [ ! -w "${parent}" ]; then
     needs_privilege=1
@@ -58,5 +60,6 @@ fi
 "${INSTALL_CMD[@]}" -Dm0644 "${DESKTOP_SRC}" "${DESKTOP_DST}"
 "${INSTALL_CMD[@]}" -Dm0644 "${APPDATA_SRC}" "${APPDATA_DST}"
 "${INSTALL_CMD[@]}" -Dm0644 "${ICON_SRC}" "${ICON_DST}"
+"${INSTALL_CMD[@]}" -Dm0644 "${SERVICE_SRC}" "${SERVICE_DST}"
 
 echo "Installed ${APP_NAME} into ${BASE_DIR}."
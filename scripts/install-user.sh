@@ -8,6 +8,7 @@ BIN_DIR="${ROOT}/bin"
 APPS_DIR="${ROOT}/share/applications"
 APPDATA_DIR="${ROOT}/share/appdata"
 ICON_DIR="${ROOT}/share/icons/hicolor/scalable/apps"
+SYSTEMD_USER_DIR="${XDG_CONFIG_HOME:-${HOME}/.config}/systemd/user"
 
 cargo build --release
 
@@ -18,9 +19,17 @@ install -Dm0644 \
   "${ICON_DIR}/${APP_ID}.svg"
 
 mkdir -p "${APPS_DIR}"
-sed "s|^Exec=.*|Exec=${BIN_DIR}/${APP_NAME} %F|" "resources/app.desktop" \
+sed "s|^Exec=.*|Exec=${BIN_DIR}/${APP_NAME} %u|" "resources/app.desktop" \
   > "${APPS_DIR}/${APP_ID}.desktop"
 
+mkdir -p "${SYSTEMD_USER_DIR}"
+sed "s|^ExecStart=.*|ExecStart=${BIN_DIR}/${APP_NAME} --collector|" "resources/collector.service" \
+  > "${SYSTEMD_USER_DIR}/${APP_NAME}-collector.service"
+
+if command -v systemctl >/dev/null 2>&1; then
+  systemctl --user daemon-reload >/dev/null 2>&1 || true
+fi
+
 if command -v update-desktop-database >/dev/null 2>&1; then
   update-desktop-database "${APPS_DIR}" >/dev/null 2>&1 || true
 fi
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::config::Config;
+use crate::config::{AppsViewMode, Config, GroupBy, Page, SortColumn};
 use crate::fl;
 use cosmic::app::context_drawer;
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
@@ -11,6 +11,7 @@ use cosmic::theme;
 use cosmic::widget::{self, about::About, icon, menu, nav_bar};
 use cosmic::{iced_futures, prelude::*};
 use futures_util::SinkExt;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::env;
@@ -20,9 +21,11 @@ use std::io::Write;
 use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
-use sysinfo::{Disks, Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind};
+use sysinfo::{
+    Disks, Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind, Users,
+};
 
 const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
 const APP_ICON: &[u8] = include_bytes!(
@@ -37,11 +40,165 @@ const GPU_ACCENT: Color = Color::from_rgb(231.0 / 255.0, 141.0 / 255.0, 56.0 / 2
 const NETWORK_ACCENT: Color = Color::from_rgb(81.0 / 255.0, 150.0 / 255.0, 214.0 / 255.0);
 const DISK_ACCENT: Color = Color::from_rgb(197.0 / 255.0, 196.0 / 255.0, 67.0 / 255.0);
 
-mod apps;
+mod alerts;
+mod audio;
+mod automation;
 mod autostart;
+mod background;
+mod baseline;
+mod browser_processes;
+mod categories;
+mod cgroups;
+mod charts;
+mod collector;
+mod comparison;
+mod cpu_topology;
+mod data_sources;
+mod debug_tools;
+mod deep_link;
+mod descriptors;
+mod encoder;
+mod events;
+mod expression;
+mod fixture;
+mod free_port;
+mod freezer;
+mod gamemode;
+mod games;
+mod global_shortcut;
+mod helpers;
+mod heroic;
+mod highlight;
+mod history;
+mod icon_cache;
+mod icon_resolver;
+mod install_size;
+mod journal;
+mod launch_overrides;
+mod libraries;
+mod load_average;
+mod log_capture;
+mod memory;
+mod memory_growth;
+mod memory_trim;
+mod namespaces;
+mod naming;
+mod origin;
+mod pages;
+mod playtime;
+mod pressure;
+mod privacy;
+mod proc_access;
+mod proc_events;
 mod process;
+mod procfs_source;
+mod profiler;
+mod profiles;
+mod protected;
+mod proton;
+mod quick_actions;
+mod quit;
+mod remote;
+mod restarts;
+mod row_click;
+mod scheduling;
+mod screen_recording;
+mod search;
+mod session;
+mod settings_transfer;
+mod snapshot;
+mod source;
+mod split_view;
+mod states;
 mod steam_helper;
-mod system_stats;
+mod steam_maintenance;
+mod steam_resolver;
+mod system_info;
+mod terminal;
+mod thumbnail;
+mod tray;
+mod units;
+mod updates;
+mod usage_bar;
+mod value_flash;
+mod vdf;
+mod wakeups;
+
+use alerts::AlertMonitor;
+use audio::{AudioMonitor, AudioStream};
+use automation::HookRunner;
+use background::BackgroundKind;
+use baseline::ProcessBaseline;
+use browser_processes::BrowserProcesses;
+use cgroups::CgroupTree;
+use charts::{Chart, ChartKind, ChartScale};
+pub use collector::run_collector;
+use comparison::AppComparison;
+use cpu_topology::CpuTopology;
+use data_sources::{DataSource, DataSourceHealth};
+use debug_tools::{AttachTool, PtracePrompt};
+pub use deep_link::Flags;
+use deep_link::PendingSelection;
+use descriptors::FdUsage;
+use encoder::{EngineMonitor, EngineSample};
+use events::AppEventLog;
+use expression::Expression;
+pub use fixture::record_fixture;
+use free_port::FreePortDialog;
+use freezer::PausedApps;
+use gamemode::GameModeMonitor;
+use games::GameLibrary;
+use global_shortcut::GlobalShortcut;
+use helpers::SpawnedHelper;
+use heroic::HeroicResolver;
+use highlight::Highlighter;
+use icon_cache::IconCache;
+use icon_resolver::IconResolver;
+use install_size::InstallSize;
+use journal::{JournalChunk, JournalViewer};
+use launch_overrides::{LaunchOverrideEditor, LaunchSettings};
+use libraries::LoadedLibraries;
+use load_average::LoadStats;
+use log_capture::CapturedLogs;
+use memory::SharedMemory;
+use memory_growth::MemoryGrowth;
+use memory_trim::MemoryTrim;
+use namespaces::NamespaceResolver;
+use origin::{AppOrigin, OriginResolver};
+use pages::{
+    CgroupsMessage, CgroupsPage, EventsMessage, EventsPage, GamesMessage, InfoMessage, InfoPage,
+    ProcessesMessage, ProcessesPage, ResourcesMessage, ResourcesPage, SettingsMessage,
+    SettingsPage,
+};
+use playtime::PlaytimeTracker;
+use pressure::PressureStats;
+use privacy::{PrivacyMonitor, PrivacyUsage};
+use proc_access::ProcAccess;
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub use process::benches;
+use profiler::{Profile, ProfileReport};
+use protected::{ProtectedStop, StopKind};
+use quick_actions::QuickAction;
+use quit::{PendingRestart, QuitStage, Relaunch};
+use restarts::RestartMonitor;
+use row_click::RowClick;
+use scheduling::ThreadPlacement;
+use screen_recording::ScreenRecorder;
+use search::{SearchLookup, SearchLookups, SearchQuery};
+use session::{SessionChange, SessionState};
+use settings_transfer::SettingsTransfer;
+use source::{ProcessSample, SysinfoSource, SystemSource};
+use split_view::SplitView;
+use states::StateMonitor;
+use steam_maintenance::SteamMaintenance;
+use steam_resolver::SteamResolver;
+use system_info::{MemoryModules, SystemSummary};
+use thumbnail::WindowThumbnail;
+use updates::UpdateChecker;
+use usage_bar::usage_bar_class;
+use value_flash::{FlashColumn, ValueFlashes};
+use wakeups::WakeupMonitor;
 
 fn table_cell_style(theme: &Theme) -> widget::container::Style {
     widget::container::Style {
@@ -93,6 +250,34 @@ fn table_row_button_style() -> theme::Button {
     }
 }
 
+/// Sortable table headers: drawn like the other cells, with an accent border while focused
+/// or hovered so keyboard focus is visible.
+fn table_header_button_style() -> theme::Button {
+    fn style(theme: &Theme, highlighted: bool) -> widget::button::Style {
+        let mut style = widget::button::Style::new();
+        if highlighted {
+            style.background = Some(Background::Color(
+                theme.current_container().component.hover.into(),
+            ));
+        }
+        style.border_width = 1.0;
+        style.border_color = if highlighted {
+            theme.cosmic().accent_color().into()
+        } else {
+            theme.cosmic().bg_divider().into()
+        };
+        style.border_radius = 0.0.into();
+        style
+    }
+
+    theme::Button::Custom {
+        active: Box::new(|focused, theme| style(theme, focused)),
+        hovered: Box::new(|_focused, theme| style(theme, true)),
+        pressed: Box::new(|_focused, theme| style(theme, true)),
+        disabled: Box::new(|theme| style(theme, false)),
+    }
+}
+
 fn section_toggle_button_style() -> theme::Button {
     theme::Button::Custom {
         active: Box::new(|_focused, _theme| {
@@ -140,9 +325,50 @@ struct ProcessEntry {
     is_background: bool,
     icon_handle: Option<icon::Handle>,
     pid: u32,
+    pids: Vec<u32>,
+    exe_path: Option<PathBuf>,
+    category: Option<&'static str>,
     cpu_percent: f32,
     rss_bytes: u64,
+    swap_bytes: u64,
     threads: u32,
+    /// Video encoder load, while the Encoder column is shown and the app encodes.
+    encoder_percent: Option<f32>,
+    /// Wakeups per second summed over the group, while the Wakeups column is shown.
+    wakeups_per_sec: Option<f32>,
+    /// Open file descriptors, while the FD column is shown.
+    fds: Option<FdUsage>,
+    /// Members per state code, e.g. `('S', 12)`.
+    states: Vec<(char, u32)>,
+    /// Values of the user-defined columns, in [`Config::custom_columns`] order.
+    custom_values: Vec<Option<f64>>,
+}
+
+#[cfg(test)]
+impl ProcessEntry {
+    /// An app named after its id with one idle process, for tests to fill in what they check.
+    fn for_app(app_id: &str) -> Self {
+        Self {
+            app_id: app_id.to_string(),
+            name: app_id.to_string(),
+            display_name: app_id.to_string(),
+            is_background: false,
+            icon_handle: None,
+            pid: 1,
+            pids: vec![1],
+            exe_path: None,
+            category: None,
+            cpu_percent: 0.0,
+            rss_bytes: 0,
+            swap_bytes: 0,
+            threads: 1,
+            encoder_percent: None,
+            wakeups_per_sec: None,
+            fds: None,
+            states: Vec::new(),
+            custom_values: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -182,17 +408,41 @@ struct AutostartFeedback {
 struct DesktopAppMeta {
     app_id: String,
     name: String,
-    icon_handle: Option<icon::Handle>,
+    icon_handle: Option<Arc<icon::Handle>>,
     primary_exec_keys: HashSet<String>,
     desktop_entry_id: Option<String>,
     desktop_entry_path: Option<PathBuf>,
     exec_command: Option<String>,
+    categories: Vec<String>,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 struct SteamAppMeta {
     name: String,
     icon_handle: Option<icon::Handle>,
+    /// Wine prefix of a game that runs through Proton.
+    compat_prefix: Option<PathBuf>,
+    /// The Proton build that last set up the prefix.
+    proton_build: Option<String>,
+    /// The compatibility tool seen on the game's command line.
+    proton_tool: Option<String>,
+}
+
+/// The app a process was matched to, kept for as long as the process runs. Its strings are
+/// shared with the process sample and the groups built from it, so a refresh of a matched
+/// process allocates nothing.
+#[derive(Clone)]
+struct ProcessMatch {
+    start_time: u64,
+    /// The executable and command line matched by; a process that `exec`s something else
+    /// is matched again.
+    exe: Option<Arc<Path>>,
+    cmd: Arc<[String]>,
+    app_id: Arc<str>,
+    name: Arc<str>,
+    is_background: bool,
+    icon_handle: Option<icon::Handle>,
+    category: Option<&'static str>,
 }
 
 #[derive(Debug, Clone)]
@@ -310,7 +560,7 @@ impl Default for GpuRuntimeInfo {
 
 #[derive(Debug, Clone)]
 enum LaunchCandidate {
-    SteamUri(String),
+    Uri(String),
     GtkLaunch(String),
     GioLaunch(PathBuf),
     DesktopExec(String),
@@ -318,25 +568,20 @@ enum LaunchCandidate {
     Executable(PathBuf),
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum SortColumn {
-    Name,
-    Cpu,
-    Pid,
-    Ram,
-    Threads,
-}
-
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum SortDirection {
     Asc,
     Desc,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub enum AppsViewMode {
-    List,
-    Tile,
+impl GroupBy {
+    fn label(self) -> String {
+        match self {
+            GroupBy::App => fl!("group-by-app"),
+            GroupBy::User => fl!("group-by-user"),
+            GroupBy::Category => fl!("group-by-category"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -361,25 +606,81 @@ pub struct AppModel {
     nav: nav_bar::Model,
     key_binds: HashMap<menu::KeyBind, MenuAction>,
     config: Config,
+    config_handler: Option<cosmic_config::Config>,
+    /// Shown when stored settings could not be read and defaults are used instead.
+    config_warning: Option<String>,
+    data_sources: DataSourceHealth,
     system: System,
+    /// The processes the app table is built from.
+    process_source: Box<dyn SystemSource>,
     disks: Disks,
+    users: Users,
     desktop_apps_by_exec: HashMap<String, DesktopAppMeta>,
+    icon_cache: IconCache,
     steam_apps_by_id: HashMap<String, SteamAppMeta>,
+    steam_resolver: SteamResolver,
+    /// Match results by pid, so a process is matched to its app only once.
+    process_matches: HashMap<u32, ProcessMatch>,
     process_entries: Vec<ProcessEntry>,
+    /// Smoothed CPU usage per group key from the last refresh.
+    smoothed_cpu: HashMap<String, f32>,
+    icon_resolver: IconResolver,
+    audio: AudioMonitor,
+    privacy: PrivacyMonitor,
+    gamemode: GameModeMonitor,
+    steam_maintenance: SteamMaintenance,
+    heroic: HeroicResolver,
+    playtime: PlaytimeTracker,
+    games: GameLibrary,
+    highlighter: Highlighter,
+    hook_runner: HookRunner,
+    value_flashes: ValueFlashes,
+    install_sizes: HashMap<String, InstallSize>,
+    origins: OriginResolver,
+    updates: UpdateChecker,
+    pressure: PressureStats,
+    load: LoadStats,
     selected_process: Option<SelectedProcess>,
+    last_row_click: Option<RowClick>,
+    split_view: SplitView,
+    comparison: AppComparison,
+    launch_override_editor: LaunchOverrideEditor,
+    pending_selection: Option<PendingSelection>,
+    /// App a deep link selected, listed even when the Filter menu hides its kind of
+    /// background process, until the filter is changed.
+    revealed_app: Option<String>,
     selected_autostart_entry: Option<SelectedAutostartEntry>,
     apps_view_mode: AppsViewMode,
-    apps_desktop_expanded: bool,
-    apps_background_expanded: bool,
+    processes_page: ProcessesPage,
+    resources_page: ResourcesPage,
+    cgroups_page: CgroupsPage,
+    cgroup_tree: CgroupTree,
+    events_page: EventsPage,
+    info_page: InfoPage,
+    app_events: AppEventLog,
+    settings_page: SettingsPage,
     autostart_entries: Vec<AutostartEntry>,
     autostart_add_options: Vec<AutostartAddOption>,
     autostart_modal_open: bool,
+    /// Name typed into the save-profile dialog, while it is open.
+    profile_dialog_name: Option<String>,
+    free_port_dialog: Option<FreePortDialog>,
+    /// When the last scheduled snapshot was written, or the task monitor started.
+    scheduled_snapshot_at: Instant,
+    /// Settings import waiting for confirmation, or the outcome of an export or import.
+    settings_transfer: Option<SettingsTransfer>,
+    /// Stop or kill of a protected app waiting for its name to be typed.
+    protected_stop: Option<ProtectedStop>,
+    /// Restarts waiting for their app to exit, by app id.
+    pending_restarts: HashMap<String, PendingRestart>,
+    search_lookups: SearchLookups,
+    /// Header quick action waiting for confirmation, running, or reporting its outcome.
+    quick_action: Option<QuickAction>,
     autostart_remove_modal_open: bool,
     autostart_modal_selected_option: Option<usize>,
     autostart_feedback: Option<AutostartFeedback>,
     autostart_desktop_expanded: bool,
     autostart_background_expanded: bool,
-    performance_view_mode: PerformanceViewMode,
     cpu_usage_history_per_core: Vec<Vec<f32>>,
     ram_usage_history: Vec<f32>,
     gpu_usage_history: Vec<f32>,
@@ -393,8 +694,35 @@ pub struct AppModel {
     disk_runtime_info: HashMap<String, DiskRuntimeInfo>,
     disk_previous_snapshots: HashMap<String, DiskIoSnapshot>,
     cpu_static_info: CpuStaticInfo,
+    cpu_topology: CpuTopology,
+    system_summary: SystemSummary,
+    global_shortcut: GlobalShortcut,
+    thread_placement: ThreadPlacement,
     gpu_runtime_info: GpuRuntimeInfo,
-    sort_state: SortState,
+    engine_monitor: EngineMonitor,
+    wakeup_monitor: WakeupMonitor,
+    states: StateMonitor,
+    restarts: RestartMonitor,
+    /// Hidden background processes by the app that spawned them.
+    spawned_helpers: HashMap<String, Vec<SpawnedHelper>>,
+    namespaces: NamespaceResolver,
+    session: SessionState,
+    /// Refreshes so far, which place samples in the graphs' downsampled buckets.
+    history_ticks: u64,
+    shared_memory: SharedMemory,
+    browser_processes: BrowserProcesses,
+    memory_trim: MemoryTrim,
+    paused_apps: PausedApps,
+    loaded_libraries: LoadedLibraries,
+    captured_logs: CapturedLogs,
+    journal: JournalViewer,
+    screen_recorder: ScreenRecorder,
+    thumbnail: WindowThumbnail,
+    profile: Option<Profile>,
+    ptrace_prompt: Option<PtracePrompt>,
+    alerts: AlertMonitor,
+    memory_growth: MemoryGrowth,
+    proc_access: ProcAccess,
 }
 
 #[derive(Debug, Clone)]
@@ -403,9 +731,20 @@ pub enum Message {
     ToggleContextPage(ContextPage),
     UpdateConfig(Config),
     RefreshProcesses,
+    /// Apps started or exited between two refreshes.
+    ProcessesChanged,
+    /// Writes a scheduled snapshot if one is due.
+    WriteScheduledSnapshot,
+    /// Redraws cells whose highlight for a changed value is fading.
+    FadeValueFlashes,
     SetAppsViewMode(AppsViewMode),
-    ToggleAppsDesktopSection,
-    ToggleAppsBackgroundSection,
+    Processes(ProcessesMessage),
+    Resources(ResourcesMessage),
+    Cgroups(CgroupsMessage),
+    Games(GamesMessage),
+    Events(EventsMessage),
+    Info(InfoMessage),
+    Settings(SettingsMessage),
     OpenAutostartModal,
     CloseAutostartModal,
     SelectAutostartModalOption(usize),
@@ -413,6 +752,45 @@ pub enum Message {
     CreateCustomAutostartDesktop,
     ImportAutostartDesktopFromFile,
     DismissAutostartFeedback,
+    DismissConfigWarning,
+    RetryDataSource(DataSource),
+    DismissDataSourceProblem(DataSource),
+    DismissProcAccessWarning,
+    OpenSaveViewProfile,
+    SetProfileName(String),
+    SaveViewProfile,
+    CancelSaveViewProfile,
+    ApplyViewProfile(usize),
+    ToggleSplitView,
+    StartSplitterDrag,
+    DragSplitter(f32),
+    EndSplitterDrag,
+    OpenFreePort,
+    ToggleBackgroundKind(BackgroundKind),
+    SetFreePortInput(String),
+    FindPortOwners,
+    PortOwnersFound {
+        port: u16,
+        pids: HashSet<u32>,
+    },
+    FreePort(String),
+    CloseFreePort,
+    ExportSettings,
+    ImportSettings,
+    ConfirmSettingsImport,
+    CloseSettingsTransfer,
+    ToggleSelectedAppProtected,
+    SetProtectedConfirmation(String),
+    ConfirmProtectedStop,
+    CancelProtectedStop,
+    EndTopCpuConsumer,
+    ConfirmQuickAction,
+    DropCaches,
+    CachesDropped(Result<Option<u64>, String>),
+    CloseQuickAction,
+    SessionChanged(SessionChange),
+    EnterSelectedContainer,
+    StopSelectedContainer,
     OpenAutostartEntryMenu {
         name: String,
         autostart_path: String,
@@ -426,27 +804,110 @@ pub enum Message {
     ConfirmRemoveSelectedAutostart,
     ToggleAutostartDesktopSection,
     ToggleAutostartBackgroundSection,
-    SetPerformanceViewMode(PerformanceViewMode),
-    MountDisk(String),
-    UnmountDisk(String),
-    ToggleSort(SortColumn),
     OpenProcessMenu {
         app_id: String,
         display_name: String,
         pid: u32,
     },
+    PressProcessRow {
+        app_id: String,
+        display_name: String,
+        pid: u32,
+    },
     CloseProcessMenu,
     RestartSelectedApplication,
     FocusSelectedApplication,
     StopSelectedApplication,
+    PauseSelectedApplication,
+    ResumeSelectedApplication,
     KillSelectedApplication,
     OpenSelectedApplicationPath,
+    OpenSelectedTerminal,
+    OpenSelectedProtonPrefix,
+    OpenLoadedLibraries,
+    RestartWithLogCapture,
+    OpenCapturedLog,
+    OpenCapturedLogFile,
+    CloseCapturedLog,
+    CloseLoadedLibraries,
+    LoadedLibrariesQuery(String),
+    ProfileSelectedApplication,
+    ProfileFinished {
+        app_id: String,
+        report: Option<ProfileReport>,
+    },
+    CloseProfile,
+    OpenJournal,
+    JournalLoaded {
+        app_id: String,
+        result: Result<JournalChunk, String>,
+    },
+    SetJournalFollow(bool),
+    CloseJournal,
+    StartScreenRecording,
+    StopScreenRecording,
+    OpenScreenRecording,
+    TrimSelectedMemory,
+    MemoryTrimmed {
+        app_id: String,
+        result: Result<u64, String>,
+    },
+    WindowThumbnailCaptured {
+        app_id: String,
+        png: Option<Vec<u8>>,
+    },
+    EnginesSampled(Option<EngineSample>),
+    CollectorAttached(Option<CollectorHistory>),
+    StraceSelectedApplication,
+    RelaunchSelectedUnderStrace,
+    AttachDebugger,
+    ConfirmPtracePrompt,
+    ClosePtracePrompt,
     CopySelectedApplicationInfo,
+    StartComparison,
+    CancelComparison,
+    CloseComparison,
+    SetLaunchOverrideEnv(String),
+    SetLaunchOverrideArgs(String),
+    SaveLaunchOverride,
+    ToggleSelectedAppMute,
+    ToggleSelectedAppGameMode,
+    InstallSizeResolved {
+        app_id: String,
+        bytes: Option<u64>,
+    },
+    OriginsResolved(Vec<(PathBuf, AppOrigin)>),
+    SteamAppsResolved(Vec<(String, SteamAppMeta)>),
+    UpdatesChecked(HashSet<String>),
+    UpdateSelectedAppInStore,
+    SetSelectedAppVolume(u32),
+    AudioStreamsRead(Vec<AudioStream>),
+    PrivacyUsageRead(PrivacyUsage),
+    GameModeListed(HashSet<u32>),
+    GameModeChanged,
+    SearchLookedUp {
+        query: SearchQuery,
+        found: SearchLookup,
+    },
+    ClearAlertHistory,
+    ExportAlertHistory,
+    TrayOpenWindow,
+    TrayQuitApp(String),
+    ContinueQuit {
+        app_id: String,
+        stage: QuitStage,
+    },
+    ContinueRestart(String),
+}
+
+/// Opens the task monitor, or hands `flags` to the instance that is already running.
+pub fn run(settings: cosmic::app::Settings, flags: Flags) -> cosmic::iced::Result {
+    cosmic::app::run_single_instance::<AppModel>(settings, flags)
 }
 
 impl cosmic::Application for AppModel {
     type Executor = cosmic::executor::Default;
-    type Flags = ();
+    type Flags = Flags;
     type Message = Message;
 
     const APP_ID: &'static str = "com.github.exepta.cosmic-task-monitor";
@@ -459,28 +920,7 @@ impl cosmic::Application for AppModel {
         &mut self.core
     }
 
-    fn init(
-        core: cosmic::Core,
-        _flags: Self::Flags,
-    ) -> (Self, Task<cosmic::Action<Self::Message>>) {
-        let mut nav = nav_bar::Model::default();
-
-        nav.insert()
-            .text(fl!("nav-apps"))
-            .data::<Page>(Page::Page1)
-            .icon(icon::from_name("applications-other-symbolic"))
-            .activate();
-
-        nav.insert()
-            .text(fl!("nav-autostart"))
-            .data::<Page>(Page::Page2)
-            .icon(icon::from_name("system-run-symbolic"));
-
-        nav.insert()
-            .text(fl!("nav-performance"))
-            .data::<Page>(Page::Page3)
-            .icon(icon::from_name("utilities-system-monitor-symbolic"));
-
+    fn init(core: cosmic::Core, flags: Self::Flags) -> (Self, Task<cosmic::Action<Self::Message>>) {
         let about = About::default()
             .name(fl!("app-title"))
             .icon(icon::from_svg_bytes(APP_ICON))
@@ -488,36 +928,85 @@ impl cosmic::Application for AppModel {
             .links([(fl!("repository"), REPOSITORY)])
             .license(env!("CARGO_PKG_LICENSE"));
 
+        let (config_handler, config, config_errors) = Config::load(Self::APP_ID);
+        let mut nav = nav_bar::Model::default();
+        Self::insert_nav_pages(&mut nav, config.startup_page);
+        for err in &config_errors {
+            eprintln!("failed to load config: {err}");
+        }
+        let process_source = source::source_for(config.process_backend);
+        let mut icon_cache = IconCache::default();
+
         let mut app = AppModel {
             core,
             context_page: ContextPage::default(),
             about,
             nav,
             key_binds: HashMap::new(),
-            config: cosmic_config::Config::new(Self::APP_ID, Config::VERSION)
-                .map(|context| {
-                    Config::get_entry(&context).unwrap_or_else(|(_errors, config)| config)
-                })
-                .unwrap_or_default(),
+            config,
+            config_handler,
+            config_warning: (!config_errors.is_empty()).then(|| fl!("config-load-failed")),
+            data_sources: DataSourceHealth::default(),
             system: System::new_all(),
+            process_source,
             disks: Disks::new_with_refreshed_list(),
-            desktop_apps_by_exec: Self::load_desktop_app_map(),
+            users: Users::new(),
+            desktop_apps_by_exec: Self::load_desktop_app_map(&mut icon_cache),
+            icon_cache,
             steam_apps_by_id: HashMap::new(),
+            steam_resolver: SteamResolver::default(),
+            process_matches: HashMap::new(),
             process_entries: Vec::new(),
+            smoothed_cpu: HashMap::new(),
+            icon_resolver: IconResolver::default(),
+            audio: AudioMonitor::default(),
+            privacy: PrivacyMonitor::default(),
+            gamemode: GameModeMonitor::default(),
+            steam_maintenance: SteamMaintenance::default(),
+            heroic: HeroicResolver::default(),
+            playtime: PlaytimeTracker::default(),
+            games: GameLibrary::default(),
+            highlighter: Highlighter::default(),
+            hook_runner: HookRunner::default(),
+            value_flashes: ValueFlashes::default(),
+            install_sizes: HashMap::new(),
+            origins: OriginResolver::default(),
+            updates: UpdateChecker::default(),
+            pressure: PressureStats::default(),
+            load: LoadStats::default(),
             selected_process: None,
+            last_row_click: None,
+            split_view: SplitView::default(),
+            comparison: AppComparison::default(),
+            launch_override_editor: LaunchOverrideEditor::default(),
+            pending_selection: None,
+            revealed_app: None,
             selected_autostart_entry: None,
             apps_view_mode: AppsViewMode::List,
-            apps_desktop_expanded: true,
-            apps_background_expanded: false,
+            processes_page: ProcessesPage::default(),
+            resources_page: ResourcesPage::default(),
+            cgroups_page: CgroupsPage::default(),
+            events_page: EventsPage::default(),
+            info_page: InfoPage::default(),
+            app_events: AppEventLog::default(),
+            cgroup_tree: CgroupTree::default(),
+            settings_page: SettingsPage::default(),
             autostart_entries: Vec::new(),
             autostart_add_options: Vec::new(),
             autostart_modal_open: false,
+            profile_dialog_name: None,
+            free_port_dialog: None,
+            scheduled_snapshot_at: Instant::now(),
+            settings_transfer: None,
+            protected_stop: None,
+            pending_restarts: HashMap::new(),
+            search_lookups: SearchLookups::default(),
+            quick_action: None,
             autostart_remove_modal_open: false,
             autostart_modal_selected_option: None,
             autostart_feedback: None,
             autostart_desktop_expanded: true,
             autostart_background_expanded: false,
-            performance_view_mode: PerformanceViewMode::Cpu,
             cpu_usage_history_per_core: Vec::new(),
             ram_usage_history: Vec::new(),
             gpu_usage_history: Vec::new(),
@@ -531,16 +1020,42 @@ impl cosmic::Application for AppModel {
             disk_runtime_info: HashMap::new(),
             disk_previous_snapshots: HashMap::new(),
             cpu_static_info: Self::read_cpu_static_info(),
+            cpu_topology: CpuTopology::read(),
+            system_summary: SystemSummary::read(),
+            global_shortcut: GlobalShortcut::read(),
+            thread_placement: ThreadPlacement::default(),
             gpu_runtime_info: GpuRuntimeInfo::default(),
-            sort_state: SortState {
-                column: SortColumn::Ram,
-                direction: SortDirection::Desc,
-            },
+            engine_monitor: EngineMonitor::default(),
+            wakeup_monitor: WakeupMonitor::default(),
+            states: StateMonitor::default(),
+            restarts: RestartMonitor::default(),
+            spawned_helpers: HashMap::new(),
+            namespaces: NamespaceResolver::default(),
+            session: SessionState::default(),
+            history_ticks: 0,
+            shared_memory: SharedMemory::default(),
+            browser_processes: BrowserProcesses::default(),
+            memory_trim: MemoryTrim::default(),
+            paused_apps: PausedApps::default(),
+            loaded_libraries: LoadedLibraries::default(),
+            captured_logs: CapturedLogs::default(),
+            journal: JournalViewer::default(),
+            screen_recorder: ScreenRecorder::default(),
+            thumbnail: WindowThumbnail::default(),
+            profile: None,
+            ptrace_prompt: None,
+            alerts: AlertMonitor::default(),
+            memory_growth: MemoryGrowth::default(),
+            proc_access: ProcAccess::default(),
         };
 
+        let collector = app.connect_collector();
         app.refresh_autostart_state();
-        let command = app.update_title();
-        (app, command)
+        let command = match flags.into_link() {
+            Some(link) => app.open_deep_link(link),
+            None => app.update_title(),
+        };
+        (app, Task::batch([command, collector]))
     }
 
     fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Self::Message>> {
@@ -561,56 +1076,8 @@ impl cosmic::Application for AppModel {
                     .map(|entry| entry.display_name.clone())
                     .unwrap_or_else(|| fl!("process-actions-title"));
 
-                let button_height = Length::Fixed(38.0);
-                let content: Element<'_, Message> =
-                    if let Some(selected) = self.selected_process.as_ref() {
-                        widget::column::with_capacity(8)
-                            .push(widget::text(fl!("process-pid", pid = selected.pid)))
-                            .push(
-                                widget::button::standard(fl!("process-action-restart"))
-                                    .class(theme::Button::Standard)
-                                    .on_press(Message::RestartSelectedApplication)
-                                    .width(Length::Fill)
-                                    .height(button_height),
-                            )
-                            .push(
-                                widget::button::standard(fl!("process-action-focus"))
-                                    .on_press(Message::FocusSelectedApplication)
-                                    .width(Length::Fill)
-                                    .height(button_height),
-                            )
-                            .push(
-                                widget::button::standard(fl!("process-action-stop"))
-                                    .on_press(Message::StopSelectedApplication)
-                                    .width(Length::Fill)
-                                    .height(button_height),
-                            )
-                            .push(
-                                widget::button::destructive(fl!("process-action-kill"))
-                                    .on_press(Message::KillSelectedApplication)
-                                    .width(Length::Fill)
-                                    .height(button_height),
-                            )
-                            .push(
-                                widget::button::standard(fl!("process-action-open-path"))
-                                    .on_press(Message::OpenSelectedApplicationPath)
-                                    .width(Length::Fill)
-                                    .height(button_height),
-                            )
-                            .push(
-                                widget::button::standard(fl!("process-action-copy-info"))
-                                    .on_press(Message::CopySelectedApplicationInfo)
-                                    .width(Length::Fill)
-                                    .height(button_height),
-                            )
-                            .spacing(8)
-                            .width(Length::Fill)
-                            .into()
-                    } else {
-                        widget::text(fl!("process-none-selected")).into()
-                    };
-
-                let padded_content = widget::container(content).padding([0, 20, 0, 0]);
+                let padded_content =
+                    widget::container(self.process_actions_content()).padding([0, 20, 0, 0]);
                 context_drawer::context_drawer(padded_content, Message::CloseProcessMenu)
                     .title(title)
             }
@@ -655,12 +1122,47 @@ impl cosmic::Application for AppModel {
                 context_drawer::context_drawer(padded_content, Message::CloseAutostartEntryMenu)
                     .title(title)
             }
+            ContextPage::AlertHistory => context_drawer::context_drawer(
+                self.alert_history_drawer(),
+                Message::ToggleContextPage(ContextPage::AlertHistory),
+            )
+            .title(fl!("alert-history")),
+            ContextPage::LoadedLibraries => context_drawer::context_drawer(
+                widget::container(self.loaded_libraries_drawer()).padding([0, 20, 0, 0]),
+                Message::CloseLoadedLibraries,
+            )
+            .title(fl!("loaded-libraries")),
+            ContextPage::Profile => context_drawer::context_drawer(
+                widget::container(self.profile_drawer()).padding([0, 20, 0, 0]),
+                Message::CloseProfile,
+            )
+            .title(fl!("profile-title")),
+            ContextPage::CapturedLog => context_drawer::context_drawer(
+                widget::container(self.captured_log_drawer()).padding([0, 20, 0, 0]),
+                Message::CloseCapturedLog,
+            )
+            .title(fl!("log-capture-title")),
+            ContextPage::Journal => context_drawer::context_drawer(
+                widget::container(self.journal_drawer()).padding([0, 20, 0, 0]),
+                Message::CloseJournal,
+            )
+            .title(fl!("journal-title")),
         })
     }
 
     fn dialog(&self) -> Option<Element<'_, Self::Message>> {
         self.autostart_remove_dialog()
             .or_else(|| self.autostart_add_dialog())
+            .or_else(|| self.save_profile_dialog())
+            .or_else(|| self.free_port_dialog())
+            .or_else(|| self.settings_transfer_dialog())
+            .or_else(|| self.protected_stop_dialog())
+            .or_else(|| self.quick_action_dialog())
+            .or_else(|| self.ptrace_prompt_dialog())
+    }
+
+    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
+        self.quick_action_buttons()
     }
 
     fn header_start(&self) -> Vec<Element<'_, Self::Message>> {
@@ -682,6 +1184,40 @@ impl cosmic::Application for AppModel {
                             self.apps_view_mode == AppsViewMode::Tile,
                             MenuAction::ViewTile,
                         ),
+                        menu::Item::CheckBox(
+                            fl!("split-view"),
+                            None,
+                            self.config.split_view,
+                            MenuAction::ToggleSplitView,
+                        ),
+                        menu::Item::Divider,
+                        menu::Item::Folder(fl!("profiles"), self.view_profile_menu_items()),
+                        menu::Item::Divider,
+                        menu::Item::Button(fl!("alert-history"), None, MenuAction::AlertHistory),
+                    ],
+                ),
+            ),
+            menu::Tree::with_children(
+                menu::root(fl!("filter")).apply(Element::from),
+                menu::items(&self.key_binds, self.background_filter_menu_items()),
+            ),
+            menu::Tree::with_children(
+                menu::root(fl!("tools")).apply(Element::from),
+                menu::items(
+                    &self.key_binds,
+                    vec![
+                        menu::Item::Button(fl!("free-port"), None, MenuAction::FreePort),
+                        menu::Item::Divider,
+                        menu::Item::Button(
+                            fl!("settings-export"),
+                            None,
+                            MenuAction::ExportSettings,
+                        ),
+                        menu::Item::Button(
+                            fl!("settings-import"),
+                            None,
+                            MenuAction::ImportSettings,
+                        ),
                     ],
                 ),
             ),
@@ -701,8 +1237,22 @@ impl cosmic::Application for AppModel {
         Some(&self.nav)
     }
 
+    fn dbus_activation(
+        &mut self,
+        msg: cosmic::dbus_activation::Message,
+    ) -> Task<cosmic::Action<Self::Message>> {
+        self.handle_dbus_activation(msg.msg)
+    }
+
     fn on_nav_select(&mut self, id: nav_bar::Id) -> Task<cosmic::Action<Self::Message>> {
         self.nav.activate(id);
+        self.refresh_cgroup_tree();
+        self.refresh_games();
+        if self.active_page() == Page::Info
+            && matches!(self.info_page.memory_modules, MemoryModules::NotRead)
+        {
+            return Task::batch([self.request_memory_modules(false), self.update_title()]);
+        }
         self.update_title()
     }
 
@@ -713,29 +1263,91 @@ impl cosmic::Application for AppModel {
                 .map(|update| Message::UpdateConfig(update.config)),
         ];
 
-        subscriptions.push(Subscription::run(|| {
-            iced_futures::stream::channel(1, |mut emitter| async move {
-                let mut interval = tokio::time::interval(PROCESS_REFRESH_INTERVAL);
-                loop {
-                    interval.tick().await;
-                    _ = emitter.send(Message::RefreshProcesses).await;
-                }
-            })
-        }));
+        // Without the interval nothing is sampled while the session is locked or idle.
+        if !self.sampling_paused() {
+            subscriptions.push(Subscription::run(|| {
+                iced_futures::stream::channel(1, |mut emitter| async move {
+                    let mut interval = tokio::time::interval(PROCESS_REFRESH_INTERVAL);
+                    let mut process_events = proc_events::listen_for_process_events();
+                    let mut refreshed_at = Instant::now();
+                    loop {
+                        let message = tokio::select! {
+                            _ = interval.tick() => Message::RefreshProcesses,
+                            () = proc_events::next_process_event_burst(&mut process_events) => {
+                                let since = refreshed_at.elapsed();
+                                if since < proc_events::MIN_EVENT_REFRESH_GAP {
+                                    tokio::time::sleep(proc_events::MIN_EVENT_REFRESH_GAP - since)
+                                        .await;
+                                }
+                                Message::ProcessesChanged
+                            }
+                        };
+                        refreshed_at = Instant::now();
+                        _ = emitter.send(message).await;
+                    }
+                })
+            }));
+        }
+        subscriptions.push(Self::session_subscription());
+
+        if self.config.snapshot_interval.seconds().is_some() {
+            subscriptions.push(Self::snapshot_subscription());
+        }
+
+        if self.value_flashes.is_running() && !self.config.reduce_motion {
+            subscriptions.push(Self::value_flash_subscription());
+        }
+
+        if self.config.show_tray_icon {
+            subscriptions.push(Self::tray_subscription());
+        }
+
+        if self.config.remote_mode {
+            subscriptions.push(self.remote_subscription());
+        }
 
         Subscription::batch(subscriptions)
     }
 
     fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
         match message {
-            Message::RefreshProcesses => self.refresh_processes(),
+            Message::RefreshProcesses => {
+                self.refresh_processes();
+                return Task::batch([
+                    self.request_origin_lookups(),
+                    self.request_steam_lookups(),
+                    self.request_update_check(),
+                    self.select_pending_app(),
+                    self.follow_journal(),
+                    self.request_window_thumbnail(),
+                    self.request_engine_sample(),
+                    self.request_audio_streams(),
+                    self.request_privacy_usage(),
+                    self.request_gamemode_state(),
+                    self.request_search_lookup(),
+                ]);
+            }
+            Message::ProcessesChanged => {
+                self.refresh_process_list();
+                return Task::batch([
+                    self.request_origin_lookups(),
+                    self.request_steam_lookups(),
+                    self.select_pending_app(),
+                ]);
+            }
+            Message::WriteScheduledSnapshot => return self.write_scheduled_snapshot(),
+            Message::FadeValueFlashes => self.fade_value_flashes(),
             Message::SetAppsViewMode(mode) => self.apps_view_mode = mode,
-            Message::ToggleAppsDesktopSection => {
-                self.apps_desktop_expanded = !self.apps_desktop_expanded;
-            }
-            Message::ToggleAppsBackgroundSection => {
-                self.apps_background_expanded = !self.apps_background_expanded;
-            }
+            Message::Processes(message) => {
+                self.update_processes_page(message);
+                return self.request_search_lookup();
+            }
+            Message::Resources(message) => self.update_resources_page(message),
+            Message::Cgroups(message) => self.update_cgroups_page(message),
+            Message::Games(message) => return self.update_games_page(message),
+            Message::Events(message) => self.update_events_page(message),
+            Message::Info(message) => return self.update_info_page(message),
+            Message::Settings(message) => return self.update_settings_page(message),
             Message::OpenAutostartModal => self.open_autostart_modal(),
             Message::CloseAutostartModal => self.autostart_modal_open = false,
             Message::SelectAutostartModalOption(index) => {
@@ -751,6 +1363,49 @@ impl cosmic::Application for AppModel {
                 self.import_autostart_desktop_from_file();
             }
             Message::DismissAutostartFeedback => self.dismiss_autostart_feedback(),
+            Message::DismissConfigWarning => self.config_warning = None,
+            Message::RetryDataSource(source) => self.retry_data_source(source),
+            Message::DismissDataSourceProblem(source) => self.dismiss_data_source_problem(source),
+            Message::DismissProcAccessWarning => self.dismiss_proc_access_warning(),
+            Message::OpenSaveViewProfile => self.profile_dialog_name = Some(String::new()),
+            Message::SetProfileName(name) => self.profile_dialog_name = Some(name),
+            Message::SaveViewProfile => self.save_view_profile(),
+            Message::CancelSaveViewProfile => self.profile_dialog_name = None,
+            Message::ApplyViewProfile(index) => self.apply_view_profile(index),
+            Message::ToggleSplitView => self.toggle_split_view(),
+            Message::StartSplitterDrag => self.start_splitter_drag(),
+            Message::DragSplitter(position) => self.drag_splitter(position),
+            Message::EndSplitterDrag => self.end_splitter_drag(),
+            Message::OpenFreePort => self.open_free_port_dialog(),
+            Message::ToggleBackgroundKind(kind) => self.toggle_background_kind(kind),
+            Message::SetFreePortInput(port) => self.set_free_port_input(port),
+            Message::FindPortOwners => return self.find_port_owners(),
+            Message::PortOwnersFound { port, pids } => self.set_port_owners(port, pids),
+            Message::FreePort(app_id) => return self.free_port(app_id),
+            Message::CloseFreePort => self.free_port_dialog = None,
+            Message::ExportSettings => self.export_settings(),
+            Message::ImportSettings => self.import_settings(),
+            Message::ConfirmSettingsImport => self.confirm_settings_import(),
+            Message::CloseSettingsTransfer => self.close_settings_transfer(),
+            Message::ToggleSelectedAppProtected => self.toggle_selected_app_protected(),
+            Message::SetProtectedConfirmation(typed) => self.set_protected_confirmation(typed),
+            Message::ConfirmProtectedStop => return self.confirm_protected_stop(),
+            Message::CancelProtectedStop => self.protected_stop = None,
+            Message::EndTopCpuConsumer => self.end_top_cpu_consumer(),
+            Message::ConfirmQuickAction => return self.confirm_quick_action(),
+            Message::DropCaches => return self.start_drop_caches(),
+            Message::CachesDropped(result) => self.finish_drop_caches(result),
+            Message::CloseQuickAction => self.close_quick_action(),
+            Message::SessionChanged(change) => {
+                if self.update_session(change) {
+                    return self.update(Message::RefreshProcesses);
+                }
+            }
+            Message::EnterSelectedContainer => self.enter_selected_container(),
+            Message::StopSelectedContainer => {
+                self.stop_selected_container();
+                self.core.window.show_context = false;
+            }
             Message::OpenAutostartEntryMenu {
                 name,
                 autostart_path,
@@ -794,23 +1449,6 @@ impl cosmic::Application for AppModel {
             Message::ToggleAutostartBackgroundSection => {
                 self.autostart_background_expanded = !self.autostart_background_expanded;
             }
-            Message::SetPerformanceViewMode(mode) => self.performance_view_mode = mode,
-            Message::MountDisk(disk_name) => {
-                self.mount_disk(&disk_name);
-                self.refresh_processes();
-            }
-            Message::UnmountDisk(disk_name) => {
-                let is_system_disk = self
-                    .collect_disk_groups()
-                    .into_iter()
-                    .find(|disk| disk.name == disk_name)
-                    .is_some_and(|disk| disk.is_system_disk);
-                if !is_system_disk {
-                    self.unmount_disk(&disk_name);
-                    self.refresh_processes();
-                }
-            }
-            Message::ToggleSort(column) => self.toggle_sort(column),
             Message::OpenProcessMenu {
                 app_id,
                 display_name,
@@ -822,8 +1460,17 @@ impl cosmic::Application for AppModel {
                     pid,
                 });
                 self.context_page = ContextPage::ProcessActions;
-                self.core.window.show_context = true;
+                // The split view shows the selection in its own pane.
+                self.core.window.show_context = !self.config.split_view;
+                self.record_selected_app_history();
+                self.refresh_selected_shared_memory();
+                return self.request_install_size();
             }
+            Message::PressProcessRow {
+                app_id,
+                display_name,
+                pid,
+            } => return self.press_process_row(app_id, display_name, pid),
             Message::CloseProcessMenu => {
                 self.core.window.show_context = false;
                 if self.context_page == ContextPage::ProcessActions {
@@ -831,29 +1478,120 @@ impl cosmic::Application for AppModel {
                 }
             }
             Message::RestartSelectedApplication => {
-                self.restart_selected_application();
                 self.core.window.show_context = false;
+                return self.restart_selected_application();
             }
             Message::FocusSelectedApplication => {
                 self.focus_selected_application();
                 self.core.window.show_context = false;
             }
             Message::StopSelectedApplication => {
-                self.signal_selected_application(Signal::Term);
                 self.core.window.show_context = false;
+                return self.request_stop_selected(StopKind::Stop);
             }
+            Message::PauseSelectedApplication => self.pause_selected_application(),
+            Message::ResumeSelectedApplication => self.resume_selected_application(),
             Message::KillSelectedApplication => {
-                self.signal_selected_application(Signal::Kill);
                 self.core.window.show_context = false;
+                return self.request_stop_selected(StopKind::Kill);
             }
             Message::OpenSelectedApplicationPath => {
                 self.open_selected_application_path();
                 self.core.window.show_context = false;
             }
+            Message::OpenSelectedTerminal => {
+                self.open_selected_terminal();
+                self.core.window.show_context = false;
+            }
+            Message::OpenSelectedProtonPrefix => self.open_selected_proton_prefix(),
+            Message::OpenLoadedLibraries => self.open_loaded_libraries(),
+            Message::RestartWithLogCapture => return self.restart_with_log_capture(),
+            Message::OpenCapturedLog => self.open_captured_log(),
+            Message::OpenCapturedLogFile => self.open_captured_log_file(),
+            Message::CloseCapturedLog => self.close_captured_log(),
+            Message::CloseLoadedLibraries => self.close_loaded_libraries(),
+            Message::LoadedLibrariesQuery(query) => self.set_loaded_libraries_query(query),
+            Message::ProfileSelectedApplication => return self.profile_selected_application(),
+            Message::ProfileFinished { app_id, report } => {
+                self.set_profile_report(app_id, report);
+            }
+            Message::CloseProfile => self.close_profile(),
+            Message::OpenJournal => return self.open_journal(),
+            Message::JournalLoaded { app_id, result } => self.set_journal_result(app_id, result),
+            Message::SetJournalFollow(follow) => self.set_journal_follow(follow),
+            Message::CloseJournal => self.close_journal(),
+            Message::StartScreenRecording => self.start_screen_recording(),
+            Message::StopScreenRecording => self.stop_screen_recording(),
+            Message::OpenScreenRecording => self.open_screen_recording(),
+            Message::TrimSelectedMemory => return self.trim_selected_memory(),
+            Message::MemoryTrimmed { app_id, result } => {
+                self.set_memory_trim_result(app_id, result);
+            }
+            Message::WindowThumbnailCaptured { app_id, png } => {
+                self.set_window_thumbnail(app_id, png);
+            }
+            Message::EnginesSampled(sample) => self.set_engine_sample(sample),
+            Message::CollectorAttached(history) => {
+                if let Some(history) = history {
+                    self.set_collector_history(history);
+                }
+            }
+            Message::StraceSelectedApplication => {
+                self.request_attach(AttachTool::Strace);
+                self.core.window.show_context = false;
+            }
+            Message::AttachDebugger => {
+                self.request_attach(AttachTool::Debugger);
+                self.core.window.show_context = false;
+            }
+            Message::ConfirmPtracePrompt => self.confirm_ptrace_prompt(),
+            Message::ClosePtracePrompt => self.ptrace_prompt = None,
+            Message::RelaunchSelectedUnderStrace => {
+                self.core.window.show_context = false;
+                return self.relaunch_selected_under_strace();
+            }
+            Message::StartComparison => self.start_comparison(),
+            Message::CancelComparison => self.cancel_comparison(),
+            Message::CloseComparison => self.close_comparison(),
+            Message::SetLaunchOverrideEnv(env) => self.set_launch_override_env(env),
+            Message::SetLaunchOverrideArgs(args) => self.set_launch_override_args(args),
+            Message::SaveLaunchOverride => self.save_launch_override(),
             Message::CopySelectedApplicationInfo => {
                 self.copy_selected_application_info();
                 self.core.window.show_context = false;
             }
+            Message::ToggleSelectedAppMute => return self.toggle_selected_app_mute(),
+            Message::ToggleSelectedAppGameMode => return self.toggle_selected_app_gamemode(),
+            Message::GameModeChanged => return self.refresh_changed_gamemode(),
+            Message::GameModeListed(registered) => self.set_gamemode_state(registered),
+            Message::SearchLookedUp { query, found } => {
+                return self.set_search_lookup(query, found);
+            }
+            Message::InstallSizeResolved { app_id, bytes } => {
+                self.set_install_size(app_id, bytes);
+            }
+            Message::OriginsResolved(origins) => self.set_origins(origins),
+            Message::SteamAppsResolved(apps) => self.set_steam_apps(apps),
+            Message::UpdatesChecked(app_ids) => self.set_apps_with_updates(app_ids),
+            Message::UpdateSelectedAppInStore => {
+                self.update_selected_app_in_store();
+                self.core.window.show_context = false;
+            }
+            Message::SetSelectedAppVolume(volume_percent) => {
+                return self.set_selected_app_volume(volume_percent);
+            }
+            Message::AudioStreamsRead(streams) => self.set_audio_streams(streams),
+            Message::PrivacyUsageRead(usage) => self.set_privacy_usage(usage),
+            Message::ClearAlertHistory => self.clear_alert_history(),
+            Message::ExportAlertHistory => self.export_alert_history(),
+            Message::TrayOpenWindow => {
+                if let Some(id) = self.core.main_window_id() {
+                    return cosmic::iced::window::gain_focus(id);
+                }
+            }
+            Message::TrayQuitApp(app_id) => return self.request_stop(app_id, StopKind::Stop),
+            Message::ContinueQuit { app_id, stage } => return self.continue_quit(app_id, stage),
+            Message::ContinueRestart(app_id) => return self.continue_restart(app_id),
             Message::ToggleContextPage(context_page) => {
                 if self.context_page == context_page {
                     self.core.window.show_context = !self.core.window.show_context;
@@ -874,12 +1612,13 @@ impl cosmic::Application for AppModel {
 
     fn view(&self) -> Element<'_, Self::Message> {
         let space_s = theme::spacing().space_s;
-        let content: Element<_> = match self.nav.active_data::<Page>().unwrap() {
-            Page::Page1 => self.apps_view(space_s),
-            Page::Page2 => self.autostart_view(space_s),
-            Page::Page3 => self.performance_view(space_s),
-        };
-
+        let content = widget::column::with_capacity(4)
+            .push_maybe(self.config_warning_banner())
+            .push_maybe(self.proc_access_banner())
+            .push_maybe(self.data_source_banners())
+            .push_maybe(self.comparison_banner())
+            .push(self.page_view(space_s))
+            .spacing(space_s);
         widget::container(content)
             .width(Length::Fill)
             .height(Length::Fill)
@@ -888,16 +1627,192 @@ impl cosmic::Application for AppModel {
 }
 
 impl AppModel {
+    /// Pid, install size, and actions for the selected app, shown in the context drawer or
+    /// the split view's detail pane.
+    fn process_actions_content(&self) -> Element<'_, Message> {
+        let button_height = Length::Fixed(38.0);
+        if let Some(selected) = self.selected_process.as_ref() {
+            widget::column::with_capacity(8)
+                .push_maybe(self.selected_app_thumbnail())
+                .push(widget::text(fl!("process-pid", pid = selected.pid)))
+                .push_maybe(self.selected_app_install_size())
+                .push_maybe(self.selected_app_memory())
+                .push_maybe(self.selected_app_browser_processes())
+                .push_maybe(self.selected_app_memory_trim())
+                .push_maybe(self.selected_app_helpers())
+                .push_maybe(self.selected_app_cores())
+                .push_maybe(self.selected_app_states())
+                .push_maybe(self.selected_app_restarts())
+                .push_maybe(self.selected_app_update_button())
+                .push_maybe(self.selected_container_actions())
+                .push_maybe(self.selected_steam_maintenance())
+                .push_maybe(self.selected_app_proton())
+                .push_maybe(self.selected_app_playtime())
+                .push_maybe(self.selected_app_launch_override())
+                .push(
+                    widget::button::standard(fl!("process-action-restart"))
+                        .class(theme::Button::Standard)
+                        .on_press(Message::RestartSelectedApplication)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push_maybe(self.selected_app_log_buttons())
+                .push(
+                    widget::button::standard(fl!("process-action-focus"))
+                        .on_press(Message::FocusSelectedApplication)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push_maybe(self.selected_app_pause_button())
+                .push(
+                    widget::button::standard(fl!("process-action-stop"))
+                        .on_press(Message::StopSelectedApplication)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::destructive(fl!("process-action-kill"))
+                        .on_press(Message::KillSelectedApplication)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::standard(fl!("process-action-open-path"))
+                        .on_press(Message::OpenSelectedApplicationPath)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::standard(fl!("process-action-open-terminal"))
+                        .on_press(Message::OpenSelectedTerminal)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::standard(fl!("loaded-libraries"))
+                        .on_press(Message::OpenLoadedLibraries)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::standard(fl!("journal-title"))
+                        .on_press(Message::OpenJournal)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push_maybe(self.selected_app_screen_recording())
+                .push(
+                    widget::button::standard(fl!(
+                        "process-action-profile",
+                        seconds = profiler::PROFILE_DURATION.as_secs()
+                    ))
+                    .on_press_maybe(
+                        (!self.selected_app_profiling())
+                            .then_some(Message::ProfileSelectedApplication),
+                    )
+                    .width(Length::Fill)
+                    .height(button_height),
+                )
+                .push_maybe(debug_tools::strace_installed().then(|| {
+                    widget::button::standard(fl!("process-action-strace"))
+                        .on_press(Message::StraceSelectedApplication)
+                        .width(Length::Fill)
+                        .height(button_height)
+                }))
+                .push_maybe(debug_tools::strace_installed().then(|| {
+                    widget::button::standard(fl!("process-action-strace-relaunch"))
+                        .on_press(Message::RelaunchSelectedUnderStrace)
+                        .width(Length::Fill)
+                        .height(button_height)
+                }))
+                .push(
+                    widget::button::standard(fl!("process-action-debugger"))
+                        .on_press(Message::AttachDebugger)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::standard(fl!("process-action-copy-info"))
+                        .on_press(Message::CopySelectedApplicationInfo)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::standard(fl!("compare-with"))
+                        .on_press(Message::StartComparison)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push_maybe(self.selected_app_protected_toggle())
+                .push_maybe(self.selected_app_gamemode_toggle())
+                .push_maybe(self.selected_app_audio_controls())
+                .spacing(8)
+                .width(Length::Fill)
+                .into()
+        } else {
+            widget::text(fl!("process-none-selected")).into()
+        }
+    }
+
+    fn config_warning_banner(&self) -> Option<Element<'_, Message>> {
+        let warning = self.config_warning.as_ref()?;
+        Some(Self::warning_banner(
+            warning.clone(),
+            None,
+            Message::DismissConfigWarning,
+        ))
+    }
+
+    /// A dismissable warning across the top of the window, with an optional action button.
+    fn warning_banner<'a>(
+        warning: String,
+        action: Option<(String, Message)>,
+        dismiss: Message,
+    ) -> Element<'a, Message> {
+        let dismiss_button = widget::button::custom(widget::text("x").size(16))
+            .on_press(dismiss)
+            .padding([0, 8])
+            .class(theme::Button::Text);
+
+        widget::container(
+            widget::row::with_capacity(3)
+                .push(widget::text(warning).size(14).width(Length::Fill))
+                .push_maybe(
+                    action
+                        .map(|(label, message)| widget::button::standard(label).on_press(message)),
+                )
+                .push(dismiss_button)
+                .align_y(Alignment::Center)
+                .spacing(8)
+                .width(Length::Fill),
+        )
+        .padding([10, 12])
+        .class(theme::Container::custom(|theme| {
+            let warning: Color = theme.cosmic().warning_color().into();
+            widget::container::Style {
+                background: Some(Background::Color(Color { a: 0.14, ..warning })),
+                border: Border {
+                    color: warning,
+                    width: 1.0,
+                    radius: 10.0.into(),
+                },
+                ..Default::default()
+            }
+        }))
+        .width(Length::Fill)
+        .into()
+    }
+
     fn format_ghz(mhz: u64) -> String {
-        format!("{:.2}", mhz as f32 / 1000.0).replace('.', ",")
+        units::format_decimal(mhz as f64 / 1000.0, 2)
     }
 
     fn format_rate_mib(rate_mib_s: f32) -> String {
-        format!("{rate_mib_s:.2} MiB/s").replace('.', ",")
+        format!("{} MiB/s", units::format_decimal(f64::from(rate_mib_s), 2))
     }
 
     fn format_temp_c(temp_celsius: f32) -> String {
-        format!("{temp_celsius:.1} °C").replace('.', ",")
+        format!("{} °C", units::format_decimal(f64::from(temp_celsius), 1))
     }
 
     fn format_uptime(total_seconds: u64) -> String {
@@ -2016,18 +2931,17 @@ impl AppModel {
     }
 }
 
-pub enum Page {
-    Page1,
-    Page2,
-    Page3,
-}
-
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub enum ContextPage {
     #[default]
     About,
     ProcessActions,
     AutostartActions,
+    AlertHistory,
+    LoadedLibraries,
+    Profile,
+    CapturedLog,
+    Journal,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -2035,6 +2949,14 @@ pub enum MenuAction {
     About,
     ViewList,
     ViewTile,
+    ToggleSplitView,
+    AlertHistory,
+    SaveProfile,
+    ApplyProfile(usize),
+    FreePort,
+    ExportSettings,
+    ImportSettings,
+    ToggleBackgroundKind(BackgroundKind),
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -2045,6 +2967,14 @@ impl menu::action::MenuAction for MenuAction {
             MenuAction::About => Message::ToggleContextPage(ContextPage::About),
             MenuAction::ViewList => Message::SetAppsViewMode(AppsViewMode::List),
             MenuAction::ViewTile => Message::SetAppsViewMode(AppsViewMode::Tile),
+            MenuAction::ToggleSplitView => Message::ToggleSplitView,
+            MenuAction::AlertHistory => Message::ToggleContextPage(ContextPage::AlertHistory),
+            MenuAction::SaveProfile => Message::OpenSaveViewProfile,
+            MenuAction::ApplyProfile(index) => Message::ApplyViewProfile(*index),
+            MenuAction::FreePort => Message::OpenFreePort,
+            MenuAction::ExportSettings => Message::ExportSettings,
+            MenuAction::ImportSettings => Message::ImportSettings,
+            MenuAction::ToggleBackgroundKind(kind) => Message::ToggleBackgroundKind(*kind),
         }
     }
 }
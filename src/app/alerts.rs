@@ -0,0 +1,338 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Opt-in threshold alerts for apps that hold high CPU or RAM usage, sent as desktop
+//! notifications and kept in an in-app history, which memory growth alerts share.
+
+use super::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const APP_CPU_ALERT_PERCENT: f32 = 90.0;
+const APP_RAM_ALERT_PERCENT: f32 = 30.0;
+/// Consecutive refreshes an app has to stay above a threshold before the alert fires.
+const ALERT_SUSTAINED_SAMPLES: u32 = 10;
+const ALERT_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+const ALERT_HISTORY_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub(super) enum AlertMetric {
+    Cpu,
+    Ram,
+    MemoryGrowth,
+}
+
+impl AlertMetric {
+    fn label(self) -> String {
+        match self {
+            AlertMetric::Cpu => fl!("alert-metric-cpu"),
+            AlertMetric::Ram => fl!("alert-metric-ram"),
+            AlertMetric::MemoryGrowth => fl!("alert-metric-memory-growth"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AlertRecord {
+    fired_at: SystemTime,
+    app_name: String,
+    metric: AlertMetric,
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AlertTracker {
+    samples_over: u32,
+    last_fired: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct AlertMonitor {
+    trackers: HashMap<(String, AlertMetric), AlertTracker>,
+    history: Vec<AlertRecord>,
+    export_feedback: Option<String>,
+}
+
+/// Where exports are saved: the Downloads folder, else the home folder.
+pub(super) fn export_directory() -> PathBuf {
+    Command::new("xdg-user-dir")
+        .arg("DOWNLOAD")
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(PathBuf::from))
+        .unwrap_or_else(env::temp_dir)
+}
+
+/// Formats a unix timestamp as an RFC 3339 UTC date-time, e.g. `2024-03-09T14:05:00Z`.
+fn format_rfc3339_utc(unix_seconds: u64) -> String {
+    let (year, month, day) = system_info::civil_date((unix_seconds / 86_400) as i64);
+    let seconds_of_day = unix_seconds % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds_of_day / 3_600,
+        (seconds_of_day % 3_600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl AppModel {
+    /// Updates the threshold trackers from the latest refresh and fires any alerts that
+    /// have been sustained long enough.
+    pub(super) fn check_alerts(&mut self) {
+        // Alerts are about apps, so other groupings pause them instead of alerting per user.
+        if self.processes_page.group_by != GroupBy::App {
+            return;
+        }
+
+        let total_memory = self.system.total_memory();
+        let mut readings = Vec::new();
+        for entry in &self.process_entries {
+            readings.push((
+                entry.app_id.clone(),
+                entry.display_name.clone(),
+                AlertMetric::Cpu,
+                entry.cpu_percent >= APP_CPU_ALERT_PERCENT,
+                units::format_percent(entry.cpu_percent, 1),
+            ));
+            let ram_percent = if total_memory > 0 {
+                entry.rss_bytes as f32 / total_memory as f32 * 100.0
+            } else {
+                0.0
+            };
+            readings.push((
+                entry.app_id.clone(),
+                entry.display_name.clone(),
+                AlertMetric::Ram,
+                ram_percent >= APP_RAM_ALERT_PERCENT,
+                units::format_bytes(entry.rss_bytes),
+            ));
+        }
+
+        let running = readings
+            .iter()
+            .map(|(app_id, _, metric, _, _)| (app_id.clone(), *metric))
+            .collect::<HashSet<_>>();
+        self.alerts.trackers.retain(|key, tracker| {
+            running.contains(key)
+                || tracker
+                    .last_fired
+                    .is_some_and(|fired| fired.elapsed() < ALERT_COOLDOWN)
+        });
+
+        for (app_id, app_name, metric, is_over, value) in readings {
+            let tracker = self.alerts.trackers.entry((app_id, metric)).or_default();
+            if !is_over {
+                tracker.samples_over = 0;
+                continue;
+            }
+
+            tracker.samples_over += 1;
+            let cooled_down = tracker
+                .last_fired
+                .is_none_or(|fired| fired.elapsed() >= ALERT_COOLDOWN);
+            if tracker.samples_over < ALERT_SUSTAINED_SAMPLES || !cooled_down {
+                continue;
+            }
+
+            tracker.last_fired = Some(Instant::now());
+            self.raise_alert(app_name, metric, value);
+        }
+    }
+
+    /// Sends a notification for an alert and adds it to the history.
+    pub(super) fn raise_alert(&mut self, app_name: String, metric: AlertMetric, value: String) {
+        let record = AlertRecord {
+            fired_at: SystemTime::now(),
+            app_name,
+            metric,
+            value,
+        };
+        Self::send_alert_notification(&record);
+        self.alerts.history.push(record);
+
+        let overflow = self
+            .alerts
+            .history
+            .len()
+            .saturating_sub(ALERT_HISTORY_LIMIT);
+        self.alerts.history.drain(..overflow);
+    }
+
+    fn send_alert_notification(record: &AlertRecord) {
+        let (summary, body) = match record.metric {
+            AlertMetric::MemoryGrowth => (
+                fl!(
+                    "memory-growth-notification-summary",
+                    name = record.app_name.clone()
+                ),
+                fl!(
+                    "memory-growth-notification-body",
+                    rate = record.value.clone()
+                ),
+            ),
+            AlertMetric::Cpu | AlertMetric::Ram => (
+                fl!(
+                    "alert-notification-summary",
+                    name = record.app_name.clone(),
+                    metric = record.metric.label()
+                ),
+                fl!(
+                    "alert-notification-body",
+                    metric = record.metric.label(),
+                    value = record.value.clone()
+                ),
+            ),
+        };
+        let result = Command::new("notify-send")
+            .args([
+                "--app-name",
+                &fl!("app-title"),
+                "--icon",
+                <AppModel as cosmic::Application>::APP_ID,
+                &summary,
+                &body,
+            ])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Err(err) = result {
+            eprintln!("failed to send alert notification: {err}");
+        }
+    }
+
+    pub(super) fn clear_alert_history(&mut self) {
+        self.alerts.history.clear();
+        self.alerts.export_feedback = None;
+    }
+
+    pub(super) fn export_alert_history(&mut self) {
+        let mut csv = String::from("timestamp,app,metric,value\n");
+        for record in &self.alerts.history {
+            let unix_seconds = record
+                .fired_at
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0);
+            csv.push_str(&format!(
+                "{},{},{},{}\n",
+                format_rfc3339_utc(unix_seconds),
+                csv_field(&record.app_name),
+                csv_field(&record.metric.label()),
+                csv_field(&record.value)
+            ));
+        }
+
+        let directory = export_directory();
+        let generated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        let path = directory.join(format!("cosmic-task-monitor-alerts-{generated_at}.csv"));
+
+        self.alerts.export_feedback = Some(match fs::write(&path, csv) {
+            Ok(()) => fl!("alert-export-success", path = path.display().to_string()),
+            Err(err) => fl!("alert-export-failed", error = err.to_string()),
+        });
+    }
+
+    pub(super) fn toggle_threshold_alerts(&mut self) {
+        let enabled = !self.config.threshold_alerts;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_threshold_alerts(handler, enabled) {
+                eprintln!("failed to save threshold alerts setting: {err}");
+            }
+        } else {
+            self.config.threshold_alerts = enabled;
+        }
+
+        if !enabled {
+            self.alerts.trackers.clear();
+        }
+    }
+
+    pub(super) fn alert_history_drawer(&self) -> Element<'_, Message> {
+        let button_height = Length::Fixed(38.0);
+        let actions = widget::row::with_capacity(2)
+            .push(
+                widget::button::standard(fl!("alert-history-export"))
+                    .on_press_maybe(
+                        (!self.alerts.history.is_empty()).then_some(Message::ExportAlertHistory),
+                    )
+                    .width(Length::Fill)
+                    .height(button_height),
+            )
+            .push(
+                widget::button::destructive(fl!("alert-history-clear"))
+                    .on_press_maybe(
+                        (!self.alerts.history.is_empty()).then_some(Message::ClearAlertHistory),
+                    )
+                    .width(Length::Fill)
+                    .height(button_height),
+            )
+            .spacing(8);
+
+        let mut content = widget::column::with_capacity(4)
+            .push(actions)
+            .spacing(8)
+            .width(Length::Fill);
+        if let Some(feedback) = self.alerts.export_feedback.as_ref() {
+            content = content.push(widget::text(feedback.clone()).size(12));
+        }
+        if !self.config.threshold_alerts {
+            content = content.push(widget::text(fl!("alert-history-disabled")).size(12));
+        }
+
+        if self.alerts.history.is_empty() {
+            content = content.push(widget::text(fl!("alert-history-empty")));
+        } else {
+            for record in self.alerts.history.iter().rev() {
+                let age = record.fired_at.elapsed().unwrap_or_default().as_secs();
+                content = content.push(
+                    widget::column::with_capacity(2)
+                        .push(widget::text(fl!(
+                            "alert-history-entry",
+                            name = record.app_name.clone(),
+                            metric = record.metric.label(),
+                            value = record.value.clone()
+                        )))
+                        .push(
+                            widget::text(fl!("alert-history-age", age = Self::format_uptime(age)))
+                                .size(12),
+                        )
+                        .spacing(2),
+                );
+            }
+        }
+
+        widget::container(content).padding([0, 20, 0, 0]).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, format_rfc3339_utc};
+
+    #[test]
+    fn formats_unix_timestamps_as_utc() {
+        assert_eq!(format_rfc3339_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_rfc3339_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_rfc3339_utc(1_709_993_100), "2024-03-09T14:05:00Z");
+    }
+
+    #[test]
+    fn quotes_csv_fields_when_needed() {
+        assert_eq!(csv_field("Firefox"), "Firefox");
+        assert_eq!(csv_field("Foo, \"Bar\""), "\"Foo, \"\"Bar\"\"\"");
+    }
+}
@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Playback streams from PipeWire or PulseAudio, read and controlled through `pactl`.
+//! `pactl` can take a while to answer, so it only runs in the background.
+
+use super::*;
+
+const MAX_STREAM_VOLUME_PERCENT: u32 = 150;
+
+#[derive(Debug, Default)]
+pub(super) struct AudioMonitor {
+    streams: Vec<AudioStream>,
+    /// Whether the streams are being read in the background.
+    reading: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct AudioStream {
+    index: u32,
+    pub(super) pid: Option<u32>,
+    pub(super) is_playing: bool,
+    muted: bool,
+    volume_percent: u32,
+}
+
+/// Parses the output of `pactl --format=json list sink-inputs` or `source-outputs`.
+fn parse_pactl_streams(json: &str) -> Vec<AudioStream> {
+    let Ok(serde_json::Value::Array(inputs)) = serde_json::from_str(json) else {
+        return Vec::new();
+    };
+
+    inputs
+        .iter()
+        .filter_map(|input| {
+            let index = input.get("index")?.as_u64()? as u32;
+            let pid = input
+                .get("properties")
+                .and_then(|properties| properties.get("application.process.id"))
+                .and_then(|value| value.as_str())
+                .and_then(|value| value.parse::<u32>().ok());
+            let channel_volumes = input
+                .get("volume")
+                .and_then(|volume| volume.as_object())
+                .map(|channels| {
+                    channels
+                        .values()
+                        .filter_map(|channel| channel.get("value_percent")?.as_str())
+                        .filter_map(|value| value.trim_end_matches('%').trim().parse::<u32>().ok())
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let volume_percent = if channel_volumes.is_empty() {
+                100
+            } else {
+                channel_volumes.iter().sum::<u32>() / channel_volumes.len() as u32
+            };
+
+            Some(AudioStream {
+                index,
+                pid,
+                is_playing: !input.get("corked").and_then(|value| value.as_bool())?,
+                muted: input.get("mute").and_then(|value| value.as_bool())?,
+                volume_percent,
+            })
+        })
+        .collect()
+}
+
+fn run_pactl(args: &[&str]) {
+    let result = Command::new("pactl")
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("pactl {args:?} exited with {status}"),
+        Err(err) => eprintln!("failed to run pactl {args:?}: {err}"),
+    }
+}
+
+/// Lists playback (`sink-inputs`) or recording (`source-outputs`) streams.
+pub(super) fn read_pactl_streams(kind: &str) -> Vec<AudioStream> {
+    Command::new("pactl")
+        .args(["--format=json", "list", kind])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_pactl_streams(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Runs the `pactl` commands, then reads the playback streams.
+fn control_and_read_streams(commands: Vec<Vec<String>>) -> Task<cosmic::Action<Message>> {
+    Task::perform(
+        async move {
+            tokio::task::spawn_blocking(move || {
+                for args in commands {
+                    run_pactl(&args.iter().map(String::as_str).collect::<Vec<_>>());
+                }
+                read_pactl_streams("sink-inputs")
+            })
+            .await
+            .unwrap_or_default()
+        },
+        |streams| cosmic::Action::App(Message::AudioStreamsRead(streams)),
+    )
+}
+
+impl AppModel {
+    /// Reads the playback streams in the background, unless a read is still running.
+    pub(super) fn request_audio_streams(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.audio.reading {
+            return Task::none();
+        }
+        self.audio.reading = true;
+        control_and_read_streams(Vec::new())
+    }
+
+    pub(super) fn set_audio_streams(&mut self, streams: Vec<AudioStream>) {
+        self.audio = AudioMonitor {
+            streams,
+            reading: false,
+        };
+    }
+
+    fn audio_streams_for_app(&self, app_id: &str) -> Vec<&AudioStream> {
+        let Some(entry) = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == app_id)
+        else {
+            return Vec::new();
+        };
+
+        self.audio
+            .streams
+            .iter()
+            .filter(|stream| stream.pid.is_some_and(|pid| entry.pids.contains(&pid)))
+            .collect()
+    }
+
+    pub(super) fn is_playing_audio(&self, entry: &ProcessEntry) -> bool {
+        self.audio.streams.iter().any(|stream| {
+            stream.is_playing && stream.pid.is_some_and(|pid| entry.pids.contains(&pid))
+        })
+    }
+
+    pub(super) fn toggle_selected_app_mute(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return Task::none();
+        };
+        let streams = self.audio_streams_for_app(&selected.app_id);
+        // Unmute everything if any stream is muted, so mixed states converge on one click.
+        let mute = if streams.iter().any(|stream| stream.muted) {
+            "0"
+        } else {
+            "1"
+        };
+        let commands = streams
+            .iter()
+            .map(|stream| {
+                vec![
+                    "set-sink-input-mute".to_string(),
+                    stream.index.to_string(),
+                    mute.to_string(),
+                ]
+            })
+            .collect();
+        self.audio.reading = true;
+        control_and_read_streams(commands)
+    }
+
+    pub(super) fn set_selected_app_volume(
+        &mut self,
+        volume_percent: u32,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return Task::none();
+        };
+        let volume = format!("{}%", volume_percent.min(MAX_STREAM_VOLUME_PERCENT));
+        let commands = self
+            .audio_streams_for_app(&selected.app_id)
+            .iter()
+            .map(|stream| {
+                vec![
+                    "set-sink-input-volume".to_string(),
+                    stream.index.to_string(),
+                    volume.clone(),
+                ]
+            })
+            .collect();
+        self.audio.reading = true;
+        control_and_read_streams(commands)
+    }
+
+    /// Mute and volume controls for the selected app, if it has any playback streams.
+    pub(super) fn selected_app_audio_controls(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let streams = self.audio_streams_for_app(&selected.app_id);
+        if streams.is_empty() {
+            return None;
+        }
+
+        let muted = streams.iter().all(|stream| stream.muted);
+        let volume_percent = streams
+            .iter()
+            .map(|stream| stream.volume_percent)
+            .max()
+            .unwrap_or(100);
+        let mute_icon = if muted {
+            "audio-volume-muted-symbolic"
+        } else {
+            "audio-volume-high-symbolic"
+        };
+
+        let controls = widget::row::with_capacity(3)
+            .push(
+                widget::button::icon(widget::icon::from_name(mute_icon))
+                    .on_press(Message::ToggleSelectedAppMute),
+            )
+            .push(widget::slider(
+                0..=MAX_STREAM_VOLUME_PERCENT,
+                volume_percent,
+                Message::SetSelectedAppVolume,
+            ))
+            .push(
+                widget::text(format!("{volume_percent}%"))
+                    .width(Length::Fixed(48.0))
+                    .align_x(Horizontal::Right),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8);
+
+        Some(
+            widget::column::with_capacity(2)
+                .push(widget::text(fl!("audio-title")).size(14))
+                .push(controls)
+                .spacing(4)
+                .width(Length::Fill)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_pactl_streams;
+
+    #[test]
+    fn parses_pactl_streams() {
+        let json = r#"[
+            {
+                "index": 57,
+                "corked": false,
+                "mute": false,
+                "volume": {
+                    "front-left": {"value": 65536, "value_percent": "100%", "db": "0.00 dB"},
+                    "front-right": {"value": 52429, "value_percent": "80%", "db": "-5.81 dB"}
+                },
+                "properties": {"application.name": "Firefox", "application.process.id": "4242"}
+            },
+            {
+                "index": 58,
+                "corked": true,
+                "mute": true,
+                "volume": {"mono": {"value": 32768, "value_percent": "50%", "db": "-18.06 dB"}},
+                "properties": {}
+            }
+        ]"#;
+
+        let streams = parse_pactl_streams(json);
+        assert_eq!(streams.len(), 2);
+        assert_eq!(streams[0].index, 57);
+        assert_eq!(streams[0].pid, Some(4242));
+        assert!(streams[0].is_playing);
+        assert_eq!(streams[0].volume_percent, 90);
+        assert_eq!(streams[1].pid, None);
+        assert!(!streams[1].is_playing);
+        assert!(streams[1].muted);
+    }
+
+    #[test]
+    fn ignores_unparseable_output() {
+        assert!(parse_pactl_streams("Invalid format").is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! User-defined automation hooks: a shell command run when an app whose name matches a
+//! regex starts, or when its CPU or RAM usage rises to a threshold, e.g. to lower a game's
+//! priority or post to a chat. Commands run detached with `sh -c`, and a hook runs at most
+//! once a minute, so an app hovering around a threshold does not start it on every refresh.
+
+use super::*;
+use crate::config::{AutomationHook, HookTrigger};
+use highlight::compile_pattern;
+use regex::Regex;
+
+/// Shortest time between two runs of the same hook; runs due sooner are dropped.
+const HOOK_MIN_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default)]
+pub(super) struct HookRunner {
+    /// The hooks `compiled` was built from, to notice config changes.
+    hooks: Vec<AutomationHook>,
+    compiled: Vec<Option<Regex>>,
+    last_run: Vec<Option<Instant>>,
+    /// App ids running at the last refresh; `None` before the first, so apps already
+    /// running when the task monitor starts do not count as started.
+    running: Option<HashSet<String>>,
+    /// Hook index and app id of the apps at or above a hook's threshold at the last refresh.
+    over: HashSet<(usize, String)>,
+}
+
+/// The command with `{app_id}`, `{pid}`, and `{name}` replaced, each quoted for the shell.
+/// Values are not searched for placeholders again.
+fn expand_command(command: &str, app_id: &str, pid: u32, name: &str) -> String {
+    let values = [
+        ("{app_id}", debug_tools::shell_quote(app_id)),
+        ("{pid}", pid.to_string()),
+        ("{name}", debug_tools::shell_quote(name)),
+    ];
+    let mut expanded = String::with_capacity(command.len());
+    let mut rest = command;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                expanded.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                expanded.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Whether the app is at or above the hook's threshold; never for start hooks.
+fn is_over(trigger: HookTrigger, cpu_percent: f32, ram_percent: f32) -> bool {
+    match trigger {
+        HookTrigger::Start => false,
+        HookTrigger::CpuAbove(threshold) => cpu_percent >= f32::from(threshold),
+        HookTrigger::RamAbove(threshold) => ram_percent >= f32::from(threshold),
+    }
+}
+
+impl AppModel {
+    /// Runs the hooks of apps that started, or reached a threshold, since the last refresh.
+    pub(super) fn run_automation_hooks(&mut self) {
+        // Hooks are about apps, so other groupings pause them, as they do alerts.
+        if self.processes_page.group_by != GroupBy::App {
+            return;
+        }
+
+        let runner = &mut self.hook_runner;
+        if runner.hooks != self.config.automation_hooks {
+            runner.hooks = self.config.automation_hooks.clone();
+            runner.compiled = runner
+                .hooks
+                .iter()
+                .map(|hook| compile_pattern(&hook.pattern).ok())
+                .collect();
+            runner.last_run = vec![None; runner.hooks.len()];
+            runner.over.clear();
+        }
+        let previous = runner.running.replace(
+            self.process_entries
+                .iter()
+                .map(|entry| entry.app_id.clone())
+                .collect(),
+        );
+        if runner.hooks.is_empty() {
+            return;
+        }
+
+        let total_memory = self.system.total_memory();
+        let mut over = HashSet::new();
+        let mut due = Vec::new();
+        for entry in &self.process_entries {
+            let ram_percent = if total_memory > 0 {
+                entry.rss_bytes as f32 / total_memory as f32 * 100.0
+            } else {
+                0.0
+            };
+            for (index, (hook, regex)) in runner.hooks.iter().zip(&runner.compiled).enumerate() {
+                let Some(regex) = regex else {
+                    continue;
+                };
+                if ![&entry.name, &entry.display_name, &entry.app_id]
+                    .iter()
+                    .any(|text| regex.is_match(text))
+                {
+                    continue;
+                }
+                let fired = match hook.trigger {
+                    HookTrigger::Start => previous
+                        .as_ref()
+                        .is_some_and(|running| !running.contains(&entry.app_id)),
+                    trigger if is_over(trigger, entry.cpu_percent, ram_percent) => {
+                        let key = (index, entry.app_id.clone());
+                        let crossed = !runner.over.contains(&key);
+                        over.insert(key);
+                        crossed
+                    }
+                    _ => false,
+                };
+                if fired {
+                    let command =
+                        expand_command(&hook.command, &entry.app_id, entry.pid, &entry.name);
+                    due.push((index, command));
+                }
+            }
+        }
+        runner.over = over;
+
+        for (index, command) in due {
+            let last_run = &mut runner.last_run[index];
+            if last_run.is_some_and(|run| run.elapsed() < HOOK_MIN_INTERVAL) {
+                continue;
+            }
+            *last_run = Some(Instant::now());
+            if !Self::spawn_detached(Command::new("sh").arg("-c").arg(&command)) {
+                eprintln!("failed to run automation hook `{command}`");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand_command;
+
+    #[test]
+    fn replaces_placeholders_quoted() {
+        assert_eq!(
+            expand_command("renice 10 -p {pid} # {app_id}", "steam", 4242, "Steam"),
+            "renice 10 -p 4242 # steam"
+        );
+        assert_eq!(
+            expand_command("notify-send {name} {unknown}", "x", 1, "It's {pid}"),
+            r"notify-send 'It'\''s {pid}' {unknown}"
+        );
+    }
+}
@@ -54,6 +54,7 @@ impl AppModel {
             _ if !self.autostart_add_options.is_empty() => Some(0),
             _ => None,
         };
+        self.refresh_nav_counters();
     }
 
     pub(super) fn open_autostart_modal(&mut self) {
@@ -570,25 +571,27 @@ impl AppModel {
     }
 
     fn autostart_table(&self, entries: &[AutostartEntry], space_s: u16) -> Element<'_, Message> {
+        let cell_padding = self.config.density.cell_padding();
+        let row_icon_size = self.config.density.row_icon_size();
         let entry_count = entries.len();
         let owned_entries = entries.to_vec();
 
         let list_headers = widget::row::with_capacity(3)
             .push(
                 widget::container(widget::text(fl!("table-name")))
-                    .padding(10)
+                    .padding(cell_padding)
                     .class(theme::Container::custom(table_cell_style))
                     .width(Length::FillPortion(4)),
             )
             .push(
                 widget::container(widget::text(fl!("autostart-table-path")))
-                    .padding(10)
+                    .padding(cell_padding)
                     .class(theme::Container::custom(table_cell_style))
                     .width(Length::FillPortion(3)),
             )
             .push(
                 widget::container(widget::text(fl!("autostart-table-exec")))
-                    .padding(10)
+                    .padding(cell_padding)
                     .class(theme::Container::custom(table_cell_style))
                     .width(Length::FillPortion(5)),
             )
@@ -596,7 +599,7 @@ impl AppModel {
 
         let rows: Element<'_, Message> = if owned_entries.is_empty() {
             widget::container(widget::text(fl!("autostart-section-empty")))
-                .padding(10)
+                .padding(cell_padding)
                 .class(theme::Container::custom(table_cell_style))
                 .width(Length::Fill)
                 .into()
@@ -615,7 +618,7 @@ impl AppModel {
                         let name_cell: Element<'_, Message> =
                             if let Some(icon_handle) = entry.icon_handle {
                                 widget::row::with_capacity(2)
-                                    .push(icon::icon(icon_handle).size(18))
+                                    .push(icon::icon(icon_handle).size(row_icon_size))
                                     .push(
                                         widget::text(display_name.clone())
                                             .width(Length::Fill)
@@ -643,7 +646,7 @@ impl AppModel {
                                 widget::row::with_capacity(3)
                                     .push(
                                         widget::container(name_cell)
-                                            .padding(10)
+                                            .padding(cell_padding)
                                             .class(theme::Container::custom(table_cell_style))
                                             .width(Length::FillPortion(4)),
                                     )
@@ -656,7 +659,7 @@ impl AppModel {
                                                     cosmic::iced_core::text::EllipsizeHeightLimit::Lines(1),
                                                 )),
                                         )
-                                        .padding(10)
+                                        .padding(cell_padding)
                                         .class(theme::Container::custom(table_cell_style))
                                         .width(Length::FillPortion(3)),
                                     )
@@ -669,7 +672,7 @@ impl AppModel {
                                                     cosmic::iced_core::text::EllipsizeHeightLimit::Lines(1),
                                                 )),
                                         )
-                                        .padding(10)
+                                        .padding(cell_padding)
                                         .class(theme::Container::custom(table_cell_style))
                                         .width(Length::FillPortion(5)),
                                     )
@@ -699,10 +702,12 @@ impl AppModel {
     }
 
     fn autostart_tiles(&self, entries: &[AutostartEntry], space_s: u16) -> Element<'_, Message> {
+        let density = self.config.density;
+        let tile_icon_size = density.tile_icon_size();
         let owned_entries = entries.to_vec();
         if owned_entries.is_empty() {
             return widget::container(widget::text(fl!("autostart-section-empty")))
-                .padding(10)
+                .padding(density.cell_padding())
                 .class(theme::Container::custom(table_cell_style))
                 .width(Length::Fill)
                 .into();
@@ -719,10 +724,10 @@ impl AppModel {
                 let display_exec = entry.exec.clone();
                 let icon_content: Element<'_, Message> =
                     if let Some(icon_handle) = entry.icon_handle {
-                        icon::icon(icon_handle).size(56).into()
+                        icon::icon(icon_handle).size(tile_icon_size).into()
                     } else {
                         widget::container(widget::text(""))
-                            .width(Length::Fixed(56.0))
+                            .width(Length::Fixed(f32::from(tile_icon_size)))
                             .into()
                     };
 
@@ -761,13 +766,16 @@ impl AppModel {
                     widget::button::custom(
                         widget::container(
                             widget::row::with_capacity(2)
-                                .push(widget::container(icon_content).center_x(Length::Fixed(56.0)))
+                                .push(
+                                    widget::container(icon_content)
+                                        .center_x(Length::Fixed(f32::from(tile_icon_size))),
+                                )
                                 .push(details)
                                 .spacing(25)
                                 .align_y(Alignment::Center)
                                 .width(Length::Fill),
                         )
-                        .padding(12)
+                        .padding(density.cell_padding() + 2)
                         .class(theme::Container::custom(table_cell_style))
                         .width(Length::Fill),
                     )
@@ -879,7 +887,7 @@ impl AppModel {
                     name,
                     exec,
                     is_background: parsed.no_display,
-                    icon_handle: matched_meta.and_then(|meta| meta.icon_handle),
+                    icon_handle: matched_meta.and_then(|meta| meta.icon_handle.as_deref().cloned()),
                 });
             }
         }
@@ -1048,7 +1056,7 @@ impl AppModel {
     }
 
     fn pick_desktop_file_path() -> std::io::Result<Option<PathBuf>> {
-        let zenity_result = Self::pick_desktop_file_with_command(
+        let zenity_result = Self::pick_file_with_command(
             "zenity",
             &[
                 "--file-selection",
@@ -1062,7 +1070,7 @@ impl AppModel {
             Err(err) => return Err(err),
         }
 
-        let kdialog_result = Self::pick_desktop_file_with_command(
+        let kdialog_result = Self::pick_file_with_command(
             "kdialog",
             &[
                 "--title",
@@ -1082,7 +1090,8 @@ impl AppModel {
         }
     }
 
-    fn pick_desktop_file_with_command(
+    /// Runs a file chooser such as zenity, returning `None` when it was cancelled.
+    pub(super) fn pick_file_with_command(
         program: &str,
         args: &[&str],
     ) -> std::io::Result<Option<PathBuf>> {
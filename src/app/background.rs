@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Background processes left out of the Apps page, by kind: services and daemons, helpers
+//! and applets, interactive shells, and the toolkit portals behind file choosers and
+//! screen sharing. Each kind can be shown from the Filter menu, and single apps such as
+//! `syncthing` can be kept visible from the settings whatever their name looks like.
+
+use super::*;
+use crate::config::BackgroundFilter;
+
+const SHELLS: [&str; 11] = [
+    "bash", "dash", "sh", "zsh", "fish", "ksh", "mksh", "tcsh", "csh", "nu", "xonsh",
+];
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum BackgroundKind {
+    Service,
+    Helper,
+    Shell,
+    Portal,
+}
+
+impl BackgroundKind {
+    pub const ALL: [BackgroundKind; 4] = [
+        BackgroundKind::Service,
+        BackgroundKind::Helper,
+        BackgroundKind::Shell,
+        BackgroundKind::Portal,
+    ];
+
+    pub(super) fn label(self) -> String {
+        match self {
+            BackgroundKind::Service => fl!("filter-show-services"),
+            BackgroundKind::Helper => fl!("filter-show-helpers"),
+            BackgroundKind::Shell => fl!("filter-show-shells"),
+            BackgroundKind::Portal => fl!("filter-show-portals"),
+        }
+    }
+}
+
+/// The kind of background process a name suggests, e.g. `xdg-desktop-portal-gtk` is a
+/// portal and `nm-applet` a helper. Only the freedesktop portals count as portals, so a game
+/// like `portal2_linux` stays visible.
+fn classify_token(token: &str) -> Option<BackgroundKind> {
+    let token = token.trim().to_ascii_lowercase();
+    if token.is_empty() {
+        None
+    } else if token.starts_with("xdg-") && token.contains("portal") {
+        Some(BackgroundKind::Portal)
+    } else if token.contains("helper") || token.contains("applet") {
+        Some(BackgroundKind::Helper)
+    } else if token.contains("daemon") || token.contains("service") {
+        Some(BackgroundKind::Service)
+    } else if SHELLS.contains(&token.as_str()) {
+        Some(BackgroundKind::Shell)
+    } else {
+        None
+    }
+}
+
+fn is_shown(filter: &BackgroundFilter, kind: BackgroundKind) -> bool {
+    match kind {
+        BackgroundKind::Service => filter.services,
+        BackgroundKind::Helper => filter.helpers,
+        BackgroundKind::Shell => filter.shells,
+        BackgroundKind::Portal => filter.portals,
+    }
+}
+
+/// Names a process goes by: its executable, the program in its command line, and its
+/// process name, e.g. `syncthing` for `/usr/bin/syncthing serve`.
+fn process_tokens(process: &ProcessSample) -> Vec<String> {
+    let stem = |path: &Path| {
+        path.file_stem()
+            .or_else(|| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+    };
+    let mut tokens = Vec::with_capacity(3);
+    tokens.extend(process.exe.as_deref().and_then(stem));
+    if let Some(cmd0) = process.cmd.first() {
+        tokens.push(stem(Path::new(cmd0)).unwrap_or_else(|| cmd0.clone()));
+    }
+    tokens.push(process.name.to_string());
+    tokens
+}
+
+/// Whether `process` belongs to `revealed_app`, the app a deep link selected.
+fn belongs_to_revealed_app(
+    revealed_app: Option<&str>,
+    process: &ProcessSample,
+    processes: &HashMap<Pid, ProcessSample>,
+    desktop_apps: &HashMap<String, DesktopAppMeta>,
+) -> bool {
+    revealed_app.is_some_and(|app_id| {
+        AppModel::resolved_app_id_for_process(process, processes, desktop_apps).as_deref()
+            == Some(app_id)
+    })
+}
+
+impl AppModel {
+    fn is_always_visible(&self, name: &str) -> bool {
+        self.config
+            .always_visible
+            .iter()
+            .any(|visible| visible.eq_ignore_ascii_case(name))
+    }
+
+    /// Whether a process is a kind of background process the Filter menu hides.
+    pub(super) fn is_hidden_background_process(&self, process: &ProcessSample) -> bool {
+        let filter = &self.config.background_filter;
+        let tokens = process_tokens(process);
+        !tokens.iter().any(|token| self.is_always_visible(token))
+            && tokens
+                .iter()
+                .filter_map(|token| classify_token(token))
+                .any(|kind| !is_shown(filter, kind))
+            && !belongs_to_revealed_app(
+                self.revealed_app.as_deref(),
+                process,
+                self.process_source.processes(),
+                &self.desktop_apps_by_exec,
+            )
+    }
+
+    /// Apps hidden by id: COSMIC's own shell components, and portals and daemons unless the
+    /// Filter menu shows them or they are kept visible.
+    pub(super) fn is_excluded_app_id(&self, app_id: &str) -> bool {
+        let filter = &self.config.background_filter;
+        app_id.contains("cosmicapplet")
+            || app_id.contains("cosmic-applet")
+            || app_id.contains("cosmic-panel-button")
+            || app_id.contains("cosmic-status-area")
+            || app_id.contains("cosmic-notifications")
+            || app_id.contains("cosmic-osd")
+            || app_id.contains("cosmic-workspaces")
+            || app_id.contains("cosmic-launcher")
+            || app_id.contains("cosmic-greeter")
+            || (!self.is_always_visible(app_id)
+                && self.revealed_app.as_deref() != Some(app_id)
+                && ((!filter.portals && app_id.contains("xdg-desktop-portal"))
+                    || (!filter.services && app_id.contains("daemon"))))
+    }
+
+    pub(super) fn set_always_visible(&mut self, names: Vec<String>) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_always_visible(handler, names) {
+                eprintln!("failed to save always visible apps: {err}");
+            }
+        } else {
+            self.config.always_visible = names;
+        }
+        self.refresh_processes();
+    }
+
+    pub(super) fn toggle_background_kind(&mut self, kind: BackgroundKind) {
+        let mut filter = self.config.background_filter;
+        match kind {
+            BackgroundKind::Service => filter.services = !filter.services,
+            BackgroundKind::Helper => filter.helpers = !filter.helpers,
+            BackgroundKind::Shell => filter.shells = !filter.shells,
+            BackgroundKind::Portal => filter.portals = !filter.portals,
+        }
+        // The filter is the user's again, also for an app a deep link revealed.
+        self.revealed_app = None;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_background_filter(handler, filter) {
+                eprintln!("failed to save background filter: {err}");
+            }
+        } else {
+            self.config.background_filter = filter;
+        }
+        self.refresh_processes();
+    }
+
+    pub(super) fn background_filter_menu_items(&self) -> Vec<menu::Item<MenuAction, String>> {
+        BackgroundKind::ALL
+            .into_iter()
+            .map(|kind| {
+                menu::Item::CheckBox(
+                    kind.label(),
+                    None,
+                    is_shown(&self.config.background_filter, kind),
+                    MenuAction::ToggleBackgroundKind(kind),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackgroundKind, belongs_to_revealed_app, classify_token};
+    use crate::app::source::ProcessSample;
+    use std::collections::HashMap;
+    use sysinfo::Pid;
+
+    #[test]
+    fn classifies_background_names() {
+        assert_eq!(
+            classify_token("xdg-desktop-portal-gtk"),
+            Some(BackgroundKind::Portal)
+        );
+        assert_eq!(
+            classify_token("xdg-document-portal"),
+            Some(BackgroundKind::Portal)
+        );
+        assert_eq!(classify_token("portal2_linux"), None);
+        assert_eq!(classify_token("nm-applet"), Some(BackgroundKind::Helper));
+        assert_eq!(classify_token("Steam Helper"), Some(BackgroundKind::Helper));
+        assert_eq!(
+            classify_token("gvfs-udisks2-volume-monitor-daemon"),
+            Some(BackgroundKind::Service)
+        );
+        assert_eq!(classify_token("zsh"), Some(BackgroundKind::Shell));
+        assert_eq!(classify_token("zshdb"), None);
+        assert_eq!(classify_token("firefox"), None);
+        assert_eq!(classify_token(" "), None);
+    }
+
+    #[test]
+    fn deep_links_reveal_hidden_apps() {
+        let daemon = ProcessSample {
+            pid: 4400,
+            parent: Some(1),
+            start_time: 0,
+            name: "geoclue-daemon".into(),
+            exe: None,
+            cmd: vec!["/usr/libexec/geoclue-daemon".to_string()].into(),
+            user_id: Some(1000),
+            state: 'S',
+            cpu_usage: 0.0,
+            memory: 0,
+            threads: None,
+            read_bytes: 0,
+            written_bytes: 0,
+        };
+        assert_eq!(classify_token(&daemon.name), Some(BackgroundKind::Service));
+        let processes = HashMap::from([(Pid::from_u32(daemon.pid), daemon.clone())]);
+        let desktop_apps = HashMap::new();
+
+        let revealed = |app_id| belongs_to_revealed_app(app_id, &daemon, &processes, &desktop_apps);
+        assert!(revealed(Some("geoclue-daemon")));
+        assert!(!revealed(Some("firefox")));
+        assert!(!revealed(None));
+    }
+}
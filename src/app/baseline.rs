@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Frozen process snapshots used to show what changed since a user-chosen moment.
+
+use super::*;
+
+#[derive(Debug, Clone)]
+struct BaselineApp {
+    display_name: String,
+    cpu_percent: f32,
+    rss_bytes: u64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ProcessBaseline {
+    marked_at: Instant,
+    cpu_percent: f32,
+    ram_used_bytes: u64,
+    apps: HashMap<String, BaselineApp>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct AppDelta {
+    pub(super) display_name: String,
+    pub(super) cpu_delta: f32,
+    pub(super) ram_delta: i64,
+    pub(super) is_new: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct BaselineDiff {
+    pub(super) elapsed: Duration,
+    pub(super) cpu_delta: f32,
+    pub(super) ram_delta: i64,
+    /// Apps that are still or newly running, largest RAM change first.
+    pub(super) apps: Vec<AppDelta>,
+    pub(super) exited: Vec<String>,
+}
+
+fn byte_delta(current: u64, previous: u64) -> i64 {
+    current as i64 - previous as i64
+}
+
+impl ProcessBaseline {
+    pub(super) fn capture(entries: &[ProcessEntry], cpu_percent: f32, ram_used_bytes: u64) -> Self {
+        Self {
+            marked_at: Instant::now(),
+            cpu_percent,
+            ram_used_bytes,
+            apps: entries
+                .iter()
+                .map(|entry| {
+                    (
+                        entry.app_id.clone(),
+                        BaselineApp {
+                            display_name: entry.display_name.clone(),
+                            cpu_percent: entry.cpu_percent,
+                            rss_bytes: entry.rss_bytes,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub(super) fn diff(
+        &self,
+        entries: &[ProcessEntry],
+        cpu_percent: f32,
+        ram_used_bytes: u64,
+    ) -> BaselineDiff {
+        let mut apps = entries
+            .iter()
+            .map(|entry| match self.apps.get(&entry.app_id) {
+                Some(previous) => AppDelta {
+                    display_name: entry.display_name.clone(),
+                    cpu_delta: entry.cpu_percent - previous.cpu_percent,
+                    ram_delta: byte_delta(entry.rss_bytes, previous.rss_bytes),
+                    is_new: false,
+                },
+                None => AppDelta {
+                    display_name: entry.display_name.clone(),
+                    cpu_delta: entry.cpu_percent,
+                    ram_delta: entry.rss_bytes as i64,
+                    is_new: true,
+                },
+            })
+            .collect::<Vec<_>>();
+        apps.sort_by(|a, b| {
+            b.ram_delta
+                .unsigned_abs()
+                .cmp(&a.ram_delta.unsigned_abs())
+                .then_with(|| a.display_name.cmp(&b.display_name))
+        });
+
+        let running = entries
+            .iter()
+            .map(|entry| entry.app_id.as_str())
+            .collect::<HashSet<_>>();
+        let mut exited = self
+            .apps
+            .iter()
+            .filter(|(app_id, _)| !running.contains(app_id.as_str()))
+            .map(|(_, app)| app.display_name.clone())
+            .collect::<Vec<_>>();
+        exited.sort();
+
+        BaselineDiff {
+            elapsed: self.marked_at.elapsed(),
+            cpu_delta: cpu_percent - self.cpu_percent,
+            ram_delta: byte_delta(ram_used_bytes, self.ram_used_bytes),
+            apps,
+            exited,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(app_id: &str, cpu_percent: f32, rss_bytes: u64) -> ProcessEntry {
+        ProcessEntry {
+            cpu_percent,
+            rss_bytes,
+            ..ProcessEntry::for_app(app_id)
+        }
+    }
+
+    #[test]
+    fn diff_reports_new_changed_and_exited_apps() {
+        let baseline = ProcessBaseline::capture(
+            &[entry("editor", 2.0, 400), entry("chat", 1.0, 300)],
+            10.0,
+            1_000,
+        );
+        let diff = baseline.diff(
+            &[entry("editor", 5.0, 900), entry("game", 40.0, 2_000)],
+            55.0,
+            3_000,
+        );
+
+        assert_eq!(diff.cpu_delta, 45.0);
+        assert_eq!(diff.ram_delta, 2_000);
+        assert_eq!(diff.exited, vec!["chat".to_string()]);
+        assert_eq!(diff.apps[0].display_name, "game");
+        assert!(diff.apps[0].is_new);
+        assert_eq!(diff.apps[1].ram_delta, 500);
+        assert!(!diff.apps[1].is_new);
+    }
+}
@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The selected app's usage split by the kind of process, for Chromium-based browsers and
+//! Electron apps. Their members carry a `--type=` argument, e.g. `--type=gpu-process` or
+//! `--type=renderer`, and the network service is a utility process with
+//! `--utility-sub-type=network.mojom.NetworkService`. The main process has no type. This
+//! shows whether the GPU process, the tabs, or the network service hold the memory.
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+enum BrowserProcessKind {
+    Browser,
+    Gpu,
+    Renderer,
+    Network,
+    Utility,
+    Other,
+}
+
+impl BrowserProcessKind {
+    fn label(self) -> String {
+        match self {
+            Self::Browser => fl!("browser-process-main"),
+            Self::Gpu => fl!("browser-process-gpu"),
+            Self::Renderer => fl!("browser-process-renderer"),
+            Self::Network => fl!("browser-process-network"),
+            Self::Utility => fl!("browser-process-utility"),
+            Self::Other => fl!("browser-process-other"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProcessTypeUsage {
+    kind: BrowserProcessKind,
+    processes: u32,
+    rss_bytes: u64,
+    cpu_percent: f32,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct BrowserProcesses {
+    app_id: String,
+    types: Vec<ProcessTypeUsage>,
+}
+
+/// The kind of a member from its command line, or `None` when it has no `--type=`.
+fn process_kind(cmd: &[String]) -> Option<BrowserProcessKind> {
+    let process_type = cmd.iter().find_map(|arg| arg.strip_prefix("--type="))?;
+    Some(match process_type {
+        "gpu-process" => BrowserProcessKind::Gpu,
+        "renderer" => BrowserProcessKind::Renderer,
+        "utility"
+            if cmd
+                .iter()
+                .any(|arg| arg == "--utility-sub-type=network.mojom.NetworkService") =>
+        {
+            BrowserProcessKind::Network
+        }
+        "utility" => BrowserProcessKind::Utility,
+        // Zygotes, the crash handler, and plugin brokers.
+        _ => BrowserProcessKind::Other,
+    })
+}
+
+/// Usage per kind of the members, given as command line, CPU, and memory. Empty unless a
+/// member has a type, so apps that are not Chromium-based are not split.
+fn split_by_kind<'a>(
+    members: impl IntoIterator<Item = (&'a [String], f32, u64)>,
+) -> Vec<ProcessTypeUsage> {
+    let mut typed = false;
+    let mut types: Vec<ProcessTypeUsage> = Vec::new();
+    for (cmd, cpu_percent, rss_bytes) in members {
+        let kind = process_kind(cmd);
+        typed |= kind.is_some();
+        let kind = kind.unwrap_or(BrowserProcessKind::Browser);
+        let Some(usage) = types.iter_mut().find(|usage| usage.kind == kind) else {
+            types.push(ProcessTypeUsage {
+                kind,
+                processes: 1,
+                rss_bytes,
+                cpu_percent,
+            });
+            continue;
+        };
+        usage.processes += 1;
+        usage.rss_bytes += rss_bytes;
+        usage.cpu_percent += cpu_percent;
+    }
+    if !typed {
+        return Vec::new();
+    }
+    types.sort_by_key(|usage| usage.kind);
+    types
+}
+
+impl AppModel {
+    pub(super) fn refresh_selected_browser_processes(&mut self) {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            self.browser_processes = BrowserProcesses::default();
+            return;
+        };
+        let processes = self.process_source.processes();
+        let cpu_core_count = self.process_source.cpu_count().max(1) as f32;
+        let members = entry.pids.iter().filter_map(|pid| {
+            let process = processes.get(&Pid::from_u32(*pid))?;
+            Some((
+                &process.cmd[..],
+                (process.cpu_usage / cpu_core_count).clamp(0.0, 100.0),
+                process.memory,
+            ))
+        });
+        self.browser_processes = BrowserProcesses {
+            app_id: entry.app_id.clone(),
+            types: split_by_kind(members),
+        };
+    }
+
+    /// The selected app's usage per kind of process, for Chromium-based apps.
+    pub(super) fn selected_app_browser_processes(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let browser = &self.browser_processes;
+        if browser.app_id != selected.app_id || browser.types.is_empty() {
+            return None;
+        }
+        let column = widget::column::with_capacity(browser.types.len() + 1)
+            .push(widget::text::heading(fl!("browser-processes")));
+        let column = browser.types.iter().fold(column, |column, usage| {
+            column.push(
+                widget::text(fl!(
+                    "browser-process-row",
+                    kind = usage.kind.label(),
+                    count = usage.processes,
+                    size = units::format_bytes(usage.rss_bytes),
+                    cpu = units::format_percent(usage.cpu_percent, 1)
+                ))
+                .size(12),
+            )
+        });
+        Some(column.spacing(4).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BrowserProcessKind, split_by_kind};
+
+    #[test]
+    fn splits_chromium_members_by_type() {
+        let cmd = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        let main = cmd(&["/opt/google/chrome/chrome"]);
+        let gpu = cmd(&["chrome", "--type=gpu-process"]);
+        let tab = cmd(&["chrome", "--type=renderer", "--renderer-client-id=7"]);
+        let network = cmd(&[
+            "chrome",
+            "--type=utility",
+            "--utility-sub-type=network.mojom.NetworkService",
+        ]);
+        let audio = cmd(&[
+            "chrome",
+            "--type=utility",
+            "--utility-sub-type=audio.mojom.AudioService",
+        ]);
+        let zygote = cmd(&["chrome", "--type=zygote"]);
+
+        let members = [
+            (tab.as_slice(), 4.0, 300),
+            (main.as_slice(), 1.0, 200),
+            (network.as_slice(), 0.5, 50),
+            (tab.as_slice(), 2.0, 100),
+            (gpu.as_slice(), 3.0, 400),
+            (audio.as_slice(), 0.0, 20),
+            (zygote.as_slice(), 0.0, 10),
+        ];
+        let split = split_by_kind(members)
+            .into_iter()
+            .map(|usage| (usage.kind, usage.processes, usage.rss_bytes))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            split,
+            [
+                (BrowserProcessKind::Browser, 1, 200),
+                (BrowserProcessKind::Gpu, 1, 400),
+                (BrowserProcessKind::Renderer, 2, 400),
+                (BrowserProcessKind::Network, 1, 50),
+                (BrowserProcessKind::Utility, 1, 20),
+                (BrowserProcessKind::Other, 1, 10),
+            ]
+        );
+
+        let editor = cmd(&["/usr/bin/gedit"]);
+        assert!(split_by_kind([(editor.as_slice(), 1.0, 100)]).is_empty());
+    }
+}
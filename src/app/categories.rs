@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Desktop entry categories and the group keys used by the group-by selector.
+
+use super::*;
+
+/// Freedesktop main categories in the order they win when an entry lists several,
+/// paired with the category they are grouped under.
+const MAIN_CATEGORIES: [(&str, &str); 13] = [
+    ("Game", "Game"),
+    ("Development", "Development"),
+    ("Graphics", "Graphics"),
+    ("AudioVideo", "AudioVideo"),
+    ("Audio", "AudioVideo"),
+    ("Video", "AudioVideo"),
+    ("Office", "Office"),
+    ("Education", "Education"),
+    ("Science", "Science"),
+    ("Network", "Network"),
+    ("Settings", "System"),
+    ("System", "System"),
+    ("Utility", "Utility"),
+];
+
+impl AppModel {
+    pub(super) fn primary_desktop_category(categories: &[String]) -> Option<&'static str> {
+        MAIN_CATEGORIES
+            .iter()
+            .find(|(main, _)| {
+                categories
+                    .iter()
+                    .any(|category| category.eq_ignore_ascii_case(main))
+            })
+            .map(|(_, group)| *group)
+    }
+
+    pub(super) fn category_label(category: Option<&str>) -> String {
+        match category {
+            Some("Game") => fl!("category-game"),
+            Some("Development") => fl!("category-development"),
+            Some("Graphics") => fl!("category-graphics"),
+            Some("AudioVideo") => fl!("category-multimedia"),
+            Some("Office") => fl!("category-office"),
+            Some("Education") => fl!("category-education"),
+            Some("Science") => fl!("category-science"),
+            Some("Network") => fl!("category-network"),
+            Some("System") => fl!("category-system"),
+            Some("Utility") => fl!("category-utility"),
+            _ => fl!("category-other"),
+        }
+    }
+
+    pub(super) fn category_icon_name(category: Option<&str>) -> &'static str {
+        match category {
+            Some("Game") => "applications-games-symbolic",
+            Some("Development") => "applications-engineering-symbolic",
+            Some("Graphics") => "applications-graphics-symbolic",
+            Some("AudioVideo") => "applications-multimedia-symbolic",
+            Some("Office") => "applications-office-symbolic",
+            Some("Education") => "applications-education-symbolic",
+            Some("Science") => "applications-science-symbolic",
+            Some("Network") => "applications-internet-symbolic",
+            Some("System") => "applications-system-symbolic",
+            Some("Utility") => "applications-utilities-symbolic",
+            _ => "applications-other-symbolic",
+        }
+    }
+
+    /// Maps a resolved app onto the row it is aggregated into for the active grouping.
+    pub(super) fn group_for_process(
+        &self,
+        process: &ProcessSample,
+        app_id: Arc<str>,
+        app_name: Arc<str>,
+        app_icon_handle: Option<icon::Handle>,
+        app_category: Option<&str>,
+    ) -> (Arc<str>, Arc<str>, Option<icon::Handle>) {
+        match self.processes_page.group_by {
+            GroupBy::App => (app_id, app_name, app_icon_handle),
+            GroupBy::User => {
+                let user_name = process
+                    .user_id
+                    .and_then(|uid| sysinfo::Uid::try_from(uid as usize).ok())
+                    .and_then(|uid| self.users.get_user_by_id(&uid))
+                    .map(|user| user.name().to_string());
+                let uid = process
+                    .user_id
+                    .map(|uid| uid.to_string())
+                    .unwrap_or_else(|| "?".to_string());
+                (
+                    format!("user-{uid}").into(),
+                    user_name
+                        .unwrap_or_else(|| fl!("group-user-fallback", uid = uid))
+                        .into(),
+                    Some(icon::from_name("avatar-default-symbolic").handle()),
+                )
+            }
+            GroupBy::Category => (
+                format!(
+                    "category-{}",
+                    app_category.unwrap_or("Other").to_ascii_lowercase()
+                )
+                .into(),
+                Self::category_label(app_category).into(),
+                Some(icon::from_name(Self::category_icon_name(app_category)).handle()),
+            ),
+        }
+    }
+
+    /// Filter key of an entry; entries without a main category are filtered as "Other".
+    pub(super) fn category_filter_key(entry: &ProcessEntry) -> &'static str {
+        entry.category.unwrap_or("Other")
+    }
+
+    pub(super) fn matches_category_filter(&self, entry: &ProcessEntry) -> bool {
+        self.processes_page.group_by == GroupBy::User
+            || self.config.category_filter.is_empty()
+            || self
+                .config
+                .category_filter
+                .iter()
+                .any(|category| category == Self::category_filter_key(entry))
+    }
+
+    pub(super) fn toggle_category_filter(&mut self, category: String) {
+        let mut filter = self.config.category_filter.clone();
+        if let Some(index) = filter.iter().position(|value| *value == category) {
+            filter.remove(index);
+        } else {
+            filter.push(category);
+        }
+        self.set_category_filter(filter);
+    }
+
+    pub(super) fn set_category_filter(&mut self, filter: Vec<String>) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_category_filter(handler, filter) {
+                eprintln!("failed to save category filter: {err}");
+            }
+        } else {
+            self.config.category_filter = filter;
+        }
+    }
+
+    pub(super) fn category_filter_chips(&self, space_s: u16) -> Element<'_, Message> {
+        let mut categories = self
+            .process_entries
+            .iter()
+            .map(Self::category_filter_key)
+            .map(str::to_string)
+            .chain(self.config.category_filter.iter().cloned())
+            .collect::<Vec<_>>();
+        categories.sort_by_key(|category| Self::category_label(Some(category.as_str())));
+        categories.dedup();
+
+        let all_chip = if self.config.category_filter.is_empty() {
+            widget::button::suggested(fl!("category-all"))
+        } else {
+            widget::button::standard(fl!("category-all"))
+        }
+        .on_press(Message::Processes(ProcessesMessage::ClearCategoryFilter));
+
+        let chips = std::iter::once(all_chip.into())
+            .chain(categories.into_iter().map(|category| {
+                let label = Self::category_label(Some(category.as_str()));
+                let selected = self.config.category_filter.contains(&category);
+                if selected {
+                    widget::button::suggested(label)
+                } else {
+                    widget::button::standard(label)
+                }
+                .on_press(Message::Processes(ProcessesMessage::ToggleCategoryFilter(
+                    category,
+                )))
+                .into()
+            }))
+            .collect::<Vec<Element<'_, Message>>>();
+
+        widget::flex_row(chips)
+            .spacing(space_s)
+            .width(Length::Fill)
+            .into()
+    }
+}
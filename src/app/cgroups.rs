@@ -0,0 +1,206 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The cgroup v2 hierarchy systemd builds under `/sys/fs/cgroup`, e.g. `user.slice` →
+//! `user@1000.service` → `app.slice` → one scope per app, with each group's CPU and memory
+//! use read from its own `cpu.stat` and `memory.current` files.
+
+use super::*;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+#[derive(Debug, Clone)]
+pub(super) struct CgroupNode {
+    /// Path below the cgroup root, e.g. `user.slice/user-1000.slice`.
+    pub(super) path: String,
+    pub(super) name: String,
+    pub(super) depth: usize,
+    /// Share of the whole machine since the previous sample.
+    pub(super) cpu_percent: f32,
+    /// `None` where the memory controller is not enabled for the group.
+    pub(super) memory_bytes: Option<u64>,
+    pub(super) has_children: bool,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct CgroupTree {
+    /// Nodes below expanded groups, each parent followed by its children.
+    pub(super) nodes: Vec<CgroupNode>,
+    /// `usage_usec` per path from the previous sample.
+    previous_usage: HashMap<String, u64>,
+    sampled_at: Option<Instant>,
+}
+
+/// Reads `usage_usec` from a `cpu.stat` file.
+fn parse_cpu_usage_usec(contents: &str) -> Option<u64> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("usage_usec ")?.trim().parse().ok())
+}
+
+/// The cgroup path from the unified `0::/...` line of `/proc/<pid>/cgroup`.
+fn parse_own_cgroup(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("0::/"))
+        .map(|path| path.trim().to_string())
+}
+
+/// This app's own group, below the cgroup root.
+fn own_cgroup() -> Option<String> {
+    fs::read_to_string("/proc/self/cgroup")
+        .ok()
+        .and_then(|contents| parse_own_cgroup(&contents))
+}
+
+/// The directory of this app's own group.
+pub(super) fn own_cgroup_dir() -> Option<PathBuf> {
+    own_cgroup().map(|own| Path::new(CGROUP_ROOT).join(own))
+}
+
+/// The groups above this app, so the tree opens on the user's own slice and app scopes.
+pub(super) fn default_expanded_cgroups() -> HashSet<String> {
+    let Some(own) = own_cgroup() else {
+        return HashSet::new();
+    };
+    own.match_indices('/')
+        .map(|(index, _)| own[..index].to_string())
+        .collect()
+}
+
+/// Whether every pid in a `cgroup.procs` file is one of `pids`.
+fn holds_only(procs: &str, pids: &[u32]) -> bool {
+    procs
+        .lines()
+        .all(|line| line.trim().parse().is_ok_and(|pid| pids.contains(&pid)))
+}
+
+/// The group holding an app's members and nothing else, such as the scope COSMIC starts an
+/// app in. `None` when the members are spread over groups or share theirs with other
+/// processes, so that acting on the group cannot reach beyond the app. Processes whose
+/// `/proc` is not the one here, such as the host's seen from a Flatpak, have none, since
+/// their groups are not in the cgroup tree here either.
+pub(super) fn app_cgroup(source: &dyn SystemSource, pids: &[u32]) -> Option<PathBuf> {
+    let mut groups = pids.iter().map(|pid| {
+        let cgroup = fs::read_to_string(source.proc_dir(*pid)?.join("cgroup")).ok()?;
+        parse_own_cgroup(&cgroup)
+    });
+    let first = groups.next()??;
+    for group in groups {
+        if group? != first {
+            return None;
+        }
+    }
+    let dir = Path::new(CGROUP_ROOT).join(&first);
+    let procs = fs::read_to_string(dir.join("cgroup.procs")).ok()?;
+    (!first.is_empty() && holds_only(&procs, pids)).then_some(dir)
+}
+
+fn child_groups(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut children = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect::<Vec<_>>();
+    children.sort();
+    children
+}
+
+fn collect_cgroup(
+    path: String,
+    depth: usize,
+    expanded: &HashSet<String>,
+    nodes: &mut Vec<(CgroupNode, Option<u64>)>,
+) {
+    let dir = Path::new(CGROUP_ROOT).join(&path);
+    let usage = fs::read_to_string(dir.join("cpu.stat"))
+        .ok()
+        .and_then(|contents| parse_cpu_usage_usec(&contents));
+    let memory_bytes = fs::read_to_string(dir.join("memory.current"))
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok());
+    let children = child_groups(&dir);
+    let name = path.rsplit('/').next().unwrap_or_default().to_string();
+
+    let is_expanded = expanded.contains(&path);
+    nodes.push((
+        CgroupNode {
+            path: path.clone(),
+            name,
+            depth,
+            cpu_percent: 0.0,
+            memory_bytes,
+            has_children: !children.is_empty(),
+        },
+        usage,
+    ));
+    if is_expanded {
+        for child in children {
+            collect_cgroup(format!("{path}/{child}"), depth + 1, expanded, nodes);
+        }
+    }
+}
+
+impl AppModel {
+    /// Samples the groups shown on the Control groups page, only while it is open.
+    pub(super) fn refresh_cgroup_tree(&mut self) {
+        if self.active_page() != Page::Cgroups {
+            return;
+        }
+
+        let mut sampled = Vec::new();
+        for child in child_groups(Path::new(CGROUP_ROOT)) {
+            collect_cgroup(child, 0, &self.cgroups_page.expanded, &mut sampled);
+        }
+
+        let now = Instant::now();
+        let tree = &mut self.cgroup_tree;
+        let elapsed_usec = tree
+            .sampled_at
+            .map(|sampled_at| now.duration_since(sampled_at).as_micros() as f64)
+            .filter(|elapsed| *elapsed > 0.0);
+        let cpu_count = self.system.cpus().len().max(1) as f64;
+        let mut usage_by_path = HashMap::with_capacity(sampled.len());
+        tree.nodes = sampled
+            .into_iter()
+            .map(|(mut node, usage)| {
+                if let Some(usage) = usage {
+                    let previous = tree.previous_usage.get(&node.path).copied();
+                    if let (Some(previous), Some(elapsed)) = (previous, elapsed_usec) {
+                        let busy = usage.saturating_sub(previous) as f64;
+                        node.cpu_percent =
+                            (busy / elapsed / cpu_count * 100.0).clamp(0.0, 100.0) as f32;
+                    }
+                    usage_by_path.insert(node.path.clone(), usage);
+                }
+                node
+            })
+            .collect();
+        tree.previous_usage = usage_by_path;
+        tree.sampled_at = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{holds_only, parse_cpu_usage_usec, parse_own_cgroup};
+
+    #[test]
+    fn parses_cgroup_files() {
+        let cpu_stat = "usage_usec 1523000\nuser_usec 1200000\nsystem_usec 323000\n";
+        assert_eq!(parse_cpu_usage_usec(cpu_stat), Some(1_523_000));
+        assert_eq!(parse_cpu_usage_usec("nr_periods 0\n"), None);
+
+        let own = "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-x.scope\n";
+        assert_eq!(
+            parse_own_cgroup(own).as_deref(),
+            Some("user.slice/user-1000.slice/user@1000.service/app.slice/app-x.scope")
+        );
+        assert_eq!(parse_own_cgroup("1:name=systemd:/\n"), None);
+
+        assert!(holds_only("4242\n4250\n", &[4242, 4250, 4300]));
+        assert!(!holds_only("4242\n1\n", &[4242]));
+    }
+}
@@ -0,0 +1,339 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Canvas charts shared by the Resources page and the selected app's graphs: line, area,
+//! and bar series over a sample history, with optional value axes and a crosshair that
+//! follows the cursor and reads out the nearest sample.
+
+use super::*;
+use cosmic::iced::widget::canvas;
+use cosmic::iced::{Point, Rectangle, Size, mouse};
+
+/// Room left of the plot for the value axis labels.
+const AXIS_LABEL_WIDTH: f32 = 56.0;
+const AXIS_LABEL_SIZE: f32 = 11.0;
+const CHART_PADDING: f32 = 6.0;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum ChartKind {
+    Line,
+    Area,
+    Bar,
+}
+
+/// How values map to the chart's height.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum ChartScale {
+    /// 0 to 100 percent.
+    Percent,
+    /// 0 to a round number above the highest sample, labelled by the formatter.
+    Auto(fn(f32) -> String),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Chart {
+    samples: Vec<f32>,
+    kind: ChartKind,
+    accent: Color,
+    scale: ChartScale,
+    axes: bool,
+}
+
+/// The smallest 1, 2, or 5 times a power of ten that is at least `value`.
+fn nice_ceiling(value: f32) -> f32 {
+    if value <= 0.0 || !value.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10_f32.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|step| step * magnitude)
+        .find(|ceiling| *ceiling >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// The sample under horizontal offset `x` of a plot `width` wide, for `len` samples drawn
+/// as points (lines and areas) or as slots (bars).
+fn sample_at(len: usize, width: f32, x: f32, kind: ChartKind) -> Option<usize> {
+    if len == 0 || width <= 0.0 || !(0.0..=width).contains(&x) {
+        return None;
+    }
+    let index = if kind == ChartKind::Bar {
+        (x / width * len as f32).floor()
+    } else if len == 1 {
+        0.0
+    } else {
+        (x / width * (len - 1) as f32).round()
+    };
+    Some((index as usize).min(len - 1))
+}
+
+impl Chart {
+    pub(super) fn new(samples: &[f32], kind: ChartKind, accent: Color) -> Self {
+        Self {
+            samples: samples.to_vec(),
+            kind,
+            accent,
+            scale: ChartScale::Percent,
+            axes: false,
+        }
+    }
+
+    pub(super) fn scale(mut self, scale: ChartScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub(super) fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    pub(super) fn view<'a>(self, height: f32) -> Element<'a, Message> {
+        canvas(self)
+            .width(Length::Fill)
+            .height(Length::Fixed(height))
+            .into()
+    }
+
+    fn max_value(&self) -> f32 {
+        match self.scale {
+            ChartScale::Percent => 100.0,
+            ChartScale::Auto(_) => nice_ceiling(self.samples.iter().copied().fold(0.0, f32::max)),
+        }
+    }
+
+    fn format_value(&self, value: f32) -> String {
+        match self.scale {
+            ChartScale::Percent => units::format_percent(value, 0),
+            ChartScale::Auto(format) => format(value),
+        }
+    }
+
+    fn plot_area(&self, size: Size) -> Rectangle {
+        let left = if self.axes {
+            AXIS_LABEL_WIDTH
+        } else {
+            CHART_PADDING
+        };
+        Rectangle {
+            x: left,
+            y: CHART_PADDING,
+            width: (size.width - left - CHART_PADDING).max(1.0),
+            height: (size.height - 2.0 * CHART_PADDING).max(1.0),
+        }
+    }
+
+    /// Where sample `index` is drawn, at the top of its line or bar.
+    fn point(&self, plot: Rectangle, index: usize, max: f32) -> Point {
+        let len = self.samples.len().max(1);
+        let x = match self.kind {
+            ChartKind::Bar => plot.x + (index as f32 + 0.5) * plot.width / len as f32,
+            _ if len == 1 => plot.x + plot.width / 2.0,
+            _ => plot.x + index as f32 * plot.width / (len - 1) as f32,
+        };
+        let value = self.samples[index].clamp(0.0, max);
+        Point::new(x, plot.y + plot.height * (1.0 - value / max))
+    }
+
+    fn draw_axes(
+        &self,
+        frame: &mut canvas::Frame,
+        plot: Rectangle,
+        max: f32,
+        theme: &cosmic::Theme,
+    ) {
+        let divider: Color = theme.cosmic().bg_divider().into();
+        let text_color: Color = theme.cosmic().on_bg_color().into();
+        for fraction in [0.0, 0.5, 1.0] {
+            let y = plot.y + plot.height * (1.0 - fraction);
+            frame.stroke(
+                &canvas::Path::line(Point::new(plot.x, y), Point::new(plot.x + plot.width, y)),
+                canvas::Stroke::default()
+                    .with_color(divider)
+                    .with_width(1.0),
+            );
+            frame.fill_text(canvas::Text {
+                content: self.format_value(max * fraction),
+                position: Point::new(
+                    CHART_PADDING,
+                    (y - AXIS_LABEL_SIZE / 2.0).clamp(0.0, plot.y + plot.height),
+                ),
+                color: Color {
+                    a: 0.7,
+                    ..text_color
+                },
+                size: AXIS_LABEL_SIZE.into(),
+                ..Default::default()
+            });
+        }
+    }
+
+    fn draw_series(&self, frame: &mut canvas::Frame, plot: Rectangle, max: f32) {
+        let len = self.samples.len();
+        let points = (0..len)
+            .map(|index| self.point(plot, index, max))
+            .collect::<Vec<_>>();
+        let baseline = plot.y + plot.height;
+
+        match self.kind {
+            ChartKind::Bar => {
+                let slot = plot.width / len as f32;
+                let bar_width = (slot - 1.0).max(1.0);
+                for point in &points {
+                    let top = point.y.min(baseline - 1.0);
+                    frame.fill(
+                        &canvas::Path::rectangle(
+                            Point::new(point.x - bar_width / 2.0, top),
+                            Size::new(bar_width, baseline - top),
+                        ),
+                        Color {
+                            a: 0.75,
+                            ..self.accent
+                        },
+                    );
+                }
+            }
+            ChartKind::Line | ChartKind::Area => {
+                let line = canvas::Path::new(|builder| {
+                    builder.move_to(points[0]);
+                    for point in &points[1..] {
+                        builder.line_to(*point);
+                    }
+                });
+                if self.kind == ChartKind::Area {
+                    let area = canvas::Path::new(|builder| {
+                        builder.move_to(Point::new(points[0].x, baseline));
+                        for point in &points {
+                            builder.line_to(*point);
+                        }
+                        builder.line_to(Point::new(points[len - 1].x, baseline));
+                        builder.close();
+                    });
+                    frame.fill(
+                        &area,
+                        Color {
+                            a: 0.35,
+                            ..self.accent
+                        },
+                    );
+                }
+                frame.stroke(
+                    &line,
+                    canvas::Stroke::default()
+                        .with_color(self.accent)
+                        .with_width(1.5),
+                );
+            }
+        }
+    }
+
+    fn draw_crosshair(
+        &self,
+        frame: &mut canvas::Frame,
+        plot: Rectangle,
+        max: f32,
+        theme: &cosmic::Theme,
+        cursor: Point,
+    ) {
+        let Some(index) = sample_at(self.samples.len(), plot.width, cursor.x - plot.x, self.kind)
+        else {
+            return;
+        };
+        let point = self.point(plot, index, max);
+        let text_color: Color = theme.cosmic().on_bg_color().into();
+        let guide = canvas::Stroke::default()
+            .with_color(Color {
+                a: 0.5,
+                ..text_color
+            })
+            .with_width(1.0);
+        frame.stroke(
+            &canvas::Path::line(
+                Point::new(point.x, plot.y),
+                Point::new(point.x, plot.y + plot.height),
+            ),
+            guide,
+        );
+        frame.stroke(
+            &canvas::Path::line(
+                Point::new(plot.x, point.y),
+                Point::new(plot.x + plot.width, point.y),
+            ),
+            guide,
+        );
+        frame.fill(&canvas::Path::circle(point, 3.0), self.accent);
+
+        // The readout sits beside the point, on whichever side has room.
+        let readout_x = if point.x > plot.x + plot.width / 2.0 {
+            point.x - AXIS_LABEL_WIDTH
+        } else {
+            point.x + CHART_PADDING
+        };
+        frame.fill_text(canvas::Text {
+            content: self.format_value(self.samples[index]),
+            position: Point::new(readout_x, plot.y),
+            color: text_color,
+            size: (AXIS_LABEL_SIZE + 1.0).into(),
+            ..Default::default()
+        });
+    }
+}
+
+impl canvas::Program<Message, cosmic::Theme> for Chart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &cosmic::Renderer,
+        theme: &cosmic::Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        frame.fill(
+            &canvas::Path::rectangle(Point::ORIGIN, bounds.size()),
+            Color::from(theme.current_container().component.base),
+        );
+
+        let plot = self.plot_area(bounds.size());
+        let max = self.max_value();
+        if self.axes {
+            self.draw_axes(&mut frame, plot, max, theme);
+        }
+        if !self.samples.is_empty() {
+            self.draw_series(&mut frame, plot, max);
+            if let Some(cursor) = cursor.position_in(bounds) {
+                self.draw_crosshair(&mut frame, plot, max, theme, cursor);
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChartKind, nice_ceiling, sample_at};
+
+    #[test]
+    fn rounds_axis_maximum_up() {
+        assert_eq!(nice_ceiling(0.0), 1.0);
+        assert_eq!(nice_ceiling(0.3), 0.5);
+        assert_eq!(nice_ceiling(1.0), 1.0);
+        assert_eq!(nice_ceiling(13.0), 20.0);
+        assert_eq!(nice_ceiling(420.0), 500.0);
+        assert_eq!(nice_ceiling(700.0), 1000.0);
+    }
+
+    #[test]
+    fn finds_sample_under_cursor() {
+        assert_eq!(sample_at(5, 100.0, 0.0, ChartKind::Line), Some(0));
+        assert_eq!(sample_at(5, 100.0, 60.0, ChartKind::Line), Some(2));
+        assert_eq!(sample_at(5, 100.0, 100.0, ChartKind::Area), Some(4));
+        assert_eq!(sample_at(4, 100.0, 30.0, ChartKind::Bar), Some(1));
+        assert_eq!(sample_at(4, 100.0, 100.0, ChartKind::Bar), Some(3));
+        assert_eq!(sample_at(4, 100.0, 120.0, ChartKind::Bar), None);
+        assert_eq!(sample_at(0, 100.0, 10.0, ChartKind::Line), None);
+    }
+}
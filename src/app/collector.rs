@@ -0,0 +1,469 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Background collector that keeps performance history while the window is closed.
+//!
+//! The collector is the same binary started with `--collector`. It samples the
+//! performance graphs every refresh interval and serves the recorded history over
+//! a unix socket in `$XDG_RUNTIME_DIR`, so the window can attach on startup and
+//! continue the graphs instead of starting from an empty history. Without that per-user
+//! directory there is no collector, and requests from other users are ignored.
+
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read};
+use std::os::fd::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::sync::{Arc, Mutex};
+
+const COLLECTOR_SOCKET_NAME: &str = "cosmic-task-monitor.sock";
+const COLLECTOR_SERVICE_NAME: &str = "cosmic-task-monitor-collector.service";
+const COLLECTOR_IO_TIMEOUT: Duration = Duration::from_millis(500);
+const COLLECTOR_REQUEST_HISTORY: &str = "history";
+const COLLECTOR_REQUEST_STOP: &str = "stop";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(super) struct CollectorHistory {
+    cpu_usage_per_core: Vec<Vec<f32>>,
+    ram_usage: Vec<f32>,
+    gpu_usage: Vec<f32>,
+    gpu_vram_usage: Vec<f32>,
+    network_rx: HashMap<String, Vec<f32>>,
+    network_tx: HashMap<String, Vec<f32>>,
+    disk_read: HashMap<String, Vec<f32>>,
+    disk_write: HashMap<String, Vec<f32>>,
+}
+
+struct CollectorSampler {
+    system: System,
+    disks: Disks,
+    network_previous_snapshots: HashMap<String, NetworkIoSnapshot>,
+    history: CollectorHistory,
+    /// Samples kept per graph, from the retention set when the collector started.
+    retained: usize,
+    ticks: u64,
+}
+
+impl CollectorSampler {
+    fn new() -> Self {
+        let (_, config, _) = Config::load(<AppModel as cosmic::Application>::APP_ID);
+        Self {
+            system: System::new(),
+            disks: Disks::new_with_refreshed_list(),
+            network_previous_snapshots: HashMap::new(),
+            history: CollectorHistory::default(),
+            retained: history::retained_samples(config.history_retention),
+            ticks: 0,
+        }
+    }
+
+    fn sample(&mut self) {
+        let refresh_secs = PROCESS_REFRESH_INTERVAL.as_secs_f32().max(0.001);
+        self.ticks += 1;
+        let (retained, tick) = (self.retained, self.ticks);
+
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        let core_usages = self
+            .system
+            .cpus()
+            .iter()
+            .map(|cpu| cpu.cpu_usage().clamp(0.0, 100.0))
+            .collect::<Vec<_>>();
+        if self.history.cpu_usage_per_core.len() != core_usages.len() {
+            self.history.cpu_usage_per_core = vec![Vec::new(); core_usages.len()];
+        }
+        for (history, usage) in self.history.cpu_usage_per_core.iter_mut().zip(core_usages) {
+            history::push_sample(history, usage, retained, tick);
+        }
+
+        let total_memory = self.system.total_memory();
+        let used_memory = self.system.used_memory().min(total_memory);
+        let ram_usage = if total_memory > 0 {
+            (used_memory as f32 / total_memory as f32 * 100.0).clamp(0.0, 100.0)
+        } else {
+            0.0
+        };
+        history::push_sample(&mut self.history.ram_usage, ram_usage, retained, tick);
+
+        let gpu_runtime = AppModel::read_gpu_runtime_info();
+        if let Some(gpu_usage) = gpu_runtime.utilization_percent {
+            history::push_sample(&mut self.history.gpu_usage, gpu_usage, retained, tick);
+        }
+        if let (Some(vram_used), Some(vram_total)) =
+            (gpu_runtime.vram_used_bytes, gpu_runtime.vram_total_bytes)
+        {
+            if vram_total > 0 {
+                let vram_usage = (vram_used as f32 / vram_total as f32 * 100.0).clamp(0.0, 100.0);
+                history::push_sample(&mut self.history.gpu_vram_usage, vram_usage, retained, tick);
+            }
+        }
+
+        self.disks.refresh(true);
+        let mut read_by_disk: HashMap<String, u64> = HashMap::new();
+        let mut write_by_disk: HashMap<String, u64> = HashMap::new();
+        for disk in self.disks.list() {
+            let partition_name = disk.name().to_string_lossy().to_string();
+            let disk_key = AppModel::disk_device_key(&partition_name);
+            let usage = disk.usage();
+            *read_by_disk.entry(disk_key.clone()).or_insert(0) += usage.read_bytes;
+            *write_by_disk.entry(disk_key).or_insert(0) += usage.written_bytes;
+        }
+        for (disk_key, read_bytes) in &read_by_disk {
+            let write_bytes = write_by_disk.get(disk_key).copied().unwrap_or(0);
+            let read_mib_s = (*read_bytes as f32 / (1024.0 * 1024.0)) / refresh_secs;
+            let write_mib_s = (write_bytes as f32 / (1024.0 * 1024.0)) / refresh_secs;
+            history::push_sample(
+                self.history.disk_read.entry(disk_key.clone()).or_default(),
+                read_mib_s.max(0.0),
+                retained,
+                tick,
+            );
+            history::push_sample(
+                self.history.disk_write.entry(disk_key.clone()).or_default(),
+                write_mib_s.max(0.0),
+                retained,
+                tick,
+            );
+        }
+        self.history
+            .disk_read
+            .retain(|key, _| read_by_disk.contains_key(key));
+        self.history
+            .disk_write
+            .retain(|key, _| write_by_disk.contains_key(key));
+
+        let active_networks = AppModel::list_active_network_interfaces();
+        let mut known_networks = HashSet::with_capacity(active_networks.len());
+        for interface in active_networks {
+            known_networks.insert(interface.name.clone());
+            let current = NetworkIoSnapshot {
+                rx_bytes: interface.rx_bytes,
+                tx_bytes: interface.tx_bytes,
+            };
+
+            let (rx_mib_s, tx_mib_s) =
+                if let Some(previous) = self.network_previous_snapshots.get(&interface.name) {
+                    let delta_rx = current.rx_bytes.saturating_sub(previous.rx_bytes);
+                    let delta_tx = current.tx_bytes.saturating_sub(previous.tx_bytes);
+                    (
+                        (delta_rx as f32 / (1024.0 * 1024.0)) / refresh_secs,
+                        (delta_tx as f32 / (1024.0 * 1024.0)) / refresh_secs,
+                    )
+                } else {
+                    (0.0, 0.0)
+                };
+
+            history::push_sample(
+                self.history
+                    .network_rx
+                    .entry(interface.name.clone())
+                    .or_default(),
+                rx_mib_s.max(0.0),
+                retained,
+                tick,
+            );
+            history::push_sample(
+                self.history
+                    .network_tx
+                    .entry(interface.name.clone())
+                    .or_default(),
+                tx_mib_s.max(0.0),
+                retained,
+                tick,
+            );
+            self.network_previous_snapshots
+                .insert(interface.name, current);
+        }
+        self.history
+            .network_rx
+            .retain(|key, _| known_networks.contains(key));
+        self.history
+            .network_tx
+            .retain(|key, _| known_networks.contains(key));
+        self.network_previous_snapshots
+            .retain(|key, _| known_networks.contains(key));
+    }
+}
+
+/// What the collector does after answering a request.
+#[derive(Debug, PartialEq)]
+enum CollectorReply {
+    Continue,
+    Stop,
+}
+
+/// The user id of the process on the other end of the socket, from `SO_PEERCRED`.
+fn peer_uid(stream: &UnixStream) -> Option<u32> {
+    let mut credentials = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut length = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    // SAFETY: the fd is a socket owned by `stream`, and `credentials` and `length` are valid
+    // for writes of the `ucred` size passed in `length`.
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&raw mut credentials).cast(),
+            &raw mut length,
+        )
+    };
+    (result == 0).then_some(credentials.uid)
+}
+
+/// Reads one request from a client and answers it. Clients of other users get no answer.
+fn serve_collector_request(
+    stream: &mut UnixStream,
+    history: &Mutex<CollectorHistory>,
+) -> CollectorReply {
+    // SAFETY: getuid has no preconditions and cannot fail.
+    let own_uid = unsafe { libc::getuid() };
+    if peer_uid(stream) != Some(own_uid) {
+        eprintln!("ignoring collector request from another user");
+        return CollectorReply::Continue;
+    }
+    let _ = stream.set_read_timeout(Some(COLLECTOR_IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(COLLECTOR_IO_TIMEOUT));
+
+    let mut request = String::new();
+    let _ = BufReader::new(&*stream).read_line(&mut request);
+    match request.trim() {
+        COLLECTOR_REQUEST_STOP => return CollectorReply::Stop,
+        COLLECTOR_REQUEST_HISTORY => {
+            let payload = history
+                .lock()
+                .ok()
+                .and_then(|history| serde_json::to_vec(&*history).ok())
+                .unwrap_or_default();
+            if let Err(err) = stream.write_all(&payload) {
+                eprintln!("failed to send collector history: {err}");
+            }
+        }
+        other => eprintln!("unknown collector request {other:?}"),
+    }
+    CollectorReply::Continue
+}
+
+/// Runs the collector service until it receives a stop request.
+pub fn run_collector() -> std::io::Result<()> {
+    let Some(socket_path) = AppModel::collector_socket_path() else {
+        return Err(std::io::Error::other(
+            "XDG_RUNTIME_DIR is not set, so there is no private directory for the socket",
+        ));
+    };
+    if UnixStream::connect(&socket_path).is_ok() {
+        eprintln!("collector is already running at {}", socket_path.display());
+        return Ok(());
+    }
+
+    // A socket file left behind by a crashed collector would make bind fail.
+    let _ = fs::remove_file(&socket_path);
+    let listener = UnixListener::bind(&socket_path)?;
+
+    let shared_history = Arc::new(Mutex::new(CollectorHistory::default()));
+    let sampler_history = Arc::clone(&shared_history);
+    std::thread::spawn(move || {
+        let mut sampler = CollectorSampler::new();
+        loop {
+            sampler.sample();
+            if let Ok(mut history) = sampler_history.lock() {
+                *history = sampler.history.clone();
+            }
+            std::thread::sleep(PROCESS_REFRESH_INTERVAL);
+        }
+    });
+
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+            continue;
+        };
+        if serve_collector_request(&mut stream, &shared_history) == CollectorReply::Stop {
+            break;
+        }
+    }
+
+    let _ = fs::remove_file(&socket_path);
+    Ok(())
+}
+
+impl AppModel {
+    /// The socket in the user's runtime directory, which only the user can enter. A shared
+    /// directory such as `/tmp` would let other users take the socket's place.
+    pub(super) fn collector_socket_path() -> Option<PathBuf> {
+        env::var_os("XDG_RUNTIME_DIR")
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| PathBuf::from(dir).join(COLLECTOR_SOCKET_NAME))
+    }
+
+    fn send_collector_request(request: &str) -> Option<UnixStream> {
+        let mut stream = UnixStream::connect(Self::collector_socket_path()?).ok()?;
+        stream.set_read_timeout(Some(COLLECTOR_IO_TIMEOUT)).ok()?;
+        stream.set_write_timeout(Some(COLLECTOR_IO_TIMEOUT)).ok()?;
+        stream.write_all(format!("{request}\n").as_bytes()).ok()?;
+        Some(stream)
+    }
+
+    fn is_collector_running() -> bool {
+        Self::collector_socket_path().is_some_and(|path| UnixStream::connect(path).is_ok())
+    }
+
+    /// Starts the collector when it should run in the background and fetches its history,
+    /// off the UI thread since `systemctl` and the socket can take a while to answer.
+    pub(super) fn connect_collector(&self) -> Task<cosmic::Action<Message>> {
+        let start = self.config.background_collector;
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    if start && !Self::start_collector(false) {
+                        eprintln!("failed to start background collector");
+                    }
+                    Self::read_collector_history()
+                })
+                .await
+                .ok()
+                .flatten()
+            },
+            |history| cosmic::Action::App(Message::CollectorAttached(history)),
+        )
+    }
+
+    /// The history kept by a running collector.
+    fn read_collector_history() -> Option<CollectorHistory> {
+        let mut stream = Self::send_collector_request(COLLECTOR_REQUEST_HISTORY)?;
+        let mut payload = String::new();
+        stream.read_to_string(&mut payload).ok()?;
+        serde_json::from_str(&payload).ok()
+    }
+
+    /// Seeds the performance graphs with the history kept by the collector. It replaces the
+    /// few samples taken since startup, which the collector has recorded as well.
+    pub(super) fn set_collector_history(&mut self, history: CollectorHistory) {
+        self.cpu_usage_history_per_core = history.cpu_usage_per_core;
+        self.ram_usage_history = history.ram_usage;
+        self.gpu_usage_history = history.gpu_usage;
+        self.gpu_vram_usage_history = history.gpu_vram_usage;
+        self.network_rx_history = history.network_rx;
+        self.network_tx_history = history.network_tx;
+        self.disk_read_history = history.disk_read;
+        self.disk_write_history = history.disk_write;
+    }
+
+    /// Starts the collector unless it is running. With `enable`, the user service is also
+    /// enabled, so systemd starts the collector at login before the window is opened.
+    fn start_collector(enable: bool) -> bool {
+        if Self::is_collector_running() && !enable {
+            return true;
+        }
+        if Self::collector_socket_path().is_none() {
+            return false;
+        }
+
+        // Prefer the user service so the collector is managed by systemd and survives logout
+        // when lingering is enabled; fall back to a detached child process otherwise.
+        let args = if enable {
+            ["--user", "enable", "--now", COLLECTOR_SERVICE_NAME].as_slice()
+        } else {
+            ["--user", "start", COLLECTOR_SERVICE_NAME].as_slice()
+        };
+        let started_service = Command::new("systemctl")
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if started_service || Self::is_collector_running() {
+            return true;
+        }
+
+        let Ok(exe) = env::current_exe() else {
+            return false;
+        };
+        Self::spawn_detached(Command::new(exe).arg("--collector"))
+    }
+
+    /// Disables the user service, so it is not started at the next login, and stops the
+    /// collector however it was started.
+    fn stop_collector() {
+        let _ = Command::new("systemctl")
+            .args(["--user", "disable", COLLECTOR_SERVICE_NAME])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let _ = Self::send_collector_request(COLLECTOR_REQUEST_STOP);
+    }
+
+    pub(super) fn toggle_background_collector(&mut self) -> Task<cosmic::Action<Message>> {
+        let enabled = !self.config.background_collector;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_background_collector(handler, enabled) {
+                eprintln!("failed to save background collector setting: {err}");
+            }
+        } else {
+            self.config.background_collector = enabled;
+        }
+
+        Task::perform(
+            async move {
+                _ = tokio::task::spawn_blocking(move || {
+                    if !enabled {
+                        Self::stop_collector();
+                    } else if !Self::start_collector(true) {
+                        eprintln!("failed to start background collector");
+                    }
+                })
+                .await;
+            },
+            |()| cosmic::Action::None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(line: &str, history: &Mutex<CollectorHistory>) -> (CollectorReply, String) {
+        let (mut client, mut server) = UnixStream::pair().expect("socket pair");
+        client.write_all(line.as_bytes()).expect("request sent");
+        let reply = serve_collector_request(&mut server, history);
+        drop(server);
+        let mut payload = String::new();
+        client.read_to_string(&mut payload).expect("reply read");
+        (reply, payload)
+    }
+
+    #[test]
+    fn answers_history_requests_with_json() {
+        let history = Mutex::new(CollectorHistory {
+            ram_usage: vec![12.5, 13.0],
+            ..CollectorHistory::default()
+        });
+        let (reply, payload) = request("history\n", &history);
+        assert_eq!(reply, CollectorReply::Continue);
+        let sent = serde_json::from_str::<CollectorHistory>(&payload).expect("history parses");
+        assert_eq!(sent.ram_usage, vec![12.5, 13.0]);
+    }
+
+    #[test]
+    fn stops_on_stop_and_ignores_unknown_requests() {
+        let history = Mutex::new(CollectorHistory::default());
+        assert_eq!(
+            request("stop\n", &history),
+            (CollectorReply::Stop, String::new())
+        );
+        assert_eq!(
+            request("shutdown\n", &history),
+            (CollectorReply::Continue, String::new())
+        );
+    }
+
+    #[test]
+    fn peer_of_a_socket_pair_is_this_user() {
+        let (client, _server) = UnixStream::pair().expect("socket pair");
+        // SAFETY: getuid has no preconditions and cannot fail.
+        assert_eq!(peer_uid(&client), Some(unsafe { libc::getuid() }));
+    }
+}
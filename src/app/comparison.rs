@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Side-by-side comparison of two apps, such as the native and the Flatpak build of a program
+//! or one game under two Proton versions. "Compare with…" in an app's actions takes it as the
+//! first app and the next app pressed in the table as the second. Both are sampled from then
+//! on, and the Apps page shows their graphs with averages and peaks until it is closed.
+
+use super::*;
+use crate::config::CpuPercentMode;
+
+const COMPARISON_GRAPH_HEIGHT: f32 = 96.0;
+
+/// Usage of an app since the comparison started.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct UsageStats {
+    samples: u32,
+    cpu_total: f64,
+    peak_cpu: f32,
+    peak_rss: u64,
+}
+
+impl UsageStats {
+    fn record(&mut self, cpu_percent: f32, rss_bytes: u64) {
+        self.samples += 1;
+        self.cpu_total += f64::from(cpu_percent);
+        self.peak_cpu = self.peak_cpu.max(cpu_percent);
+        self.peak_rss = self.peak_rss.max(rss_bytes);
+    }
+
+    fn average_cpu(&self) -> f32 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+        (self.cpu_total / f64::from(self.samples)) as f32
+    }
+}
+
+#[derive(Debug, Default)]
+struct ComparedApp {
+    app_id: String,
+    display_name: String,
+    cpu: Vec<f32>,
+    ram: Vec<f32>,
+    stats: UsageStats,
+}
+
+impl ComparedApp {
+    fn new(app_id: String, display_name: String) -> Self {
+        Self {
+            app_id,
+            display_name,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct AppComparison {
+    /// The first app while the second one is being picked.
+    picking: Option<ComparedApp>,
+    apps: Option<Box<[ComparedApp; 2]>>,
+}
+
+impl AppComparison {
+    pub(super) fn is_open(&self) -> bool {
+        self.apps.is_some()
+    }
+}
+
+impl AppModel {
+    pub(super) fn start_comparison(&mut self) {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return;
+        };
+        self.comparison.picking = Some(ComparedApp::new(
+            selected.app_id.clone(),
+            selected.display_name.clone(),
+        ));
+        self.core.window.show_context = false;
+    }
+
+    pub(super) fn cancel_comparison(&mut self) {
+        self.comparison.picking = None;
+    }
+
+    pub(super) fn close_comparison(&mut self) {
+        self.comparison.apps = None;
+    }
+
+    /// Takes a pressed row as the second app while one is being picked. Returns whether the
+    /// press was used up, which pressing the first app again also does.
+    pub(super) fn pick_compared_app(&mut self, app_id: &str, display_name: &str) -> bool {
+        let Some(first) = self.comparison.picking.as_ref() else {
+            return false;
+        };
+        if first.app_id == app_id {
+            return true;
+        }
+        let first = self.comparison.picking.take().unwrap_or_default();
+        let second = ComparedApp::new(app_id.to_string(), display_name.to_string());
+        self.comparison.apps = Some(Box::new([first, second]));
+        self.record_comparison_history();
+        true
+    }
+
+    /// Samples both compared apps. An app that is not running keeps its graphs as they were.
+    pub(super) fn record_comparison_history(&mut self) {
+        let Some(apps) = self.comparison.apps.as_deref_mut() else {
+            return;
+        };
+        let total_memory = self.system.total_memory().max(1);
+        let retained = history::retained_samples(self.config.history_retention);
+        let tick = self.history_ticks;
+        for app in apps.iter_mut() {
+            let Some(entry) = self
+                .process_entries
+                .iter()
+                .find(|entry| entry.app_id == app.app_id)
+            else {
+                continue;
+            };
+            history::push_sample(&mut app.cpu, entry.cpu_percent, retained, tick);
+            history::push_sample(
+                &mut app.ram,
+                entry.rss_bytes as f32 / total_memory as f32 * 100.0,
+                retained,
+                tick,
+            );
+            app.stats.record(entry.cpu_percent, entry.rss_bytes);
+        }
+    }
+
+    pub(super) fn comparison_banner(&self) -> Option<Element<'_, Message>> {
+        let first = self.comparison.picking.as_ref()?;
+        Some(Self::warning_banner(
+            fl!("comparison-picking", name = first.display_name.as_str()),
+            None,
+            Message::CancelComparison,
+        ))
+    }
+
+    /// CPU usage in the unit of the CPU column, for the machine-wide `cpu_percent`.
+    fn comparison_cpu_label(&self, cpu_percent: f32) -> String {
+        let cores = match self.config.cpu_percent_mode {
+            CpuPercentMode::Machine => 1.0,
+            CpuPercentMode::Core => self.system.cpus().len().max(1) as f32,
+        };
+        units::format_percent(cpu_percent * cores, 1)
+    }
+
+    fn compared_app_column<'a>(&'a self, app: &'a ComparedApp) -> Element<'a, Message> {
+        let entry = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == app.app_id);
+        let stat = |label: String, value: String| {
+            widget::row::with_capacity(2)
+                .push(widget::text(label).width(Length::Fill))
+                .push(widget::text(value))
+        };
+
+        let column = widget::column::with_capacity(10)
+            .push(
+                widget::text::title4(app.display_name.clone())
+                    .wrapping(cosmic::iced::widget::text::Wrapping::None)
+                    .ellipsize(cosmic::iced::widget::text::Ellipsize::End(
+                        cosmic::iced_core::text::EllipsizeHeightLimit::Lines(1),
+                    )),
+            )
+            .push_maybe(
+                entry
+                    .is_none()
+                    .then(|| widget::text(fl!("comparison-not-running"))),
+            )
+            .push(widget::text(format!(
+                "{}: {}",
+                self.cpu_column_label(),
+                entry.map_or_else(
+                    || "—".to_string(),
+                    |entry| self.comparison_cpu_label(entry.cpu_percent)
+                )
+            )))
+            .push(Chart::new(&app.cpu, ChartKind::Area, CPU_ACCENT).view(COMPARISON_GRAPH_HEIGHT))
+            .push(widget::text(format!(
+                "{}: {}",
+                fl!("table-ram"),
+                entry.map_or_else(
+                    || "—".to_string(),
+                    |entry| units::format_bytes(entry.rss_bytes)
+                )
+            )))
+            .push(Chart::new(&app.ram, ChartKind::Area, RAM_ACCENT).view(COMPARISON_GRAPH_HEIGHT))
+            .push(stat(
+                fl!("comparison-average-cpu"),
+                self.comparison_cpu_label(app.stats.average_cpu()),
+            ))
+            .push(stat(
+                fl!("comparison-peak-cpu"),
+                self.comparison_cpu_label(app.stats.peak_cpu),
+            ))
+            .push(stat(
+                fl!("comparison-peak-ram"),
+                units::format_bytes(app.stats.peak_rss),
+            ))
+            .push_maybe(entry.map(|entry| {
+                stat(
+                    fl!("comparison-processes"),
+                    fl!(
+                        "comparison-processes-value",
+                        processes = entry.pids.len(),
+                        threads = entry.threads
+                    ),
+                )
+            }))
+            .spacing(8)
+            .width(Length::Fill);
+        column.into()
+    }
+
+    pub(super) fn comparison_view(&self, space_s: u16) -> Element<'_, Message> {
+        let Some(apps) = self.comparison.apps.as_deref() else {
+            return self.apps_list_view(space_s);
+        };
+        let header = widget::row::with_capacity(2)
+            .push(widget::text::title2(fl!("comparison-title")).width(Length::Fill))
+            .push(
+                widget::button::standard(fl!("comparison-close"))
+                    .on_press(Message::CloseComparison),
+            )
+            .align_y(Alignment::Center);
+        let columns = widget::row::with_capacity(2)
+            .push(self.compared_app_column(&apps[0]))
+            .push(self.compared_app_column(&apps[1]))
+            .spacing(space_s * 2);
+
+        widget::column::with_capacity(2)
+            .push(header)
+            .push(widget::scrollable(columns).height(Length::Fill))
+            .spacing(space_s)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UsageStats;
+
+    #[test]
+    fn averages_and_peaks_over_the_comparison() {
+        let mut stats = UsageStats::default();
+        assert_eq!(stats.average_cpu(), 0.0);
+        stats.record(10.0, 300);
+        stats.record(30.0, 200);
+        assert_eq!(stats.average_cpu(), 20.0);
+        assert_eq!(stats.peak_cpu, 30.0);
+        assert_eq!(stats.peak_rss, 300);
+    }
+}
@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! CPU model and core layout on any architecture. The model comes from `/proc/cpuinfo`,
+//! whose fields differ between x86, ARM, and RISC-V, or from the devicetree. Core types come
+//! from `/sys/devices/cpu_core` and `cpu_atom` on Intel hybrid CPUs and from each core's
+//! `cpu_capacity` on ARM big.LITTLE, and clusters from the cores' cpufreq limits.
+
+use super::*;
+
+const CPU_SYSFS: &str = "/sys/devices/system/cpu";
+
+/// ARM core names by implementer and part number, from the `CPU implementer` and
+/// `CPU part` fields.
+const ARM_CORE_NAMES: &[(u32, u32, &str)] = &[
+    (0x41, 0xd03, "Cortex-A53"),
+    (0x41, 0xd04, "Cortex-A35"),
+    (0x41, 0xd05, "Cortex-A55"),
+    (0x41, 0xd07, "Cortex-A57"),
+    (0x41, 0xd08, "Cortex-A72"),
+    (0x41, 0xd09, "Cortex-A73"),
+    (0x41, 0xd0a, "Cortex-A75"),
+    (0x41, 0xd0b, "Cortex-A76"),
+    (0x41, 0xd0c, "Neoverse-N1"),
+    (0x41, 0xd0d, "Cortex-A77"),
+    (0x41, 0xd40, "Neoverse-V1"),
+    (0x41, 0xd41, "Cortex-A78"),
+    (0x41, 0xd44, "Cortex-X1"),
+    (0x41, 0xd46, "Cortex-A510"),
+    (0x41, 0xd47, "Cortex-A710"),
+    (0x41, 0xd48, "Cortex-X2"),
+    (0x41, 0xd49, "Neoverse-N2"),
+    (0x41, 0xd4d, "Cortex-A715"),
+    (0x41, 0xd4e, "Cortex-X3"),
+    (0x41, 0xd80, "Cortex-A520"),
+    (0x41, 0xd81, "Cortex-A720"),
+    (0x41, 0xd82, "Cortex-X4"),
+    (0x61, 0x022, "Apple Icestorm"),
+    (0x61, 0x023, "Apple Firestorm"),
+    (0x61, 0x032, "Apple Blizzard"),
+    (0x61, 0x033, "Apple Avalanche"),
+];
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub(super) enum CoreType {
+    /// All cores are alike.
+    #[default]
+    Uniform,
+    /// The fastest tier on ARM systems with three tiers, like a Cortex-X core.
+    Prime,
+    Performance,
+    Efficiency,
+}
+
+impl CoreType {
+    pub(super) fn label(self) -> String {
+        match self {
+            CoreType::Uniform => fl!("core-type-uniform"),
+            CoreType::Prime => fl!("core-type-prime"),
+            CoreType::Performance => fl!("core-type-performance"),
+            CoreType::Efficiency => fl!("core-type-efficiency"),
+        }
+    }
+
+    /// A tag for per-core gauges, e.g. `P`.
+    pub(super) fn short_label(self) -> String {
+        match self {
+            CoreType::Uniform => String::new(),
+            CoreType::Prime => fl!("core-type-short-prime"),
+            CoreType::Performance => fl!("core-type-short-performance"),
+            CoreType::Efficiency => fl!("core-type-short-efficiency"),
+        }
+    }
+}
+
+/// Cores of one type, with the fastest clock any of them reaches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct CpuCluster {
+    pub(super) core_type: CoreType,
+    pub(super) cpus: Vec<usize>,
+    pub(super) max_mhz: Option<u64>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct CpuTopology {
+    pub(super) model: Option<String>,
+    /// Type of each logical CPU, by index.
+    pub(super) core_types: Vec<CoreType>,
+    pub(super) clusters: Vec<CpuCluster>,
+}
+
+/// CPU indexes from a sysfs list such as `0-3,8,10-11`.
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter(|range| !range.is_empty())
+        .flat_map(|range| {
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            match (start.trim().parse::<usize>(), end.trim().parse::<usize>()) {
+                (Ok(start), Ok(end)) if start <= end => (start..=end).collect(),
+                _ => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+fn parse_hex(value: &str) -> Option<u32> {
+    u32::from_str_radix(value.trim().trim_start_matches("0x"), 16).ok()
+}
+
+/// The CPU model from `/proc/cpuinfo`: `model name` on x86, the core names from
+/// `CPU implementer` and `CPU part` on ARM, where distinct cores of a big.LITTLE system are
+/// joined with ` + `, and `uarch`, else `isa`, on RISC-V.
+fn parse_cpuinfo_model(cpuinfo: &str) -> Option<String> {
+    let mut model_name = None;
+    let mut uarch = None;
+    let mut isa = None;
+    let mut implementer = None;
+    let mut arm_cores = Vec::<String>::new();
+    for line in cpuinfo.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "model name" if model_name.is_none() && !value.is_empty() => {
+                model_name = Some(value.to_string());
+            }
+            "uarch" if uarch.is_none() && !value.is_empty() => uarch = Some(value.to_string()),
+            "isa" if isa.is_none() && !value.is_empty() => isa = Some(value.to_string()),
+            "CPU implementer" => implementer = parse_hex(value),
+            "CPU part" => {
+                let (Some(implementer), Some(part)) = (implementer, parse_hex(value)) else {
+                    continue;
+                };
+                let name = ARM_CORE_NAMES
+                    .iter()
+                    .find(|(known_implementer, known_part, _)| {
+                        *known_implementer == implementer && *known_part == part
+                    })
+                    .map_or_else(
+                        || format!("ARM {implementer:#x}:{part:#05x}"),
+                        |(_, _, name)| (*name).to_string(),
+                    );
+                if !arm_cores.contains(&name) {
+                    arm_cores.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+    model_name
+        .or_else(|| (!arm_cores.is_empty()).then(|| arm_cores.join(" + ")))
+        .or(uarch)
+        .or(isa)
+}
+
+/// Core types from ARM capacities: with two tiers the faster cores are performance cores,
+/// with three the fastest are prime cores, and cores of one capacity are uniform.
+fn classify_by_capacity(capacities: &[Option<u32>]) -> Vec<CoreType> {
+    let mut tiers = capacities.iter().flatten().copied().collect::<Vec<_>>();
+    tiers.sort_unstable();
+    tiers.dedup();
+    capacities
+        .iter()
+        .map(|capacity| match (capacity, tiers.as_slice()) {
+            (Some(_), [] | [_]) | (None, _) => CoreType::Uniform,
+            (Some(capacity), [lowest, ..]) if capacity == lowest => CoreType::Efficiency,
+            (Some(capacity), [.., highest]) if tiers.len() >= 3 && capacity == highest => {
+                CoreType::Prime
+            }
+            (Some(_), _) => CoreType::Performance,
+        })
+        .collect()
+}
+
+/// Groups CPUs by type with the highest clock limit in each group, fastest type first.
+fn build_clusters(core_types: &[CoreType], max_mhz: &[Option<u64>]) -> Vec<CpuCluster> {
+    let mut clusters = Vec::<CpuCluster>::new();
+    for (cpu, core_type) in core_types.iter().enumerate() {
+        let cpu_max = max_mhz.get(cpu).copied().flatten();
+        match clusters
+            .iter_mut()
+            .find(|cluster| cluster.core_type == *core_type)
+        {
+            Some(cluster) => {
+                cluster.cpus.push(cpu);
+                cluster.max_mhz = cluster.max_mhz.max(cpu_max);
+            }
+            None => clusters.push(CpuCluster {
+                core_type: *core_type,
+                cpus: vec![cpu],
+                max_mhz: cpu_max,
+            }),
+        }
+    }
+    clusters.sort_by_key(|cluster| cluster.core_type);
+    clusters
+}
+
+fn read_cpu_file(cpu: usize, file: &str) -> Option<String> {
+    fs::read_to_string(Path::new(CPU_SYSFS).join(format!("cpu{cpu}")).join(file)).ok()
+}
+
+/// Core types on Intel hybrid CPUs, whose P-cores and E-cores are listed under the
+/// `cpu_core` and `cpu_atom` PMUs.
+fn intel_hybrid_core_types(cpu_count: usize) -> Option<Vec<CoreType>> {
+    let performance = fs::read_to_string("/sys/devices/cpu_core/cpus").ok()?;
+    let efficiency = fs::read_to_string("/sys/devices/cpu_atom/cpus").ok()?;
+    let mut core_types = vec![CoreType::Uniform; cpu_count];
+    for (list, core_type) in [
+        (performance, CoreType::Performance),
+        (efficiency, CoreType::Efficiency),
+    ] {
+        for cpu in parse_cpu_list(&list) {
+            if let Some(slot) = core_types.get_mut(cpu) {
+                *slot = core_type;
+            }
+        }
+    }
+    Some(core_types)
+}
+
+impl CpuTopology {
+    pub(super) fn read() -> Self {
+        let cpu_count = fs::read_to_string(Path::new(CPU_SYSFS).join("present"))
+            .ok()
+            .and_then(|present| parse_cpu_list(&present).into_iter().max())
+            .map_or(0, |last| last + 1);
+        let model = fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|cpuinfo| parse_cpuinfo_model(&cpuinfo))
+            .or_else(|| {
+                // Boards without CPU names in cpuinfo still name themselves, e.g.
+                // `Raspberry Pi 4 Model B Rev 1.4`.
+                fs::read_to_string("/sys/firmware/devicetree/base/model")
+                    .ok()
+                    .map(|model| model.trim_end_matches('\0').trim().to_string())
+                    .filter(|model| !model.is_empty())
+            });
+
+        let core_types = intel_hybrid_core_types(cpu_count).unwrap_or_else(|| {
+            let capacities = (0..cpu_count)
+                .map(|cpu| {
+                    read_cpu_file(cpu, "cpu_capacity")
+                        .and_then(|capacity| capacity.trim().parse::<u32>().ok())
+                })
+                .collect::<Vec<_>>();
+            classify_by_capacity(&capacities)
+        });
+        let max_mhz = (0..cpu_count)
+            .map(|cpu| {
+                read_cpu_file(cpu, "cpufreq/cpuinfo_max_freq")
+                    .and_then(|khz| khz.trim().parse::<u64>().ok())
+                    .map(|khz| khz / 1000)
+            })
+            .collect::<Vec<_>>();
+        let clusters = build_clusters(&core_types, &max_mhz);
+
+        Self {
+            model,
+            core_types,
+            clusters,
+        }
+    }
+
+    /// Whether the CPU mixes core types, like Intel hybrid or ARM big.LITTLE CPUs.
+    pub(super) fn is_heterogeneous(&self) -> bool {
+        self.clusters.len() > 1
+    }
+
+    /// The type of a logical CPU on CPUs that mix core types.
+    pub(super) fn hybrid_core_type(&self, cpu: usize) -> Option<CoreType> {
+        self.is_heterogeneous()
+            .then(|| self.core_types.get(cpu).copied())
+            .flatten()
+            .filter(|core_type| *core_type != CoreType::Uniform)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CoreType, CpuCluster, build_clusters, classify_by_capacity, parse_cpu_list,
+        parse_cpuinfo_model,
+    };
+
+    #[test]
+    fn parses_cpu_lists() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("5"), vec![5]);
+        assert!(parse_cpu_list("\n").is_empty());
+    }
+
+    #[test]
+    fn reads_model_on_each_architecture() {
+        let x86 = "processor\t: 0\nvendor_id\t: AuthenticAMD\n\
+                   model name\t: AMD Ryzen 7 7840U w/ Radeon  780M Graphics\n";
+        assert_eq!(
+            parse_cpuinfo_model(x86).as_deref(),
+            Some("AMD Ryzen 7 7840U w/ Radeon  780M Graphics")
+        );
+
+        let arm = "processor\t: 0\nCPU implementer\t: 0x41\nCPU part\t: 0xd05\n\n\
+                   processor\t: 1\nCPU implementer\t: 0x41\nCPU part\t: 0xd05\n\n\
+                   processor\t: 6\nCPU implementer\t: 0x41\nCPU part\t: 0xd0b\n\n\
+                   processor\t: 7\nCPU implementer\t: 0x51\nCPU part\t: 0x805\n";
+        assert_eq!(
+            parse_cpuinfo_model(arm).as_deref(),
+            Some("Cortex-A55 + Cortex-A76 + ARM 0x51:0x805")
+        );
+
+        let riscv = "processor\t: 0\nisa\t\t: rv64imafdc\nuarch\t\t: sifive,u74-mc\n";
+        assert_eq!(parse_cpuinfo_model(riscv).as_deref(), Some("sifive,u74-mc"));
+        assert_eq!(parse_cpuinfo_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn classifies_cores_by_capacity() {
+        use CoreType::{Efficiency, Performance, Prime, Uniform};
+        let two_tiers = [Some(446), Some(446), Some(1024), Some(1024)];
+        assert_eq!(
+            classify_by_capacity(&two_tiers),
+            vec![Efficiency, Efficiency, Performance, Performance]
+        );
+        let three_tiers = [Some(325), Some(828), Some(828), Some(1024)];
+        assert_eq!(
+            classify_by_capacity(&three_tiers),
+            vec![Efficiency, Performance, Performance, Prime]
+        );
+        assert_eq!(
+            classify_by_capacity(&[Some(1024), Some(1024), None]),
+            vec![Uniform, Uniform, Uniform]
+        );
+    }
+
+    #[test]
+    fn groups_cores_into_clusters() {
+        let core_types = [
+            CoreType::Efficiency,
+            CoreType::Efficiency,
+            CoreType::Performance,
+        ];
+        assert_eq!(
+            build_clusters(&core_types, &[Some(1800), Some(2000), Some(3200)]),
+            vec![
+                CpuCluster {
+                    core_type: CoreType::Performance,
+                    cpus: vec![2],
+                    max_mhz: Some(3200),
+                },
+                CpuCluster {
+                    core_type: CoreType::Efficiency,
+                    cpus: vec![0, 1],
+                    max_mhz: Some(2000),
+                },
+            ]
+        );
+    }
+}
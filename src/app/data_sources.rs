@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Problems with data sources that leave part of the window empty or stale, such as a Steam
+//! library on a drive that is not mounted or GPU counters the user may not read. They are
+//! checked once a minute and shown as banners with a retry button until they clear up. A
+//! dismissed problem stays hidden until it changes.
+
+use super::*;
+use std::collections::BTreeMap;
+
+const DATA_SOURCE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub(super) enum DataSource {
+    SteamLibrary,
+    GpuMetrics,
+}
+
+#[derive(Debug)]
+struct SourceProblem {
+    detail: String,
+    dismissed: bool,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct DataSourceHealth {
+    checked_at: Option<Instant>,
+    problems: BTreeMap<DataSource, SourceProblem>,
+}
+
+impl DataSourceHealth {
+    /// Records the outcome of checking `source`, keeping a dismissal while the problem is
+    /// unchanged.
+    fn update(&mut self, source: DataSource, problem: Option<String>) {
+        let Some(detail) = problem else {
+            self.problems.remove(&source);
+            return;
+        };
+        if self
+            .problems
+            .get(&source)
+            .is_some_and(|known| known.detail == detail)
+        {
+            return;
+        }
+        self.problems.insert(
+            source,
+            SourceProblem {
+                detail,
+                dismissed: false,
+            },
+        );
+    }
+}
+
+/// A Steam library that cannot be read, from `libraryfolders.vdf` that fails to load or
+/// lists a library folder that is gone, like one on an unmounted drive.
+fn steam_library_problem() -> Option<String> {
+    for steam_root in AppModel::steam_root_paths() {
+        let libraryfolders = steam_root.join("steamapps").join("libraryfolders.vdf");
+        let content = match fs::read_to_string(&libraryfolders) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                return Some(fl!(
+                    "source-steam-library-unreadable",
+                    path = libraryfolders.display().to_string(),
+                    error = err.to_string()
+                ));
+            }
+        };
+        let missing = AppModel::steam_library_roots_from_vdf(&content)
+            .into_iter()
+            .find(|library| !library.is_dir());
+        if let Some(library) = missing {
+            return Some(fl!(
+                "source-steam-library-missing",
+                path = library.display().to_string()
+            ));
+        }
+    }
+    None
+}
+
+/// Why a GPU that should report its load does not: `nvidia-smi` missing for an NVIDIA card,
+/// or `gpu_busy_percent` that is there but not readable.
+fn gpu_metrics_problem(card_path: &Path) -> Option<String> {
+    let device_path = card_path.join("device");
+    let vendor = fs::read_to_string(device_path.join("vendor")).unwrap_or_default();
+    if vendor.trim() == "0x10de" {
+        return match Command::new("nvidia-smi")
+            .arg("--list-gpus")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+        {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Some(fl!("source-gpu-nvidia-smi-missing"))
+            }
+            Ok(status) if !status.success() => Some(fl!("source-gpu-nvidia-smi-failed")),
+            _ => None,
+        };
+    }
+    match fs::read_to_string(device_path.join("gpu_busy_percent")) {
+        Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => Some(fl!(
+            "source-gpu-permission",
+            path = device_path.join("gpu_busy_percent").display().to_string()
+        )),
+        _ => None,
+    }
+}
+
+impl AppModel {
+    /// Checks every data source once the interval has passed since the last check.
+    pub(super) fn refresh_data_sources(&mut self) {
+        if self
+            .data_sources
+            .checked_at
+            .is_some_and(|checked_at| checked_at.elapsed() < DATA_SOURCE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.data_sources.checked_at = Some(Instant::now());
+        self.check_data_source(DataSource::SteamLibrary);
+        self.check_data_source(DataSource::GpuMetrics);
+    }
+
+    fn check_data_source(&mut self, source: DataSource) {
+        let problem = match source {
+            DataSource::SteamLibrary => steam_library_problem(),
+            // A GPU without load counters at all is not a problem that could clear up.
+            DataSource::GpuMetrics => self
+                .gpu_runtime_info
+                .utilization_percent
+                .is_none()
+                .then(Self::primary_drm_card_path)
+                .flatten()
+                .and_then(|card_path| gpu_metrics_problem(&card_path)),
+        };
+        self.data_sources.update(source, problem);
+    }
+
+    pub(super) fn retry_data_source(&mut self, source: DataSource) {
+        if source == DataSource::GpuMetrics {
+            self.gpu_runtime_info = Self::read_gpu_runtime_info();
+        }
+        self.check_data_source(source);
+        if source == DataSource::SteamLibrary {
+            // Names resolved while the library was missing fell back to app ids.
+            self.steam_apps_by_id.clear();
+            self.process_matches.clear();
+            self.refresh_processes();
+        }
+    }
+
+    pub(super) fn dismiss_data_source_problem(&mut self, source: DataSource) {
+        if let Some(problem) = self.data_sources.problems.get_mut(&source) {
+            problem.dismissed = true;
+        }
+    }
+
+    pub(super) fn data_source_banners(&self) -> Option<Element<'_, Message>> {
+        let banners = self
+            .data_sources
+            .problems
+            .iter()
+            .filter(|(_, problem)| !problem.dismissed)
+            .map(|(source, problem)| {
+                Self::warning_banner(
+                    problem.detail.clone(),
+                    Some((fl!("source-retry"), Message::RetryDataSource(*source))),
+                    Message::DismissDataSourceProblem(*source),
+                )
+            })
+            .collect::<Vec<_>>();
+        if banners.is_empty() {
+            return None;
+        }
+        Some(widget::column::with_children(banners).spacing(4).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataSource, DataSourceHealth};
+
+    #[test]
+    fn keeps_dismissals_until_the_problem_changes() {
+        let mut health = DataSourceHealth::default();
+        health.update(DataSource::GpuMetrics, Some("no permission".to_string()));
+        health
+            .problems
+            .get_mut(&DataSource::GpuMetrics)
+            .unwrap()
+            .dismissed = true;
+
+        health.update(DataSource::GpuMetrics, Some("no permission".to_string()));
+        assert!(health.problems[&DataSource::GpuMetrics].dismissed);
+        health.update(
+            DataSource::GpuMetrics,
+            Some("nvidia-smi missing".to_string()),
+        );
+        assert!(!health.problems[&DataSource::GpuMetrics].dismissed);
+        health.update(DataSource::GpuMetrics, None);
+        assert!(health.problems.is_empty());
+    }
+}
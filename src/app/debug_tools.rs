@@ -0,0 +1,298 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Debugging tools run on the selected app in a terminal: strace, either attached to the
+//! running processes or relaunching the app under it, and gdb or a configured debugger
+//! attached to the app's main process. Attaching to a process that is not a child of the
+//! tool needs `CAP_SYS_PTRACE` once Yama's `ptrace_scope` is 1 or higher, so the user is
+//! then asked whether to run the tool through `sudo`, which prompts in the terminal.
+
+use super::*;
+
+/// Follow threads and children, timestamp each call with its duration, show the paths
+/// behind file descriptors, and print enough of each string to read paths and messages.
+const STRACE_FLAGS: [&str; 6] = ["-f", "-tt", "-T", "-y", "-s", "256"];
+/// The debugger used when none is configured; `{pid}` is replaced with the process id.
+pub(super) const DEFAULT_DEBUGGER: &str = "gdb -p {pid}";
+
+/// A tool that attaches to running processes with ptrace.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum AttachTool {
+    Strace,
+    Debugger,
+}
+
+/// An attach waiting for the user to allow `sudo`, with the `ptrace_scope` that blocks it.
+#[derive(Debug)]
+pub(super) struct PtracePrompt {
+    tool: AttachTool,
+    scope: u8,
+}
+
+/// Quotes an argument for `sh`, e.g. `it's` as `'it'\''s'`.
+pub(super) fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty()
+        && arg
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"-_./=:,+@%".contains(&byte))
+    {
+        return arg.to_string();
+    }
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+/// Wraps a command so the terminal stays open after it ends, showing its last output and
+/// `prompt` until Enter is pressed.
+fn hold_terminal_open(command: &[String], prompt: &str) -> Vec<String> {
+    let command = command
+        .iter()
+        .map(|arg| shell_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        format!(
+            "{command}; printf '\\n%s ' {}; read -r _",
+            shell_quote(prompt)
+        ),
+    ]
+}
+
+fn strace_attach_command(pids: &[u32], elevate: bool) -> Vec<String> {
+    let mut command = Vec::with_capacity(2 + STRACE_FLAGS.len() + 2 * pids.len());
+    if elevate {
+        command.push("sudo".to_string());
+    }
+    command.push("strace".to_string());
+    command.extend(STRACE_FLAGS.map(str::to_string));
+    for pid in pids {
+        command.push("-p".to_string());
+        command.push(pid.to_string());
+    }
+    command
+}
+
+/// The debugger command line from a template such as `gdb -p {pid}`. A template without
+/// `{pid}` gets `-p <pid>` appended, which gdb and lldb both understand.
+fn debugger_command(template: &str, pid: u32, elevate: bool) -> Vec<String> {
+    let template = Some(template.trim())
+        .filter(|template| !template.is_empty())
+        .unwrap_or(DEFAULT_DEBUGGER);
+    let mut command = Vec::new();
+    if elevate {
+        command.push("sudo".to_string());
+    }
+    command.extend(
+        template
+            .split_whitespace()
+            .map(|arg| arg.replace("{pid}", &pid.to_string())),
+    );
+    if !template.contains("{pid}") {
+        command.push("-p".to_string());
+        command.push(pid.to_string());
+    }
+    command
+}
+
+fn strace_launch_command(program: &str, args: &[String]) -> Vec<String> {
+    let mut command = vec!["strace".to_string()];
+    command.extend(STRACE_FLAGS.map(str::to_string));
+    command.push("--".to_string());
+    command.push(program.to_string());
+    command.extend(args.iter().cloned());
+    command
+}
+
+/// Yama's `ptrace_scope`: 0 lets a user trace all of their processes, 1 only their own
+/// children, 2 only administrators, and 3 nobody. Kernels without Yama act like 0.
+fn ptrace_scope() -> u8 {
+    fs::read_to_string("/proc/sys/kernel/yama/ptrace_scope")
+        .ok()
+        .and_then(|scope| scope.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+fn is_root() -> bool {
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Whether a program can be found in `PATH`.
+pub(super) fn is_installed(program: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|path| env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Whether strace is installed, checked once.
+pub(super) fn strace_installed() -> bool {
+    static INSTALLED: OnceLock<bool> = OnceLock::new();
+    *INSTALLED.get_or_init(|| is_installed("strace"))
+}
+
+impl AppModel {
+    pub(super) fn run_debug_tool(&self, command: &[String], working_dir: Option<&Path>) {
+        let command = hold_terminal_open(command, &fl!("debug-press-enter"));
+        let command = command.iter().map(String::as_str).collect::<Vec<_>>();
+        if !terminal::spawn_in_terminal(&command, working_dir) {
+            eprintln!("failed to open a terminal");
+        }
+    }
+
+    /// Attaches a tool to the selected app, first asking whether to use `sudo` when
+    /// `ptrace_scope` only allows tracing child processes.
+    pub(super) fn request_attach(&mut self, tool: AttachTool) {
+        let scope = ptrace_scope();
+        if scope == 0 || (scope < 3 && is_root()) {
+            self.attach_to_selected(tool, false);
+        } else {
+            self.ptrace_prompt = Some(PtracePrompt { tool, scope });
+        }
+    }
+
+    pub(super) fn confirm_ptrace_prompt(&mut self) {
+        if let Some(prompt) = self.ptrace_prompt.take() {
+            self.attach_to_selected(prompt.tool, true);
+        }
+    }
+
+    /// strace follows all of the app's processes; a debugger attaches to the main one.
+    fn attach_to_selected(&self, tool: AttachTool, elevate: bool) {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return;
+        };
+        let command = match tool {
+            AttachTool::Strace => {
+                let Some(entry) = self
+                    .process_entries
+                    .iter()
+                    .find(|entry| entry.app_id == selected.app_id)
+                else {
+                    return;
+                };
+                strace_attach_command(&entry.pids, elevate)
+            }
+            AttachTool::Debugger => {
+                debugger_command(&self.config.debugger_command, selected.pid, elevate)
+            }
+        };
+        self.run_debug_tool(&command, None);
+    }
+
+    pub(super) fn set_debugger_command(&mut self, command: String) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_debugger_command(handler, command) {
+                eprintln!("failed to save debugger command: {err}");
+            }
+        } else {
+            self.config.debugger_command = command;
+        }
+    }
+
+    pub(super) fn ptrace_prompt_dialog(&self) -> Option<Element<'_, Message>> {
+        let prompt = self.ptrace_prompt.as_ref()?;
+        // Scope 3 disables ptrace until reboot, even for root.
+        let blocked = prompt.scope >= 3;
+        let body = if blocked {
+            fl!("ptrace-blocked-description")
+        } else {
+            fl!("ptrace-sudo-description", scope = prompt.scope)
+        };
+        Some(
+            widget::dialog()
+                .title(fl!("ptrace-title"))
+                .body(body)
+                .control(widget::text(fl!("ptrace-sysctl-hint")).size(12))
+                .secondary_action(
+                    widget::button::standard(fl!("autostart-modal-cancel"))
+                        .on_press(Message::ClosePtracePrompt),
+                )
+                .primary_action(
+                    widget::button::suggested(fl!("ptrace-use-sudo"))
+                        .on_press_maybe((!blocked).then_some(Message::ConfirmPtracePrompt)),
+                )
+                .into(),
+        )
+    }
+
+    /// Stops the selected app and starts its command line again under strace, in the
+    /// directory it was running in, so its startup is traced too. strace starts once the
+    /// app has exited.
+    pub(super) fn relaunch_selected_under_strace(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(selected) = self.selected_process.as_ref().cloned() else {
+            return Task::none();
+        };
+        let Some((program, args)) = self
+            .process_source
+            .processes()
+            .get(&Pid::from_u32(selected.pid))
+            .and_then(|process| {
+                let (program, args) = process.cmd.split_first()?;
+                Some((program.clone(), args.to_vec()))
+            })
+        else {
+            eprintln!(
+                "failed to read the command line of process {}",
+                selected.pid
+            );
+            return Task::none();
+        };
+        let cwd = self
+            .process_source
+            .proc_dir(selected.pid)
+            .and_then(|dir| fs::read_link(dir.join("cwd")).ok())
+            .filter(|cwd| cwd.is_dir());
+
+        self.request_stop(
+            selected.app_id,
+            StopKind::Restart(Relaunch::InTerminal {
+                command: strace_launch_command(&program, &args),
+                cwd,
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        debugger_command, hold_terminal_open, shell_quote, strace_attach_command,
+        strace_launch_command,
+    };
+
+    #[test]
+    fn quotes_shell_arguments() {
+        assert_eq!(shell_quote("/usr/bin/gedit"), "/usr/bin/gedit");
+        assert_eq!(shell_quote("My Game"), "'My Game'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn builds_strace_commands() {
+        assert_eq!(
+            strace_attach_command(&[812, 813], true).join(" "),
+            "sudo strace -f -tt -T -y -s 256 -p 812 -p 813"
+        );
+        assert_eq!(
+            strace_launch_command("gedit", &["notes.txt".to_string()]).join(" "),
+            "strace -f -tt -T -y -s 256 -- gedit notes.txt"
+        );
+        assert_eq!(debugger_command("", 812, false).join(" "), "gdb -p 812");
+        assert_eq!(
+            debugger_command("lldb --attach-pid {pid}", 812, true).join(" "),
+            "sudo lldb --attach-pid 812"
+        );
+        assert_eq!(
+            debugger_command("gdb -q", 812, false).join(" "),
+            "gdb -q -p 812"
+        );
+        assert_eq!(
+            hold_terminal_open(&["echo".to_string(), "a b".to_string()], "Press Enter"),
+            vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                r"echo 'a b'; printf '\n%s ' 'Press Enter'; read -r _".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Deep links that open the Apps page with an app selected, from
+//! `cosmic-task-monitor --select-app <app_id>` or a `task-monitor://app/<app_id>` URI.
+//!
+//! A second launch hands the link to the running instance over D-Bus activation, so the
+//! link is encoded as the activation action string.
+
+use super::*;
+use std::fmt;
+use std::str::FromStr;
+
+const URI_PREFIX: &str = "task-monitor://app/";
+const SELECT_APP_ACTION: &str = "select-app=";
+/// How long a link waits for its app to show up, e.g. when it is still starting.
+const DEEP_LINK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DeepLink {
+    SelectApp(String),
+}
+
+impl DeepLink {
+    fn from_uri(uri: &str) -> Option<Self> {
+        let app_id = uri.strip_prefix(URI_PREFIX)?.trim_end_matches('/');
+        (!app_id.is_empty()).then(|| DeepLink::SelectApp(app_id.to_string()))
+    }
+
+    /// Finds a deep link among command-line arguments.
+    fn from_args(args: impl IntoIterator<Item = String>) -> Option<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--select-app" {
+                return args.next().map(DeepLink::SelectApp);
+            }
+            if let Some(app_id) = arg.strip_prefix("--select-app=") {
+                return Some(DeepLink::SelectApp(app_id.to_string()));
+            }
+            if let Some(link) = DeepLink::from_uri(&arg) {
+                return Some(link);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for DeepLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeepLink::SelectApp(app_id) => write!(f, "{SELECT_APP_ACTION}{app_id}"),
+        }
+    }
+}
+
+impl FromStr for DeepLink {
+    type Err = String;
+
+    fn from_str(action: &str) -> Result<Self, Self::Err> {
+        action
+            .strip_prefix(SELECT_APP_ACTION)
+            .map(|app_id| DeepLink::SelectApp(app_id.to_string()))
+            .ok_or_else(|| format!("unknown action `{action}`"))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Flags {
+    link: Option<DeepLink>,
+}
+
+impl Flags {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            link: DeepLink::from_args(args),
+        }
+    }
+
+    pub(super) fn into_link(self) -> Option<DeepLink> {
+        self.link
+    }
+}
+
+impl cosmic::app::CosmicFlags for Flags {
+    type SubCommand = DeepLink;
+    type Args = Vec<String>;
+
+    fn action(&self) -> Option<&DeepLink> {
+        self.link.as_ref()
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct PendingSelection {
+    app_id: String,
+    requested_at: Instant,
+}
+
+impl AppModel {
+    pub(super) fn handle_dbus_activation(
+        &mut self,
+        details: cosmic::dbus_activation::Details,
+    ) -> Task<cosmic::Action<Message>> {
+        let link = match details {
+            cosmic::dbus_activation::Details::ActivateAction { action, .. } => action.parse().ok(),
+            cosmic::dbus_activation::Details::Open { url } => {
+                url.iter().find_map(|url| DeepLink::from_uri(url.as_str()))
+            }
+            cosmic::dbus_activation::Details::Activate => None,
+        };
+        match link {
+            Some(link) => self.open_deep_link(link),
+            // A plain second launch, such as from the global shortcut, brings the window up.
+            None => match self.core.main_window_id() {
+                Some(id) => cosmic::iced::window::gain_focus(id),
+                None => Task::none(),
+            },
+        }
+    }
+
+    pub(super) fn open_deep_link(&mut self, link: DeepLink) -> Task<cosmic::Action<Message>> {
+        let DeepLink::SelectApp(app_id) = link;
+        let processes_page = self
+            .nav
+            .iter()
+            .find(|id| self.nav.data::<Page>(*id) == Some(&Page::Processes));
+        if let Some(id) = processes_page {
+            self.nav.activate(id);
+        }
+
+        // A background app the Filter menu hides would never show up to be selected.
+        if self.revealed_app.as_ref() != Some(&app_id) {
+            self.revealed_app = Some(app_id.clone());
+            self.refresh_process_list();
+        }
+        self.pending_selection = Some(PendingSelection {
+            app_id,
+            requested_at: Instant::now(),
+        });
+        Task::batch([self.update_title(), self.select_pending_app()])
+    }
+
+    /// Selects and scrolls to the app a deep link asked for, once it is in the table.
+    pub(super) fn select_pending_app(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(pending) = self.pending_selection.as_ref() else {
+            return Task::none();
+        };
+        if pending.requested_at.elapsed() > DEEP_LINK_TIMEOUT {
+            eprintln!("no running app matches deep link `{}`", pending.app_id);
+            self.pending_selection = None;
+            return Task::none();
+        }
+        let Some(entry) = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == pending.app_id)
+            .cloned()
+        else {
+            return Task::none();
+        };
+        self.pending_selection = None;
+
+        // Nothing else may hide the row being scrolled to.
+        if self.processes_page.group_by != GroupBy::App {
+            self.update_processes_page(ProcessesMessage::SetGroupBy(GroupBy::App));
+        }
+        self.update_processes_page(ProcessesMessage::SetSearch(String::new()));
+        if !self.matches_category_filter(&entry) {
+            self.set_category_filter(Vec::new());
+        }
+        let scroll = self.scroll_to_app(&entry);
+
+        let select = cosmic::Application::update(
+            self,
+            Message::OpenProcessMenu {
+                app_id: entry.app_id,
+                display_name: entry.display_name,
+                pid: entry.pid,
+            },
+        );
+        Task::batch([select, scroll])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeepLink;
+
+    fn link(args: &[&str]) -> Option<DeepLink> {
+        DeepLink::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_cli_flags_and_uris() {
+        let firefox = Some(DeepLink::SelectApp("firefox".to_string()));
+        assert_eq!(link(&["--select-app", "firefox"]), firefox);
+        assert_eq!(link(&["--select-app=firefox"]), firefox);
+        assert_eq!(link(&["task-monitor://app/firefox/"]), firefox);
+        assert_eq!(link(&["task-monitor://app/"]), None);
+        assert_eq!(link(&["--select-app"]), None);
+        assert_eq!(link(&[]), None);
+
+        let action = DeepLink::SelectApp("org.gnome.Nautilus".to_string());
+        assert_eq!(action.to_string().parse::<DeepLink>(), Ok(action));
+    }
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Open file descriptors per app, counted from `/proc/<pid>/fd`, with a warning once a
+//! process nears its `RLIMIT_NOFILE` soft limit from `/proc/<pid>/limits`. A count that
+//! only ever grows is the usual sign of a descriptor leak.
+
+use super::*;
+
+/// Share of the soft limit from which a process is flagged.
+const FD_WARNING_FRACTION: f64 = 0.8;
+
+/// Descriptors of an app, summed over its processes.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct FdUsage {
+    pub(super) count: u32,
+    /// Descriptors and soft limit of the member closest to its limit, once past the warning
+    /// fraction.
+    pub(super) near_limit: Option<(u32, u64)>,
+}
+
+/// The soft limit from the `Max open files  1024  524288  files` line of a limits file.
+/// An unlimited soft limit has nothing to warn about.
+fn parse_nofile_limit(limits: &str) -> Option<u64> {
+    let line = limits
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))?;
+    line.split_whitespace().next()?.parse::<u64>().ok()
+}
+
+fn is_near_limit(count: u32, limit: u64) -> bool {
+    limit > 0 && f64::from(count) >= limit as f64 * FD_WARNING_FRACTION
+}
+
+impl FdUsage {
+    /// Adds a process's descriptors. Processes of other users cannot be read and add nothing.
+    pub(super) fn add_process(&mut self, source: &dyn SystemSource, pid: u32) {
+        let Some(Ok(fds)) = source.proc_dir(pid).map(|dir| fs::read_dir(dir.join("fd"))) else {
+            return;
+        };
+        let count = fds.count() as u32;
+        self.count += count;
+
+        let Some(limit) = source
+            .read_proc_file(pid, "limits")
+            .and_then(|limits| parse_nofile_limit(&String::from_utf8_lossy(&limits)))
+        else {
+            return;
+        };
+        let closer = self
+            .near_limit
+            .is_none_or(|(nearest_count, nearest_limit)| {
+                f64::from(count) / limit as f64 > f64::from(nearest_count) / nearest_limit as f64
+            });
+        if is_near_limit(count, limit) && closer {
+            self.near_limit = Some((count, limit));
+        }
+    }
+}
+
+impl AppModel {
+    pub(super) fn toggle_fd_column(&mut self) {
+        let enabled = !self.config.show_fd_column;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_show_fd_column(handler, enabled) {
+                eprintln!("failed to save file descriptor column setting: {err}");
+            }
+        } else {
+            self.config.show_fd_column = enabled;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_near_limit, parse_nofile_limit};
+
+    #[test]
+    fn parses_open_files_soft_limit() {
+        let limits = "Limit            Soft Limit  Hard Limit  Units\n\
+                      Max processes    23960       23960       processes\n\
+                      Max open files   1024        524288      files\n";
+        assert_eq!(parse_nofile_limit(limits), Some(1024));
+        assert_eq!(
+            parse_nofile_limit("Max open files   unlimited   unlimited   files\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn warns_near_the_limit() {
+        assert!(!is_near_limit(100, 1024));
+        assert!(is_near_limit(900, 1024));
+        assert!(!is_near_limit(5, 0));
+    }
+}
@@ -0,0 +1,320 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Per-process GPU engine load. Video encoder load shows OBS or game capture encoding
+//! separately from 3D load, which the Games page shows per game. NVIDIA load comes from
+//! `nvidia-smi pmon`; AMD and Intel engines come from the `drm-engine-*` busy counters in
+//! `/proc/<pid>/fdinfo`. `nvidia-smi pmon` takes about a second and the DRM clients are
+//! found by walking every process's file descriptors, so one background sampler measures
+//! both kinds of engine for the Encoder column and the Games page, which show the last
+//! finished sample.
+
+use super::*;
+
+/// fdinfo engines that encode: `enc` and `vcn` on amdgpu, `video` on i915 and xe, where the
+/// same engine also decodes.
+const ENCODER_ENGINES: [&str; 3] = ["drm-engine-enc", "drm-engine-vcn", "drm-engine-video"];
+/// fdinfo engines that render: `gfx` on amdgpu, `render` on i915, `gpu` on msm.
+const RENDER_ENGINES: [&str; 3] = ["drm-engine-gfx", "drm-engine-render", "drm-engine-gpu"];
+
+/// Busy time of the encoder and render engines of one DRM client, when it has them.
+type ClientBusy = (Option<u64>, Option<u64>);
+
+/// Busy time of the GPU engines at a sample, and the load per pid since the one before.
+#[derive(Debug, Clone)]
+pub(super) struct EngineSample {
+    /// Busy time per pid and DRM client id.
+    busy_ns: HashMap<(u32, String), ClientBusy>,
+    sampled_at: Instant,
+    encoder: HashMap<u32, f32>,
+    render: HashMap<u32, f32>,
+}
+
+/// Load of the encoder and render engines per pid, from busy time between two samples.
+#[derive(Debug, Default)]
+pub(super) struct EngineMonitor {
+    last: Option<EngineSample>,
+    /// Whether a sample is being taken in the background.
+    sampling: bool,
+}
+
+fn is_engine(key: &str, engines: &[&str]) -> bool {
+    engines.iter().any(|engine| {
+        key.strip_prefix(engine)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+    })
+}
+
+/// The client id and busy time of the given engines on a DRM file descriptor, from lines
+/// such as `drm-client-id: 42` and `drm-engine-enc: 1500 ns`.
+fn parse_drm_fdinfo(contents: &str, engines: &[&str]) -> Option<(String, u64)> {
+    let mut client_id = None;
+    let mut busy_ns = None;
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if key == "drm-client-id" {
+            client_id = Some(value.to_string());
+            continue;
+        }
+        let ns = value
+            .strip_suffix("ns")
+            .and_then(|ns| ns.trim().parse::<u64>().ok());
+        if let Some(ns) = ns.filter(|_| is_engine(key, engines)) {
+            busy_ns = Some(busy_ns.unwrap_or(0) + ns);
+        }
+    }
+    Some((client_id?, busy_ns?))
+}
+
+/// Utilization per pid in one column of `nvidia-smi pmon -s u`, such as `enc` or `sm`, whose
+/// header names the columns, e.g. `# gpu  pid  type  sm  mem  enc  dec  command`. Idle
+/// columns read `-`.
+fn parse_pmon_column(output: &str, column: &str) -> HashMap<u32, f32> {
+    let mut columns = None;
+    let mut usage = HashMap::new();
+    for line in output.lines() {
+        if let Some(header) = line.strip_prefix('#') {
+            let names = header.split_whitespace().collect::<Vec<_>>();
+            let pid = names.iter().position(|name| *name == "pid");
+            let value = names.iter().position(|name| *name == column);
+            if let (Some(pid), Some(value)) = (pid, value) {
+                columns = Some((pid, value));
+            }
+            continue;
+        }
+        let Some((pid_column, value_column)) = columns else {
+            continue;
+        };
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let pid = fields
+            .get(pid_column)
+            .and_then(|pid| pid.parse::<u32>().ok());
+        let value = fields
+            .get(value_column)
+            .and_then(|value| value.parse::<f32>().ok());
+        if let (Some(pid), Some(value)) = (pid, value) {
+            *usage.entry(pid).or_default() += value;
+        }
+    }
+    usage
+}
+
+/// Output of `nvidia-smi pmon`, whose columns include `sm` for 3D and `enc` for encoding.
+fn read_nvidia_pmon() -> Option<String> {
+    let output = Command::new("nvidia-smi")
+        .args(["pmon", "--count", "1", "--select", "u"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// DRM clients a process holds open, with the busy time of their encoder and render engines,
+/// from its `/proc` directory.
+fn read_drm_clients(proc_dir: &Path) -> Vec<(String, ClientBusy)> {
+    let Ok(fds) = fs::read_dir(proc_dir.join("fd")) else {
+        return Vec::new();
+    };
+    fds.flatten()
+        .filter(|fd| fs::read_link(fd.path()).is_ok_and(|target| target.starts_with("/dev/dri")))
+        .filter_map(|fd| fs::read_to_string(proc_dir.join("fdinfo").join(fd.file_name())).ok())
+        .filter_map(|contents| {
+            let encoder = parse_drm_fdinfo(&contents, &ENCODER_ENGINES);
+            let render = parse_drm_fdinfo(&contents, &RENDER_ENGINES);
+            let client_id = encoder.as_ref().or(render.as_ref())?.0.clone();
+            Some((
+                client_id,
+                (encoder.map(|(_, busy)| busy), render.map(|(_, busy)| busy)),
+            ))
+        })
+        .collect()
+}
+
+/// Load per pid in percent of an engine, added up over the DRM clients of each pid and, on
+/// NVIDIA, the `nvidia-smi pmon` columns, since the `previous` sample. DRM clients are
+/// read from each pid's `/proc` directory.
+fn sample_engines(
+    proc_dirs: &[(u32, PathBuf)],
+    nvidia: bool,
+    previous: Option<EngineSample>,
+) -> EngineSample {
+    let pmon = nvidia.then(read_nvidia_pmon).flatten();
+    let mut encoder = pmon
+        .as_deref()
+        .map(|pmon| parse_pmon_column(pmon, "enc"))
+        .unwrap_or_default();
+    let mut render = pmon
+        .as_deref()
+        .map(|pmon| parse_pmon_column(pmon, "sm"))
+        .unwrap_or_default();
+
+    let busy_ns = proc_dirs
+        .iter()
+        .flat_map(|(pid, proc_dir)| {
+            let pid = *pid;
+            read_drm_clients(proc_dir)
+                .into_iter()
+                .map(move |(client_id, busy)| ((pid, client_id), busy))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let sampled_at = Instant::now();
+    if let Some(previous) = previous {
+        let elapsed_ns = sampled_at.duration_since(previous.sampled_at).as_nanos() as f64;
+        let add_load = |usage: &mut HashMap<u32, f32>, pid: u32, busy, before| {
+            let (Some(busy), Some(before)): (Option<u64>, Option<u64>) = (busy, before) else {
+                return;
+            };
+            let percent = busy.saturating_sub(before) as f64 / elapsed_ns * 100.0;
+            *usage.entry(pid).or_default() += percent.clamp(0.0, 100.0) as f32;
+        };
+        if elapsed_ns > 0.0 {
+            for (key, (encoder_busy, render_busy)) in &busy_ns {
+                let Some((encoder_before, render_before)) = previous.busy_ns.get(key) else {
+                    continue;
+                };
+                add_load(&mut encoder, key.0, *encoder_busy, *encoder_before);
+                add_load(&mut render, key.0, *render_busy, *render_before);
+            }
+        }
+    }
+    EngineSample {
+        busy_ns,
+        sampled_at,
+        encoder,
+        render,
+    }
+}
+
+impl AppModel {
+    pub(super) fn toggle_encoder_column(&mut self) {
+        let enabled = !self.config.show_encoder_column;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_show_encoder_column(handler, enabled) {
+                eprintln!("failed to save encoder column setting: {err}");
+            }
+        } else {
+            self.config.show_encoder_column = enabled;
+        }
+    }
+
+    /// Starts a background sample of the GPU engines, unless one is still running. Nothing
+    /// is sampled unless the Encoder column is shown or the Games page is open.
+    pub(super) fn request_engine_sample(&mut self) -> Task<cosmic::Action<Message>> {
+        let encoder = self.config.show_encoder_column;
+        if !encoder && self.active_page() != Page::Games {
+            self.engine_monitor = EngineMonitor::default();
+            return Task::none();
+        }
+        if self.engine_monitor.sampling {
+            return Task::none();
+        }
+        self.engine_monitor.sampling = true;
+
+        // The Encoder column needs every process; the Games page only the games.
+        let pids = if encoder {
+            self.process_source
+                .processes()
+                .values()
+                .map(|process| process.pid)
+                .collect::<Vec<_>>()
+        } else {
+            self.running_games()
+                .into_iter()
+                .flat_map(|entry| entry.pids.iter().copied())
+                .collect()
+        };
+        let proc_dirs = pids
+            .into_iter()
+            .filter_map(|pid| Some((pid, self.process_source.proc_dir(pid)?)))
+            .collect::<Vec<_>>();
+        let nvidia = self.gpu_runtime_info.provider == "NVIDIA";
+        let previous = self.engine_monitor.last.clone();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || sample_engines(&proc_dirs, nvidia, previous))
+                    .await
+                    .ok()
+            },
+            |sample| cosmic::Action::App(Message::EnginesSampled(sample)),
+        )
+    }
+
+    pub(super) fn set_engine_sample(&mut self, sample: Option<EngineSample>) {
+        let monitor = &mut self.engine_monitor;
+        monitor.sampling = false;
+        if sample.is_some() {
+            monitor.last = sample;
+        }
+    }
+
+    /// Encoder load per pid in percent of an encoder engine, from the last sample, while the
+    /// Encoder column is shown.
+    pub(super) fn encoder_usage(&self) -> HashMap<u32, f32> {
+        self.engine_monitor
+            .last
+            .as_ref()
+            .filter(|_| self.config.show_encoder_column)
+            .map(|sample| sample.encoder.clone())
+            .unwrap_or_default()
+    }
+
+    /// 3D load per pid in percent of a render engine, from the last sample.
+    pub(super) fn render_usage(&self) -> Option<&HashMap<u32, f32>> {
+        self.engine_monitor
+            .last
+            .as_ref()
+            .map(|sample| &sample.render)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ENCODER_ENGINES, RENDER_ENGINES, parse_drm_fdinfo, parse_pmon_column};
+
+    #[test]
+    fn parses_amdgpu_and_i915_fdinfo() {
+        let amdgpu = "pos:\t0\ndrm-driver:\tamdgpu\ndrm-client-id:\t17\n\
+                      drm-engine-gfx:\t9000 ns\ndrm-engine-enc:\t1500 ns\n\
+                      drm-engine-enc_1:\t500 ns\n";
+        assert_eq!(
+            parse_drm_fdinfo(amdgpu, &ENCODER_ENGINES),
+            Some(("17".to_string(), 2000))
+        );
+        assert_eq!(
+            parse_drm_fdinfo(amdgpu, &RENDER_ENGINES),
+            Some(("17".to_string(), 9000))
+        );
+
+        let i915 = "drm-driver:\ti915\ndrm-client-id:\t3\ndrm-engine-render:\t10 ns\n\
+                    drm-engine-video:\t40 ns\ndrm-engine-video-enhance:\t7 ns\n";
+        assert_eq!(
+            parse_drm_fdinfo(i915, &ENCODER_ENGINES),
+            Some(("3".to_string(), 40))
+        );
+
+        assert_eq!(
+            parse_drm_fdinfo(
+                "drm-client-id:\t3\ndrm-engine-gfx:\t1 ns\n",
+                &ENCODER_ENGINES
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_nvidia_pmon_columns() {
+        let output = "# gpu         pid   type     sm    mem    enc    dec    command\n\
+                      # Idx           #    C/G      %      %      %      %    name\n\
+                          0        4242     G     12      5     31      -    obs\n\
+                          0        1337     G     80     20      -      -    game\n";
+        let usage = parse_pmon_column(output, "enc");
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[&4242], 31.0);
+        assert_eq!(parse_pmon_column(output, "sm")[&1337], 80.0);
+    }
+}
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Apps starting and exiting, found by comparing each refresh's app list with the previous
+//! one and kept for the Events page. Refreshes follow proc connector events when those are
+//! available, so the times are exact rather than up to a refresh late.
+
+use super::*;
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Events kept before the oldest are dropped.
+const MAX_APP_EVENTS: usize = 500;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum AppEventKind {
+    Started,
+    Exited,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct AppEvent {
+    pub(super) kind: AppEventKind,
+    pub(super) app_id: String,
+    pub(super) name: String,
+    pub(super) at: SystemTime,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct AppEventLog {
+    /// Names of the apps running at the last refresh, by app id; `None` before the first.
+    running: Option<HashMap<String, String>>,
+    pub(super) events: VecDeque<AppEvent>,
+}
+
+/// Apps that started and exited between two app lists of app id and name.
+fn diff_app_lists(
+    previous: &HashMap<String, String>,
+    current: &HashMap<String, String>,
+) -> Vec<(AppEventKind, String, String)> {
+    let mut exited = previous
+        .iter()
+        .filter(|(app_id, _)| !current.contains_key(*app_id))
+        .map(|(app_id, name)| (AppEventKind::Exited, app_id.clone(), name.clone()))
+        .collect::<Vec<_>>();
+    let mut started = current
+        .iter()
+        .filter(|(app_id, _)| !previous.contains_key(*app_id))
+        .map(|(app_id, name)| (AppEventKind::Started, app_id.clone(), name.clone()))
+        .collect::<Vec<_>>();
+    exited.sort_by(|left, right| left.2.cmp(&right.2));
+    started.sort_by(|left, right| left.2.cmp(&right.2));
+    exited.extend(started);
+    exited
+}
+
+/// Local time of day as `HH:MM:SS`.
+pub(super) fn format_time_of_day(unix_seconds: u64, offset_seconds: i64) -> String {
+    let seconds = (unix_seconds as i64 + offset_seconds).rem_euclid(86_400);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        (seconds % 3_600) / 60,
+        seconds % 60
+    )
+}
+
+impl AppEvent {
+    pub(super) fn time_of_day(&self) -> String {
+        let unix_seconds = self
+            .at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        format_time_of_day(unix_seconds, playtime::local_utc_offset(unix_seconds))
+    }
+}
+
+impl AppModel {
+    /// Records the apps that started or exited since the last refresh.
+    pub(super) fn record_app_events(&mut self) {
+        let current = self
+            .process_entries
+            .iter()
+            .map(|entry| (entry.app_id.clone(), entry.display_name.clone()))
+            .collect::<HashMap<_, _>>();
+        let log = &mut self.app_events;
+        if let Some(previous) = log.running.as_ref() {
+            let now = SystemTime::now();
+            for (kind, app_id, name) in diff_app_lists(previous, &current) {
+                log.events.push_back(AppEvent {
+                    kind,
+                    app_id,
+                    name,
+                    at: now,
+                });
+            }
+            while log.events.len() > MAX_APP_EVENTS {
+                log.events.pop_front();
+            }
+        }
+        log.running = Some(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppEventKind, diff_app_lists, format_time_of_day};
+    use std::collections::HashMap;
+
+    fn apps(list: &[(&str, &str)]) -> HashMap<String, String> {
+        list.iter()
+            .map(|(app_id, name)| (app_id.to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_started_and_exited_apps() {
+        let previous = apps(&[("firefox", "Firefox"), ("steam", "Steam")]);
+        let current = apps(&[("steam", "Steam"), ("code", "Code"), ("gimp", "GIMP")]);
+        assert_eq!(
+            diff_app_lists(&previous, &current),
+            vec![
+                (
+                    AppEventKind::Exited,
+                    "firefox".to_string(),
+                    "Firefox".to_string()
+                ),
+                (
+                    AppEventKind::Started,
+                    "code".to_string(),
+                    "Code".to_string()
+                ),
+                (
+                    AppEventKind::Started,
+                    "gimp".to_string(),
+                    "GIMP".to_string()
+                ),
+            ]
+        );
+        assert!(diff_app_lists(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn formats_local_time_of_day() {
+        assert_eq!(format_time_of_day(0, 0), "00:00:00");
+        assert_eq!(format_time_of_day(1_709_335_800, 7_200), "01:30:00");
+        assert_eq!(format_time_of_day(3_661, -7_200), "23:01:01");
+    }
+}
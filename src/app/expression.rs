@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Arithmetic over per-app fields for user-defined columns, e.g. `rss / threads` or
+//! `(read_bytes + write_bytes) / 1024`.
+
+/// Fields an expression can refer to, in the order they are documented in Settings.
+pub(super) const EXPRESSION_FIELDS: &[&str] = &[
+    "cpu",
+    "rss",
+    "threads",
+    "processes",
+    "read_bytes",
+    "write_bytes",
+    "wakeups",
+    "fds",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Field(String),
+    Negate(Box<Node>),
+    Binary(Box<Node>, char, Box<Node>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Expression(Node);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+        } else if ch.is_ascii_digit() || ch == '.' {
+            let mut number = String::new();
+            while let Some(&ch) = chars.peek().filter(|ch| ch.is_ascii_digit() || **ch == '.') {
+                number.push(ch);
+                chars.next();
+            }
+            tokens.push(Token::Number(
+                number
+                    .parse()
+                    .map_err(|_| format!("invalid number `{number}`"))?,
+            ));
+        } else if ch.is_ascii_alphabetic() || ch == '_' {
+            let mut ident = String::new();
+            while let Some(&ch) = chars
+                .peek()
+                .filter(|ch| ch.is_ascii_alphanumeric() || **ch == '_')
+            {
+                ident.push(ch);
+                chars.next();
+            }
+            tokens.push(Token::Ident(ident));
+        } else if "+-*/()".contains(ch) {
+            tokens.push(Token::Op(ch));
+            chars.next();
+        } else {
+            return Err(format!("unexpected `{ch}`"));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser for `+ - * /`, unary minus, and parentheses.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek_op(&self) -> Option<char> {
+        match self.tokens.get(self.position) {
+            Some(Token::Op(op)) => Some(*op),
+            _ => None,
+        }
+    }
+
+    fn binary(
+        &mut self,
+        ops: &str,
+        operand: fn(&mut Self) -> Result<Node, String>,
+    ) -> Result<Node, String> {
+        let mut node = operand(self)?;
+        while let Some(op) = self.peek_op().filter(|op| ops.contains(*op)) {
+            self.position += 1;
+            node = Node::Binary(Box::new(node), op, Box::new(operand(self)?));
+        }
+        Ok(node)
+    }
+
+    fn sum(&mut self) -> Result<Node, String> {
+        self.binary("+-", Self::product)
+    }
+
+    fn product(&mut self) -> Result<Node, String> {
+        self.binary("*/", Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Node, String> {
+        if self.peek_op() == Some('-') {
+            self.position += 1;
+            return Ok(Node::Negate(Box::new(self.unary()?)));
+        }
+
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        match token {
+            Some(Token::Number(value)) => Ok(Node::Number(value)),
+            Some(Token::Ident(name)) if EXPRESSION_FIELDS.contains(&name.as_str()) => {
+                Ok(Node::Field(name))
+            }
+            Some(Token::Ident(name)) => Err(format!("unknown field `{name}`")),
+            Some(Token::Op('(')) => {
+                let node = self.sum()?;
+                if self.peek_op() != Some(')') {
+                    return Err("missing `)`".to_string());
+                }
+                self.position += 1;
+                Ok(node)
+            }
+            Some(Token::Op(op)) => Err(format!("unexpected `{op}`")),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+impl Expression {
+    pub(super) fn parse(input: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            tokens: tokenize(input)?,
+            position: 0,
+        };
+        let node = parser.sum()?;
+        if parser.position < parser.tokens.len() {
+            return Err("unexpected trailing input".to_string());
+        }
+        Ok(Self(node))
+    }
+
+    /// Evaluates the expression; `None` for unknown fields or division by zero.
+    pub(super) fn eval(&self, field: &dyn Fn(&str) -> Option<f64>) -> Option<f64> {
+        fn eval_node(node: &Node, field: &dyn Fn(&str) -> Option<f64>) -> Option<f64> {
+            match node {
+                Node::Number(value) => Some(*value),
+                Node::Field(name) => field(name),
+                Node::Negate(inner) => eval_node(inner, field).map(|value| -value),
+                Node::Binary(left, op, right) => {
+                    let (left, right) = (eval_node(left, field)?, eval_node(right, field)?);
+                    match op {
+                        '+' => Some(left + right),
+                        '-' => Some(left - right),
+                        '*' => Some(left * right),
+                        _ => (right != 0.0).then(|| left / right),
+                    }
+                }
+            }
+        }
+
+        eval_node(&self.0, field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Expression;
+
+    fn eval(input: &str) -> Option<f64> {
+        let fields = |name: &str| match name {
+            "rss" => Some(4096.0),
+            "threads" => Some(4.0),
+            "read_bytes" => Some(100.0),
+            "write_bytes" => Some(28.0),
+            _ => Some(0.0),
+        };
+        Expression::parse(input).unwrap().eval(&fields)
+    }
+
+    #[test]
+    fn evaluates_with_precedence_and_parentheses() {
+        assert_eq!(eval("rss/threads"), Some(1024.0));
+        assert_eq!(eval("read_bytes + write_bytes * 2"), Some(156.0));
+        assert_eq!(eval("(read_bytes + write_bytes) / 2"), Some(64.0));
+        assert_eq!(eval("-threads + 10"), Some(6.0));
+        assert_eq!(eval("rss / cpu"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(Expression::parse("rss +").is_err());
+        assert!(Expression::parse("rss * (threads").is_err());
+        assert!(Expression::parse("uid + 1").is_err());
+        assert!(Expression::parse("rss $ 2").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Test fixtures recorded from a live system. `cosmic-task-monitor --record-fixture <file>`
+//! saves one scan as JSON: every process, the desktop entries processes are matched
+//! against, and the metadata of the Steam games that are running. In tests and benchmarks,
+//! [`FixtureSource`] replays a fixture in place of the live system, so matching and
+//! grouping can be checked and timed against real process tables.
+
+use super::*;
+use std::collections::BTreeMap;
+
+/// A desktop app without its icon, which only makes sense on the recording system.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DesktopAppFixture {
+    app_id: String,
+    name: String,
+    primary_exec_keys: Vec<String>,
+    desktop_entry_id: Option<String>,
+    desktop_entry_path: Option<PathBuf>,
+    exec_command: Option<String>,
+    categories: Vec<String>,
+}
+
+impl DesktopAppFixture {
+    fn from_meta(meta: &DesktopAppMeta) -> Self {
+        let mut primary_exec_keys = meta.primary_exec_keys.iter().cloned().collect::<Vec<_>>();
+        primary_exec_keys.sort();
+        Self {
+            app_id: meta.app_id.clone(),
+            name: meta.name.clone(),
+            primary_exec_keys,
+            desktop_entry_id: meta.desktop_entry_id.clone(),
+            desktop_entry_path: meta.desktop_entry_path.clone(),
+            exec_command: meta.exec_command.clone(),
+            categories: meta.categories.clone(),
+        }
+    }
+
+    #[cfg(any(test, feature = "bench"))]
+    fn to_meta(&self) -> DesktopAppMeta {
+        DesktopAppMeta {
+            app_id: self.app_id.clone(),
+            name: self.name.clone(),
+            icon_handle: None,
+            primary_exec_keys: self.primary_exec_keys.iter().cloned().collect(),
+            desktop_entry_id: self.desktop_entry_id.clone(),
+            desktop_entry_path: self.desktop_entry_path.clone(),
+            exec_command: self.exec_command.clone(),
+            categories: self.categories.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SteamAppFixture {
+    name: String,
+    compat_prefix: Option<PathBuf>,
+    proton_build: Option<String>,
+    proton_tool: Option<String>,
+}
+
+impl SteamAppFixture {
+    fn from_meta(meta: &SteamAppMeta) -> Self {
+        Self {
+            name: meta.name.clone(),
+            compat_prefix: meta.compat_prefix.clone(),
+            proton_build: meta.proton_build.clone(),
+            proton_tool: meta.proton_tool.clone(),
+        }
+    }
+
+    #[cfg(test)]
+    fn to_meta(&self) -> SteamAppMeta {
+        SteamAppMeta {
+            name: self.name.clone(),
+            icon_handle: None,
+            compat_prefix: self.compat_prefix.clone(),
+            proton_build: self.proton_build.clone(),
+            proton_tool: self.proton_tool.clone(),
+        }
+    }
+}
+
+/// One scan of the system. Maps are ordered so a re-recorded fixture diffs cleanly.
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    user_id: Option<u32>,
+    cpu_count: usize,
+    used_swap: u64,
+    processes: Vec<ProcessSample>,
+    /// Desktop apps by the executable key they are matched on.
+    desktop_apps: BTreeMap<String, DesktopAppFixture>,
+    /// Steam games by app id.
+    steam_apps: BTreeMap<String, SteamAppFixture>,
+}
+
+impl Fixture {
+    fn record() -> Self {
+        let mut source = SysinfoSource::new();
+        // CPU usage is measured between two refreshes.
+        source.refresh();
+        std::thread::sleep(PROCESS_REFRESH_INTERVAL);
+        source.refresh();
+
+        let processes = source.processes();
+        let mut steam_apps = BTreeMap::new();
+        for process in processes.values() {
+            let Some(steam_app_id) = AppModel::steam_app_id_for_process(process, processes)
+                .or_else(|| AppModel::shader_precache_for_process(process).flatten())
+            else {
+                continue;
+            };
+            let recorded = steam_apps.entry(steam_app_id.clone()).or_insert_with(|| {
+                SteamAppFixture::from_meta(&AppModel::load_steam_app_meta(&steam_app_id, None))
+            });
+            if recorded.compat_prefix.is_some() && recorded.proton_tool.is_none() {
+                recorded.proton_tool = AppModel::proton_tool_for_process(process);
+            }
+        }
+
+        let mut samples = processes.values().cloned().collect::<Vec<_>>();
+        samples.sort_by_key(|sample| sample.pid);
+
+        Self {
+            user_id: source.current_user_id(),
+            cpu_count: source.cpu_count(),
+            used_swap: source.used_swap(),
+            processes: samples,
+            desktop_apps: AppModel::load_desktop_app_map(&mut IconCache::default())
+                .iter()
+                .map(|(key, meta)| (key.clone(), DesktopAppFixture::from_meta(meta)))
+                .collect(),
+            steam_apps,
+        }
+    }
+}
+
+/// Records one scan of the system to a fixture file.
+pub fn record_fixture(path: &Path) -> std::io::Result<()> {
+    let fixture = serde_json::to_vec_pretty(&Fixture::record()).map_err(std::io::Error::other)?;
+    fs::write(path, fixture)
+}
+
+/// Replays a recorded fixture as if it were the live system.
+#[cfg(any(test, feature = "bench"))]
+pub(super) struct FixtureSource {
+    fixture: Fixture,
+    processes: HashMap<Pid, ProcessSample>,
+    /// A directory standing in for `/proc`, with one directory per pid.
+    proc_root: Option<PathBuf>,
+}
+
+#[cfg(any(test, feature = "bench"))]
+impl FixtureSource {
+    pub(super) fn from_json(json: &str) -> serde_json::Result<Self> {
+        let fixture = serde_json::from_str::<Fixture>(json)?;
+        let processes = fixture
+            .processes
+            .iter()
+            .map(|sample| (Pid::from_u32(sample.pid), sample.clone()))
+            .collect();
+        Ok(Self {
+            fixture,
+            processes,
+            proc_root: None,
+        })
+    }
+
+    /// The recorded desktop apps, keyed like [`AppModel::load_desktop_app_map`].
+    pub(super) fn desktop_apps(&self) -> HashMap<String, DesktopAppMeta> {
+        self.fixture
+            .desktop_apps
+            .iter()
+            .map(|(key, app)| (key.clone(), app.to_meta()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl FixtureSource {
+    /// Reads the files of the recorded processes below `root`, e.g. `root/4210/status`.
+    pub(super) fn with_proc_root(mut self, root: &Path) -> Self {
+        self.proc_root = Some(root.to_path_buf());
+        self
+    }
+
+    /// The recorded Steam games by app id.
+    pub(super) fn steam_apps(&self) -> HashMap<String, SteamAppMeta> {
+        self.fixture
+            .steam_apps
+            .iter()
+            .map(|(app_id, app)| (app_id.clone(), app.to_meta()))
+            .collect()
+    }
+}
+
+#[cfg(any(test, feature = "bench"))]
+impl SystemSource for FixtureSource {
+    /// A fixture is a single scan, so every refresh sees it again.
+    fn refresh(&mut self) {}
+
+    fn processes(&self) -> &HashMap<Pid, ProcessSample> {
+        &self.processes
+    }
+
+    fn current_user_id(&self) -> Option<u32> {
+        self.fixture.user_id
+    }
+
+    fn cpu_count(&self) -> usize {
+        self.fixture.cpu_count
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.fixture.used_swap
+    }
+
+    /// Recorded processes are not running.
+    fn signal(&self, _pid: u32, _signal: Signal) -> bool {
+        false
+    }
+
+    /// Recorded processes have no `/proc` directory, unless a test lays one out.
+    fn proc_dir(&self, pid: u32) -> Option<PathBuf> {
+        Some(self.proc_root.as_ref()?.join(pid.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FixtureSource, SystemSource};
+    use sysinfo::Pid;
+
+    const DESKTOP_SESSION: &str = include_str!("../../tests/fixtures/desktop-session.json");
+
+    #[test]
+    fn replays_recorded_scan() {
+        let mut source = FixtureSource::from_json(DESKTOP_SESSION).expect("fixture parses");
+        source.refresh();
+        assert_eq!(source.cpu_count(), 8);
+        assert_eq!(source.current_user_id(), Some(1000));
+
+        let game = &source.processes()[&Pid::from_u32(4210)];
+        assert_eq!(&*game.name, "portal2_linux");
+        assert_eq!(game.parent, Some(4200));
+
+        let desktop_apps = source.desktop_apps();
+        assert_eq!(desktop_apps["firefox"].app_id, "firefox");
+        assert!(desktop_apps["firefox"].icon_handle.is_none());
+        assert_eq!(source.steam_apps()["620"].name, "Portal 2");
+    }
+}
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The "Free a port" dialog: finds the processes listening on a port, with the same lookup
+//! as the `port:` search run in the background, and offers to stop the app each belongs to.
+//! Owners are found among all processes rather than the listed entries, so the lookup does
+//! not depend on how the list is grouped.
+
+use super::search::SearchQuery;
+use super::*;
+
+#[derive(Debug, Default)]
+pub(super) struct FreePortDialog {
+    port: String,
+    /// Whether a lookup is running.
+    looking_up: bool,
+    /// Processes listening on the port, once a lookup has run: app id, process name, and pid.
+    owners: Option<Vec<(String, String, u32)>>,
+}
+
+impl AppModel {
+    pub(super) fn open_free_port_dialog(&mut self) {
+        self.free_port_dialog = Some(FreePortDialog::default());
+    }
+
+    pub(super) fn set_free_port_input(&mut self, port: String) {
+        if let Some(dialog) = self.free_port_dialog.as_mut() {
+            dialog.port = port;
+            dialog.owners = None;
+        }
+    }
+
+    pub(super) fn find_port_owners(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(dialog) = self.free_port_dialog.as_mut() else {
+            return Task::none();
+        };
+        let Ok(port) = dialog.port.trim().parse::<u16>() else {
+            return Task::none();
+        };
+        dialog.looking_up = true;
+        dialog.owners = None;
+
+        let proc_dirs = self
+            .process_source
+            .processes()
+            .values()
+            .filter_map(|process| Some((process.pid, self.process_source.proc_dir(process.pid)?)))
+            .collect::<Vec<_>>();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    search::look_up_search(&SearchQuery::Port(port), &proc_dirs).pids
+                })
+                .await
+                .unwrap_or_default()
+            },
+            move |pids| cosmic::Action::App(Message::PortOwnersFound { port, pids }),
+        )
+    }
+
+    /// Lists the processes found listening on `port`, with the app each belongs to, unless
+    /// the dialog has moved on to another port since.
+    pub(super) fn set_port_owners(&mut self, port: u16, pids: HashSet<u32>) {
+        let processes = self.process_source.processes();
+        let Some(dialog) = self.free_port_dialog.as_mut() else {
+            return;
+        };
+        if dialog.port.trim().parse::<u16>() != Ok(port) {
+            return;
+        }
+        let mut owners = processes
+            .values()
+            .filter(|process| pids.contains(&process.pid))
+            .filter_map(|process| {
+                let app_id = Self::resolved_app_id_for_process(
+                    process,
+                    processes,
+                    &self.desktop_apps_by_exec,
+                )?;
+                Some((app_id, process.name.to_string(), process.pid))
+            })
+            .collect::<Vec<_>>();
+        owners.sort_by_key(|(_, _, pid)| *pid);
+        dialog.looking_up = false;
+        dialog.owners = Some(owners);
+    }
+
+    pub(super) fn free_port(&mut self, app_id: String) -> Task<cosmic::Action<Message>> {
+        self.free_port_dialog = None;
+        self.request_stop(app_id, StopKind::Stop)
+    }
+
+    pub(super) fn free_port_dialog(&self) -> Option<Element<'_, Message>> {
+        let dialog = self.free_port_dialog.as_ref()?;
+        let port_is_valid = dialog.port.trim().parse::<u16>().is_ok();
+
+        let mut control = widget::column::with_capacity(2)
+            .push(
+                widget::text_input(fl!("free-port-placeholder"), dialog.port.as_str())
+                    .on_input(Message::SetFreePortInput)
+                    .on_submit(|_| Message::FindPortOwners),
+            )
+            .spacing(8);
+        match dialog.owners.as_deref() {
+            Some([]) => {
+                control = control.push(widget::text(fl!("free-port-none")));
+            }
+            Some(owners) => {
+                control = owners.iter().fold(control, |column, (app_id, name, pid)| {
+                    column.push(
+                        widget::row::with_capacity(2)
+                            .push(
+                                widget::text(format!("{name} ({})", fl!("process-pid", pid = pid)))
+                                    .width(Length::Fill),
+                            )
+                            .push(
+                                widget::button::destructive(fl!("free-port-stop"))
+                                    .on_press(Message::FreePort(app_id.clone())),
+                            )
+                            .align_y(Alignment::Center)
+                            .spacing(8),
+                    )
+                });
+            }
+            None => {}
+        }
+
+        Some(
+            widget::dialog()
+                .title(fl!("free-port-title"))
+                .body(fl!("free-port-description"))
+                .control(control)
+                .secondary_action(
+                    widget::button::standard(fl!("autostart-modal-cancel"))
+                        .on_press(Message::CloseFreePort),
+                )
+                .primary_action(
+                    widget::button::suggested(fl!("free-port-find")).on_press_maybe(
+                        (port_is_valid && !dialog.looking_up).then_some(Message::FindPortOwners),
+                    ),
+                )
+                .into(),
+        )
+    }
+}
@@ -0,0 +1,264 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Pausing an app and resuming it later, e.g. to free the CPU for a game without closing a
+//! build. An app in a systemd scope of its own, as COSMIC starts apps, is frozen through the
+//! scope's `cgroup.freeze`, which stops all of its processes at once, including ones it
+//! starts while paused. Other apps get SIGSTOP and SIGCONT on every member, which a process
+//! started in between misses.
+//!
+//! A frozen scope is read back from its `cgroup.events`, so one paused before the task
+//! monitor restarted still shows as paused and is thawed rather than sent SIGCONT. The task
+//! monitor itself and the desktop shell are never paused, as nothing could resume them.
+
+use super::*;
+
+/// Processes of the desktop shell and session, by process name or app id. Pausing one of
+/// them stops the session from drawing or taking input.
+const SHELL_PROCESSES: [&str; 6] = [
+    "cosmic-comp",
+    "cosmic-session",
+    "cosmic-panel",
+    "cosmic-greeter",
+    "xwayland",
+    "systemd",
+];
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum PauseMethod {
+    /// Frozen through the scope at this path.
+    Freezer(PathBuf),
+    Signals,
+}
+
+/// Apps paused from the task monitor, and how, so they are resumed the same way.
+#[derive(Debug, Default)]
+pub(super) struct PausedApps {
+    apps: HashMap<String, PauseMethod>,
+}
+
+/// The app's own scope, when it has one with a freezer.
+fn freezer_scope(source: &dyn SystemSource, pids: &[u32]) -> Option<PathBuf> {
+    cgroups::app_cgroup(source, pids).filter(|group| is_freezer_scope(group))
+}
+
+/// Whether a group is a scope with a freezer, rather than a service or slice shared with
+/// processes started in other ways.
+fn is_freezer_scope(group: &Path) -> bool {
+    group
+        .extension()
+        .is_some_and(|extension| extension == "scope")
+        && group.join("cgroup.freeze").is_file()
+}
+
+/// Whether a `cgroup.events` file reports the group as frozen.
+fn parse_frozen(events: &str) -> bool {
+    events.lines().any(|line| {
+        line.strip_prefix("frozen ")
+            .is_some_and(|value| value.trim() == "1")
+    })
+}
+
+fn is_scope_frozen(scope: &Path) -> bool {
+    fs::read_to_string(scope.join("cgroup.events")).is_ok_and(|events| parse_frozen(&events))
+}
+
+/// Freezes the scope, or falls back to signals without one or when it cannot be written,
+/// e.g. a scope the user does not own.
+fn freeze(scope: Option<PathBuf>) -> PauseMethod {
+    scope
+        .filter(|scope| match fs::write(scope.join("cgroup.freeze"), "1") {
+            Ok(()) => true,
+            Err(err) => {
+                eprintln!("failed to freeze {}: {err}", scope.display());
+                false
+            }
+        })
+        .map_or(PauseMethod::Signals, PauseMethod::Freezer)
+}
+
+/// The task monitor itself and the desktop shell, which no one could resume once paused.
+fn is_unpausable(entry: &ProcessEntry) -> bool {
+    entry.pids.contains(&std::process::id())
+        || SHELL_PROCESSES.iter().any(|shell| {
+            entry.name.eq_ignore_ascii_case(shell) || entry.app_id.eq_ignore_ascii_case(shell)
+        })
+}
+
+impl AppModel {
+    pub(super) fn pause_selected_application(&mut self) {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            return;
+        };
+        if is_unpausable(entry) {
+            return;
+        }
+        let app_id = entry.app_id.clone();
+        let method = freeze(freezer_scope(self.process_source.as_ref(), &entry.pids));
+        if method == PauseMethod::Signals {
+            self.signal_application(&app_id, Signal::Stop);
+        }
+        self.paused_apps.apps.insert(app_id, method);
+    }
+
+    pub(super) fn resume_selected_application(&mut self) {
+        let Some(app_id) = self
+            .selected_process
+            .as_ref()
+            .map(|selected| selected.app_id.clone())
+        else {
+            return;
+        };
+        match self.paused_apps.apps.remove(&app_id) {
+            Some(PauseMethod::Freezer(scope)) => {
+                if let Err(err) = fs::write(scope.join("cgroup.freeze"), "0") {
+                    eprintln!("failed to thaw {}: {err}", scope.display());
+                }
+            }
+            Some(PauseMethod::Signals) | None => {
+                self.signal_application(&app_id, Signal::Continue);
+            }
+        }
+    }
+
+    /// Forgets paused apps that are gone or were thawed elsewhere, and picks up the selected
+    /// app's scope when it is frozen, e.g. from before the task monitor restarted.
+    pub(super) fn refresh_paused_apps(&mut self) {
+        let entries = &self.process_entries;
+        self.paused_apps.apps.retain(|app_id, method| {
+            entries.iter().any(|entry| &entry.app_id == app_id)
+                && match method {
+                    PauseMethod::Freezer(scope) => is_scope_frozen(scope),
+                    PauseMethod::Signals => true,
+                }
+        });
+
+        let Some(entry) = self
+            .selected_process
+            .as_ref()
+            .and_then(|selected| entries.iter().find(|entry| entry.app_id == selected.app_id))
+        else {
+            return;
+        };
+        if self.paused_apps.apps.contains_key(&entry.app_id) {
+            return;
+        }
+        if let Some(scope) = freezer_scope(self.process_source.as_ref(), &entry.pids)
+            .filter(|scope| is_scope_frozen(scope))
+        {
+            self.paused_apps
+                .apps
+                .insert(entry.app_id.clone(), PauseMethod::Freezer(scope));
+        }
+    }
+
+    fn is_app_paused(&self, app_id: &str) -> bool {
+        self.paused_apps.apps.contains_key(app_id)
+    }
+
+    pub(super) fn paused_badge_icons(&self, entry: &ProcessEntry) -> Vec<&'static str> {
+        if self.is_app_paused(&entry.app_id) {
+            vec!["media-playback-pause-symbolic"]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Pause or Resume in the selected app's actions, with how a paused app was paused.
+    pub(super) fn selected_app_pause_button(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let button_height = Length::Fixed(38.0);
+        let method = self.paused_apps.apps.get(&selected.app_id);
+        let unpausable = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == selected.app_id)
+            .is_some_and(is_unpausable);
+        let button = match method {
+            Some(_) => widget::button::suggested(fl!("process-action-resume"))
+                .on_press(Message::ResumeSelectedApplication),
+            None => widget::button::standard(fl!("process-action-pause"))
+                .on_press_maybe((!unpausable).then_some(Message::PauseSelectedApplication)),
+        };
+        let caption = match method {
+            Some(PauseMethod::Freezer(_)) => Some(fl!("pause-method-freezer")),
+            Some(PauseMethod::Signals) => Some(fl!("pause-method-signals")),
+            None => unpausable.then(|| fl!("pause-unavailable")),
+        };
+        Some(
+            widget::column::with_capacity(2)
+                .push(button.width(Length::Fill).height(button_height))
+                .push_maybe(caption.map(|caption| widget::text(caption).size(12)))
+                .spacing(4)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_frozen_state_from_events() {
+        assert!(parse_frozen("populated 1\nfrozen 1\n"));
+        assert!(!parse_frozen("populated 1\nfrozen 0\n"));
+        assert!(!parse_frozen("populated 1\n"));
+    }
+
+    #[test]
+    fn only_scopes_with_a_freezer_are_frozen() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = dir.path().join("app-firefox.scope");
+        let service = dir.path().join("steam.service");
+        let bare = dir.path().join("app-steam.scope");
+        for group in [&scope, &service, &bare] {
+            fs::create_dir(group).unwrap();
+        }
+        fs::write(scope.join("cgroup.freeze"), "0").unwrap();
+        fs::write(service.join("cgroup.freeze"), "0").unwrap();
+
+        assert!(is_freezer_scope(&scope));
+        assert!(!is_freezer_scope(&service));
+        assert!(!is_freezer_scope(&bare));
+    }
+
+    #[test]
+    fn freezes_writable_scopes_and_falls_back_to_signals() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = dir.path().join("app-firefox.scope");
+        fs::create_dir(&scope).unwrap();
+        fs::write(scope.join("cgroup.freeze"), "0").unwrap();
+
+        assert_eq!(
+            freeze(Some(scope.clone())),
+            PauseMethod::Freezer(scope.clone())
+        );
+        assert_eq!(
+            fs::read_to_string(scope.join("cgroup.freeze")).unwrap(),
+            "1"
+        );
+        assert_eq!(freeze(None), PauseMethod::Signals);
+        assert_eq!(
+            freeze(Some(dir.path().join("gone.scope"))),
+            PauseMethod::Signals
+        );
+    }
+
+    #[test]
+    fn never_pauses_itself_or_the_shell() {
+        assert!(is_unpausable(&ProcessEntry {
+            pids: vec![1, std::process::id()],
+            ..ProcessEntry::for_app("com-github-exepta-cosmic-task-monitor")
+        }));
+        assert!(is_unpausable(&ProcessEntry::for_app("cosmic-comp")));
+        assert!(is_unpausable(&ProcessEntry {
+            name: "Xwayland".to_string(),
+            ..ProcessEntry::for_app("x11")
+        }));
+        assert!(!is_unpausable(&ProcessEntry::for_app("firefox")));
+    }
+}
@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Feral GameMode and Wine/Proton FSR detection for games, plus requesting or releasing
+//! GameMode for the selected game through the daemon's D-Bus API.
+
+use super::*;
+
+const GAMEMODE_REFRESH_INTERVAL: Duration = Duration::from_secs(3);
+const GAMEMODE_BUS_NAME: &str = "com.feralinteractive.GameMode";
+const GAMEMODE_OBJECT_PATH: &str = "/com/feralinteractive/GameMode";
+
+#[derive(Debug, Default)]
+pub(super) struct GameModeMonitor {
+    registered: HashSet<u32>,
+    fsr: HashSet<u32>,
+    /// When the registered games were last asked for.
+    refreshed_at: Option<Instant>,
+    /// Whether the daemon's answer is still awaited.
+    listing: bool,
+}
+
+/// Parses the reply of `busctl call ... ListGames`, e.g.
+/// `a(io) 2 1234 "/com/feralinteractive/GameMode/Games/1234" 5678 "/com/..."`.
+fn parse_busctl_games(output: &str) -> HashSet<u32> {
+    let mut tokens = output.split_whitespace();
+    if tokens.next() != Some("a(io)") {
+        return HashSet::new();
+    }
+    let count = tokens
+        .next()
+        .and_then(|count| count.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    tokens
+        .step_by(2)
+        .take(count)
+        .filter_map(|pid| pid.parse().ok())
+        .collect()
+}
+
+async fn call_gamemode(method: &str, signature: &str, args: &[String]) -> Option<String> {
+    let output = tokio::process::Command::new("busctl")
+        .args([
+            "--user",
+            "call",
+            GAMEMODE_BUS_NAME,
+            GAMEMODE_OBJECT_PATH,
+            GAMEMODE_BUS_NAME,
+            method,
+        ])
+        .args((!signature.is_empty()).then_some(signature))
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn has_fsr_enabled(source: &dyn SystemSource, pid: u32) -> bool {
+    source
+        .read_proc_file(pid, "environ")
+        .is_some_and(|environ| {
+            environ
+                .split(|byte| *byte == 0)
+                .any(|variable| variable == b"WINE_FULLSCREEN_FSR=1")
+        })
+}
+
+pub(super) fn is_game(entry: &ProcessEntry) -> bool {
+    entry.category == Some("Game") || entry.app_id.starts_with("steam-app-")
+}
+
+impl AppModel {
+    /// Asks the daemon for its registered games once the interval has passed.
+    pub(super) fn request_gamemode_state(&mut self) -> Task<cosmic::Action<Message>> {
+        let gamemode = &mut self.gamemode;
+        if gamemode.listing
+            || gamemode
+                .refreshed_at
+                .is_some_and(|refreshed| refreshed.elapsed() < GAMEMODE_REFRESH_INTERVAL)
+        {
+            return Task::none();
+        }
+        gamemode.listing = true;
+        gamemode.refreshed_at = Some(Instant::now());
+
+        Task::perform(
+            async {
+                call_gamemode("ListGames", "", &[])
+                    .await
+                    .map(|reply| parse_busctl_games(&reply))
+                    .unwrap_or_default()
+            },
+            |registered| cosmic::Action::App(Message::GameModeListed(registered)),
+        )
+    }
+
+    pub(super) fn set_gamemode_state(&mut self, registered: HashSet<u32>) {
+        // Only games are checked, since reading every process' environment is wasted work.
+        let fsr = self
+            .process_entries
+            .iter()
+            .filter(|entry| is_game(entry))
+            .flat_map(|entry| entry.pids.iter().copied())
+            .filter(|pid| has_fsr_enabled(self.process_source.as_ref(), *pid))
+            .collect();
+
+        self.gamemode.registered = registered;
+        self.gamemode.fsr = fsr;
+        self.gamemode.listing = false;
+    }
+
+    fn is_gamemode_active(&self, entry: &ProcessEntry) -> bool {
+        entry
+            .pids
+            .iter()
+            .any(|pid| self.gamemode.registered.contains(pid))
+    }
+
+    /// Symbolic icon names for GameMode and FSR, if the entry is using them.
+    pub(super) fn game_badge_icons(&self, entry: &ProcessEntry) -> Vec<&'static str> {
+        let mut icons = Vec::new();
+        if self.is_gamemode_active(entry) {
+            icons.push("input-gaming-symbolic");
+        }
+        if entry.pids.iter().any(|pid| self.gamemode.fsr.contains(pid)) {
+            icons.push("view-fullscreen-symbolic");
+        }
+        icons
+    }
+
+    pub(super) fn toggle_selected_app_gamemode(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            return Task::none();
+        };
+
+        let caller_pid = std::process::id().to_string();
+        let requests = if self.is_gamemode_active(entry) {
+            entry
+                .pids
+                .iter()
+                .filter(|pid| self.gamemode.registered.contains(pid))
+                .map(|pid| ("UnregisterGameByPID", *pid))
+                .collect::<Vec<_>>()
+        } else {
+            vec![("RegisterGameByPID", entry.pid)]
+        };
+        Task::perform(
+            async move {
+                for (method, pid) in requests {
+                    let reply =
+                        call_gamemode(method, "ii", &[caller_pid.clone(), pid.to_string()]).await;
+                    // The daemon answers `i 0` on success and `i -1` when it rejects it.
+                    if reply.as_deref().map(str::trim) != Some("i 0") {
+                        eprintln!("gamemode {method} for pid {pid} failed: {reply:?}");
+                    }
+                }
+            },
+            |()| cosmic::Action::App(Message::GameModeChanged),
+        )
+    }
+
+    /// Lists the registered games again after the selected game was toggled.
+    pub(super) fn refresh_changed_gamemode(&mut self) -> Task<cosmic::Action<Message>> {
+        self.gamemode.refreshed_at = None;
+        self.request_gamemode_state()
+    }
+
+    /// GameMode toggle for the selected app, if it is a game.
+    pub(super) fn selected_app_gamemode_toggle(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let entry = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == selected.app_id)
+            .filter(|entry| is_game(entry))?;
+
+        Some(
+            widget::settings::item(
+                fl!("gamemode-title"),
+                widget::toggler(self.is_gamemode_active(entry))
+                    .on_toggle(|_| Message::ToggleSelectedAppGameMode),
+            )
+            .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_busctl_games;
+
+    #[test]
+    fn parses_registered_game_pids() {
+        let reply = "a(io) 2 1234 \"/com/feralinteractive/GameMode/Games/1234\" \
+                     5678 \"/com/feralinteractive/GameMode/Games/5678\"\n";
+        let pids = parse_busctl_games(reply);
+        assert_eq!(pids.len(), 2);
+        assert!(pids.contains(&1234) && pids.contains(&5678));
+        assert!(parse_busctl_games("a(io) 0\n").is_empty());
+        assert!(parse_busctl_games("").is_empty());
+    }
+}
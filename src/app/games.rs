@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Data for the Games page: the 3D load of running games, sampled in the background by the
+//! engine sampler only while the page is open, and the cover art of recently played games
+//! that are not running.
+
+use super::*;
+use crate::config::GamePlaytime;
+
+/// Recently played games shown below the running ones.
+const RECENT_GAMES: usize = 8;
+
+#[derive(Debug, Default)]
+pub(super) struct GameLibrary {
+    /// 3D load per game app id, for games with a GPU client.
+    gpu_percent: HashMap<String, f32>,
+    /// Cover art of games that are not running, looked up once per game.
+    recent_icons: HashMap<String, Option<icon::Handle>>,
+}
+
+impl AppModel {
+    /// Takes the GPU load of running games from the last engine sample and finds cover art
+    /// for recent ones, only while the Games page is open.
+    pub(super) fn refresh_games(&mut self) {
+        if self.active_page() != Page::Games {
+            self.games.gpu_percent.clear();
+            return;
+        }
+
+        let usage = self.render_usage().cloned().unwrap_or_default();
+        self.games.gpu_percent = self
+            .running_games()
+            .into_iter()
+            .filter_map(|entry| {
+                let loads = entry
+                    .pids
+                    .iter()
+                    .filter_map(|pid| usage.get(pid))
+                    .collect::<Vec<_>>();
+                (!loads.is_empty()).then(|| {
+                    let percent = loads.into_iter().sum::<f32>().min(100.0);
+                    (entry.app_id.clone(), percent)
+                })
+            })
+            .collect();
+
+        let missing = self
+            .recent_games()
+            .into_iter()
+            .filter(|record| !self.games.recent_icons.contains_key(&record.app_id))
+            .map(|record| record.app_id.clone())
+            .collect::<Vec<_>>();
+        for app_id in missing {
+            let icon = self.game_cover_art(&app_id);
+            self.games.recent_icons.insert(app_id, icon);
+        }
+    }
+
+    fn game_cover_art(&self, app_id: &str) -> Option<icon::Handle> {
+        if let Some(steam_app_id) = app_id.strip_prefix("steam-app-") {
+            return Self::steam_icon_path(steam_app_id)
+                .or_else(|| Self::steam_shortcut(steam_app_id).and_then(|(_, icon)| icon))
+                .map(icon::from_path);
+        }
+        if app_id.starts_with("heroic-") {
+            return heroic::heroic_cover_art(app_id).map(icon::from_path);
+        }
+        self.desktop_meta_for_app_id(app_id)
+            .and_then(|meta| meta.icon_handle.as_deref().cloned())
+    }
+
+    /// Running games, in the Apps page's order.
+    pub(super) fn running_games(&self) -> Vec<&ProcessEntry> {
+        self.process_entries
+            .iter()
+            .filter(|entry| gamemode::is_game(entry))
+            .collect()
+    }
+
+    pub(super) fn game_gpu_percent(&self, app_id: &str) -> Option<f32> {
+        self.games.gpu_percent.get(app_id).copied()
+    }
+
+    /// Played games that are not running, most recently played first.
+    pub(super) fn recent_games(&self) -> Vec<&GamePlaytime> {
+        let mut recent = self
+            .playtime_records()
+            .iter()
+            .filter(|record| {
+                !self
+                    .process_entries
+                    .iter()
+                    .any(|entry| entry.app_id == record.app_id)
+            })
+            .collect::<Vec<_>>();
+        recent.sort_by(|left, right| {
+            right
+                .day
+                .cmp(&left.day)
+                .then(right.total_secs.cmp(&left.total_secs))
+        });
+        recent.truncate(RECENT_GAMES);
+        recent
+    }
+
+    pub(super) fn recent_game_icon(&self, app_id: &str) -> Option<icon::Handle> {
+        self.games.recent_icons.get(app_id).cloned().flatten()
+    }
+}
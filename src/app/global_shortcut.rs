@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Ctrl+Shift+Esc as a system-wide shortcut for the task monitor. COSMIC does not offer the
+//! GlobalShortcuts portal yet, so the binding is added to the custom shortcuts of COSMIC
+//! Settings, where it also shows up for the user to change. The file is read and written with
+//! the shortcut types of COSMIC Settings itself, so other bindings are kept as they are. It
+//! launches the task monitor, which as a single instance focuses the window that is open.
+
+use super::*;
+use cosmic_settings_config::shortcuts::{Action, Binding, Shortcuts};
+
+/// The command the shortcut launches the task monitor with outside Flatpak.
+const OWN_COMMAND: &str = "cosmic-task-monitor";
+
+/// How the binding is written to the custom shortcuts.
+const SHORTCUT_BINDING: &str = r#"(modifiers: [Ctrl, Shift], key: "Escape")"#;
+
+#[derive(Debug, Default)]
+pub(super) struct GlobalShortcut {
+    pub(super) registered: bool,
+    pub(super) error: Option<String>,
+}
+
+fn escape_binding() -> Binding {
+    ron::from_str(SHORTCUT_BINDING).expect("valid shortcut binding")
+}
+
+/// Whether `binding` is Ctrl+Shift+Esc, whatever its description.
+fn is_escape_binding(binding: &Binding) -> bool {
+    let escape = escape_binding();
+    binding.modifiers == escape.modifiers && binding.key == escape.key
+}
+
+/// Whether `action` launches the task monitor with one of the commands it registers.
+fn is_own_action(action: &Action) -> bool {
+    match action {
+        Action::Spawn(command) => {
+            let command = command.trim();
+            command == OWN_COMMAND || command == flatpak_command()
+        }
+        _ => false,
+    }
+}
+
+/// The action Ctrl+Shift+Esc runs in the custom shortcuts.
+fn bound_action(custom: &Shortcuts) -> Option<&Action> {
+    custom
+        .0
+        .iter()
+        .find(|(binding, _)| is_escape_binding(binding))
+        .map(|(_, action)| action)
+}
+
+/// Binds Ctrl+Shift+Esc to launch `command`, or returns the action that already holds it.
+fn add_binding(custom: &mut Shortcuts, command: &str) -> Result<(), String> {
+    match bound_action(custom) {
+        Some(action) if is_own_action(action) => return Ok(()),
+        Some(action) => return Err(format!("{action:?}")),
+        None => {}
+    }
+    custom
+        .0
+        .insert(escape_binding(), Action::Spawn(command.to_string()));
+    Ok(())
+}
+
+/// Removes a Ctrl+Shift+Esc binding that launches the task monitor.
+fn remove_binding(custom: &mut Shortcuts) {
+    custom
+        .0
+        .retain(|binding, action| !(is_escape_binding(binding) && is_own_action(action)));
+}
+
+/// The custom shortcuts COSMIC Settings keeps, on the host when running as a Flatpak.
+fn custom_shortcuts_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|_| !procfs_source::in_flatpak_sandbox())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("cosmic/com.system76.CosmicSettings.Shortcuts/v1/custom"))
+}
+
+fn flatpak_command() -> String {
+    format!("flatpak run {}", AppModel::APP_ID)
+}
+
+fn launch_command() -> String {
+    if procfs_source::in_flatpak_sandbox() {
+        flatpak_command()
+    } else {
+        OWN_COMMAND.to_string()
+    }
+}
+
+/// The custom shortcuts in `path`, none if the file does not exist yet.
+fn read_custom_shortcuts(path: &Path) -> std::io::Result<Shortcuts> {
+    match fs::read_to_string(path) {
+        Ok(custom) => ron::from_str(&custom)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Shortcuts::default()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Writes the custom shortcuts next to `path` and renames them into place, so COSMIC
+/// Settings never reads a partly written file.
+fn write_custom_shortcuts(path: &Path, custom: &Shortcuts) -> std::io::Result<()> {
+    let contents = ron::ser::to_string_pretty(custom, ron::ser::PrettyConfig::default())
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+    let Some(directory) = path.parent() else {
+        return Err(std::io::ErrorKind::NotFound.into());
+    };
+    fs::create_dir_all(directory)?;
+    let partial = directory.join(".custom.partial");
+    let mut file = fs::File::create(&partial)?;
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&partial, path)
+}
+
+impl GlobalShortcut {
+    pub(super) fn read() -> Self {
+        let registered = custom_shortcuts_path()
+            .and_then(|path| read_custom_shortcuts(&path).ok())
+            .is_some_and(|custom| bound_action(&custom).is_some_and(is_own_action));
+        Self {
+            registered,
+            error: None,
+        }
+    }
+}
+
+impl AppModel {
+    pub(super) fn toggle_global_shortcut(&mut self) {
+        let Some(path) = custom_shortcuts_path() else {
+            return;
+        };
+        // A file that does not parse is left alone rather than replaced.
+        let mut custom = match read_custom_shortcuts(&path) {
+            Ok(custom) => custom,
+            Err(err) => {
+                eprintln!("failed to read {}: {err}", path.display());
+                self.global_shortcut.error = Some(err.to_string());
+                return;
+            }
+        };
+
+        let enable = !self.global_shortcut.registered;
+        if enable {
+            if let Err(action) = add_binding(&mut custom, &launch_command()) {
+                self.global_shortcut.error = Some(fl!("global-shortcut-taken", action = action));
+                return;
+            }
+        } else {
+            remove_binding(&mut custom);
+        }
+
+        match write_custom_shortcuts(&path, &custom) {
+            Ok(()) => {
+                self.global_shortcut.registered = enable;
+                self.global_shortcut.error = None;
+            }
+            Err(err) => {
+                eprintln!("failed to write {}: {err}", path.display());
+                self.global_shortcut.error = Some(err.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Shortcuts, add_binding, bound_action, is_own_action, remove_binding};
+
+    fn parse(custom: &str) -> Shortcuts {
+        ron::from_str(custom).expect("valid shortcuts")
+    }
+
+    #[test]
+    fn adds_and_removes_the_binding() {
+        let original = parse(
+            "{\n    (\n        modifiers: [\n            Super,\n        ],\n        \
+             key: \"t\",\n    ): Spawn(\"cosmic-term\"),\n}\n",
+        );
+        let mut custom = original.clone();
+        add_binding(&mut custom, "cosmic-task-monitor").unwrap();
+        assert_eq!(
+            bound_action(&custom),
+            Some(&Action::Spawn("cosmic-task-monitor".to_string()))
+        );
+        assert_eq!(custom.0.len(), 2);
+        let added = custom.clone();
+        add_binding(&mut custom, "cosmic-task-monitor").unwrap();
+        assert_eq!(custom, added);
+        remove_binding(&mut custom);
+        assert_eq!(custom, original);
+
+        let mut empty = Shortcuts::default();
+        add_binding(&mut empty, "cosmic-task-monitor").unwrap();
+        assert!(bound_action(&empty).is_some_and(is_own_action));
+    }
+
+    #[test]
+    fn keeps_other_actions_on_the_binding() {
+        for command in [
+            "htop",
+            "cosmic-task-monitor-legacy",
+            "sh -c cosmic-task-monitor",
+        ] {
+            let original = parse(&format!(
+                "{{(modifiers: [Shift, Ctrl], key: \"Escape\"): Spawn(\"{command}\")}}"
+            ));
+            let mut custom = original.clone();
+            assert_eq!(
+                add_binding(&mut custom, "cosmic-task-monitor"),
+                Err(format!("Spawn({command:?})"))
+            );
+            remove_binding(&mut custom);
+            assert_eq!(custom, original);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Helper processes an app spawned that the Filter menu hides as background components,
+//! such as crash handlers or an editor's language servers. They are not part of any row
+//! unless a setting counts them towards the app's totals, so the app's details list them
+//! with their memory and CPU use, attributed to the app whose member is their nearest
+//! ancestor.
+
+use super::*;
+
+/// Parents followed from a helper before giving up on finding the app that spawned it.
+const MAX_ANCESTORS: usize = 32;
+
+#[derive(Debug, Clone)]
+pub(super) struct SpawnedHelper {
+    pub(super) pid: u32,
+    name: String,
+    cpu_percent: f32,
+    rss_bytes: u64,
+}
+
+/// The group of the nearest ancestor of `process` that is a member of one.
+fn spawning_group<'a>(
+    process: &ProcessSample,
+    processes: &HashMap<Pid, ProcessSample>,
+    member_groups: &HashMap<u32, &'a str>,
+) -> Option<&'a str> {
+    let mut parent = process.parent;
+    for _ in 0..MAX_ANCESTORS {
+        let pid = parent?;
+        if let Some(&group) = member_groups.get(&pid) {
+            return Some(group);
+        }
+        parent = processes.get(&Pid::from_u32(pid))?.parent;
+    }
+    None
+}
+
+impl AppModel {
+    /// The hidden background processes of the current user, by the group that spawned them,
+    /// largest first.
+    pub(super) fn collect_spawned_helpers(
+        &self,
+        processes: &HashMap<Pid, ProcessSample>,
+        eligible_pids: &HashSet<Pid>,
+        current_user_id: Option<u32>,
+        member_groups: &HashMap<u32, &str>,
+        cpu_core_count: f32,
+    ) -> HashMap<String, Vec<SpawnedHelper>> {
+        let mut helpers: HashMap<String, Vec<SpawnedHelper>> = HashMap::new();
+        for (pid, process) in processes {
+            if eligible_pids.contains(pid)
+                || current_user_id.is_some_and(|uid| process.user_id != Some(uid))
+                || process.name.trim().is_empty()
+                || process.name.starts_with('[')
+                || !self.is_hidden_background_process(process)
+            {
+                continue;
+            }
+            let Some(group) = spawning_group(process, processes, member_groups) else {
+                continue;
+            };
+            helpers
+                .entry(group.to_string())
+                .or_default()
+                .push(SpawnedHelper {
+                    pid: process.pid,
+                    name: process.name.to_string(),
+                    cpu_percent: (process.cpu_usage / cpu_core_count).clamp(0.0, 100.0),
+                    rss_bytes: process.memory,
+                });
+        }
+        for group_helpers in helpers.values_mut() {
+            group_helpers.sort_by(|a, b| b.rss_bytes.cmp(&a.rss_bytes).then(a.pid.cmp(&b.pid)));
+        }
+        helpers
+    }
+
+    pub(super) fn toggle_include_helpers(&mut self) {
+        let enabled = !self.config.include_helpers;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_include_helpers(handler, enabled) {
+                eprintln!("failed to save include helpers setting: {err}");
+            }
+        } else {
+            self.config.include_helpers = enabled;
+        }
+        self.refresh_processes();
+    }
+
+    /// The selected app's hidden helpers, with their memory and CPU use.
+    pub(super) fn selected_app_helpers(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let helpers = self
+            .spawned_helpers
+            .get(&selected.app_id)
+            .filter(|helpers| !helpers.is_empty())?;
+
+        let total_bytes = helpers.iter().map(|helper| helper.rss_bytes).sum();
+        let total = if self.config.include_helpers {
+            fl!(
+                "helpers-total-included",
+                count = helpers.len(),
+                size = units::format_bytes(total_bytes)
+            )
+        } else {
+            fl!(
+                "helpers-total",
+                count = helpers.len(),
+                size = units::format_bytes(total_bytes)
+            )
+        };
+        let mut column = widget::column::with_capacity(helpers.len() + 2)
+            .push(widget::text::heading(fl!("helpers-title")))
+            .push(widget::text(total));
+        for helper in helpers {
+            column = column.push(
+                widget::text(fl!(
+                    "helpers-row",
+                    name = helper.name.clone(),
+                    pid = helper.pid,
+                    size = units::format_bytes(helper.rss_bytes),
+                    cpu = units::format_percent(helper.cpu_percent, 1)
+                ))
+                .size(12),
+            );
+        }
+        Some(column.spacing(4).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::spawning_group;
+    use crate::app::source::ProcessSample;
+    use std::collections::HashMap;
+    use sysinfo::Pid;
+
+    #[test]
+    fn attributes_helpers_to_nearest_member() {
+        let sample = |pid: u32, parent: Option<u32>| ProcessSample {
+            pid,
+            parent,
+            start_time: 0,
+            name: format!("process-{pid}").into(),
+            exe: None,
+            cmd: Vec::new().into(),
+            user_id: None,
+            state: 'S',
+            cpu_usage: 0.0,
+            memory: 0,
+            threads: None,
+            read_bytes: 0,
+            written_bytes: 0,
+        };
+        let processes = [
+            sample(1, None),
+            sample(100, Some(1)),
+            sample(101, Some(100)),
+            sample(102, Some(101)),
+            sample(200, Some(1)),
+        ]
+        .into_iter()
+        .map(|process| (Pid::from_u32(process.pid), process))
+        .collect::<HashMap<_, _>>();
+        let member_groups = HashMap::from([(100, "code"), (101, "code-helper-host")]);
+
+        let group =
+            |pid: u32| spawning_group(&processes[&Pid::from_u32(pid)], &processes, &member_groups);
+        assert_eq!(group(102), Some("code-helper-host"));
+        assert_eq!(group(101), Some("code"));
+        assert_eq!(group(200), None);
+    }
+}
@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! GOG and Epic games started from the Heroic Games Launcher. Heroic marks the processes it
+//! launches with `HEROIC_APP_NAME` and `HEROIC_APP_SOURCE` in their environment; titles come
+//! from its store cache and cover art from the icons it keeps for game shortcuts, much like
+//! Steam games are named from their manifests and drawn from the library cache.
+
+use super::*;
+
+/// Store cache files, with the key of the game list in each.
+const STORE_CACHES: [(&str, &str); 2] = [
+    ("gog_library.json", "games"),
+    ("legendary_library.json", "library"),
+];
+
+/// A game as Heroic names it, e.g. source `gog` and app name `1207658924`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub(super) struct HeroicGame {
+    source: String,
+    app_name: String,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct HeroicResolver {
+    /// The game each process was launched for, read once per pid.
+    by_pid: HashMap<u32, Option<HeroicGame>>,
+    /// Title and cover art per app name, from the store cache once it has been read.
+    store: Option<HashMap<String, StoreEntry>>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct StoreEntry {
+    title: String,
+    /// Square box art, when the cache points at a local file, as for sideloaded games.
+    art: Option<String>,
+}
+
+impl HeroicGame {
+    pub(super) fn app_id(&self) -> String {
+        format!("heroic-{}-{}", self.source, self.app_name)
+    }
+}
+
+/// The game a process was launched for, from its NUL-separated environment.
+fn parse_heroic_environ(environ: &[u8]) -> Option<HeroicGame> {
+    let mut source = None;
+    let mut app_name = None;
+    for variable in environ.split(|byte| *byte == 0) {
+        let variable = String::from_utf8_lossy(variable);
+        if let Some(value) = variable.strip_prefix("HEROIC_APP_SOURCE=") {
+            source = Some(value.to_string());
+        } else if let Some(value) = variable.strip_prefix("HEROIC_APP_NAME=") {
+            app_name = Some(value.to_string());
+        }
+    }
+    let source = source.filter(|source| !source.is_empty())?;
+    let app_name = app_name.filter(|app_name| !app_name.is_empty())?;
+    Some(HeroicGame { source, app_name })
+}
+
+/// Title and local art per app name from a store cache file such as `gog_library.json`,
+/// whose `list_key` array holds objects like `{"app_name": "...", "title": "...",
+/// "art_square": "..."}`.
+fn parse_store_cache(json: &str, list_key: &str) -> HashMap<String, StoreEntry> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return HashMap::new();
+    };
+    let Some(games) = value.get(list_key).and_then(|games| games.as_array()) else {
+        return HashMap::new();
+    };
+    games
+        .iter()
+        .filter_map(|game| {
+            let field = |key: &str| game.get(key)?.as_str().map(str::to_string);
+            let app_name = field("app_name")?;
+            let title = field("title").filter(|title| !title.trim().is_empty())?;
+            let art = field("art_square")
+                .map(|art| art.strip_prefix("file://").unwrap_or(&art).to_string())
+                .filter(|art| art.starts_with('/'));
+            Some((app_name, StoreEntry { title, art }))
+        })
+        .collect()
+}
+
+/// Heroic's config directories, native and Flatpak.
+fn heroic_config_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(config_home) = env::var("XDG_CONFIG_HOME") {
+        dirs.push(PathBuf::from(config_home).join("heroic"));
+    }
+    if let Ok(home) = env::var("HOME") {
+        let home = PathBuf::from(home);
+        dirs.push(home.join(".config").join("heroic"));
+        dirs.push(
+            home.join(".var")
+                .join("app")
+                .join("com.heroicgameslauncher.hgl")
+                .join("config")
+                .join("heroic"),
+        );
+    }
+    dirs.dedup();
+    dirs.into_iter().filter(|dir| dir.is_dir()).collect()
+}
+
+fn load_store() -> HashMap<String, StoreEntry> {
+    let mut store = HashMap::new();
+    for config_dir in heroic_config_dirs() {
+        for (file, list_key) in STORE_CACHES {
+            let Ok(json) = fs::read_to_string(config_dir.join("store_cache").join(file)) else {
+                continue;
+            };
+            for (app_name, entry) in parse_store_cache(&json, list_key) {
+                store.entry(app_name).or_insert(entry);
+            }
+        }
+    }
+    store
+}
+
+impl HeroicResolver {
+    /// The Heroic game a process belongs to. Environments are read once per new pid, and
+    /// pids that have exited are forgotten.
+    pub(super) fn game_for_process(
+        &mut self,
+        process: &ProcessSample,
+        source: &dyn SystemSource,
+    ) -> Option<HeroicGame> {
+        let pid = process.pid;
+        self.by_pid
+            .entry(pid)
+            .or_insert_with(|| {
+                source
+                    .read_proc_file(pid, "environ")
+                    .and_then(|environ| parse_heroic_environ(&environ))
+            })
+            .clone()
+    }
+
+    pub(super) fn retain_pids(&mut self, running: &HashSet<Pid>) {
+        self.by_pid
+            .retain(|pid, _| running.contains(&Pid::from_u32(*pid)));
+    }
+
+    /// The game's title from Heroic's store cache, or its app name.
+    pub(super) fn title(&mut self, game: &HeroicGame) -> String {
+        self.store
+            .get_or_insert_with(load_store)
+            .get(&game.app_name)
+            .map_or_else(|| game.app_name.clone(), |entry| entry.title.clone())
+    }
+}
+
+/// Cover art of a Heroic game from its `heroic-<source>-<app name>` app id: the box art the
+/// store cache points to if it is a local file, else the icon Heroic saved for shortcuts.
+pub(super) fn heroic_cover_art(app_id: &str) -> Option<PathBuf> {
+    let (_, app_name) = app_id.strip_prefix("heroic-")?.split_once('-')?;
+    let store_art = load_store()
+        .remove(app_name)
+        .and_then(|entry| entry.art)
+        .map(PathBuf::from);
+    let shortcut_icons = heroic_config_dirs().into_iter().flat_map(|config_dir| {
+        ["png", "jpg"].map(|extension| {
+            config_dir
+                .join("icons")
+                .join(format!("{app_name}.{extension}"))
+        })
+    });
+    store_art
+        .into_iter()
+        .chain(shortcut_icons)
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeroicGame, StoreEntry, parse_heroic_environ, parse_store_cache};
+
+    #[test]
+    fn parses_heroic_launch_environment() {
+        let environ = b"HOME=/home/user\0HEROIC_APP_NAME=1207658924\0HEROIC_APP_SOURCE=gog\0\
+                        HEROIC_APP_RUNNER=gog\0";
+        assert_eq!(
+            parse_heroic_environ(environ),
+            Some(HeroicGame {
+                source: "gog".to_string(),
+                app_name: "1207658924".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_heroic_environ(b"HOME=/home/user\0HEROIC_APP_NAME=x\0"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_store_cache_titles_and_local_art() {
+        let json = r#"{"library": [
+            {"app_name": "Fortnite", "title": "Fortnite", "art_square": "https://cdn/x.jpg"},
+            {"app_name": "Sideload1", "title": "Game", "art_square": "file:///home/u/game.png"},
+            {"app_name": "NoTitle"}
+        ]}"#;
+        let store = parse_store_cache(json, "library");
+        assert_eq!(store.len(), 2);
+        assert_eq!(
+            store["Fortnite"],
+            StoreEntry {
+                title: "Fortnite".to_string(),
+                art: None,
+            }
+        );
+        assert_eq!(store["Sideload1"].art.as_deref(), Some("/home/u/game.png"));
+        assert!(parse_store_cache(json, "games").is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! User-defined highlight rules that tint apps whose name or command line matches a regex,
+//! e.g. `wine|proton` in purple. Matches are worked out once per refresh, not per frame.
+
+use super::*;
+use crate::config::{HighlightColor, HighlightRule};
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Default)]
+pub(super) struct Highlighter {
+    /// The rules `compiled` was built from, to notice config changes.
+    rules: Vec<HighlightRule>,
+    compiled: Vec<Option<Regex>>,
+    /// Index of the first matching rule per app id.
+    matches: HashMap<String, usize>,
+}
+
+pub(super) fn compile_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+pub(super) fn highlight_color(color: HighlightColor) -> Color {
+    match color {
+        HighlightColor::Purple => CPU_ACCENT,
+        HighlightColor::Red => Color::from_rgb(224.0 / 255.0, 82.0 / 255.0, 82.0 / 255.0),
+        HighlightColor::Orange => GPU_ACCENT,
+        HighlightColor::Yellow => DISK_ACCENT,
+        HighlightColor::Green => Color::from_rgb(98.0 / 255.0, 178.0 / 255.0, 98.0 / 255.0),
+        HighlightColor::Blue => NETWORK_ACCENT,
+    }
+}
+
+impl AppModel {
+    pub(super) fn refresh_highlights(&mut self) {
+        if self.highlighter.rules != self.config.highlight_rules {
+            self.highlighter.rules = self.config.highlight_rules.clone();
+            self.highlighter.compiled = self
+                .config
+                .highlight_rules
+                .iter()
+                .map(|rule| compile_pattern(&rule.pattern).ok())
+                .collect();
+        }
+
+        let highlighter = &self.highlighter;
+        let matches = if highlighter.rules.is_empty() {
+            HashMap::new()
+        } else {
+            self.process_entries
+                .iter()
+                .filter_map(|entry| {
+                    let command_lines = entry
+                        .pids
+                        .iter()
+                        .filter_map(|pid| self.process_source.processes().get(&Pid::from_u32(*pid)))
+                        .map(ProcessSample::cmdline)
+                        .collect::<Vec<_>>();
+                    let index = highlighter
+                        .rules
+                        .iter()
+                        .zip(&highlighter.compiled)
+                        .position(|(rule, regex)| {
+                            let Some(regex) = regex else {
+                                return false;
+                            };
+                            if rule.match_command_line {
+                                command_lines.iter().any(|line| regex.is_match(line))
+                            } else {
+                                regex.is_match(&entry.name) || regex.is_match(&entry.display_name)
+                            }
+                        })?;
+                    Some((entry.app_id.clone(), index))
+                })
+                .collect()
+        };
+        self.highlighter.matches = matches;
+    }
+
+    fn highlight_rule(&self, entry: &ProcessEntry) -> Option<&HighlightRule> {
+        let index = *self.highlighter.matches.get(&entry.app_id)?;
+        self.highlighter.rules.get(index)
+    }
+
+    /// Translucent row background for an app matched by a highlight rule.
+    pub(super) fn highlight_tint(&self, entry: &ProcessEntry) -> Option<Color> {
+        let rule = self.highlight_rule(entry)?;
+        Some(Color {
+            a: 0.2,
+            ..highlight_color(rule.color)
+        })
+    }
+
+    pub(super) fn highlight_badge(
+        &self,
+        entry: &ProcessEntry,
+    ) -> Option<Element<'static, Message>> {
+        let rule = self
+            .highlight_rule(entry)
+            .filter(|rule| !rule.badge.is_empty())?;
+        let color = highlight_color(rule.color);
+        Some(
+            widget::container(widget::text(rule.badge.clone()).size(11))
+                .padding([1, 6])
+                .class(theme::Container::custom(move |_theme| {
+                    widget::container::Style {
+                        border: Border {
+                            color,
+                            width: 1.0,
+                            radius: 8.0.into(),
+                        },
+                        ..Default::default()
+                    }
+                }))
+                .into(),
+        )
+    }
+}
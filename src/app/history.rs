@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Sample history behind the performance and detail graphs. The newest minute is kept at
+//! full resolution; with longer retention, older samples are averaged into buckets so every
+//! graph stays within a fixed number of points however much time it covers.
+
+use super::*;
+use crate::config::HistoryRetention;
+
+/// Points any one graph keeps, whatever the retention.
+const HISTORY_POINT_BUDGET: usize = 120;
+
+/// Samples a retention covers at the refresh interval.
+pub(super) fn retained_samples(retention: HistoryRetention) -> usize {
+    let interval = PROCESS_REFRESH_INTERVAL.as_secs().max(1);
+    (retention.seconds() / interval) as usize
+}
+
+/// Samples averaged into each point older than the full-resolution window.
+fn bucket_size(retained: usize) -> usize {
+    let older_samples = retained.saturating_sub(PERFORMANCE_HISTORY_POINTS);
+    let older_points = HISTORY_POINT_BUDGET - PERFORMANCE_HISTORY_POINTS;
+    older_samples.div_ceil(older_points).max(1)
+}
+
+/// Appends a sample taken on refresh `tick`. The sample leaving the full-resolution window
+/// joins the bucket it falls into, and the oldest points are dropped beyond the budget.
+pub(super) fn push_sample(history: &mut Vec<f32>, value: f32, retained: usize, tick: u64) {
+    history.push(value);
+    if retained <= HISTORY_POINT_BUDGET {
+        let overflow = history.len().saturating_sub(retained);
+        history.drain(..overflow);
+        return;
+    }
+
+    let bucket_size = bucket_size(retained);
+    // Every series is sampled on the same ticks, so the tick says how full the bucket is.
+    let merged = (tick % bucket_size as u64) as usize;
+    if merged > 0 && history.len() > PERFORMANCE_HISTORY_POINTS + 1 {
+        let graduated = history.len() - PERFORMANCE_HISTORY_POINTS - 1;
+        let sample = history.remove(graduated);
+        let bucket = &mut history[graduated - 1];
+        *bucket += (sample - *bucket) / (merged + 1) as f32;
+    }
+    let overflow = history.len().saturating_sub(HISTORY_POINT_BUDGET);
+    history.drain(..overflow);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HISTORY_POINT_BUDGET, PERFORMANCE_HISTORY_POINTS, push_sample};
+
+    #[test]
+    fn keeps_short_retention_at_full_resolution() {
+        let mut history = Vec::new();
+        for tick in 0..100 {
+            push_sample(&mut history, tick as f32, 60, tick);
+        }
+        assert_eq!(history.len(), 60);
+        assert_eq!(history[0], 40.0);
+        assert_eq!(history[59], 99.0);
+    }
+
+    #[test]
+    fn averages_older_samples_within_the_budget() {
+        let retained = 3600;
+        let mut history = Vec::new();
+        for tick in 0..10_000 {
+            push_sample(&mut history, (tick % 2) as f32, retained, tick);
+            assert!(history.len() <= HISTORY_POINT_BUDGET);
+        }
+        assert_eq!(history.len(), HISTORY_POINT_BUDGET);
+
+        let (older, recent) = history.split_at(HISTORY_POINT_BUDGET - PERFORMANCE_HISTORY_POINTS);
+        assert!(recent.iter().all(|sample| *sample == 0.0 || *sample == 1.0));
+        assert!(older.iter().all(|bucket| (bucket - 0.5).abs() < 0.05));
+    }
+}
@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Icon handles of desktop apps, shared between refreshes. The desktop entries are read
+//! again on every refresh, and each app is listed under several lookup keys, so without the
+//! cache every key got its own copy of a handle that was created anew each time. Handles are
+//! kept per app id for as long as the entry's icon stays the same, and the least recently
+//! used are dropped beyond a fixed number, so uninstalled apps do not linger.
+
+use super::*;
+
+/// Handles kept at most, well above the desktop entries a typical system has installed.
+const ICON_CACHE_CAPACITY: usize = 512;
+
+#[derive(Debug)]
+struct CachedIcon {
+    /// The icon the desktop entry named when the handle was made.
+    source: String,
+    handle: Arc<icon::Handle>,
+    used_at: u64,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct IconCache {
+    icons: HashMap<String, CachedIcon>,
+    /// Counts lookups, to order entries by when they were last used.
+    uses: u64,
+}
+
+impl IconCache {
+    /// The handle for `app_id`, made with `make` if it is not cached or its icon changed.
+    pub(super) fn get_or_insert(
+        &mut self,
+        app_id: &str,
+        source: &str,
+        make: impl FnOnce() -> icon::Handle,
+    ) -> Arc<icon::Handle> {
+        self.uses += 1;
+        let used_at = self.uses;
+        if let Some(cached) = self
+            .icons
+            .get_mut(app_id)
+            .filter(|cached| cached.source == source)
+        {
+            cached.used_at = used_at;
+            return Arc::clone(&cached.handle);
+        }
+
+        let handle = Arc::new(make());
+        self.icons.insert(
+            app_id.to_string(),
+            CachedIcon {
+                source: source.to_string(),
+                handle: Arc::clone(&handle),
+                used_at,
+            },
+        );
+        self.evict_least_recently_used();
+        handle
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        while self.icons.len() > ICON_CACHE_CAPACITY {
+            let Some(oldest) = self
+                .icons
+                .iter()
+                .min_by_key(|(_, cached)| cached.used_at)
+                .map(|(app_id, _)| app_id.clone())
+            else {
+                break;
+            };
+            self.icons.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ICON_CACHE_CAPACITY, IconCache};
+    use cosmic::widget::icon;
+    use std::sync::Arc;
+
+    #[test]
+    fn shares_handles_and_drops_least_recently_used() {
+        let mut cache = IconCache::default();
+        let make = || icon::from_name("firefox").handle();
+        let first = cache.get_or_insert("firefox", "Name(\"firefox\")", make);
+        let again = cache.get_or_insert("firefox", "Name(\"firefox\")", make);
+        assert!(Arc::ptr_eq(&first, &again));
+        let changed = cache.get_or_insert("firefox", "Name(\"firefox-nightly\")", make);
+        assert!(!Arc::ptr_eq(&first, &changed));
+
+        for index in 0..ICON_CACHE_CAPACITY {
+            cache.get_or_insert(&format!("app-{index}"), "", make);
+            // Firefox stays in use while the others come and go.
+            cache.get_or_insert("firefox", "Name(\"firefox-nightly\")", make);
+        }
+        assert_eq!(cache.icons.len(), ICON_CACHE_CAPACITY);
+        assert!(cache.icons.contains_key("firefox"));
+        assert!(!cache.icons.contains_key("app-0"));
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Fallback icons for entries without an app icon, so every row gets one.
+//!
+//! The chain is: the app's own icon, then cover art for Heroic games, then the desktop
+//! category's icon, then an icon for the kind of binary (game, Wine app, terminal program),
+//! then a generic executable.
+
+use super::*;
+
+const TERMINAL_PROGRAMS: &[&str] = &[
+    "bash", "sh", "zsh", "fish", "dash", "nu", "tmux", "screen", "ssh", "htop", "btop", "top",
+    "vim", "nvim", "nano", "less", "man",
+];
+
+#[derive(Debug, Default)]
+pub(super) struct IconResolver {
+    fallbacks: HashMap<String, icon::Handle>,
+}
+
+/// Themed icon name for an entry that has no icon of its own.
+fn fallback_icon_name(
+    app_id: &str,
+    category: Option<&str>,
+    exe_path: Option<&Path>,
+) -> &'static str {
+    if category.is_some() {
+        return AppModel::category_icon_name(category);
+    }
+
+    let path = exe_path
+        .map(|path| path.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let file_name = exe_path
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_else(|| app_id.to_ascii_lowercase());
+
+    if app_id.starts_with("steam-app-") || path.contains("/steamapps/") {
+        "input-gaming-symbolic"
+    } else if file_name.starts_with("wine")
+        || file_name.ends_with(".exe")
+        || path.contains("/proton")
+    {
+        // Installed by Wine itself, so it is there whenever a Wine app is running.
+        "wine"
+    } else if TERMINAL_PROGRAMS.contains(&file_name.as_str()) {
+        "utilities-terminal-symbolic"
+    } else {
+        "application-x-executable-symbolic"
+    }
+}
+
+impl IconResolver {
+    /// Fills in missing icons, reusing handles across refreshes and forgetting exited apps.
+    pub(super) fn resolve_missing(&mut self, entries: &mut [ProcessEntry]) {
+        let running = entries
+            .iter()
+            .map(|entry| entry.app_id.clone())
+            .collect::<HashSet<_>>();
+        self.fallbacks.retain(|app_id, _| running.contains(app_id));
+
+        for entry in entries
+            .iter_mut()
+            .filter(|entry| entry.icon_handle.is_none())
+        {
+            let handle = self
+                .fallbacks
+                .entry(entry.app_id.clone())
+                .or_insert_with(|| {
+                    heroic::heroic_cover_art(&entry.app_id)
+                        .map(icon::from_path)
+                        .unwrap_or_else(|| {
+                            icon::from_name(fallback_icon_name(
+                                &entry.app_id,
+                                entry.category,
+                                entry.exe_path.as_deref(),
+                            ))
+                            .handle()
+                        })
+                });
+            entry.icon_handle = Some(handle.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fallback_icon_name;
+    use std::path::Path;
+
+    #[test]
+    fn picks_fallback_by_category_then_binary_type() {
+        assert_eq!(
+            fallback_icon_name("gimp", Some("Graphics"), None),
+            "applications-graphics-symbolic"
+        );
+        assert_eq!(
+            fallback_icon_name("steam-app-570", None, Some(Path::new("/games/dota2"))),
+            "input-gaming-symbolic"
+        );
+        assert_eq!(
+            fallback_icon_name("setup", None, Some(Path::new("/home/u/.wine/Setup.exe"))),
+            "wine"
+        );
+        assert_eq!(
+            fallback_icon_name("zsh", None, Some(Path::new("/usr/bin/zsh"))),
+            "utilities-terminal-symbolic"
+        );
+        assert_eq!(
+            fallback_icon_name("daemon", None, Some(Path::new("/usr/libexec/daemon"))),
+            "application-x-executable-symbolic"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Disk space used by an app's installation, looked up in the background when its detail
+//! drawer opens: Steam manifests, `flatpak info`, or the package owning the executable.
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum InstallSize {
+    Pending,
+    Known(u64),
+    Unknown,
+}
+
+#[derive(Debug, Clone)]
+enum InstallSource {
+    Steam(String),
+    Flatpak(String),
+    Package(PathBuf),
+}
+
+/// Trimmed stdout of a program that succeeded.
+pub(super) fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn steam_size_on_disk(app_id: &str) -> Option<u64> {
+    AppModel::steam_library_roots()
+        .into_iter()
+        .find_map(|library_root| {
+            let manifest =
+                AppModel::steamapps_dir(&library_root).join(format!("appmanifest_{app_id}.acf"));
+            let content = fs::read_to_string(manifest).ok()?;
+            AppModel::acf_value(&content, "SizeOnDisk")?
+                .trim()
+                .parse()
+                .ok()
+        })
+}
+
+/// Installed size of the distro package owning `exe`, via rpm or dpkg.
+fn package_size(exe: &Path) -> Option<u64> {
+    let exe = exe.to_str()?;
+    if let Some(bytes) =
+        command_stdout("rpm", &["-qf", "--qf", "%{SIZE}", exe]).and_then(|size| size.parse().ok())
+    {
+        return Some(bytes);
+    }
+
+    // `dpkg -S` answers `package[:arch]: /path`, and sizes are in KiB.
+    let owner = command_stdout("dpkg", &["-S", exe])?;
+    let package = owner.lines().next()?.split_once(": ")?.0;
+    command_stdout("dpkg-query", &["-W", "-f=${Installed-Size}", package])?
+        .parse::<u64>()
+        .ok()
+        .map(|kib| kib * 1024)
+}
+
+fn compute_install_size(source: InstallSource) -> Option<u64> {
+    match source {
+        InstallSource::Steam(app_id) => steam_size_on_disk(&app_id),
+        InstallSource::Flatpak(app_id) => {
+            command_stdout("flatpak", &["info", "--show-size", &app_id])?
+                .parse()
+                .ok()
+        }
+        InstallSource::Package(exe) => package_size(&exe),
+    }
+}
+
+impl AppModel {
+    /// The Flatpak application id, if the app's desktop entry is exported by Flatpak.
+    pub(super) fn flatpak_app_id(&self, app_id: &str) -> Option<String> {
+        let meta = self.desktop_meta_for_app_id(app_id)?;
+        let is_flatpak = meta
+            .desktop_entry_path
+            .as_ref()
+            .is_some_and(|path| path.to_string_lossy().contains("/flatpak/exports/"));
+        let entry_id = meta.desktop_entry_id.filter(|_| is_flatpak)?;
+        Some(
+            entry_id
+                .strip_suffix(".desktop")
+                .unwrap_or(&entry_id)
+                .to_string(),
+        )
+    }
+
+    fn install_source(&self, app_id: &str) -> Option<InstallSource> {
+        if let Some(steam_app_id) = app_id.strip_prefix("steam-app-") {
+            return Some(InstallSource::Steam(steam_app_id.to_string()));
+        }
+
+        if let Some(flatpak_id) = self.flatpak_app_id(app_id) {
+            return Some(InstallSource::Flatpak(flatpak_id));
+        }
+
+        self.process_entries
+            .iter()
+            .find(|entry| entry.app_id == app_id)
+            .and_then(|entry| entry.exe_path.clone())
+            .map(InstallSource::Package)
+    }
+
+    /// Starts a background lookup of the selected app's install size, unless one is known.
+    pub(super) fn request_install_size(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(app_id) = self
+            .selected_process
+            .as_ref()
+            .map(|selected| selected.app_id.clone())
+        else {
+            return Task::none();
+        };
+        if self.install_sizes.contains_key(&app_id) {
+            return Task::none();
+        }
+        let Some(source) = self.install_source(&app_id) else {
+            self.install_sizes.insert(app_id, InstallSize::Unknown);
+            return Task::none();
+        };
+
+        self.install_sizes
+            .insert(app_id.clone(), InstallSize::Pending);
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || compute_install_size(source))
+                    .await
+                    .ok()
+                    .flatten()
+            },
+            move |bytes| cosmic::Action::App(Message::InstallSizeResolved { app_id, bytes }),
+        )
+    }
+
+    pub(super) fn set_install_size(&mut self, app_id: String, bytes: Option<u64>) {
+        self.install_sizes.insert(
+            app_id,
+            bytes.map_or(InstallSize::Unknown, InstallSize::Known),
+        );
+    }
+
+    pub(super) fn selected_app_install_size(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let value = match self.install_sizes.get(&selected.app_id)? {
+            InstallSize::Pending => fl!("install-size-pending"),
+            InstallSize::Known(bytes) => units::format_bytes(*bytes),
+            InstallSize::Unknown => return None,
+        };
+
+        Some(widget::text(fl!("install-size", size = value)).into())
+    }
+}
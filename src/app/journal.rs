@@ -0,0 +1,268 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Recent journald messages of the selected app, read with `journalctl` for the app's pids
+//! and the syslog identifier of its executable, which also finds messages of earlier runs.
+//! Without `--user` journalctl reads the system journal and the user's own, so messages of
+//! apps started in the session are found as well as those of services. In follow mode the
+//! drawer asks for what was logged after the last entry on every refresh.
+
+use super::*;
+
+/// Entries read when the drawer opens.
+const JOURNAL_INITIAL_LINES: usize = 200;
+
+/// Lines kept while following, dropping the oldest.
+const JOURNAL_LINES_KEPT: usize = 2000;
+
+/// Lines read from journalctl, and the cursor of the last entry to continue from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct JournalChunk {
+    lines: Vec<String>,
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct JournalViewer {
+    app_id: String,
+    pids: Vec<u32>,
+    identifier: Option<String>,
+    lines: Vec<String>,
+    cursor: Option<String>,
+    follow: bool,
+    loading: bool,
+    error: Option<String>,
+}
+
+/// Matches for entries from any of `pids` or with the syslog identifier, e.g.
+/// `_PID=4242 _PID=4250 + SYSLOG_IDENTIFIER=firefox`. journalctl ORs matches on the same
+/// field, and `+` ORs the two groups.
+fn journal_matches(pids: &[u32], identifier: Option<&str>) -> Vec<String> {
+    let mut matches = pids
+        .iter()
+        .map(|pid| format!("_PID={pid}"))
+        .collect::<Vec<_>>();
+    if let Some(identifier) = identifier {
+        if !matches.is_empty() {
+            matches.push("+".to_string());
+        }
+        matches.push(format!("SYSLOG_IDENTIFIER={identifier}"));
+    }
+    matches
+}
+
+/// Splits journalctl output with `--show-cursor` into the entries and the trailing
+/// `-- cursor: …` line. There is no cursor line when nothing matched.
+fn parse_journal_output(output: &str) -> JournalChunk {
+    let mut chunk = JournalChunk::default();
+    for line in output.lines() {
+        if let Some(cursor) = line.strip_prefix("-- cursor: ") {
+            chunk.cursor = Some(cursor.trim().to_string());
+        } else if !line.starts_with("-- ") {
+            chunk.lines.push(line.to_string());
+        }
+    }
+    chunk
+}
+
+/// Reads the last entries matching `matches`, or those after `cursor` when following.
+fn read_journal(matches: &[String], cursor: Option<&str>) -> Result<JournalChunk, String> {
+    let mut command = Command::new("journalctl");
+    command.args([
+        "--no-pager",
+        "--quiet",
+        "--output=short-iso",
+        "--show-cursor",
+    ]);
+    match cursor {
+        Some(cursor) => command.arg(format!("--after-cursor={cursor}")),
+        None => command.arg(format!("--lines={JOURNAL_INITIAL_LINES}")),
+    };
+    let output = command
+        .args(matches)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(parse_journal_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+impl AppModel {
+    pub(super) fn open_journal(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            return Task::none();
+        };
+        let identifier = entry
+            .exe_path
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| entry.name.clone());
+        self.journal = JournalViewer {
+            app_id: entry.app_id.clone(),
+            pids: entry.pids.clone(),
+            identifier: Some(identifier),
+            ..Default::default()
+        };
+        self.context_page = ContextPage::Journal;
+        self.core.window.show_context = true;
+        self.request_journal()
+    }
+
+    fn request_journal(&mut self) -> Task<cosmic::Action<Message>> {
+        let journal = &mut self.journal;
+        if journal.loading {
+            return Task::none();
+        }
+        journal.loading = true;
+        let app_id = journal.app_id.clone();
+        let matches = journal_matches(&journal.pids, journal.identifier.as_deref());
+        let cursor = journal.cursor.clone();
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || read_journal(&matches, cursor.as_deref()))
+                    .await
+                    .unwrap_or_else(|err| Err(err.to_string()))
+            },
+            move |result| cosmic::Action::App(Message::JournalLoaded { app_id, result }),
+        )
+    }
+
+    /// Reads new entries on a refresh while the drawer is open and follows the log. Pids the
+    /// app started since are matched from then on.
+    pub(super) fn follow_journal(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.journal.follow
+            || self.context_page != ContextPage::Journal
+            || !self.core.window.show_context
+        {
+            return Task::none();
+        }
+        if let Some(entry) = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == self.journal.app_id)
+        {
+            self.journal.pids.clone_from(&entry.pids);
+        }
+        self.request_journal()
+    }
+
+    /// Adds what was read, unless the drawer shows another app by now.
+    pub(super) fn set_journal_result(
+        &mut self,
+        app_id: String,
+        result: Result<JournalChunk, String>,
+    ) {
+        let journal = &mut self.journal;
+        if journal.app_id != app_id {
+            return;
+        }
+        journal.loading = false;
+        match result {
+            Ok(chunk) => {
+                journal.lines.extend(chunk.lines);
+                let dropped = journal.lines.len().saturating_sub(JOURNAL_LINES_KEPT);
+                journal.lines.drain(..dropped);
+                if chunk.cursor.is_some() {
+                    journal.cursor = chunk.cursor;
+                }
+                journal.error = None;
+            }
+            Err(err) => {
+                eprintln!("failed to read the journal of `{app_id}`: {err}");
+                journal.error = Some(err);
+            }
+        }
+    }
+
+    pub(super) fn set_journal_follow(&mut self, follow: bool) {
+        self.journal.follow = follow;
+    }
+
+    /// Returns to the app's actions, or closes the drawer when the split view shows them.
+    pub(super) fn close_journal(&mut self) {
+        self.journal = JournalViewer::default();
+        if self.config.split_view {
+            self.core.window.show_context = false;
+        } else {
+            self.context_page = ContextPage::ProcessActions;
+        }
+    }
+
+    pub(super) fn journal_drawer(&self) -> Element<'_, Message> {
+        let journal = &self.journal;
+        let lines: Element<'_, Message> = if !journal.lines.is_empty() {
+            widget::text(journal.lines.join("\n"))
+                .size(12)
+                .font(cosmic::font::mono())
+                .into()
+        } else if journal.loading {
+            widget::text(fl!("journal-loading")).into()
+        } else {
+            widget::text(fl!("journal-empty")).into()
+        };
+
+        widget::column::with_capacity(4)
+            .push(widget::settings::item(
+                fl!("journal-follow"),
+                widget::toggler(journal.follow).on_toggle(Message::SetJournalFollow),
+            ))
+            .push_maybe(journal.identifier.as_ref().map(|identifier| {
+                widget::text(fl!(
+                    "journal-matches",
+                    processes = journal.pids.len(),
+                    identifier = identifier.as_str()
+                ))
+                .size(12)
+            }))
+            .push_maybe(
+                journal.error.as_ref().map(|error| {
+                    widget::text(fl!("journal-failed", error = error.as_str())).size(12)
+                }),
+            )
+            .push(lines)
+            .spacing(8)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JournalChunk, journal_matches, parse_journal_output};
+
+    #[test]
+    fn builds_matches_and_parses_output() {
+        assert_eq!(
+            journal_matches(&[4242, 4250], Some("firefox")),
+            ["_PID=4242", "_PID=4250", "+", "SYSLOG_IDENTIFIER=firefox"]
+        );
+        assert_eq!(
+            journal_matches(&[], Some("vkcube")),
+            ["SYSLOG_IDENTIFIER=vkcube"]
+        );
+
+        let output = "2026-10-16T09:12:03+0200 host firefox[4242]: first\n\
+                      2026-10-16T09:12:04+0200 host firefox[4242]: second\n\
+                      -- cursor: s=ab12;i=1f\n";
+        assert_eq!(
+            parse_journal_output(output),
+            JournalChunk {
+                lines: vec![
+                    "2026-10-16T09:12:03+0200 host firefox[4242]: first".to_string(),
+                    "2026-10-16T09:12:04+0200 host firefox[4242]: second".to_string(),
+                ],
+                cursor: Some("s=ab12;i=1f".to_string()),
+            }
+        );
+        assert_eq!(parse_journal_output(""), JournalChunk::default());
+    }
+}
@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Environment variables and extra arguments an app is restarted with, such as
+//! `MANGOHUD=1` or `PROTON_LOG=1`, edited in the app's details and kept in the config per
+//! app id. Both are split at spaces, like the debugger command. Launches through a
+//! launcher URI, as for Steam and Heroic games, cannot carry them; those launchers have
+//! launch options of their own.
+
+use super::*;
+use crate::config::LaunchOverride;
+
+/// What the override fields of the selected app hold while they are being edited.
+#[derive(Debug, Default)]
+pub(super) struct LaunchOverrideEditor {
+    app_id: String,
+    env: String,
+    args: String,
+    error: Option<String>,
+}
+
+/// A stored override, split into what a launch applies.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub(super) struct LaunchSettings {
+    pub(super) env: Vec<(String, String)>,
+    pub(super) args: Vec<String>,
+}
+
+impl LaunchSettings {
+    /// Adds the environment and arguments to `command`.
+    pub(super) fn apply(&self, command: &mut Command) {
+        command.envs(self.env.iter().cloned()).args(&self.args);
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Splits `KEY=value` pairs, returning the first word that is not one.
+fn parse_env(text: &str) -> Result<Vec<(String, String)>, String> {
+    text.split_whitespace()
+        .map(|assignment| match assignment.split_once('=') {
+            Some((key, value)) if is_env_name(key) => Ok((key.to_string(), value.to_string())),
+            _ => Err(assignment.to_string()),
+        })
+        .collect()
+}
+
+fn parse_args(text: &str) -> Vec<String> {
+    text.split_whitespace().map(str::to_string).collect()
+}
+
+/// Whether an app is restarted through a launcher URI rather than by the task monitor.
+pub(super) fn restarts_through_launcher(app_id: &str) -> bool {
+    ["steam-app-", "heroic-"]
+        .iter()
+        .any(|prefix| app_id.starts_with(prefix))
+}
+
+impl AppModel {
+    fn launch_override(&self, app_id: &str) -> Option<&LaunchOverride> {
+        self.config
+            .launch_overrides
+            .iter()
+            .find(|launch_override| launch_override.app_id == app_id)
+    }
+
+    /// The environment and arguments `app_id` is restarted with. Entries are checked when
+    /// saved, so an entry that fails to parse was edited by hand and is left out.
+    pub(super) fn launch_settings(&self, app_id: &str) -> LaunchSettings {
+        let Some(launch_override) = self.launch_override(app_id) else {
+            return LaunchSettings::default();
+        };
+        LaunchSettings {
+            env: parse_env(&launch_override.env).unwrap_or_default(),
+            args: parse_args(&launch_override.args),
+        }
+    }
+
+    /// Starts editing the selected app's override, from what is stored for it.
+    fn launch_override_editor(&mut self) -> Option<&mut LaunchOverrideEditor> {
+        let app_id = self.selected_process.as_ref()?.app_id.clone();
+        if self.launch_override_editor.app_id != app_id {
+            let stored = self.launch_override(&app_id);
+            self.launch_override_editor = LaunchOverrideEditor {
+                env: stored.map(|stored| stored.env.clone()).unwrap_or_default(),
+                args: stored.map(|stored| stored.args.clone()).unwrap_or_default(),
+                app_id,
+                error: None,
+            };
+        }
+        Some(&mut self.launch_override_editor)
+    }
+
+    pub(super) fn set_launch_override_env(&mut self, env: String) {
+        if let Some(editor) = self.launch_override_editor() {
+            editor.env = env;
+            editor.error = None;
+        }
+    }
+
+    pub(super) fn set_launch_override_args(&mut self, args: String) {
+        if let Some(editor) = self.launch_override_editor() {
+            editor.args = args;
+        }
+    }
+
+    pub(super) fn save_launch_override(&mut self) {
+        let Some(editor) = self.launch_override_editor() else {
+            return;
+        };
+        if let Err(word) = parse_env(&editor.env) {
+            editor.error = Some(fl!("launch-override-invalid-env", word = word));
+            return;
+        }
+        let launch_override = LaunchOverride {
+            app_id: editor.app_id.clone(),
+            env: editor.env.trim().to_string(),
+            args: editor.args.trim().to_string(),
+        };
+
+        let mut overrides = self.config.launch_overrides.clone();
+        overrides.retain(|stored| stored.app_id != launch_override.app_id);
+        if !launch_override.env.is_empty() || !launch_override.args.is_empty() {
+            overrides.push(launch_override);
+        }
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_launch_overrides(handler, overrides) {
+                eprintln!("failed to save launch overrides: {err}");
+            }
+        } else {
+            self.config.launch_overrides = overrides;
+        }
+    }
+
+    /// The override fields in the selected app's details.
+    pub(super) fn selected_app_launch_override(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let stored = self.launch_override(&selected.app_id);
+        let editor = &self.launch_override_editor;
+        let (env, args) = if editor.app_id == selected.app_id {
+            (editor.env.as_str(), editor.args.as_str())
+        } else {
+            (
+                stored.map_or("", |stored| stored.env.as_str()),
+                stored.map_or("", |stored| stored.args.as_str()),
+            )
+        };
+        let changed = stored.map_or("", |stored| stored.env.as_str()) != env.trim()
+            || stored.map_or("", |stored| stored.args.as_str()) != args.trim();
+        let through_launcher = restarts_through_launcher(&selected.app_id);
+
+        Some(
+            widget::column::with_capacity(8)
+                .push(widget::text::heading(fl!("launch-override")))
+                .push(widget::text(fl!("launch-override-env")).size(12))
+                .push(
+                    widget::text_input(fl!("launch-override-env-placeholder"), env)
+                        .on_input(Message::SetLaunchOverrideEnv),
+                )
+                .push(widget::text(fl!("launch-override-args")).size(12))
+                .push(
+                    widget::text_input(fl!("launch-override-args-placeholder"), args)
+                        .on_input(Message::SetLaunchOverrideArgs),
+                )
+                .push_maybe(
+                    editor
+                        .error
+                        .as_ref()
+                        .filter(|_| editor.app_id == selected.app_id)
+                        .map(|error| widget::text(error.clone()).size(12)),
+                )
+                .push_maybe(
+                    through_launcher
+                        .then(|| widget::text(fl!("launch-override-launcher")).size(12)),
+                )
+                .push(
+                    widget::button::standard(fl!("launch-override-save"))
+                        .on_press_maybe(changed.then_some(Message::SaveLaunchOverride)),
+                )
+                .spacing(4)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_args, parse_env};
+
+    #[test]
+    fn parses_environment_and_arguments() {
+        assert_eq!(
+            parse_env(" MANGOHUD=1  PROTON_LOG=1 DXVK_HUD= "),
+            Ok(vec![
+                ("MANGOHUD".to_string(), "1".to_string()),
+                ("PROTON_LOG".to_string(), "1".to_string()),
+                ("DXVK_HUD".to_string(), String::new()),
+            ])
+        );
+        assert_eq!(parse_env("MANGOHUD"), Err("MANGOHUD".to_string()));
+        assert_eq!(parse_env("1X=2"), Err("1X=2".to_string()));
+        assert_eq!(
+            parse_args("--fullscreen  -w 2"),
+            ["--fullscreen", "-w", "2"]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The shared libraries the selected app has loaded, from the file-backed mappings in
+//! `/proc/<pid>/maps`, so it can be checked which Mesa, Proton, or driver build a game
+//! actually picked up.
+
+use super::*;
+use std::collections::BTreeSet;
+
+/// Libraries of the app whose list is open, and the filter typed over them.
+#[derive(Debug, Default)]
+pub(super) struct LoadedLibraries {
+    paths: Vec<String>,
+    query: String,
+}
+
+/// Whether a mapped file is a shared library, e.g. `libc.so.6` or `libvulkan_radeon.so`.
+fn is_shared_library(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.ends_with(".so") || name.contains(".so.")
+}
+
+/// Unique library paths from the lines of a maps file, e.g.
+/// `7f2a1c000000-7f2a1c028000 r--p 00000000 103:02 1835 /usr/lib/libc.so.6`. Libraries
+/// replaced on disk since they were loaded end in ` (deleted)`, and are kept with it.
+fn parse_mapped_libraries(maps: &str) -> Vec<String> {
+    maps.lines()
+        .filter_map(|line| {
+            // The path is the sixth field and may itself contain spaces.
+            let path = line.splitn(6, char::is_whitespace).nth(5)?.trim_start();
+            let library = path.strip_suffix(" (deleted)").unwrap_or(path);
+            (path.starts_with('/') && is_shared_library(library)).then(|| path.to_string())
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+impl AppModel {
+    pub(super) fn open_loaded_libraries(&mut self) {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            return;
+        };
+
+        let paths = entry
+            .pids
+            .iter()
+            .filter_map(|pid| self.process_source.read_proc_file(*pid, "maps"))
+            .flat_map(|maps| parse_mapped_libraries(&String::from_utf8_lossy(&maps)))
+            .collect::<BTreeSet<_>>();
+        if paths.is_empty() {
+            eprintln!("failed to read the memory maps of `{}`", entry.app_id);
+        }
+        self.loaded_libraries = LoadedLibraries {
+            paths: paths.into_iter().collect(),
+            query: String::new(),
+        };
+        self.context_page = ContextPage::LoadedLibraries;
+        self.core.window.show_context = true;
+    }
+
+    /// Returns to the app's actions, or closes the drawer when the split view shows them.
+    pub(super) fn close_loaded_libraries(&mut self) {
+        self.loaded_libraries = LoadedLibraries::default();
+        if self.config.split_view {
+            self.core.window.show_context = false;
+        } else {
+            self.context_page = ContextPage::ProcessActions;
+        }
+    }
+
+    pub(super) fn set_loaded_libraries_query(&mut self, query: String) {
+        self.loaded_libraries.query = query;
+    }
+
+    pub(super) fn loaded_libraries_drawer(&self) -> Element<'_, Message> {
+        let libraries = &self.loaded_libraries;
+        let query = libraries.query.to_lowercase();
+        let matches = libraries
+            .paths
+            .iter()
+            .filter(|path| query.is_empty() || path.to_lowercase().contains(&query))
+            .collect::<Vec<_>>();
+
+        let search = widget::search_input(fl!("search-placeholder"), libraries.query.as_str())
+            .on_input(Message::LoadedLibrariesQuery)
+            .on_clear(Message::LoadedLibrariesQuery(String::new()))
+            .width(Length::Fill);
+        let summary = fl!(
+            "loaded-libraries-count",
+            shown = matches.len(),
+            total = libraries.paths.len()
+        );
+
+        let list = matches.into_iter().fold(
+            widget::column::with_capacity(libraries.paths.len()).spacing(4),
+            |column, path| column.push(widget::text(path.clone()).size(12)),
+        );
+        widget::column::with_capacity(3)
+            .push(search)
+            .push(widget::text(summary).size(12))
+            .push(list)
+            .spacing(8)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_mapped_libraries;
+
+    #[test]
+    fn parses_unique_libraries_from_maps() {
+        let maps = "55d0c0a00000-55d0c0a28000 r--p 00000000 103:02 10 /usr/bin/vkcube\n\
+                    7f2a1c000000-7f2a1c028000 r--p 00000000 103:02 11 /usr/lib/libc.so.6\n\
+                    7f2a1c028000-7f2a1c1bd000 r-xp 00028000 103:02 11 /usr/lib/libc.so.6\n\
+                    7f2a1d000000-7f2a1d400000 r-xp 00000000 103:02 12 \
+                    /usr/lib/libvulkan_radeon.so (deleted)\n\
+                    7f2a1e000000-7f2a1e100000 r-xp 00000000 103:02 13 \
+                    /home/user/My Games/lib/libgame.so\n\
+                    7f2a1f000000-7f2a1f021000 rw-p 00000000 00:00 0 [heap]\n\
+                    7f2a20000000-7f2a20001000 rw-s 00000000 00:01 14 /memfd:pa (deleted)\n";
+        assert_eq!(
+            parse_mapped_libraries(maps),
+            vec![
+                "/home/user/My Games/lib/libgame.so",
+                "/usr/lib/libc.so.6",
+                "/usr/lib/libvulkan_radeon.so (deleted)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Load averages and the run queue from `/proc/loadavg`. Unlike CPU usage, load counts
+//! tasks waiting for a core as well as those running, so a load above the core count means
+//! work is queueing.
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LoadAverage {
+    one: f32,
+    five: f32,
+    fifteen: f32,
+    /// Tasks running or waiting for a core right now.
+    runnable: u32,
+    /// Tasks that exist, in any state.
+    total: u32,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct LoadStats {
+    current: Option<LoadAverage>,
+    /// The 1-minute load average at every refresh.
+    history: Vec<f32>,
+}
+
+/// Parses `/proc/loadavg`, e.g. `0.52 0.58 0.59 2/1234 56789`.
+fn parse_loadavg(contents: &str) -> Option<LoadAverage> {
+    let mut fields = contents.split_whitespace();
+    let mut average = || fields.next()?.parse::<f32>().ok();
+    let (one, five, fifteen) = (average()?, average()?, average()?);
+    let (runnable, total) = fields.next()?.split_once('/')?;
+    Some(LoadAverage {
+        one,
+        five,
+        fifteen,
+        runnable: runnable.parse().ok()?,
+        total: total.parse().ok()?,
+    })
+}
+
+fn format_load(value: f32) -> String {
+    units::format_decimal(f64::from(value), 2)
+}
+
+impl AppModel {
+    pub(super) fn refresh_load_average(&mut self, retained: usize, tick: u64) {
+        let current = fs::read_to_string("/proc/loadavg")
+            .ok()
+            .and_then(|contents| parse_loadavg(&contents));
+        if let Some(load) = current {
+            history::push_sample(&mut self.load.history, load.one, retained, tick);
+        }
+        self.load.current = current;
+    }
+
+    /// Load averages, the run queue, and a small history of the 1-minute load for the
+    /// Resources header, or `None` where `/proc/loadavg` cannot be read.
+    pub(super) fn load_average_header(&self) -> Option<Element<'_, Message>> {
+        let load = self.load.current?;
+        // The reading task itself is always counted as runnable.
+        let runnable = load.runnable.saturating_sub(1);
+        let queued = load.one > self.system.cpus().len().max(1) as f32;
+
+        let averages = widget::text(fl!(
+            "load-average",
+            one = format_load(load.one),
+            five = format_load(load.five),
+            fifteen = format_load(load.fifteen)
+        ))
+        .size(13);
+        let averages: Element<'_, Message> = if queued {
+            widget::tooltip(
+                averages,
+                widget::text(fl!("load-average-queued", cores = self.system.cpus().len())),
+                widget::tooltip::Position::Bottom,
+            )
+            .into()
+        } else {
+            averages.into()
+        };
+
+        Some(
+            widget::row::with_capacity(4)
+                .push(widget::text(fl!("load-title")).size(14))
+                .push(averages)
+                .push(
+                    widget::text(fl!(
+                        "load-runnable",
+                        runnable = runnable,
+                        total = load.total
+                    ))
+                    .size(13),
+                )
+                .push(
+                    widget::container(
+                        Chart::new(&self.load.history, ChartKind::Line, CPU_ACCENT)
+                            .scale(ChartScale::Auto(format_load))
+                            .view(28.0),
+                    )
+                    .width(Length::Fixed(160.0)),
+                )
+                .align_y(Alignment::Center)
+                .spacing(12)
+                .width(Length::Fill)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoadAverage, parse_loadavg};
+
+    #[test]
+    fn parses_load_and_run_queue() {
+        assert_eq!(
+            parse_loadavg("0.52 1.58 2.59 3/1234 56789\n"),
+            Some(LoadAverage {
+                one: 0.52,
+                five: 1.58,
+                fifteen: 2.59,
+                runnable: 3,
+                total: 1234,
+            })
+        );
+        assert_eq!(parse_loadavg("0.52 1.58 2.59\n"), None);
+    }
+}
@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Restarting an app with its output captured, for games and apps that crash without a
+//! word. The app is stopped only when one of its commands can be found, and once it has
+//! exited, launched again with stdout and stderr going to a file under
+//! `$XDG_STATE_HOME/cosmic-task-monitor/logs`, and a drawer follows the end of the file on
+//! every refresh while it is open. Apps restarted through a launcher URI, like Steam and
+//! Heroic games, are not offered it, since their output goes to the launcher.
+
+use super::*;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How much of the end of a log the drawer shows.
+const LOG_TAIL_BYTES: u64 = 64 * 1024;
+
+/// Logs kept on disk; the oldest are deleted as new captures start.
+const CAPTURED_LOGS_KEPT: usize = 50;
+
+#[derive(Debug, Default)]
+pub(super) struct CapturedLogs {
+    /// The newest log of each app restarted with capture.
+    latest: HashMap<String, PathBuf>,
+    /// The log shown in the drawer and the end of it as last read.
+    shown: Option<PathBuf>,
+    tail: String,
+    error: Option<String>,
+}
+
+fn log_directory() -> PathBuf {
+    snapshot::state_directory().join("logs")
+}
+
+/// A file name from the app id and the time a capture started, such as
+/// `org.mozilla.firefox-1760000000.log`. Characters that do not belong in a file name are
+/// replaced.
+pub(super) fn capture_file_name(app_id: &str, started_at: u64, extension: &str) -> String {
+    let app_id = app_id
+        .chars()
+        .map(|char| {
+            if char.is_ascii_alphanumeric() || matches!(char, '.' | '_' | '-') {
+                char
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{app_id}-{started_at}.{extension}")
+}
+
+/// The text of a log's end, without the line the read started in the middle of.
+fn complete_lines(tail: &str, truncated: bool) -> &str {
+    if !truncated {
+        return tail;
+    }
+    tail.split_once('\n').map_or("", |(_, rest)| rest)
+}
+
+fn read_log_tail(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let start = file.metadata()?.len().saturating_sub(LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let tail = String::from_utf8_lossy(&bytes);
+    Ok(complete_lines(&tail, start > 0).to_string())
+}
+
+/// Deletes the oldest logs beyond the limit. Names end in the start time, which sorts by
+/// age only within one app, so the files' modification times decide.
+fn remove_old_logs(directory: &Path) -> std::io::Result<()> {
+    let mut logs = fs::read_dir(directory)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .filter(|(_, path)| path.extension().is_some_and(|extension| extension == "log"))
+        .collect::<Vec<_>>();
+    logs.sort();
+    let expired = logs.len().saturating_sub(CAPTURED_LOGS_KEPT);
+    for (_, path) in logs.into_iter().take(expired) {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Creates the log file for a capture starting now.
+fn create_log(app_id: &str) -> std::io::Result<(PathBuf, File)> {
+    let directory = log_directory();
+    fs::create_dir_all(&directory)?;
+    if let Err(err) = remove_old_logs(&directory) {
+        eprintln!(
+            "failed to remove old logs in {}: {err}",
+            directory.display()
+        );
+    }
+    let started_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    let path = directory.join(capture_file_name(app_id, started_at, "log"));
+    let file = File::create(&path)?;
+    Ok((path, file))
+}
+
+/// Whether `program` is a file, or found in `$PATH` when it is a bare name.
+fn program_exists(program: &Path) -> bool {
+    if program.components().count() > 1 {
+        return program.is_file();
+    }
+    env::var_os("PATH")
+        .is_some_and(|path| env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// Whether the candidate is a command whose program exists. A desktop entry's `Exec` line
+/// is checked by its first word, unless that sets an environment variable.
+fn can_launch_with_log(candidate: &LaunchCandidate) -> bool {
+    match candidate {
+        LaunchCandidate::Command { program, .. } => program_exists(Path::new(program)),
+        LaunchCandidate::Executable(path) => path.is_file(),
+        LaunchCandidate::DesktopExec(exec) => exec
+            .split_whitespace()
+            .next()
+            .is_some_and(|word| word.contains('=') || program_exists(Path::new(word))),
+        LaunchCandidate::Uri(_) | LaunchCandidate::GtkLaunch(_) | LaunchCandidate::GioLaunch(_) => {
+            false
+        }
+    }
+}
+
+/// Starts `command` detached like [`AppModel::spawn_detached`], writing its output to `log`.
+fn spawn_with_log(command: &mut Command, log: &File) -> std::io::Result<()> {
+    command
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log.try_clone()?);
+
+    #[cfg(unix)]
+    {
+        command.process_group(0);
+    }
+
+    command.spawn().map(drop)
+}
+
+impl AppModel {
+    /// Whether the selected app can be restarted with its output captured.
+    pub(super) fn can_capture_selected_logs(&self) -> bool {
+        self.selected_process
+            .as_ref()
+            .is_some_and(|selected| !launch_overrides::restarts_through_launcher(&selected.app_id))
+    }
+
+    /// Restarts the selected app with its output captured, asking first if it is protected.
+    /// An app without a command that can be found is left running.
+    pub(super) fn restart_with_log_capture(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(selected) = self.selected_process.as_ref().cloned() else {
+            return Task::none();
+        };
+        let settings = self.launch_settings(&selected.app_id);
+        let candidates = self
+            .launch_candidates_for_selected(&selected)
+            .into_iter()
+            .filter(can_launch_with_log)
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            eprintln!(
+                "failed to restart `{}` with log capture: no command to launch it with",
+                selected.app_id
+            );
+            self.show_captured_log(None, Some(fl!("log-capture-no-command")));
+            return Task::none();
+        }
+
+        self.request_stop(
+            selected.app_id,
+            StopKind::Restart(Relaunch::CaptureLog {
+                candidates,
+                settings,
+            }),
+        )
+    }
+
+    /// Starts the first of the app's commands that runs, with its output going to a new log,
+    /// and shows the log.
+    pub(super) fn launch_with_log_capture(
+        &mut self,
+        app_id: String,
+        candidates: &[LaunchCandidate],
+        settings: &LaunchSettings,
+    ) {
+        let (path, log) = match create_log(&app_id) {
+            Ok(log) => log,
+            Err(err) => {
+                eprintln!("failed to create a log for `{app_id}`: {err}");
+                self.show_captured_log(None, Some(err.to_string()));
+                return;
+            }
+        };
+
+        // Stays set when none of the commands can be run by the task monitor itself.
+        let mut error = Some(fl!("log-capture-no-command"));
+        for candidate in candidates {
+            let Some(mut command) = Self::candidate_command(candidate, settings) else {
+                continue;
+            };
+            match spawn_with_log(&mut command, &log) {
+                Ok(()) => {
+                    error = None;
+                    break;
+                }
+                Err(err) => error = Some(err.to_string()),
+            }
+        }
+        if let Some(error) = error.as_ref() {
+            eprintln!("failed to restart `{app_id}` with log capture: {error}");
+        }
+        self.captured_logs.latest.insert(app_id, path.clone());
+        self.show_captured_log(Some(path), error);
+    }
+
+    fn show_captured_log(&mut self, path: Option<PathBuf>, error: Option<String>) {
+        self.captured_logs.shown = path;
+        self.captured_logs.error = error;
+        self.context_page = ContextPage::CapturedLog;
+        self.core.window.show_context = true;
+        self.refresh_captured_log();
+    }
+
+    /// The newest log of the selected app, if it was restarted with capture.
+    fn selected_captured_log(&self) -> Option<&PathBuf> {
+        let selected = self.selected_process.as_ref()?;
+        self.captured_logs.latest.get(&selected.app_id)
+    }
+
+    pub(super) fn open_captured_log(&mut self) {
+        let path = self.selected_captured_log().cloned();
+        self.show_captured_log(path, None);
+    }
+
+    /// Returns to the app's actions, or closes the drawer when the split view shows them.
+    pub(super) fn close_captured_log(&mut self) {
+        self.captured_logs.shown = None;
+        self.captured_logs.tail.clear();
+        if self.config.split_view {
+            self.core.window.show_context = false;
+        } else {
+            self.context_page = ContextPage::ProcessActions;
+        }
+    }
+
+    /// Reads the end of the shown log again while the drawer is open.
+    pub(super) fn refresh_captured_log(&mut self) {
+        if self.context_page != ContextPage::CapturedLog || !self.core.window.show_context {
+            return;
+        }
+        let Some(path) = self.captured_logs.shown.as_ref() else {
+            return;
+        };
+        match read_log_tail(path) {
+            Ok(tail) => self.captured_logs.tail = tail,
+            Err(err) => {
+                eprintln!("failed to read {}: {err}", path.display());
+                self.captured_logs.error = Some(err.to_string());
+            }
+        }
+    }
+
+    pub(super) fn open_captured_log_file(&self) {
+        if let Some(path) = self.captured_logs.shown.as_ref() {
+            if let Err(err) = open::that_detached(path) {
+                eprintln!("failed to open {}: {err}", path.display());
+            }
+        }
+    }
+
+    /// The buttons for log capture in the selected app's actions.
+    pub(super) fn selected_app_log_buttons(&self) -> Option<Element<'_, Message>> {
+        if !self.can_capture_selected_logs() {
+            return None;
+        }
+        let button_height = Length::Fixed(38.0);
+        Some(
+            widget::column::with_capacity(2)
+                .push(
+                    widget::button::standard(fl!("log-capture-restart"))
+                        .on_press(Message::RestartWithLogCapture)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push_maybe(self.selected_captured_log().map(|_| {
+                    widget::button::standard(fl!("log-capture-show"))
+                        .on_press(Message::OpenCapturedLog)
+                        .width(Length::Fill)
+                        .height(button_height)
+                }))
+                .spacing(8)
+                .into(),
+        )
+    }
+
+    pub(super) fn captured_log_drawer(&self) -> Element<'_, Message> {
+        let logs = &self.captured_logs;
+        let tail: Element<'_, Message> = if logs.tail.is_empty() {
+            widget::text(fl!("log-capture-empty")).into()
+        } else {
+            widget::text(logs.tail.as_str())
+                .size(12)
+                .font(cosmic::font::mono())
+                .into()
+        };
+        widget::column::with_capacity(4)
+            .push_maybe(
+                logs.shown
+                    .as_ref()
+                    .map(|path| widget::text(path.display().to_string()).size(12)),
+            )
+            .push_maybe(logs.error.as_ref().map(|error| {
+                widget::text(fl!("log-capture-failed", error = error.as_str())).size(12)
+            }))
+            .push_maybe(logs.shown.as_ref().map(|_| {
+                widget::button::standard(fl!("log-capture-open-file"))
+                    .on_press(Message::OpenCapturedLogFile)
+            }))
+            .push(tail)
+            .spacing(8)
+            .width(Length::Fill)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capture_file_name, complete_lines};
+
+    #[test]
+    fn names_logs_and_drops_partial_lines() {
+        assert_eq!(
+            capture_file_name("org.mozilla.firefox", 1_760_000_000, "log"),
+            "org.mozilla.firefox-1760000000.log"
+        );
+        assert_eq!(capture_file_name("a/b c", 1, "mp4"), "a_b_c-1.mp4");
+
+        assert_eq!(complete_lines("ial line\nwhole\n", true), "whole\n");
+        assert_eq!(complete_lines("whole\n", false), "whole\n");
+        assert_eq!(complete_lines("no newline", true), "");
+    }
+}
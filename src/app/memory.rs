@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Per-app memory beyond the resident set: how much of each app is paged out to swap, read
+//! from `VmSwap` in `/proc/<pid>/status`, and the POSIX shared memory (`/dev/shm`) and memfd
+//! segments it holds open, which browsers, Wayland clients, and games use for buffers.
+
+use super::*;
+use std::os::unix::fs::MetadataExt;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum SharedMemoryKind {
+    Shm,
+    Memfd,
+}
+
+/// Shared memory held open by the selected app, sampled on each refresh.
+#[derive(Debug, Default)]
+pub(super) struct SharedMemory {
+    app_id: String,
+    shm_bytes: u64,
+    memfd_bytes: u64,
+}
+
+/// The kind of segment an fd link points to, e.g. `/dev/shm/wayland.mozilla.ipc.3` or
+/// `/memfd:wayland-cursor (deleted)`.
+fn shared_memory_kind(target: &Path) -> Option<SharedMemoryKind> {
+    let target = target.to_str()?;
+    if target.starts_with("/dev/shm/") {
+        Some(SharedMemoryKind::Shm)
+    } else if target.starts_with("/memfd:") {
+        Some(SharedMemoryKind::Memfd)
+    } else {
+        None
+    }
+}
+
+/// Sizes of the segments the processes hold open. A segment shared between them, or open
+/// more than once, is counted once.
+fn read_shared_memory(source: &dyn SystemSource, pids: &[u32]) -> (u64, u64) {
+    let mut seen = HashSet::new();
+    let (mut shm_bytes, mut memfd_bytes) = (0, 0);
+    for pid in pids {
+        let Some(Ok(fds)) = source
+            .proc_dir(*pid)
+            .map(|dir| fs::read_dir(dir.join("fd")))
+        else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Some(kind) = fs::read_link(fd.path())
+                .ok()
+                .and_then(|target| shared_memory_kind(&target))
+            else {
+                continue;
+            };
+            // The fd link opens the segment itself, even when its name was unlinked.
+            let Ok(metadata) = fs::metadata(fd.path()) else {
+                continue;
+            };
+            if !seen.insert((metadata.dev(), metadata.ino())) {
+                continue;
+            }
+            match kind {
+                SharedMemoryKind::Shm => shm_bytes += metadata.len(),
+                SharedMemoryKind::Memfd => memfd_bytes += metadata.len(),
+            }
+        }
+    }
+    (shm_bytes, memfd_bytes)
+}
+
+/// Bytes from the `VmSwap:  1234 kB` line of a status file.
+fn parse_vm_swap(status: &str) -> Option<u64> {
+    status.lines().find_map(|line| {
+        let kib = line.strip_prefix("VmSwap:")?.trim().strip_suffix("kB")?;
+        kib.trim().parse::<u64>().ok().map(|kib| kib * 1024)
+    })
+}
+
+/// Swapped-out bytes of a process; zero for kernel threads and processes that are gone.
+pub(super) fn read_swap_bytes(source: &dyn SystemSource, pid: u32) -> u64 {
+    source
+        .read_proc_file(pid, "status")
+        .and_then(|status| parse_vm_swap(&String::from_utf8_lossy(&status)))
+        .unwrap_or(0)
+}
+
+impl AppModel {
+    /// Scans the selected app's file descriptors, since doing so for every app on each
+    /// refresh would be too slow.
+    pub(super) fn refresh_selected_shared_memory(&mut self) {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            self.shared_memory = SharedMemory::default();
+            return;
+        };
+        let (shm_bytes, memfd_bytes) =
+            read_shared_memory(self.process_source.as_ref(), &entry.pids);
+        self.shared_memory = SharedMemory {
+            app_id: entry.app_id.clone(),
+            shm_bytes,
+            memfd_bytes,
+        };
+    }
+
+    pub(super) fn selected_app_memory(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let entry = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == selected.app_id)?;
+
+        let shared = (self.shared_memory.app_id == entry.app_id).then_some(&self.shared_memory);
+
+        Some(
+            widget::column::with_capacity(5)
+                .push(widget::text::heading(fl!("memory-breakdown")))
+                .push(widget::text(fl!(
+                    "memory-resident",
+                    size = units::format_bytes(entry.rss_bytes)
+                )))
+                .push(widget::text(fl!(
+                    "memory-swap",
+                    size = units::format_bytes(entry.swap_bytes)
+                )))
+                .push_maybe(shared.map(|shared| {
+                    widget::text(fl!(
+                        "memory-shm",
+                        size = units::format_bytes(shared.shm_bytes)
+                    ))
+                }))
+                .push_maybe(shared.map(|shared| {
+                    widget::text(fl!(
+                        "memory-memfd",
+                        size = units::format_bytes(shared.memfd_bytes)
+                    ))
+                }))
+                .spacing(4)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SharedMemoryKind, parse_vm_swap, shared_memory_kind};
+    use std::path::Path;
+
+    #[test]
+    fn parses_vm_swap() {
+        let status = "Name:\tfirefox\nVmRSS:\t  812340 kB\nVmSwap:\t   20480 kB\nThreads:\t80\n";
+        assert_eq!(parse_vm_swap(status), Some(20 * 1024 * 1024));
+        assert_eq!(parse_vm_swap("Name:\tkthreadd\nThreads:\t1\n"), None);
+    }
+
+    #[test]
+    fn recognizes_shared_memory_links() {
+        let kind = |target: &str| shared_memory_kind(Path::new(target));
+        assert_eq!(
+            kind("/dev/shm/wayland.mozilla.ipc.3"),
+            Some(SharedMemoryKind::Shm)
+        );
+        assert_eq!(
+            kind("/memfd:wayland-cursor (deleted)"),
+            Some(SharedMemoryKind::Memfd)
+        );
+        assert_eq!(kind("/home/user/.cache/memfd:log"), None);
+        assert_eq!(kind("socket:[12345]"), None);
+    }
+}
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Memory growth per app, for spotting leaks. Each app's resident memory is sampled once a
+//! minute; an app whose samples over the last fifteen minutes rose overall without a
+//! noticeable drop is growing, and gets an arrow in its RAM cell. Growth faster than the rate
+//! picked in the settings also raises an alert, at most once an hour per app.
+
+use super::*;
+use crate::app::alerts::AlertMetric;
+use std::collections::VecDeque;
+
+const GROWTH_SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+/// Samples kept per app, covering fifteen minutes.
+const GROWTH_SAMPLES: usize = 16;
+/// Samples an app needs before its trend is judged.
+const GROWTH_MIN_SAMPLES: usize = 6;
+/// Drops smaller than this are allocator noise and do not break a rise.
+const GROWTH_TOLERANCE_BYTES: u64 = 4 * 1024 * 1024;
+const GROWTH_ALERT_COOLDOWN: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Default)]
+pub(super) struct MemoryGrowth {
+    sampled_at: Option<Instant>,
+    /// Resident memory per app, oldest first.
+    samples: HashMap<String, VecDeque<u64>>,
+    /// Growth in bytes per hour of the apps that are growing.
+    growing: HashMap<String, u64>,
+    alerted_at: HashMap<String, Instant>,
+}
+
+/// Growth in bytes per hour of samples taken `interval` apart, when they rose overall and
+/// never dropped by more than the tolerance.
+fn growth_per_hour(samples: &VecDeque<u64>, interval: Duration) -> Option<u64> {
+    if samples.len() < GROWTH_MIN_SAMPLES {
+        return None;
+    }
+    let steady = samples
+        .iter()
+        .zip(samples.iter().skip(1))
+        .all(|(previous, next)| next + GROWTH_TOLERANCE_BYTES >= *previous);
+    let growth = samples.back()?.saturating_sub(*samples.front()?);
+    if !steady || growth <= GROWTH_TOLERANCE_BYTES {
+        return None;
+    }
+    let elapsed_secs = interval.as_secs() * (samples.len() as u64 - 1);
+    Some(growth.saturating_mul(3600) / elapsed_secs.max(1))
+}
+
+impl AppModel {
+    pub(super) fn refresh_memory_growth(&mut self) {
+        let growth = &mut self.memory_growth;
+        if growth
+            .sampled_at
+            .is_some_and(|sampled_at| sampled_at.elapsed() < GROWTH_SAMPLE_INTERVAL)
+        {
+            return;
+        }
+        growth.sampled_at = Some(Instant::now());
+
+        let running = self
+            .process_entries
+            .iter()
+            .map(|entry| entry.app_id.as_str())
+            .collect::<HashSet<_>>();
+        growth
+            .samples
+            .retain(|app_id, _| running.contains(app_id.as_str()));
+        growth.growing.clear();
+        for entry in &self.process_entries {
+            let samples = growth.samples.entry(entry.app_id.clone()).or_default();
+            samples.push_back(entry.rss_bytes);
+            if samples.len() > GROWTH_SAMPLES {
+                samples.pop_front();
+            }
+            if let Some(rate) = growth_per_hour(samples, GROWTH_SAMPLE_INTERVAL) {
+                growth.growing.insert(entry.app_id.clone(), rate);
+            }
+        }
+
+        // Alerts are about apps, so other groupings only show the arrows.
+        let Some(alert_rate) = self.config.memory_growth_alert.bytes_per_hour() else {
+            return;
+        };
+        if self.processes_page.group_by != GroupBy::App {
+            return;
+        }
+        growth
+            .alerted_at
+            .retain(|_, alerted_at| alerted_at.elapsed() < GROWTH_ALERT_COOLDOWN);
+        let mut alerts = Vec::new();
+        for entry in &self.process_entries {
+            let Some(rate) = growth.growing.get(&entry.app_id).copied() else {
+                continue;
+            };
+            if rate < alert_rate || growth.alerted_at.contains_key(&entry.app_id) {
+                continue;
+            }
+            growth
+                .alerted_at
+                .insert(entry.app_id.clone(), Instant::now());
+            alerts.push((entry.display_name.clone(), rate));
+        }
+        for (app_name, rate) in alerts {
+            let rate = fl!("memory-growth-per-hour", size = units::format_bytes(rate));
+            self.raise_alert(app_name, AlertMetric::MemoryGrowth, rate);
+        }
+    }
+
+    /// The arrow shown in the RAM cell of a growing app, with its growth per hour.
+    pub(super) fn memory_growth_indicator(&self, entry: &ProcessEntry) -> Option<String> {
+        let rate = self.memory_growth.growing.get(&entry.app_id)?;
+        Some(fl!("memory-growth-rate", size = units::format_bytes(*rate)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::growth_per_hour;
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    #[test]
+    fn detects_steady_memory_growth() {
+        const MIB: u64 = 1024 * 1024;
+        let interval = Duration::from_secs(60);
+        let samples = |mib: &[u64]| mib.iter().map(|mib| mib * MIB).collect::<VecDeque<_>>();
+
+        // 50 MiB over five minutes is 600 MiB an hour, despite a small dip.
+        assert_eq!(
+            growth_per_hour(&samples(&[100, 110, 120, 118, 135, 150]), interval),
+            Some(600 * MIB)
+        );
+        // A real drop, too few samples, and a flat line are not growth.
+        assert_eq!(
+            growth_per_hour(&samples(&[100, 110, 120, 90, 135, 150]), interval),
+            None
+        );
+        assert_eq!(growth_per_hour(&samples(&[100, 120, 140]), interval), None);
+        assert_eq!(
+            growth_per_hour(&samples(&[100, 100, 101, 100, 101, 102]), interval),
+            None
+        );
+    }
+}
@@ -0,0 +1,274 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! "Trim memory" asks the kernel or the app's runtime to give back memory the app is not
+//! using right now. What works depends on the kernel and the app: the kernel's interfaces
+//! are looked for once, and the method is picked when an app is selected and shown next to
+//! the button:
+//!
+//! - `/proc/<pid>/reclaim`, found on Android and some vendor kernels but not in mainline;
+//! - `memory.reclaim` of a cgroup holding only the app, from Linux 5.19, which COSMIC's
+//!   per-app scopes are and which the user may write to;
+//! - `jcmd <pid> System.trim_native_heap` for Java apps, which returns freed malloc memory
+//!   to the system on Java 17.0.9 and later.
+//!
+//! Reclaimed pages are paged out or dropped from the cache, so they may be read back in
+//! once the app touches them again.
+
+use super::*;
+
+/// How an app's memory is trimmed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum TrimMethod {
+    ProcReclaim,
+    CgroupReclaim(PathBuf),
+    JvmTrim,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct MemoryTrim {
+    app_id: String,
+    method: Option<TrimMethod>,
+    running: bool,
+    /// Bytes the last trim freed, or why it failed.
+    outcome: Option<Result<u64, String>>,
+}
+
+/// The trim interfaces this kernel and system have, whatever the app.
+#[derive(Debug, Clone, Copy)]
+struct TrimSupport {
+    proc_reclaim: bool,
+    cgroup_reclaim: bool,
+    jcmd: bool,
+}
+
+/// The trim interfaces, looked for once.
+fn trim_support() -> TrimSupport {
+    static SUPPORT: OnceLock<TrimSupport> = OnceLock::new();
+    *SUPPORT.get_or_init(|| TrimSupport {
+        proc_reclaim: Path::new("/proc/self/reclaim").exists(),
+        // Kernels before 5.19 have no `memory.reclaim` in any group, ours included.
+        cgroup_reclaim: cgroups::own_cgroup_dir()
+            .is_some_and(|group| group.join("memory.reclaim").exists()),
+        jcmd: debug_tools::is_installed("jcmd"),
+    })
+}
+
+fn is_writable(path: &Path) -> bool {
+    fs::OpenOptions::new().write(true).open(path).is_ok()
+}
+
+/// The first method that works for the app, in the order of the module docs.
+fn detect_trim_method(
+    support: TrimSupport,
+    source: &dyn SystemSource,
+    pids: &[u32],
+    exe_path: Option<&Path>,
+) -> Option<TrimMethod> {
+    if support.proc_reclaim
+        && !pids.is_empty()
+        && pids.iter().all(|pid| {
+            source
+                .proc_dir(*pid)
+                .is_some_and(|dir| is_writable(&dir.join("reclaim")))
+        })
+    {
+        return Some(TrimMethod::ProcReclaim);
+    }
+    if let Some(group) = support
+        .cgroup_reclaim
+        .then(|| cgroups::app_cgroup(source, pids))
+        .flatten()
+        .filter(|group| is_writable(&group.join("memory.reclaim")))
+    {
+        return Some(TrimMethod::CgroupReclaim(group));
+    }
+    let is_java = exe_path
+        .and_then(Path::file_name)
+        .is_some_and(|name| name == "java");
+    // jcmd finds the JVM by pid, so it has to run where the app does.
+    let local = pids.iter().all(|pid| source.proc_dir(*pid).is_some());
+    (is_java && local && support.jcmd).then_some(TrimMethod::JvmTrim)
+}
+
+/// Resident bytes from the second field of a `/proc/<pid>/statm` file, in pages.
+fn parse_statm_resident(statm: &str, page_size: u64) -> Option<u64> {
+    let pages = statm.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(pages * page_size)
+}
+
+/// Resident bytes of the members, by pid and `/proc` directory.
+fn resident_bytes(members: &[(u32, PathBuf)]) -> u64 {
+    // SAFETY: sysconf has no preconditions.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).unwrap_or(4096);
+    members
+        .iter()
+        .filter_map(|(_, proc_dir)| fs::read_to_string(proc_dir.join("statm")).ok())
+        .filter_map(|statm| parse_statm_resident(&statm, page_size))
+        .sum()
+}
+
+/// Trims the memory of the members, by pid and `/proc` directory, returning how much less
+/// of it is resident afterwards.
+fn trim_memory(method: &TrimMethod, members: &[(u32, PathBuf)]) -> Result<u64, String> {
+    let before = resident_bytes(members);
+    match method {
+        TrimMethod::ProcReclaim => {
+            for (_, proc_dir) in members {
+                let reclaim = proc_dir.join("reclaim");
+                fs::write(&reclaim, "all")
+                    .map_err(|err| format!("{}: {err}", reclaim.display()))?;
+            }
+        }
+        TrimMethod::CgroupReclaim(group) => {
+            let current =
+                fs::read_to_string(group.join("memory.current")).map_err(|err| err.to_string())?;
+            // Asking for everything reclaims what can be; the kernel answers EAGAIN when
+            // that is less than asked for, which is still a success here.
+            fs::write(group.join("memory.reclaim"), current.trim()).or_else(|err| {
+                match err.raw_os_error() {
+                    Some(libc::EAGAIN) => Ok(()),
+                    _ => Err(err.to_string()),
+                }
+            })?;
+        }
+        TrimMethod::JvmTrim => {
+            for (pid, _) in members {
+                let output = Command::new("jcmd")
+                    .arg(pid.to_string())
+                    .arg("System.trim_native_heap")
+                    .stdin(Stdio::null())
+                    .output()
+                    .map_err(|err| err.to_string())?;
+                if !output.status.success() {
+                    return Err(String::from_utf8_lossy(&output.stdout).trim().to_string());
+                }
+            }
+        }
+    }
+    Ok(before.saturating_sub(resident_bytes(members)))
+}
+
+impl AppModel {
+    /// Picks the trim method when another app is selected; the app's own stays as it was.
+    pub(super) fn refresh_selected_trim_method(&mut self) {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            self.memory_trim = MemoryTrim::default();
+            return;
+        };
+        if self.memory_trim.app_id == entry.app_id {
+            return;
+        }
+        self.memory_trim = MemoryTrim {
+            app_id: entry.app_id.clone(),
+            method: detect_trim_method(
+                trim_support(),
+                self.process_source.as_ref(),
+                &entry.pids,
+                entry.exe_path.as_deref(),
+            ),
+            ..Default::default()
+        };
+    }
+
+    pub(super) fn trim_selected_memory(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            return Task::none();
+        };
+        let trim = &mut self.memory_trim;
+        let Some(method) = trim
+            .method
+            .clone()
+            .filter(|_| trim.app_id == entry.app_id && !trim.running)
+        else {
+            return Task::none();
+        };
+        trim.running = true;
+        trim.outcome = None;
+        let app_id = entry.app_id.clone();
+        // Only processes whose /proc is here can be trimmed or measured.
+        let members = entry
+            .pids
+            .iter()
+            .filter_map(|pid| Some((*pid, self.process_source.proc_dir(*pid)?)))
+            .collect::<Vec<_>>();
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || trim_memory(&method, &members))
+                    .await
+                    .unwrap_or_else(|err| Err(err.to_string()))
+            },
+            move |result| cosmic::Action::App(Message::MemoryTrimmed { app_id, result }),
+        )
+    }
+
+    pub(super) fn set_memory_trim_result(&mut self, app_id: String, result: Result<u64, String>) {
+        let trim = &mut self.memory_trim;
+        if trim.app_id != app_id {
+            return;
+        }
+        if let Err(err) = result.as_ref() {
+            eprintln!("failed to trim the memory of `{app_id}`: {err}");
+        }
+        trim.running = false;
+        trim.outcome = Some(result);
+    }
+
+    /// The trim button with the method it uses, or why the app's memory cannot be trimmed.
+    pub(super) fn selected_app_memory_trim(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let trim = &self.memory_trim;
+        if trim.app_id != selected.app_id {
+            return None;
+        }
+        let method = match trim.method.as_ref() {
+            Some(TrimMethod::ProcReclaim) => fl!("memory-trim-proc-reclaim"),
+            Some(TrimMethod::CgroupReclaim(_)) => fl!("memory-trim-cgroup-reclaim"),
+            Some(TrimMethod::JvmTrim) => fl!("memory-trim-jvm"),
+            None => fl!("memory-trim-unsupported"),
+        };
+        let outcome = trim.outcome.as_ref().map(|outcome| match outcome {
+            Ok(freed) => fl!("memory-trim-freed", size = units::format_bytes(*freed)),
+            Err(err) => fl!("memory-trim-failed", error = err.as_str()),
+        });
+
+        Some(
+            widget::column::with_capacity(3)
+                .push(
+                    widget::button::standard(fl!("memory-trim"))
+                        .on_press_maybe(
+                            (trim.method.is_some() && !trim.running)
+                                .then_some(Message::TrimSelectedMemory),
+                        )
+                        .width(Length::Fill)
+                        .height(Length::Fixed(38.0)),
+                )
+                .push(widget::text(method).size(12))
+                .push_maybe(outcome.map(|outcome| widget::text(outcome).size(12)))
+                .spacing(4)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_statm_resident;
+
+    #[test]
+    fn reads_resident_pages() {
+        assert_eq!(
+            parse_statm_resident("1158 316 268 5 0 97 0\n", 4096),
+            Some(316 * 4096)
+        );
+        assert_eq!(parse_statm_resident("", 4096), None);
+    }
+}
@@ -0,0 +1,291 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Processes in other PID or mount namespaces than the task monitor, such as toolbox and
+//! distrobox containers or bubblewrap sandboxes. Their generic binaries (`bash`, `python3`)
+//! are grouped and labelled by the container or sandbox they run in, and podman containers,
+//! toolbox and distrobox ones included, get their own entry with enter and stop actions.
+
+use super::*;
+
+const CONTAINER_ICON: &str = "package-x-generic-symbolic";
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum SandboxKind {
+    Toolbx,
+    Distrobox,
+    /// A podman container that is neither a toolbox nor a distrobox.
+    Podman,
+    Flatpak,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Sandbox {
+    pub(super) name: String,
+    pub(super) kind: SandboxKind,
+}
+
+impl Sandbox {
+    fn is_container(&self) -> bool {
+        matches!(
+            self.kind,
+            SandboxKind::Toolbx | SandboxKind::Distrobox | SandboxKind::Podman
+        )
+    }
+
+    /// Group key, name, and icon for the sandbox's entry in the Apps table.
+    pub(super) fn app_identity(&self) -> (String, String, Option<icon::Handle>) {
+        if self.is_container() {
+            (
+                format!("container-{}", self.name),
+                self.name.clone(),
+                Some(icon::from_name(CONTAINER_ICON).handle()),
+            )
+        } else {
+            (format!("sandbox-{}", self.name), self.name.clone(), None)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct NamespaceResolver {
+    /// The task monitor's own PID and mount namespace links, e.g. `pid:[4026531836]`.
+    own: (Option<String>, Option<String>),
+    /// Sandbox per mount namespace, since every member of a container shares one.
+    sandboxes: HashMap<String, Option<Sandbox>>,
+    /// Mount namespace of each process looked up, so namespaces no process is in anymore
+    /// are dropped.
+    members: HashMap<u32, String>,
+}
+
+impl Default for NamespaceResolver {
+    fn default() -> Self {
+        let link = |kind: &str| {
+            fs::read_link(format!("/proc/self/ns/{kind}"))
+                .ok()
+                .map(|target| target.to_string_lossy().into_owned())
+        };
+        Self {
+            own: (link("pid"), link("mnt")),
+            sandboxes: HashMap::new(),
+            members: HashMap::new(),
+        }
+    }
+}
+
+fn namespace_link(source: &dyn SystemSource, pid: u32, kind: &str) -> Option<String> {
+    fs::read_link(source.proc_dir(pid)?.join("ns").join(kind))
+        .ok()
+        .map(|target| target.to_string_lossy().into_owned())
+}
+
+/// Whether a process environment, NUL-separated as in `/proc/<pid>/environ`, is the one
+/// distrobox starts its containers with.
+fn is_distrobox_environ(environ: &[u8]) -> bool {
+    environ
+        .split(|byte| *byte == 0)
+        .any(|variable| variable == b"container=distrobox")
+}
+
+/// The `name` entry of podman's `/run/.containerenv`, used by toolbox and distrobox
+/// (`name="fedora-toolbox-40"`), or of a Flatpak's `/.flatpak-info` (`name=org.gnome.Maps`).
+fn parse_sandbox_name(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("name="))
+        .map(|name| name.trim().trim_matches('"').to_string())
+        .filter(|name| !name.is_empty())
+}
+
+impl NamespaceResolver {
+    /// The container or sandbox `process` runs in, if it is not in our own namespaces.
+    pub(super) fn sandbox_for(
+        &mut self,
+        process: &ProcessSample,
+        processes: &HashMap<Pid, ProcessSample>,
+        source: &dyn SystemSource,
+    ) -> Option<Sandbox> {
+        let pid = process.pid;
+        let proc_dir = source.proc_dir(pid)?;
+        let pid_namespace = namespace_link(source, pid, "pid");
+        let mount_namespace = namespace_link(source, pid, "mnt")?;
+        let (own_pid, own_mount) = &self.own;
+        if pid_namespace == *own_pid && Some(&mount_namespace) == own_mount.as_ref() {
+            return None;
+        }
+
+        self.members.insert(pid, mount_namespace.clone());
+        self.sandboxes
+            .entry(mount_namespace)
+            .or_insert_with(|| {
+                let root = proc_dir.join("root");
+                let read_name = |file: &str| {
+                    fs::read_to_string(root.join(file))
+                        .ok()
+                        .and_then(|contents| parse_sandbox_name(&contents))
+                };
+                // Each tool is recognized by its own marker; other podman containers are
+                // left generic rather than taken for one of them.
+                let container_kind = if root.join("run/.toolboxenv").exists() {
+                    SandboxKind::Toolbx
+                } else if root.join("run/host/etc/distrobox").exists()
+                    || source
+                        .read_proc_file(pid, "environ")
+                        .is_some_and(|environ| is_distrobox_environ(&environ))
+                {
+                    SandboxKind::Distrobox
+                } else {
+                    SandboxKind::Podman
+                };
+                read_name("run/.containerenv")
+                    .map(|name| Sandbox {
+                        name,
+                        kind: container_kind,
+                    })
+                    .or_else(|| {
+                        read_name(".flatpak-info").map(|name| Sandbox {
+                            name,
+                            kind: SandboxKind::Flatpak,
+                        })
+                    })
+                    .or_else(|| {
+                        sandbox_init_name(process, processes, source, pid_namespace.as_deref()).map(
+                            |name| Sandbox {
+                                name,
+                                kind: SandboxKind::Other,
+                            },
+                        )
+                    })
+            })
+            .clone()
+    }
+
+    /// Forgets the processes that are not `running`, and the namespaces none of the rest is in.
+    pub(super) fn retain_pids(&mut self, running: &HashSet<Pid>) {
+        self.members
+            .retain(|pid, _| running.contains(&Pid::from_u32(*pid)));
+        let seen = self.members.values().collect::<HashSet<_>>();
+        self.sandboxes
+            .retain(|mount_namespace, _| seen.contains(mount_namespace));
+    }
+
+    /// The podman container behind an Apps table entry.
+    pub(super) fn container(&self, app_id: &str) -> Option<&Sandbox> {
+        self.sandboxes
+            .values()
+            .flatten()
+            .find(|sandbox| sandbox.is_container() && sandbox.app_identity().0 == app_id)
+    }
+}
+
+impl AppModel {
+    pub(super) fn enter_selected_container(&self) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        let name = container.name.as_str();
+        let command: &[&str] = match container.kind {
+            SandboxKind::Toolbx => &["toolbox", "enter", name],
+            SandboxKind::Distrobox => &["distrobox", "enter", name],
+            _ => &["podman", "exec", "-it", name, "sh"],
+        };
+        if !terminal::spawn_in_terminal(command, None) {
+            eprintln!(
+                "failed to open a terminal for container `{}`",
+                container.name
+            );
+        }
+    }
+
+    pub(super) fn stop_selected_container(&self) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        // Toolbox has no stop command, and podman stops every kind of container.
+        let mut command = Command::new("podman");
+        command.args(["stop", &container.name]);
+        if !Self::spawn_detached(&mut command) {
+            eprintln!("failed to stop container `{}`", container.name);
+        }
+    }
+
+    fn selected_container(&self) -> Option<&Sandbox> {
+        let selected = self.selected_process.as_ref()?;
+        self.namespaces.container(&selected.app_id)
+    }
+
+    pub(super) fn selected_container_actions(&self) -> Option<Element<'_, Message>> {
+        self.selected_container()?;
+        let button_height = Length::Fixed(38.0);
+        Some(
+            widget::column::with_capacity(2)
+                .push(
+                    widget::button::standard(fl!("container-enter"))
+                        .on_press(Message::EnterSelectedContainer)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .push(
+                    widget::button::destructive(fl!("container-stop"))
+                        .on_press(Message::StopSelectedContainer)
+                        .width(Length::Fill)
+                        .height(button_height),
+                )
+                .spacing(8)
+                .into(),
+        )
+    }
+}
+
+/// Name of the sandbox's first process, e.g. `bwrap`: the outermost ancestor that still
+/// shares `process`'s PID namespace.
+fn sandbox_init_name(
+    process: &ProcessSample,
+    processes: &HashMap<Pid, ProcessSample>,
+    source: &dyn SystemSource,
+    pid_namespace: Option<&str>,
+) -> Option<String> {
+    let mut init = process;
+    while let Some(parent) = init
+        .parent
+        .and_then(|pid| processes.get(&Pid::from_u32(pid)))
+    {
+        if namespace_link(source, parent.pid, "pid").as_deref() != pid_namespace {
+            break;
+        }
+        init = parent;
+    }
+    (!init.name.is_empty()).then(|| init.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_distrobox_environ, parse_sandbox_name};
+
+    #[test]
+    fn reads_container_and_flatpak_names() {
+        let containerenv = "engine=\"podman-4.9.4\"\nname=\"fedora-toolbox-40\"\nrootless=1\n";
+        assert_eq!(
+            parse_sandbox_name(containerenv).as_deref(),
+            Some("fedora-toolbox-40")
+        );
+
+        let flatpak_info =
+            "[Application]\nname=org.gnome.Maps\nruntime=runtime/org.gnome.Platform\n";
+        assert_eq!(
+            parse_sandbox_name(flatpak_info).as_deref(),
+            Some("org.gnome.Maps")
+        );
+
+        assert_eq!(parse_sandbox_name("name=\"\"\n"), None);
+    }
+
+    #[test]
+    fn recognizes_distrobox_environments() {
+        assert!(is_distrobox_environ(
+            b"HOME=/home/me\0container=distrobox\0"
+        ));
+        assert!(!is_distrobox_environ(b"HOME=/home/me\0container=podman\0"));
+        assert!(!is_distrobox_environ(b"container=distrobox-like\0"));
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Display names that stay distinct when different apps share a name, such as a Flatpak
+//! and a native install of the same browser.
+
+use super::*;
+
+/// A short label telling apart entries that share a display name.
+fn disambiguator(entry: &ProcessEntry) -> String {
+    if entry.app_id.starts_with("steam-app-") {
+        return "Steam".to_string();
+    }
+
+    let exe_path = entry
+        .exe_path
+        .as_deref()
+        .map(|path| path.to_string_lossy())
+        .unwrap_or_default();
+    // Flatpak apps see their own files under `/app`, so that is what their exe link shows.
+    if exe_path.starts_with("/app/") || exe_path.contains("/flatpak/") {
+        "Flatpak".to_string()
+    } else if exe_path.starts_with("/snap/") {
+        "Snap".to_string()
+    } else {
+        entry.app_id.clone()
+    }
+}
+
+/// Appends a disambiguator to every display name shared by more than one app id.
+pub(super) fn disambiguate_display_names(entries: &mut [ProcessEntry]) {
+    let mut app_ids_by_name: HashMap<String, HashSet<String>> = HashMap::new();
+    for entry in entries.iter() {
+        app_ids_by_name
+            .entry(entry.display_name.clone())
+            .or_default()
+            .insert(entry.app_id.clone());
+    }
+
+    for entry in entries.iter_mut() {
+        if app_ids_by_name
+            .get(&entry.display_name)
+            .is_some_and(|app_ids| app_ids.len() > 1)
+        {
+            entry.display_name = format!("{} ({})", entry.display_name, disambiguator(entry));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(app_id: &str, display_name: &str, exe_path: &str) -> ProcessEntry {
+        ProcessEntry {
+            name: display_name.to_string(),
+            display_name: display_name.to_string(),
+            exe_path: Some(PathBuf::from(exe_path)),
+            ..ProcessEntry::for_app(app_id)
+        }
+    }
+
+    #[test]
+    fn only_shared_names_get_a_disambiguator() {
+        let mut entries = vec![
+            entry("firefox", "Firefox", "/usr/lib/firefox/firefox"),
+            entry("org.mozilla.firefox", "Firefox", "/app/lib/firefox/firefox"),
+            entry("steam-app-570", "Dota 2", "/games/dota2"),
+            entry("dota2-tool", "Dota 2", "/usr/bin/dota2-tool"),
+            entry("org.gnome.Nautilus", "Files", "/usr/bin/nautilus"),
+        ];
+        disambiguate_display_names(&mut entries);
+
+        let names = entries
+            .iter()
+            .map(|entry| entry.display_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "Firefox (firefox)",
+                "Firefox (Flatpak)",
+                "Dota 2 (Steam)",
+                "Dota 2 (dota2-tool)",
+                "Files",
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Where each app was installed from. Sandboxed and bundled formats are recognized from the
+//! executable's path; everything else is checked against the distro package database in
+//! a background worker, since rpm and dpkg are too slow to query on every refresh.
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum AppOrigin {
+    Distro,
+    Flatpak,
+    Snap,
+    AppImage,
+    Steam,
+    Manual,
+}
+
+impl AppOrigin {
+    fn label(self) -> String {
+        match self {
+            AppOrigin::Distro => fl!("origin-distro"),
+            AppOrigin::Flatpak => fl!("origin-flatpak"),
+            AppOrigin::Snap => fl!("origin-snap"),
+            AppOrigin::AppImage => fl!("origin-appimage"),
+            AppOrigin::Steam => fl!("origin-steam"),
+            AppOrigin::Manual => fl!("origin-manual"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct OriginResolver {
+    by_exe: HashMap<PathBuf, AppOrigin>,
+    lookup_running: bool,
+}
+
+/// Origin evident from the app id or executable path, or `None` if the package database
+/// has to be asked.
+fn origin_from_path(app_id: &str, exe_path: &Path) -> Option<AppOrigin> {
+    let path = exe_path.to_string_lossy();
+    if app_id.starts_with("steam-app-") || path.contains("/steamapps/") {
+        Some(AppOrigin::Steam)
+    } else if path.starts_with("/app/") || path.contains("/flatpak/") {
+        Some(AppOrigin::Flatpak)
+    } else if path.starts_with("/snap/") {
+        Some(AppOrigin::Snap)
+    } else if path.starts_with("/tmp/.mount_") {
+        // AppImages run from a FUSE mount of the image.
+        Some(AppOrigin::AppImage)
+    } else {
+        None
+    }
+}
+
+fn is_owned_by_package(exe_path: &Path) -> bool {
+    let queries: [(&str, &[&str]); 3] =
+        [("rpm", &["-qf"]), ("dpkg", &["-S"]), ("pacman", &["-Qoq"])];
+    queries.into_iter().any(|(program, args)| {
+        Command::new(program)
+            .args(args)
+            .arg(exe_path)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
+
+impl AppModel {
+    pub(super) fn app_origin(&self, entry: &ProcessEntry) -> Option<AppOrigin> {
+        let exe_path = entry.exe_path.as_deref()?;
+        origin_from_path(&entry.app_id, exe_path)
+            .or_else(|| self.origins.by_exe.get(exe_path).copied())
+    }
+
+    /// Queries the package database for executables whose origin is still unknown.
+    pub(super) fn request_origin_lookups(&mut self) -> Task<cosmic::Action<Message>> {
+        if self.origins.lookup_running || self.processes_page.group_by != GroupBy::App {
+            return Task::none();
+        }
+
+        let pending = self
+            .process_entries
+            .iter()
+            .filter_map(|entry| {
+                let exe_path = entry.exe_path.as_ref()?;
+                (origin_from_path(&entry.app_id, exe_path).is_none()
+                    && !self.origins.by_exe.contains_key(exe_path))
+                .then(|| exe_path.clone())
+            })
+            .collect::<HashSet<_>>();
+        if pending.is_empty() {
+            return Task::none();
+        }
+
+        self.origins.lookup_running = true;
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    pending
+                        .into_iter()
+                        .map(|exe_path| {
+                            let origin = if is_owned_by_package(&exe_path) {
+                                AppOrigin::Distro
+                            } else {
+                                AppOrigin::Manual
+                            };
+                            (exe_path, origin)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await
+                .unwrap_or_default()
+            },
+            |origins| cosmic::Action::App(Message::OriginsResolved(origins)),
+        )
+    }
+
+    /// Whether no package lookup is in flight, so known origins are as complete as they get.
+    pub(super) fn origins_settled(&self) -> bool {
+        !self.origins.lookup_running
+    }
+
+    pub(super) fn set_origins(&mut self, origins: Vec<(PathBuf, AppOrigin)>) {
+        self.origins.lookup_running = false;
+        self.origins.by_exe.extend(origins);
+    }
+
+    pub(super) fn origin_badge(&self, entry: &ProcessEntry) -> Option<Element<'static, Message>> {
+        let origin = self.app_origin(entry)?;
+        Some(
+            widget::container(widget::text(origin.label()).size(11))
+                .padding([1, 6])
+                .class(theme::Container::custom(|theme| widget::container::Style {
+                    border: Border {
+                        color: theme.cosmic().bg_divider().into(),
+                        width: 1.0,
+                        radius: 8.0.into(),
+                    },
+                    ..Default::default()
+                }))
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppOrigin, origin_from_path};
+    use std::path::Path;
+
+    #[test]
+    fn recognizes_bundled_origins_from_the_path() {
+        let origin = |app_id, path| origin_from_path(app_id, Path::new(path));
+        assert_eq!(
+            origin("steam-app-570", "/games/dota2"),
+            Some(AppOrigin::Steam)
+        );
+        assert_eq!(
+            origin("org.mozilla.firefox", "/app/lib/firefox/firefox"),
+            Some(AppOrigin::Flatpak)
+        );
+        assert_eq!(
+            origin("spotify", "/snap/spotify/80/usr/bin/spotify"),
+            Some(AppOrigin::Snap)
+        );
+        assert_eq!(
+            origin("obsidian", "/tmp/.mount_ObsidiX1/obsidian"),
+            Some(AppOrigin::AppImage)
+        );
+        assert_eq!(origin("nautilus", "/usr/bin/nautilus"), None);
+    }
+}
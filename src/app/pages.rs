@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Navigation pages. Each page owns its transient UI state, messages, and views, while
+//! the sampled system data stays on [`AppModel`] so every page reads the same refresh.
+
+use super::*;
+
+mod cgroups;
+mod events;
+mod games;
+mod info;
+mod processes;
+mod resources;
+mod settings;
+
+pub use cgroups::{CgroupsMessage, CgroupsPage};
+pub use events::{EventsMessage, EventsPage};
+pub use games::GamesMessage;
+pub use info::{InfoMessage, InfoPage};
+pub use processes::{ProcessesMessage, ProcessesPage};
+pub use resources::{ResourcesMessage, ResourcesPage};
+pub use settings::{SettingsMessage, SettingsPage};
+
+impl Page {
+    pub(super) fn label(self) -> String {
+        match self {
+            Page::Processes => fl!("nav-apps"),
+            Page::Autostart => fl!("nav-autostart"),
+            Page::Games => fl!("nav-games"),
+            Page::Resources => fl!("nav-performance"),
+            Page::Cgroups => fl!("nav-cgroups"),
+            Page::Events => fl!("nav-events"),
+            Page::Info => fl!("nav-info"),
+            Page::Settings => fl!("nav-settings"),
+        }
+    }
+}
+
+impl AppModel {
+    pub(super) fn insert_nav_pages(nav: &mut nav_bar::Model, startup_page: Page) {
+        nav.insert()
+            .text(fl!("nav-apps"))
+            .data::<Page>(Page::Processes)
+            .icon(icon::from_name("applications-other-symbolic"));
+
+        nav.insert()
+            .text(fl!("nav-autostart"))
+            .data::<Page>(Page::Autostart)
+            .icon(icon::from_name("system-run-symbolic"));
+
+        nav.insert()
+            .text(fl!("nav-games"))
+            .data::<Page>(Page::Games)
+            .icon(icon::from_name("input-gaming-symbolic"));
+
+        nav.insert()
+            .text(fl!("nav-performance"))
+            .data::<Page>(Page::Resources)
+            .icon(icon::from_name("utilities-system-monitor-symbolic"));
+
+        nav.insert()
+            .text(fl!("nav-cgroups"))
+            .data::<Page>(Page::Cgroups)
+            .icon(icon::from_name("view-list-symbolic"));
+
+        nav.insert()
+            .text(fl!("nav-events"))
+            .data::<Page>(Page::Events)
+            .icon(icon::from_name("document-open-recent-symbolic"));
+
+        nav.insert()
+            .text(fl!("nav-info"))
+            .data::<Page>(Page::Info)
+            .icon(icon::from_name("help-about-symbolic"));
+
+        nav.insert()
+            .text(fl!("nav-settings"))
+            .data::<Page>(Page::Settings)
+            .icon(icon::from_name("preferences-system-symbolic"));
+
+        let startup = nav
+            .iter()
+            .find(|id| nav.data::<Page>(*id) == Some(&startup_page));
+        if let Some(id) = startup {
+            nav.activate(id);
+        }
+    }
+
+    pub(super) fn set_startup_page(&mut self, page: Page) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_startup_page(handler, page) {
+                eprintln!("failed to save startup page: {err}");
+            }
+        } else {
+            self.config.startup_page = page;
+        }
+    }
+
+    /// Shows live counts next to page names, so activity is visible before switching pages.
+    pub(super) fn refresh_nav_counters(&mut self) {
+        let ids = self.nav.iter().collect::<Vec<_>>();
+        for id in ids {
+            let text = match self.nav.data::<Page>(id) {
+                Some(Page::Processes) => fl!("nav-apps-count", count = self.process_entries.len()),
+                Some(Page::Autostart) => {
+                    fl!("nav-autostart-count", count = self.autostart_entries.len())
+                }
+                Some(Page::Games) => fl!("nav-games-count", count = self.running_games().len()),
+                _ => continue,
+            };
+            self.nav.text_set(id, text);
+        }
+    }
+
+    pub(super) fn active_page(&self) -> Page {
+        self.nav
+            .active_data::<Page>()
+            .copied()
+            .unwrap_or(Page::Processes)
+    }
+
+    pub(super) fn page_view(&self, space_s: u16) -> Element<'_, Message> {
+        match self.active_page() {
+            Page::Processes => self.processes_view(space_s),
+            Page::Autostart => self.autostart_view(space_s),
+            Page::Games => self.games_view(space_s),
+            Page::Resources => self.resources_view(space_s),
+            Page::Cgroups => self.cgroups_view(space_s),
+            Page::Events => self.events_view(space_s),
+            Page::Info => self.info_view(space_s),
+            Page::Settings => self.settings_view(space_s),
+        }
+    }
+}
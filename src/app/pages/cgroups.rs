@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Control groups page: the systemd slices and scopes apps run in, shown as a tree.
+
+use super::*;
+use crate::app::cgroups::default_expanded_cgroups;
+
+const CGROUP_INDENT: f32 = 16.0;
+
+pub struct CgroupsPage {
+    /// Paths of the groups whose children are shown.
+    pub(in crate::app) expanded: HashSet<String>,
+}
+
+impl Default for CgroupsPage {
+    fn default() -> Self {
+        Self {
+            expanded: default_expanded_cgroups(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CgroupsMessage {
+    ToggleNode(String),
+}
+
+impl AppModel {
+    pub(in crate::app) fn update_cgroups_page(&mut self, message: CgroupsMessage) {
+        match message {
+            CgroupsMessage::ToggleNode(path) => {
+                if !self.cgroups_page.expanded.remove(&path) {
+                    self.cgroups_page.expanded.insert(path);
+                }
+                self.refresh_cgroup_tree();
+            }
+        }
+    }
+
+    pub(in crate::app) fn cgroups_view(&self, space_s: u16) -> Element<'_, Message> {
+        let cell_padding = self.config.density.cell_padding();
+        let header = widget::text::title2(fl!("cgroups-title"));
+
+        let nodes = &self.cgroup_tree.nodes;
+        if nodes.is_empty() {
+            return widget::column::with_capacity(2)
+                .push(header)
+                .push(widget::text(fl!("cgroups-unavailable")))
+                .spacing(space_s)
+                .into();
+        }
+
+        let header_cell = |label: String, portion: u16| {
+            widget::container(widget::text(label))
+                .padding(cell_padding)
+                .class(theme::Container::custom(table_cell_style))
+                .width(Length::FillPortion(portion))
+        };
+        let table_headers = widget::row::with_capacity(3)
+            .push(header_cell(fl!("table-name"), 6))
+            .push(header_cell(fl!("table-cpu"), 2))
+            .push(header_cell(fl!("table-ram"), 2));
+
+        let rows = nodes.iter().fold(
+            widget::column::with_capacity(nodes.len()),
+            |column, node| {
+                let toggle: Element<'_, Message> = if node.has_children {
+                    let icon_name = if self.cgroups_page.expanded.contains(&node.path) {
+                        "pan-down-symbolic"
+                    } else {
+                        "pan-end-symbolic"
+                    };
+                    widget::button::icon(widget::icon::from_name(icon_name))
+                        .on_press(Message::Cgroups(CgroupsMessage::ToggleNode(
+                            node.path.clone(),
+                        )))
+                        .into()
+                } else {
+                    widget::container(widget::text(""))
+                        .width(Length::Fixed(32.0))
+                        .into()
+                };
+                let name = widget::row::with_capacity(3)
+                    .push(
+                        widget::container(widget::text(""))
+                            .width(Length::Fixed(node.depth as f32 * CGROUP_INDENT)),
+                    )
+                    .push(toggle)
+                    .push(widget::tooltip(
+                        widget::text(node.name.clone()),
+                        widget::text(node.path.clone()),
+                        widget::tooltip::Position::Top,
+                    ))
+                    .align_y(Alignment::Center);
+                let memory = node
+                    .memory_bytes
+                    .map_or_else(|| "—".to_string(), units::format_bytes);
+
+                column.push(
+                    widget::row::with_capacity(3)
+                        .push(
+                            widget::container(name)
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(6)),
+                        )
+                        .push(
+                            widget::container(widget::text(units::format_percent(
+                                node.cpu_percent,
+                                1,
+                            )))
+                            .padding(cell_padding)
+                            .class(theme::Container::custom(table_cell_style))
+                            .width(Length::FillPortion(2)),
+                        )
+                        .push(
+                            widget::container(widget::text(memory))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(2)),
+                        )
+                        .align_y(Alignment::Center),
+                )
+            },
+        );
+
+        widget::column::with_capacity(3)
+            .push(header)
+            .push(table_headers)
+            .push(widget::scrollable(rows).height(Length::Fill))
+            .spacing(space_s)
+            .into()
+    }
+}
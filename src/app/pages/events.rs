@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Events page: a live feed of apps starting and exiting, newest first, filtered by app.
+
+use super::*;
+use crate::app::events::{AppEvent, AppEventKind};
+
+const EVENT_TIME_WIDTH: f32 = 80.0;
+
+#[derive(Default)]
+pub struct EventsPage {
+    /// Shows only events of apps whose name or app id contains it.
+    filter: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum EventsMessage {
+    SetFilter(String),
+    Clear,
+}
+
+fn event_matches(event: &AppEvent, filter: &str) -> bool {
+    filter.is_empty()
+        || event.name.to_lowercase().contains(filter)
+        || event.app_id.to_lowercase().contains(filter)
+}
+
+impl AppModel {
+    pub(in crate::app) fn update_events_page(&mut self, message: EventsMessage) {
+        match message {
+            EventsMessage::SetFilter(filter) => self.events_page.filter = filter,
+            EventsMessage::Clear => self.app_events.events.clear(),
+        }
+    }
+
+    pub(in crate::app) fn events_view(&self, space_s: u16) -> Element<'_, Message> {
+        let cell_padding = self.config.density.cell_padding();
+        let header = widget::row::with_capacity(2)
+            .push(widget::text::title2(fl!("nav-events")).width(Length::Fill))
+            .push(
+                widget::button::standard(fl!("events-clear")).on_press_maybe(
+                    (!self.app_events.events.is_empty())
+                        .then_some(Message::Events(EventsMessage::Clear)),
+                ),
+            )
+            .align_y(Alignment::Center);
+        let search = widget::search_input(fl!("events-filter"), self.events_page.filter.as_str())
+            .on_input(|filter| Message::Events(EventsMessage::SetFilter(filter)))
+            .on_clear(Message::Events(EventsMessage::SetFilter(String::new())))
+            .width(Length::Fill);
+
+        let filter = self.events_page.filter.to_lowercase();
+        let events = self
+            .app_events
+            .events
+            .iter()
+            .rev()
+            .filter(|event| event_matches(event, &filter))
+            .collect::<Vec<_>>();
+
+        let list: Element<'_, Message> = if events.is_empty() {
+            widget::text(fl!("events-empty")).into()
+        } else {
+            events
+                .into_iter()
+                .fold(
+                    widget::column::with_capacity(self.app_events.events.len()),
+                    |column, event| {
+                        let (icon_name, label) = match event.kind {
+                            AppEventKind::Started => {
+                                ("media-playback-start-symbolic", fl!("events-started"))
+                            }
+                            AppEventKind::Exited => {
+                                ("media-playback-stop-symbolic", fl!("events-exited"))
+                            }
+                        };
+                        let row = widget::row::with_capacity(4)
+                            .push(
+                                widget::text(event.time_of_day())
+                                    .width(Length::Fixed(EVENT_TIME_WIDTH)),
+                            )
+                            .push(widget::icon::from_name(icon_name).icon().size(16))
+                            .push(widget::text(label).width(Length::FillPortion(2)))
+                            .push(widget::text(event.name.clone()).width(Length::FillPortion(6)))
+                            .align_y(Alignment::Center)
+                            .spacing(8);
+                        column.push(
+                            widget::container(row)
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::Fill),
+                        )
+                    },
+                )
+                .into()
+        };
+
+        widget::column::with_capacity(3)
+            .push(header)
+            .push(search)
+            .push(list)
+            .spacing(space_s)
+            .width(Length::Fill)
+            .into()
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Games page: running games as cards with their cover art, load, and playtime, and the
+//! games played recently, ready to launch again.
+
+use super::*;
+use crate::app::playtime::format_playtime;
+use crate::config::GamePlaytime;
+
+const GAME_CARD_WIDTH: f32 = 280.0;
+const GAME_COVER_SIZE: u16 = 96;
+
+#[derive(Debug, Clone)]
+pub enum GamesMessage {
+    Launch(String),
+    Kill(String),
+}
+
+impl AppModel {
+    pub(in crate::app) fn update_games_page(
+        &mut self,
+        message: GamesMessage,
+    ) -> Task<cosmic::Action<Message>> {
+        match message {
+            GamesMessage::Launch(app_id) => {
+                self.launch_app(&app_id);
+                Task::none()
+            }
+            GamesMessage::Kill(app_id) => self.request_stop(app_id, StopKind::Kill),
+        }
+    }
+
+    pub(in crate::app) fn games_view(&self, space_s: u16) -> Element<'_, Message> {
+        let header = widget::row::with_capacity(2)
+            .push(widget::text::title2(fl!("nav-games")).width(Length::Fill))
+            .push(widget::text(fl!(
+                "games-playtime-today",
+                playtime = format_playtime(self.playtime_today())
+            )))
+            .align_y(Alignment::Center);
+
+        let running = self.running_games();
+        let running_cards: Element<'_, Message> = if running.is_empty() {
+            widget::text(fl!("games-none-running")).into()
+        } else {
+            Self::game_grid(
+                running
+                    .into_iter()
+                    .map(|entry| self.running_game_card(entry))
+                    .collect(),
+                space_s,
+            )
+        };
+
+        let recent = self.recent_games();
+        let recent_section = (!recent.is_empty()).then(|| {
+            widget::column::with_capacity(2)
+                .push(widget::text::heading(fl!("games-recently-played")))
+                .push(Self::game_grid(
+                    recent
+                        .into_iter()
+                        .map(|record| self.recent_game_card(record))
+                        .collect(),
+                    space_s,
+                ))
+                .spacing(space_s)
+        });
+
+        widget::column::with_capacity(3)
+            .push(header)
+            .push(running_cards)
+            .push_maybe(recent_section)
+            .spacing(space_s)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn game_grid(cards: Vec<Element<'_, Message>>, space_s: u16) -> Element<'_, Message> {
+        widget::flex_row(cards)
+            .spacing(space_s)
+            .min_item_width(GAME_CARD_WIDTH)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn game_cover(handle: Option<icon::Handle>) -> Element<'static, Message> {
+        let cover: Element<'static, Message> = match handle {
+            Some(handle) => icon::icon(handle).size(GAME_COVER_SIZE).into(),
+            None => icon::from_name("input-gaming-symbolic")
+                .icon()
+                .size(GAME_COVER_SIZE / 2)
+                .into(),
+        };
+        widget::container(cover)
+            .center_x(Length::Fill)
+            .center_y(Length::Fixed(f32::from(GAME_COVER_SIZE)))
+            .into()
+    }
+
+    fn game_card<'a>(
+        &self,
+        content: widget::Column<'a, Message>,
+        on_press: Option<Message>,
+    ) -> Element<'a, Message> {
+        let card = widget::container(content.spacing(6).width(Length::Fill))
+            .padding(self.config.density.cell_padding() + 2)
+            .class(theme::Container::custom(table_cell_style))
+            .width(Length::Fill);
+        widget::button::custom(card)
+            .on_press_maybe(on_press)
+            .padding(0)
+            .class(table_row_button_style())
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn running_game_card<'a>(&'a self, entry: &'a ProcessEntry) -> Element<'a, Message> {
+        let stat = |label: String, value: String| {
+            widget::row::with_capacity(2)
+                .push(widget::text(label).size(12).width(Length::Fill))
+                .push(widget::text(value).size(12))
+        };
+        let gpu = self.game_gpu_percent(&entry.app_id).map_or_else(
+            || fl!("gpu-not-available"),
+            |percent| units::format_percent(percent, 1),
+        );
+        let session = self
+            .session_playtime(&entry.app_id)
+            .map_or(0, |session| session.as_secs());
+
+        let content = widget::column::with_capacity(7)
+            .push(Self::game_cover(entry.icon_handle.clone()))
+            .push(widget::text(entry.display_name.clone()).size(18))
+            .push(stat(
+                self.cpu_column_label(),
+                units::format_percent(self.display_cpu(entry), 1),
+            ))
+            .push(stat(fl!("table-gpu"), gpu))
+            .push(stat(fl!("table-ram"), units::format_bytes(entry.rss_bytes)))
+            .push(stat(fl!("playtime-session"), format_playtime(session)))
+            .push(
+                widget::button::destructive(fl!("process-action-kill"))
+                    .on_press(Message::Games(GamesMessage::Kill(entry.app_id.clone())))
+                    .width(Length::Fill),
+            );
+        self.game_card(
+            content,
+            Some(Message::PressProcessRow {
+                app_id: entry.app_id.clone(),
+                display_name: entry.display_name.clone(),
+                pid: entry.pid,
+            }),
+        )
+    }
+
+    fn recent_game_card<'a>(&'a self, record: &'a GamePlaytime) -> Element<'a, Message> {
+        let name = if record.name.trim().is_empty() {
+            record.app_id.clone()
+        } else {
+            record.name.clone()
+        };
+        let content = widget::column::with_capacity(4)
+            .push(Self::game_cover(self.recent_game_icon(&record.app_id)))
+            .push(widget::text(name).size(18))
+            .push(
+                widget::text(fl!(
+                    "games-total-playtime",
+                    playtime = format_playtime(record.total_secs)
+                ))
+                .size(12),
+            )
+            .push(
+                widget::button::suggested(fl!("games-launch"))
+                    .on_press(Message::Games(GamesMessage::Launch(record.app_id.clone())))
+                    .width(Length::Fill),
+            );
+        self.game_card(content, None)
+    }
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Info page: a read-only summary of the system and its hardware.
+
+use super::*;
+use crate::app::system_info::{MemoryModule, MemoryModules};
+
+pub struct InfoPage {
+    pub(in crate::app) memory_modules: MemoryModules,
+}
+
+impl Default for InfoPage {
+    fn default() -> Self {
+        Self {
+            memory_modules: MemoryModules::NotRead,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum InfoMessage {
+    /// Reads the memory modules, through `pkexec` if `true`.
+    ReadMemoryModules(bool),
+    MemoryModulesRead(MemoryModules),
+}
+
+fn info_item<'a>(label: String, value: String) -> Element<'a, Message> {
+    widget::settings::item(label, widget::text(value)).into()
+}
+
+fn memory_module_label(module: &MemoryModule) -> String {
+    [
+        Some(module.size.clone()),
+        module.kind.clone(),
+        module.speed.clone(),
+        module.manufacturer.clone(),
+        module.part_number.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" · ")
+}
+
+impl AppModel {
+    pub(in crate::app) fn update_info_page(
+        &mut self,
+        message: InfoMessage,
+    ) -> Task<cosmic::Action<Message>> {
+        match message {
+            InfoMessage::ReadMemoryModules(privileged) => {
+                return self.request_memory_modules(privileged);
+            }
+            InfoMessage::MemoryModulesRead(modules) => self.info_page.memory_modules = modules,
+        }
+        Task::none()
+    }
+
+    pub(in crate::app) fn info_view(&self, space_s: u16) -> Element<'_, Message> {
+        let summary = &self.system_summary;
+        let unknown = || fl!("gpu-not-available");
+        let system = widget::settings::section()
+            .title(fl!("info-system"))
+            .add(info_item(
+                fl!("info-distribution"),
+                summary.distribution.clone().unwrap_or_else(unknown),
+            ))
+            .add(info_item(
+                fl!("info-kernel"),
+                summary.kernel.clone().unwrap_or_else(unknown),
+            ))
+            .add(info_item(
+                fl!("info-host-name"),
+                summary.host_name.clone().unwrap_or_else(unknown),
+            ))
+            .add(info_item(
+                fl!("info-uptime"),
+                Self::format_uptime(System::uptime()),
+            ))
+            .add(info_item(fl!("info-boot-time"), self.boot_time_label()));
+
+        let gpu = &self.gpu_runtime_info;
+        let hardware = widget::settings::section()
+            .title(fl!("info-hardware"))
+            .add(info_item(
+                fl!("info-cpu"),
+                self.cpu_topology.model.clone().unwrap_or_else(unknown),
+            ))
+            .add(info_item(
+                fl!("info-architecture"),
+                summary.architecture.clone(),
+            ))
+            .add(info_item(
+                fl!("info-gpu"),
+                fl!(
+                    "info-gpu-value",
+                    name = gpu.name.as_str(),
+                    driver = gpu.driver.as_str()
+                ),
+            ))
+            .add(info_item(
+                fl!("info-memory"),
+                units::format_bytes(self.system.total_memory()),
+            ));
+
+        let content = widget::column::with_capacity(4)
+            .push(widget::text::title2(fl!("nav-info")))
+            .push(system)
+            .push(hardware)
+            .push(self.memory_modules_section())
+            .spacing(space_s)
+            .width(Length::Fill);
+
+        widget::container(widget::scrollable(content).height(Length::Fill))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn memory_modules_section(&self) -> Element<'_, Message> {
+        let section = widget::settings::section().title(fl!("info-memory-modules"));
+        let read = |label: String, privileged: bool| {
+            widget::button::standard(label)
+                .on_press(Message::Info(InfoMessage::ReadMemoryModules(privileged)))
+        };
+        let section = match &self.info_page.memory_modules {
+            MemoryModules::NotRead => section.add(widget::settings::item(
+                fl!("info-memory-modules-not-read"),
+                read(fl!("info-memory-modules-read"), false),
+            )),
+            MemoryModules::Reading => section.add(widget::text(fl!("info-memory-modules-reading"))),
+            MemoryModules::Read(modules) if modules.is_empty() => {
+                section.add(widget::text(fl!("info-memory-modules-none")))
+            }
+            MemoryModules::Read(modules) => modules.iter().fold(section, |section, module| {
+                section.add(info_item(
+                    module.locator.clone(),
+                    memory_module_label(module),
+                ))
+            }),
+            MemoryModules::Denied => section.add(widget::settings::item(
+                fl!("info-memory-modules-denied"),
+                read(fl!("info-memory-modules-read-privileged"), true),
+            )),
+            MemoryModules::Failed(error) => section.add(widget::text(fl!(
+                "info-memory-modules-failed",
+                error = error.as_str()
+            ))),
+        };
+        section.into()
+    }
+}
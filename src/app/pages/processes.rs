@@ -0,0 +1,887 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Processes page: the running apps table and tiles.
+
+use super::*;
+use crate::app::search::{SearchQuery, parse_search_query};
+use crate::config::CpuPercentMode;
+
+const BASELINE_TOP_CHANGES: usize = 8;
+
+fn apps_scroll_id() -> widget::Id {
+    widget::Id::new("apps-scroll")
+}
+
+pub struct ProcessesPage {
+    pub(in crate::app) group_by: GroupBy,
+    group_by_labels: Vec<String>,
+    desktop_expanded: bool,
+    background_expanded: bool,
+    pub(in crate::app) sort_state: SortState,
+    baseline: Option<ProcessBaseline>,
+    search: String,
+    /// App ids matching the search, or `None` while the search is empty.
+    search_matches: Option<HashSet<String>>,
+}
+
+impl Default for ProcessesPage {
+    fn default() -> Self {
+        Self {
+            group_by: GroupBy::App,
+            group_by_labels: GroupBy::ALL
+                .iter()
+                .map(|group_by| group_by.label())
+                .collect(),
+            desktop_expanded: true,
+            background_expanded: false,
+            sort_state: SortState {
+                column: SortColumn::Ram,
+                direction: SortDirection::Desc,
+            },
+            baseline: None,
+            search: String::new(),
+            search_matches: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ProcessesMessage {
+    SetGroupBy(GroupBy),
+    ToggleCategoryFilter(String),
+    ClearCategoryFilter,
+    ToggleDesktopSection,
+    ToggleBackgroundSection,
+    ToggleSort(SortColumn),
+    MarkBaseline,
+    ClearBaseline,
+    SetSearch(String),
+}
+
+impl ProcessesPage {
+    pub(in crate::app) fn search_query(&self) -> Option<SearchQuery> {
+        parse_search_query(&self.search)
+    }
+}
+
+impl AppModel {
+    pub(in crate::app) fn update_processes_page(&mut self, message: ProcessesMessage) {
+        match message {
+            ProcessesMessage::SetGroupBy(group_by) => {
+                if group_by == GroupBy::User {
+                    self.users.refresh();
+                }
+                self.processes_page.group_by = group_by;
+                // Group keys change with the grouping, so an old baseline would match nothing.
+                self.processes_page.baseline = None;
+                self.refresh_processes();
+            }
+            ProcessesMessage::ToggleCategoryFilter(category) => {
+                self.toggle_category_filter(category);
+            }
+            ProcessesMessage::ClearCategoryFilter => self.set_category_filter(Vec::new()),
+            ProcessesMessage::ToggleDesktopSection => {
+                self.processes_page.desktop_expanded = !self.processes_page.desktop_expanded;
+            }
+            ProcessesMessage::ToggleBackgroundSection => {
+                self.processes_page.background_expanded = !self.processes_page.background_expanded;
+            }
+            ProcessesMessage::ToggleSort(column) => self.toggle_sort(column),
+            ProcessesMessage::MarkBaseline => {
+                self.processes_page.baseline = Some(ProcessBaseline::capture(
+                    &self.process_entries,
+                    self.system.global_cpu_usage(),
+                    self.system.used_memory(),
+                ));
+            }
+            ProcessesMessage::ClearBaseline => self.processes_page.baseline = None,
+            ProcessesMessage::SetSearch(search) => {
+                self.processes_page.search = search;
+                self.refresh_search_matches();
+            }
+        }
+    }
+
+    /// Matches the search against the latest entries, with the last lookup for queries
+    /// that need one.
+    pub(in crate::app) fn refresh_search_matches(&mut self) {
+        self.processes_page.search_matches = self
+            .processes_page
+            .search_query()
+            .map(|query| self.search_matches(&query));
+    }
+
+    pub(in crate::app) fn processes_view(&self, space_s: u16) -> Element<'_, Message> {
+        if self.comparison.is_open() {
+            self.comparison_view(space_s)
+        } else if self.config.split_view {
+            self.split_view(space_s)
+        } else {
+            self.apps_list_view(space_s)
+        }
+    }
+
+    pub(in crate::app) fn apps_list_view(&self, space_s: u16) -> Element<'_, Message> {
+        let page = &self.processes_page;
+        let group_by_index = GroupBy::ALL
+            .iter()
+            .position(|group_by| *group_by == page.group_by);
+        let mut header = widget::row::with_capacity(5)
+            .push(widget::text::title2(fl!(
+                "apps-title",
+                count = self.process_entries.len()
+            )))
+            .push(widget::horizontal_space())
+            .push(
+                widget::search_input(fl!("search-placeholder"), page.search.as_str())
+                    .on_input(|search| Message::Processes(ProcessesMessage::SetSearch(search)))
+                    .on_clear(Message::Processes(ProcessesMessage::SetSearch(
+                        String::new(),
+                    )))
+                    .width(Length::Fixed(280.0)),
+            );
+        if page.baseline.is_some() {
+            header = header.push(
+                widget::button::standard(fl!("baseline-clear"))
+                    .on_press(Message::Processes(ProcessesMessage::ClearBaseline)),
+            );
+        }
+        let header = header
+            .push(
+                widget::button::standard(fl!("baseline-mark"))
+                    .on_press(Message::Processes(ProcessesMessage::MarkBaseline)),
+            )
+            .push(widget::dropdown(
+                &page.group_by_labels,
+                group_by_index,
+                |index| Message::Processes(ProcessesMessage::SetGroupBy(GroupBy::ALL[index])),
+            ))
+            .align_y(Alignment::Center)
+            .spacing(space_s);
+
+        let visible_entries = self
+            .process_entries
+            .iter()
+            .filter(|entry| self.matches_category_filter(entry))
+            .filter(|entry| {
+                page.search_matches
+                    .as_ref()
+                    .is_none_or(|matches| matches.contains(&entry.app_id))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut content = widget::column::with_capacity(4)
+            .push(header)
+            .spacing(space_s)
+            .width(Length::Fill);
+        if page.group_by != GroupBy::User {
+            content = content.push(self.category_filter_chips(space_s));
+        }
+        if let Some(baseline) = page.baseline.as_ref() {
+            content = content.push(self.baseline_panel(baseline, space_s));
+        }
+
+        if page.group_by == GroupBy::App {
+            let (background_entries, desktop_entries): (Vec<_>, Vec<_>) = visible_entries
+                .into_iter()
+                .partition(|entry| entry.is_background);
+
+            content = content
+                .push(self.apps_section(
+                    fl!("autostart-desktop-apps"),
+                    page.desktop_expanded,
+                    Message::Processes(ProcessesMessage::ToggleDesktopSection),
+                    &desktop_entries,
+                    space_s,
+                ))
+                .push(self.apps_section(
+                    fl!("autostart-background-apps"),
+                    page.background_expanded,
+                    Message::Processes(ProcessesMessage::ToggleBackgroundSection),
+                    &background_entries,
+                    space_s,
+                ));
+        } else {
+            content = content.push(self.apps_section(
+                page.group_by.label(),
+                page.desktop_expanded,
+                Message::Processes(ProcessesMessage::ToggleDesktopSection),
+                &visible_entries,
+                space_s,
+            ));
+        }
+
+        widget::container(
+            widget::scrollable(content)
+                .id(apps_scroll_id())
+                .height(Length::Fill),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    /// Expands the section holding `entry` and scrolls the table to roughly its row.
+    pub(in crate::app) fn scroll_to_app(
+        &mut self,
+        entry: &ProcessEntry,
+    ) -> Task<cosmic::Action<Message>> {
+        if entry.is_background {
+            self.processes_page.background_expanded = true;
+        } else {
+            self.processes_page.desktop_expanded = true;
+        }
+
+        // Desktop apps are listed above background apps.
+        let (background, desktop): (Vec<_>, Vec<_>) = self
+            .process_entries
+            .iter()
+            .filter(|candidate| self.matches_category_filter(candidate))
+            .partition(|candidate| candidate.is_background);
+        let ordered = desktop.into_iter().chain(background).collect::<Vec<_>>();
+        let Some(index) = ordered
+            .iter()
+            .position(|candidate| candidate.app_id == entry.app_id)
+        else {
+            return Task::none();
+        };
+
+        let y = index as f32 / ordered.len().saturating_sub(1).max(1) as f32;
+        cosmic::iced::widget::scrollable::snap_to(
+            apps_scroll_id(),
+            cosmic::iced::widget::scrollable::RelativeOffset { x: 0.0, y },
+        )
+    }
+
+    fn baseline_panel(&self, baseline: &ProcessBaseline, space_s: u16) -> Element<'_, Message> {
+        let diff = baseline.diff(
+            &self.process_entries,
+            self.system.global_cpu_usage(),
+            self.system.used_memory(),
+        );
+
+        let mut panel = widget::column::with_capacity(4)
+            .push(
+                widget::text(fl!(
+                    "baseline-title",
+                    elapsed = Self::format_uptime(diff.elapsed.as_secs())
+                ))
+                .size(14),
+            )
+            .push(widget::text(fl!(
+                "baseline-summary",
+                cpu = units::format_percent_signed(diff.cpu_delta, 1),
+                ram = units::format_bytes_signed(diff.ram_delta)
+            )))
+            .spacing(space_s / 2)
+            .width(Length::Fill);
+
+        let changed_rows = diff
+            .apps
+            .into_iter()
+            .filter(|app| app.is_new || app.ram_delta != 0 || app.cpu_delta.abs() >= 0.1)
+            .take(BASELINE_TOP_CHANGES)
+            .map(|app| {
+                let name = if app.is_new {
+                    fl!("baseline-new-app", name = app.display_name)
+                } else {
+                    app.display_name
+                };
+                widget::row::with_capacity(3)
+                    .push(widget::text(name).width(Length::Fill))
+                    .push(
+                        widget::text(units::format_percent_signed(app.cpu_delta, 1))
+                            .width(Length::Fixed(80.0))
+                            .align_x(Horizontal::Right),
+                    )
+                    .push(
+                        widget::text(units::format_bytes_signed(app.ram_delta))
+                            .width(Length::Fixed(100.0))
+                            .align_x(Horizontal::Right),
+                    )
+                    .spacing(space_s)
+                    .into()
+            })
+            .collect::<Vec<Element<'_, Message>>>();
+        if !changed_rows.is_empty() {
+            panel = panel.push(widget::column::with_children(changed_rows).spacing(4));
+        }
+
+        if !diff.exited.is_empty() {
+            panel = panel.push(
+                widget::text(fl!("baseline-exited", names = diff.exited.join(", "))).size(12),
+            );
+        }
+
+        widget::container(panel)
+            .padding([10, 12])
+            .class(theme::Container::custom(|theme| widget::container::Style {
+                border: Border {
+                    color: theme.cosmic().accent_color().into(),
+                    width: 1.0,
+                    radius: 10.0.into(),
+                },
+                ..Default::default()
+            }))
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn apps_section(
+        &self,
+        title: String,
+        expanded: bool,
+        toggle_message: Message,
+        entries: &[ProcessEntry],
+        space_s: u16,
+    ) -> Element<'_, Message> {
+        let arrow_icon_name = if expanded {
+            "pan-down-symbolic"
+        } else {
+            "pan-end-symbolic"
+        };
+        let section_title = format!("{title} ({})", entries.len());
+
+        let header_button = widget::button::custom(
+            widget::row::with_capacity(3)
+                .push(widget::text(section_title).size(14))
+                .push(
+                    widget::icon::from_name(arrow_icon_name)
+                        .icon()
+                        .size(16)
+                        .class(theme::Svg::custom(|theme| {
+                            cosmic::iced_widget::svg::Style {
+                                color: Some(theme.cosmic().accent_color().into()),
+                            }
+                        })),
+                )
+                .push(widget::horizontal_space())
+                .spacing(8)
+                .width(Length::Fill)
+                .align_y(Alignment::Center),
+        )
+        .on_press(toggle_message)
+        .padding(0)
+        .class(section_toggle_button_style())
+        .width(Length::Fill);
+
+        let mut section = widget::column::with_capacity(2)
+            .push(
+                widget::container(header_button)
+                    .padding(10)
+                    .width(Length::Fill),
+            )
+            .spacing(space_s);
+
+        if expanded {
+            section = section.push(match self.apps_view_mode {
+                AppsViewMode::List => self.apps_table(entries, space_s),
+                AppsViewMode::Tile => self.apps_tiles(entries, space_s),
+            });
+        }
+
+        section.into()
+    }
+
+    fn apps_table(&self, entries: &[ProcessEntry], space_s: u16) -> Element<'_, Message> {
+        let cell_padding = self.config.density.cell_padding();
+        let row_icon_size = self.config.density.row_icon_size();
+        let owned_entries = entries.to_vec();
+        let entry_count = owned_entries.len();
+
+        let custom_columns = &self.config.custom_columns;
+
+        let list_headers = widget::row::with_capacity(9 + custom_columns.len())
+            .push(self.sortable_header(fl!("table-name"), SortColumn::Name, 6))
+            .push(self.sortable_header(self.cpu_column_label(), SortColumn::Cpu, 2))
+            .push(self.sortable_header(fl!("table-pid"), SortColumn::Pid, 2))
+            .push(self.sortable_header(fl!("table-ram"), SortColumn::Ram, 2))
+            .push(self.sortable_header(fl!("table-swap"), SortColumn::Swap, 2))
+            .push(self.sortable_header(fl!("table-threads"), SortColumn::Threads, 2))
+            .push_maybe(self.config.show_encoder_column.then(|| {
+                widget::container(widget::text(fl!("table-encoder")))
+                    .padding(cell_padding)
+                    .class(theme::Container::custom(table_cell_style))
+                    .width(Length::FillPortion(2))
+            }))
+            .push_maybe(self.config.show_wakeups_column.then(|| {
+                widget::container(widget::text(fl!("table-wakeups")))
+                    .padding(cell_padding)
+                    .class(theme::Container::custom(table_cell_style))
+                    .width(Length::FillPortion(2))
+            }))
+            .push_maybe(self.config.show_fd_column.then(|| {
+                widget::container(widget::text(fl!("table-fds")))
+                    .padding(cell_padding)
+                    .class(theme::Container::custom(table_cell_style))
+                    .width(Length::FillPortion(2))
+            }));
+        // Custom columns are not sortable, so their headers are plain labels.
+        let list_headers = custom_columns
+            .iter()
+            .fold(list_headers, |row, column| {
+                row.push(
+                    widget::container(widget::text(column.name.clone()))
+                        .padding(cell_padding)
+                        .class(theme::Container::custom(table_cell_style))
+                        .width(Length::FillPortion(2)),
+                )
+            })
+            .spacing(0);
+
+        let rows: Element<'_, Message> = if owned_entries.is_empty() {
+            widget::container(widget::text(fl!("autostart-section-empty")))
+                .padding(cell_padding)
+                .class(theme::Container::custom(table_cell_style))
+                .width(Length::Fill)
+                .into()
+        } else {
+            owned_entries
+                .into_iter()
+                .fold(
+                    widget::column::with_capacity(entry_count),
+                    |column, process| {
+                        let name_cell_content: Element<'_, Message> =
+                            if let Some(icon_handle) = process.icon_handle.as_ref() {
+                                widget::row::with_capacity(2)
+                                .push(icon::icon(icon_handle.clone()).size(row_icon_size))
+                                .push(
+                                    widget::text(process.display_name.clone())
+                                        .width(Length::Fill)
+                                        .wrapping(cosmic::iced::widget::text::Wrapping::None)
+                                        .ellipsize(cosmic::iced::widget::text::Ellipsize::End(
+                                            cosmic::iced_core::text::EllipsizeHeightLimit::Lines(1),
+                                        )),
+                                )
+                                .align_y(Alignment::Center)
+                                .spacing(space_s)
+                                .width(Length::Fill)
+                                .into()
+                            } else {
+                                widget::text(process.display_name.clone())
+                                    .width(Length::Fill)
+                                    .wrapping(cosmic::iced::widget::text::Wrapping::None)
+                                    .ellipsize(cosmic::iced::widget::text::Ellipsize::End(
+                                        cosmic::iced_core::text::EllipsizeHeightLimit::Lines(1),
+                                    ))
+                                    .into()
+                            };
+
+                        let name_cell_content: Element<'_, Message> =
+                            match self.status_badges(&process) {
+                                Some(badge) => widget::row::with_capacity(2)
+                                    .push(name_cell_content)
+                                    .push(badge)
+                                    .align_y(Alignment::Center)
+                                    .spacing(space_s)
+                                    .width(Length::Fill)
+                                    .into(),
+                                None => name_cell_content,
+                            };
+
+                        let name_tooltip = Self::process_path_tooltip_text(
+                            process.display_name.clone(),
+                            process.exe_path.as_deref(),
+                        );
+                        let pid_tooltip = Self::process_path_tooltip_text(
+                            fl!("process-pid", pid = process.pid),
+                            process.exe_path.as_deref(),
+                        );
+
+                        let cells = widget::row::with_capacity(9 + custom_columns.len())
+                            .push(
+                                widget::container(Self::text_tooltip(
+                                    name_cell_content,
+                                    name_tooltip,
+                                ))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(6)),
+                            )
+                            .push(
+                                widget::container(Self::text_tooltip(
+                                    widget::text(units::format_percent(
+                                        self.display_cpu(&process),
+                                        1,
+                                    )),
+                                    units::format_percent(self.display_cpu(&process), 3),
+                                ))
+                                .padding(cell_padding)
+                                .class(usage_bar_class(
+                                    process.cpu_percent / 100.0,
+                                    CPU_ACCENT,
+                                    self.value_flash_tint(&process.app_id, FlashColumn::Cpu),
+                                ))
+                                .width(Length::FillPortion(2)),
+                            )
+                            .push(
+                                widget::container(Self::text_tooltip(
+                                    widget::text(process.pid.to_string()),
+                                    pid_tooltip,
+                                ))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(2)),
+                            )
+                            .push(
+                                widget::container(self.ram_cell(&process))
+                                    .padding(cell_padding)
+                                    .class(usage_bar_class(
+                                        self.memory_share(process.rss_bytes),
+                                        RAM_ACCENT,
+                                        self.value_flash_tint(&process.app_id, FlashColumn::Ram),
+                                    ))
+                                    .width(Length::FillPortion(2)),
+                            )
+                            .push(
+                                widget::container(Self::exact_bytes_tooltip(
+                                    widget::text(units::format_bytes(process.swap_bytes)),
+                                    process.swap_bytes,
+                                ))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(2)),
+                            )
+                            .push(
+                                widget::container(Self::text_tooltip(
+                                    widget::text(process.threads.to_string()),
+                                    fl!("threads-exact", count = process.threads),
+                                ))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(2)),
+                            )
+                            .push_maybe(self.config.show_encoder_column.then(|| {
+                                widget::container(widget::text(
+                                    process.encoder_percent.map_or_else(
+                                        || "—".to_string(),
+                                        |usage| units::format_percent(usage, 0),
+                                    ),
+                                ))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(2))
+                            }))
+                            .push_maybe(self.config.show_wakeups_column.then(|| {
+                                widget::container(widget::text(
+                                    process.wakeups_per_sec.map_or_else(
+                                        || "—".to_string(),
+                                        |rate| units::format_decimal(f64::from(rate), 0),
+                                    ),
+                                ))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(2))
+                            }))
+                            .push_maybe(self.config.show_fd_column.then(|| {
+                                widget::container(Self::fd_cell(process.fds))
+                                    .padding(cell_padding)
+                                    .class(theme::Container::custom(table_cell_style))
+                                    .width(Length::FillPortion(2))
+                            }));
+                        let cells = process.custom_values.iter().fold(cells, |row, value| {
+                            row.push(
+                                widget::container(widget::text(
+                                    value.map_or_else(|| "—".to_string(), units::format_number),
+                                ))
+                                .padding(cell_padding)
+                                .class(theme::Container::custom(table_cell_style))
+                                .width(Length::FillPortion(2)),
+                            )
+                        });
+
+                        let tint = self.highlight_tint(&process);
+                        let cells = widget::container(cells.spacing(0).width(Length::Fill)).class(
+                            theme::Container::custom(move |_theme| widget::container::Style {
+                                background: tint.map(Background::Color),
+                                ..Default::default()
+                            }),
+                        );
+
+                        column.push(
+                            widget::button::custom(cells)
+                                .on_press_maybe(
+                                    (self.processes_page.group_by == GroupBy::App).then(|| {
+                                        Message::PressProcessRow {
+                                            app_id: process.app_id,
+                                            display_name: process.display_name,
+                                            pid: process.pid,
+                                        }
+                                    }),
+                                )
+                                .padding(0)
+                                .class(table_row_button_style())
+                                .width(Length::Fill),
+                        )
+                    },
+                )
+                .into()
+        };
+
+        widget::column::with_capacity(2)
+            .push(list_headers)
+            .push(rows)
+            .spacing(0)
+            .width(Length::Fill)
+            .into()
+    }
+
+    fn apps_tiles(&self, entries: &[ProcessEntry], space_s: u16) -> Element<'_, Message> {
+        let density = self.config.density;
+        let tile_icon_size = density.tile_icon_size();
+        let owned_entries = entries.to_vec();
+        if owned_entries.is_empty() {
+            return widget::container(widget::text(fl!("autostart-section-empty")))
+                .padding(density.cell_padding())
+                .class(theme::Container::custom(table_cell_style))
+                .width(Length::Fill)
+                .into();
+        }
+
+        let tiles: Vec<Element<'_, Message>> = owned_entries
+            .into_iter()
+            .map(|process| {
+                let icon_content: Element<'_, Message> =
+                    if let Some(icon_handle) = process.icon_handle.as_ref() {
+                        icon::icon(icon_handle.clone()).size(tile_icon_size).into()
+                    } else {
+                        widget::container(widget::text(""))
+                            .width(Length::Fixed(f32::from(tile_icon_size)))
+                            .into()
+                    };
+
+                let tile_name = process.display_name.clone();
+                let tile_app_id = process.app_id.clone();
+                let tile_pid = process.pid;
+
+                let name_tooltip =
+                    Self::process_path_tooltip_text(tile_name.clone(), process.exe_path.as_deref());
+
+                let details = widget::column::with_capacity(5 + process.custom_values.len())
+                    .push(
+                        widget::row::with_capacity(2)
+                            .push(Self::text_tooltip(
+                                widget::text(tile_name.clone())
+                                    .size(20)
+                                    .width(Length::Fill)
+                                    .wrapping(cosmic::iced::widget::text::Wrapping::None)
+                                    .ellipsize(cosmic::iced::widget::text::Ellipsize::End(
+                                        cosmic::iced_core::text::EllipsizeHeightLimit::Lines(1),
+                                    )),
+                                name_tooltip,
+                            ))
+                            .push_maybe(self.status_badges(&process))
+                            .align_y(Alignment::Center)
+                            .spacing(8),
+                    )
+                    .push(widget::text(format!("{}: {}", fl!("table-pid"), tile_pid)).size(12))
+                    .push(Self::text_tooltip(
+                        widget::text(format!(
+                            "{}: {}",
+                            self.cpu_column_label(),
+                            units::format_percent(self.display_cpu(&process), 1)
+                        ))
+                        .size(12),
+                        units::format_percent(self.display_cpu(&process), 3),
+                    ))
+                    .push(Self::exact_bytes_tooltip(
+                        widget::text(format!(
+                            "{}: {}",
+                            fl!("table-ram"),
+                            units::format_bytes(process.rss_bytes)
+                        ))
+                        .size(12),
+                        process.rss_bytes,
+                    ))
+                    .push(
+                        widget::text(format!("{}: {}", fl!("table-threads"), process.threads))
+                            .size(12),
+                    );
+                let details = self
+                    .config
+                    .custom_columns
+                    .iter()
+                    .zip(&process.custom_values)
+                    .fold(details, |details, (column, value)| {
+                        let value = value.map_or_else(|| "—".to_string(), units::format_number);
+                        details.push(widget::text(format!("{}: {value}", column.name)).size(12))
+                    })
+                    .spacing(6)
+                    .width(Length::Fill);
+
+                let tint = self.highlight_tint(&process);
+                let tile_content = widget::container(
+                    widget::row::with_capacity(2)
+                        .push(
+                            widget::container(icon_content)
+                                .center_x(Length::Fixed(f32::from(tile_icon_size))),
+                        )
+                        .push(details)
+                        .spacing(25)
+                        .align_y(Alignment::Center)
+                        .width(Length::Fill),
+                )
+                .padding(density.cell_padding() + 2)
+                .class(theme::Container::custom(move |theme| {
+                    let mut style = table_cell_style(theme);
+                    style.background = tint.map(Background::Color);
+                    style
+                }))
+                .width(Length::Fill);
+
+                widget::container(
+                    widget::button::custom(tile_content)
+                        .on_press_maybe((self.processes_page.group_by == GroupBy::App).then(|| {
+                            Message::PressProcessRow {
+                                app_id: tile_app_id,
+                                display_name: tile_name,
+                                pid: tile_pid,
+                            }
+                        }))
+                        .padding(0)
+                        .class(table_row_button_style())
+                        .width(Length::Fill),
+                )
+                .width(Length::Fill)
+                .into()
+            })
+            .collect();
+
+        widget::container(
+            widget::flex_row(tiles)
+                .spacing(space_s)
+                .min_item_width(320.0)
+                .width(Length::Fill),
+        )
+        .width(Length::Fill)
+        .height(Length::Shrink)
+        .into()
+    }
+
+    /// CPU usage in the configured mode. Entries keep the machine share, and scaling by the
+    /// core count keeps the sort order the same in both modes.
+    pub(in crate::app) fn display_cpu(&self, entry: &ProcessEntry) -> f32 {
+        match self.config.cpu_percent_mode {
+            CpuPercentMode::Machine => entry.cpu_percent,
+            CpuPercentMode::Core => entry.cpu_percent * self.system.cpus().len().max(1) as f32,
+        }
+    }
+
+    pub(in crate::app) fn cpu_column_label(&self) -> String {
+        match self.config.cpu_percent_mode {
+            CpuPercentMode::Machine => fl!("table-cpu"),
+            CpuPercentMode::Core => fl!("table-cpu-per-core"),
+        }
+    }
+
+    /// Origin, audio, privacy, game, and update indicators shown next to an app's name.
+    fn status_badges(&self, entry: &ProcessEntry) -> Option<Element<'static, Message>> {
+        let mut icon_names = self.state_badge_icons(entry);
+        icon_names.extend(self.restart_badge_icons(entry));
+        icon_names.extend(self.paused_badge_icons(entry));
+        icon_names.extend(self.privacy_badge_icons(entry));
+        icon_names.extend(self.game_badge_icons(entry));
+        icon_names.extend(self.steam_maintenance_badge_icons(entry));
+        if self.is_playing_audio(entry) {
+            icon_names.insert(0, "audio-volume-high-symbolic");
+        }
+        if self.has_pending_update(entry) {
+            icon_names.push("software-update-available-symbolic");
+        }
+        let origin_badge = (self.processes_page.group_by == GroupBy::App)
+            .then(|| self.origin_badge(entry))
+            .flatten();
+        let highlight_badge = self.highlight_badge(entry);
+        if icon_names.is_empty() && origin_badge.is_none() && highlight_badge.is_none() {
+            return None;
+        }
+
+        let badges = highlight_badge
+            .into_iter()
+            .chain(origin_badge)
+            .chain(
+                icon_names
+                    .into_iter()
+                    .map(|icon_name| widget::icon::from_name(icon_name).icon().size(16).into()),
+            )
+            .collect::<Vec<Element<'static, Message>>>();
+        Some(
+            widget::row::with_children(badges)
+                .align_y(Alignment::Center)
+                .spacing(4)
+                .into(),
+        )
+    }
+
+    /// The RAM column's cell: the resident memory, with an arrow while it keeps growing.
+    fn ram_cell(&self, entry: &ProcessEntry) -> Element<'static, Message> {
+        let size = widget::text(units::format_bytes(entry.rss_bytes));
+        let exact = fl!(
+            "bytes-exact",
+            bytes = units::format_bytes_grouped(entry.rss_bytes)
+        );
+        let Some(growth) = self.memory_growth_indicator(entry) else {
+            return Self::text_tooltip(size, exact);
+        };
+        Self::text_tooltip(
+            widget::row::with_capacity(2)
+                .push(size)
+                .push(widget::icon::from_name("go-up-symbolic").icon().size(16))
+                .align_y(Alignment::Center)
+                .spacing(4),
+            format!("{exact}\n{growth}"),
+        )
+    }
+
+    /// The FD column's cell: the count, with a warning badge once a member nears its limit.
+    fn fd_cell(usage: Option<FdUsage>) -> Element<'static, Message> {
+        let Some(usage) = usage else {
+            return widget::text("—").into();
+        };
+        let count = widget::text(units::format_number(f64::from(usage.count)));
+        let Some((count_near_limit, limit)) = usage.near_limit else {
+            return count.into();
+        };
+        Self::text_tooltip(
+            widget::row::with_capacity(2)
+                .push(count)
+                .push(
+                    widget::icon::from_name("dialog-warning-symbolic")
+                        .icon()
+                        .size(16),
+                )
+                .align_y(Alignment::Center)
+                .spacing(4),
+            fl!(
+                "fd-near-limit",
+                count = count_near_limit,
+                limit = limit.to_string()
+            ),
+        )
+    }
+
+    fn text_tooltip<'a>(
+        content: impl Into<Element<'a, Message>>,
+        text: String,
+    ) -> Element<'a, Message> {
+        widget::tooltip(content, widget::text(text), widget::tooltip::Position::Top).into()
+    }
+
+    fn exact_bytes_tooltip<'a>(
+        content: impl Into<Element<'a, Message>>,
+        bytes: u64,
+    ) -> Element<'a, Message> {
+        Self::text_tooltip(
+            content,
+            fl!("bytes-exact", bytes = units::format_bytes_grouped(bytes)),
+        )
+    }
+
+    fn process_path_tooltip_text(title: String, exe_path: Option<&Path>) -> String {
+        match exe_path {
+            Some(path) => format!("{title}\n{}", path.display()),
+            None => title,
+        }
+    }
+}
@@ -1,9 +1,51 @@
 // SPDX-License-Identifier: MPL-2.0
 
+//! Resources page: CPU, memory, GPU, network, and disk usage.
+
 use super::*;
 
+pub struct ResourcesPage {
+    view_mode: PerformanceViewMode,
+}
+
+impl Default for ResourcesPage {
+    fn default() -> Self {
+        Self {
+            view_mode: PerformanceViewMode::Cpu,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ResourcesMessage {
+    SetViewMode(PerformanceViewMode),
+    MountDisk(String),
+    UnmountDisk(String),
+}
+
 impl AppModel {
-    pub(super) fn performance_view(&self, space_s: u16) -> Element<'_, Message> {
+    pub(in crate::app) fn update_resources_page(&mut self, message: ResourcesMessage) {
+        match message {
+            ResourcesMessage::SetViewMode(mode) => self.resources_page.view_mode = mode,
+            ResourcesMessage::MountDisk(disk_name) => {
+                self.mount_disk(&disk_name);
+                self.refresh_processes();
+            }
+            ResourcesMessage::UnmountDisk(disk_name) => {
+                let is_system_disk = self
+                    .collect_disk_groups()
+                    .into_iter()
+                    .find(|disk| disk.name == disk_name)
+                    .is_some_and(|disk| disk.is_system_disk);
+                if !is_system_disk {
+                    self.unmount_disk(&disk_name);
+                    self.refresh_processes();
+                }
+            }
+        }
+    }
+
+    pub(in crate::app) fn resources_view(&self, space_s: u16) -> Element<'_, Message> {
         let cpu_usage = self.system.global_cpu_usage().clamp(0.0, 100.0);
         let avg_freq_mhz = if self.system.cpus().is_empty() {
             0_u64
@@ -32,33 +74,40 @@ impl AppModel {
 
         let cpu_card = self.performance_selector_card(
             fl!("table-cpu"),
-            format!("{cpu_usage:.1}%"),
+            units::format_percent(cpu_usage, 1),
             Some(format!("{} GHz", Self::format_ghz(current_speed_mhz))),
             CPU_ACCENT,
-            self.performance_view_mode == PerformanceViewMode::Cpu,
-            Some(Message::SetPerformanceViewMode(PerformanceViewMode::Cpu)),
+            self.resources_page.view_mode == PerformanceViewMode::Cpu,
+            Some(Message::Resources(ResourcesMessage::SetViewMode(
+                PerformanceViewMode::Cpu,
+            ))),
         );
         let ram_card = self.performance_selector_card(
             fl!("table-ram"),
             format!(
-                "{} / {} ({ram_usage:.0}%)",
-                Self::format_rss(used_memory),
-                Self::format_rss(total_memory)
+                "{} / {} ({})",
+                units::format_bytes(used_memory),
+                units::format_bytes(total_memory),
+                units::format_percent(ram_usage, 0)
             ),
             None,
             RAM_ACCENT,
-            self.performance_view_mode == PerformanceViewMode::Ram,
-            Some(Message::SetPerformanceViewMode(PerformanceViewMode::Ram)),
+            self.resources_page.view_mode == PerformanceViewMode::Ram,
+            Some(Message::Resources(ResourcesMessage::SetViewMode(
+                PerformanceViewMode::Ram,
+            ))),
         );
         let gpu_card = self.performance_selector_card(
             fl!("table-gpu"),
             gpu_usage
-                .map(|value| format!("{value:.1}%"))
+                .map(|value| units::format_percent(value, 1))
                 .unwrap_or_else(|| fl!("gpu-not-available")),
             None,
             GPU_ACCENT,
-            self.performance_view_mode == PerformanceViewMode::Gpu,
-            Some(Message::SetPerformanceViewMode(PerformanceViewMode::Gpu)),
+            self.resources_page.view_mode == PerformanceViewMode::Gpu,
+            Some(Message::Resources(ResourcesMessage::SetViewMode(
+                PerformanceViewMode::Gpu,
+            ))),
         );
 
         let mut grouped_disks = self.collect_disk_groups();
@@ -84,7 +133,7 @@ impl AppModel {
                 .and_then(|history| history.last().copied())
                 .unwrap_or(0.0);
             let mode = PerformanceViewMode::Network(network.name.clone());
-            let is_selected = self.performance_view_mode == mode;
+            let is_selected = self.resources_page.view_mode == mode;
 
             sidebar = sidebar.push(self.network_selector_card(
                 network.name.clone(),
@@ -100,7 +149,7 @@ impl AppModel {
                 ),
                 network.is_wireless,
                 is_selected,
-                Some(Message::SetPerformanceViewMode(mode)),
+                Some(Message::Resources(ResourcesMessage::SetViewMode(mode))),
             ));
         }
 
@@ -111,27 +160,28 @@ impl AppModel {
                 0.0
             };
             let mode = PerformanceViewMode::Disk(disk.name.clone());
-            let is_selected = self.performance_view_mode == mode;
+            let is_selected = self.resources_page.view_mode == mode;
             let is_usb = disk.kind_label.to_ascii_lowercase().contains("usb");
 
             sidebar = sidebar.push(self.disk_selector_card(
                 format!("Disk {}", disk.name),
                 disk.kind_label.clone(),
                 format!(
-                    "{} / {} ({usage:.0}%)",
-                    Self::format_rss(disk.used_bytes),
-                    Self::format_rss(disk.total_bytes)
+                    "{} / {} ({})",
+                    units::format_bytes(disk.used_bytes),
+                    units::format_bytes(disk.total_bytes),
+                    units::format_percent(usage, 0)
                 ),
                 disk.is_mounted,
                 is_usb,
                 is_selected,
-                Some(Message::SetPerformanceViewMode(mode)),
+                Some(Message::Resources(ResourcesMessage::SetViewMode(mode))),
             ));
         }
 
         let sidebar = sidebar.width(Length::Fill);
 
-        let detail: Element<'_, Message> = match &self.performance_view_mode {
+        let detail: Element<'_, Message> = match &self.resources_page.view_mode {
             PerformanceViewMode::Cpu => self.cpu_detail_panel(cpu_usage, space_s),
             PerformanceViewMode::Ram => {
                 self.ram_detail_panel(used_memory, total_memory, ram_usage, space_s)
@@ -187,7 +237,7 @@ impl AppModel {
             }
         };
 
-        widget::row::with_capacity(2)
+        let panels = widget::row::with_capacity(2)
             .push(
                 widget::container(widget::scrollable(sidebar).height(Length::Fill))
                     .width(Length::FillPortion(2))
@@ -196,6 +246,14 @@ impl AppModel {
             .push(widget::container(detail).width(Length::FillPortion(5)))
             .spacing(space_s)
             .width(Length::Fill)
+            .height(Length::Fill);
+
+        widget::column::with_capacity(3)
+            .push_maybe(self.load_average_header())
+            .push_maybe(self.pressure_header())
+            .push(panels)
+            .spacing(space_s)
+            .width(Length::Fill)
             .height(Length::Fill)
             .into()
     }
@@ -538,11 +596,11 @@ impl AppModel {
             .push(right_line(fl!("network-link-speed"), speed_text))
             .push(right_line(
                 fl!("network-rx-total"),
-                Self::format_rss(interface.rx_bytes),
+                units::format_bytes(interface.rx_bytes),
             ))
             .push(right_line(
                 fl!("network-tx-total"),
-                Self::format_rss(interface.tx_bytes),
+                units::format_bytes(interface.tx_bytes),
             ))
             .push(right_line(fl!("network-state"), fl!("network-active")))
             .spacing(6)
@@ -575,13 +633,23 @@ impl AppModel {
                     .width(Length::Fill),
             )
             .push(widget::text(fl!("network-download-history")).size(14))
-            .push(self.sparkline_solid(&rx_history, NETWORK_ACCENT, 130.0))
+            .push(
+                Chart::new(&rx_history, ChartKind::Area, NETWORK_ACCENT)
+                    .scale(ChartScale::Auto(Self::format_rate_mib))
+                    .axes(true)
+                    .view(130.0),
+            )
             .push(widget::text(fl!("network-upload-history")).size(14))
-            .push(self.sparkline_solid(
-                &tx_history,
-                Color::from_rgb(57.0 / 255.0, 103.0 / 255.0, 150.0 / 255.0),
-                130.0,
-            ))
+            .push(
+                Chart::new(
+                    &tx_history,
+                    ChartKind::Area,
+                    Color::from_rgb(57.0 / 255.0, 103.0 / 255.0, 150.0 / 255.0),
+                )
+                .scale(ChartScale::Auto(Self::format_rate_mib))
+                .axes(true)
+                .view(130.0),
+            )
             .push(widget::Space::with_height(Length::Fixed(24.0)))
             .push(widget::container(stats).width(Length::Fill))
             .spacing(space_s)
@@ -829,12 +897,12 @@ impl AppModel {
             )))
             .push(widget::text(format!("Type: {kind_label}")))
             .push(widget::text(format!(
-                "Aktive Zeit: {:.1}%",
-                runtime_info.active_time_percent
+                "Aktive Zeit: {}",
+                units::format_percent(runtime_info.active_time_percent, 1)
             )))
             .push(widget::text(format!(
-                "Antwortzeit (Durchschnitt): {:.1} ms",
-                runtime_info.avg_response_ms
+                "Antwortzeit (Durchschnitt): {} ms",
+                units::format_decimal(f64::from(runtime_info.avg_response_ms), 1)
             )))
             .spacing(6)
             .width(Length::Fill);
@@ -878,7 +946,7 @@ impl AppModel {
                 widget::column::with_capacity(2)
                     .push(widget::text("Momentan belegt").size(13))
                     .push(
-                        widget::text(Self::format_rss(used))
+                        widget::text(units::format_bytes(used))
                             .size(20)
                             .class(theme::Text::Color(DISK_ACCENT)),
                     )
@@ -890,7 +958,7 @@ impl AppModel {
                 widget::column::with_capacity(2)
                     .push(widget::text("Maximal").size(13))
                     .push(
-                        widget::text(Self::format_rss(total))
+                        widget::text(units::format_bytes(total))
                             .size(20)
                             .class(theme::Text::Color(DISK_ACCENT)),
                     )
@@ -911,13 +979,17 @@ impl AppModel {
             } else {
                 widget::button::standard("Unmount")
                     .class(theme::Button::Suggested)
-                    .on_press(Message::UnmountDisk(disk_name.to_string()))
+                    .on_press(Message::Resources(ResourcesMessage::UnmountDisk(
+                        disk_name.to_string(),
+                    )))
                     .into()
             }
         } else {
             widget::button::standard("Mounten")
                 .class(theme::Button::Suggested)
-                .on_press(Message::MountDisk(disk_name.to_string()))
+                .on_press(Message::Resources(ResourcesMessage::MountDisk(
+                    disk_name.to_string(),
+                )))
                 .into()
         };
 
@@ -940,12 +1012,22 @@ impl AppModel {
             )
             .push(usage_bar)
             .push(usage_labels)
-            .push(self.sparkline_solid(&read_history, DISK_ACCENT, 130.0))
-            .push(self.sparkline_solid(
-                &write_history,
-                Color::from_rgb(158.0 / 255.0, 158.0 / 255.0, 54.0 / 255.0),
-                130.0,
-            ))
+            .push(
+                Chart::new(&read_history, ChartKind::Area, DISK_ACCENT)
+                    .scale(ChartScale::Auto(Self::format_rate_mib))
+                    .axes(true)
+                    .view(130.0),
+            )
+            .push(
+                Chart::new(
+                    &write_history,
+                    ChartKind::Area,
+                    Color::from_rgb(158.0 / 255.0, 158.0 / 255.0, 54.0 / 255.0),
+                )
+                .scale(ChartScale::Auto(Self::format_rate_mib))
+                .axes(true)
+                .view(130.0),
+            )
             .push(io_stats)
             .push(extra_stats)
             .push(widget::text("Partitionen").size(14))
@@ -979,7 +1061,13 @@ impl AppModel {
 
     fn cpu_detail_panel(&self, cpu_usage: f32, space_s: u16) -> Element<'_, Message> {
         let cores = self.system.cpus();
-        let cpu_brand = cores.first().map_or("CPU", |cpu| cpu.brand());
+        let cpu_brand = self
+            .cpu_topology
+            .model
+            .as_deref()
+            .or_else(|| cores.first().map(|cpu| cpu.brand()))
+            .filter(|brand| !brand.trim().is_empty())
+            .unwrap_or("CPU");
         let avg_freq_mhz = if cores.is_empty() {
             0_u64
         } else {
@@ -987,12 +1075,15 @@ impl AppModel {
         };
         let current_speed_mhz = Self::read_current_cpu_speed_mhz().unwrap_or(avg_freq_mhz);
         let base_freq_mhz = cores.iter().map(|cpu| cpu.frequency()).max().unwrap_or(0);
-        let process_count = self.system.processes().len();
-        let thread_count = self
-            .system
-            .processes()
+        let processes = self.process_source.processes();
+        let process_count = processes.len();
+        let thread_count = processes
             .values()
-            .map(|process| process.tasks().map_or(1_usize, |tasks| tasks.len().max(1)))
+            .map(|process| {
+                process
+                    .threads
+                    .map_or(1_usize, |threads| threads.max(1) as usize)
+            })
             .sum::<usize>();
         let logical_cores = cores.len();
         let uptime = Self::format_uptime(System::uptime());
@@ -1032,30 +1123,50 @@ impl AppModel {
                 for (offset, history) in chunk.iter().enumerate() {
                     let index = base_index + offset;
                     let current_usage = cores.get(index).map_or(0.0, |core| core.cpu_usage());
+                    let core_label = self.cpu_topology.hybrid_core_type(index).map_or_else(
+                        || format!("Core {}", index + 1),
+                        |core_type| {
+                            fl!(
+                                "cpu-core-typed",
+                                index = index + 1,
+                                kind = core_type.short_label()
+                            )
+                        },
+                    );
+                    // Cores the selected app's threads last ran on stand out.
+                    let ran_on = self.selected_app_ran_on(index);
 
                     let card = widget::container(
                         widget::column::with_capacity(3)
-                            .push(widget::text(format!("Core {}", index + 1)).size(14))
+                            .push(widget::text(core_label).size(14))
                             .push(
-                                widget::text(format!("{current_usage:.1}%"))
+                                widget::text(units::format_percent(current_usage, 1))
                                     .size(16)
                                     .class(theme::Text::Color(CPU_ACCENT)),
                             )
-                            .push(self.sparkline(history, CPU_ACCENT, graph_height))
+                            .push(
+                                Chart::new(history, ChartKind::Bar, CPU_ACCENT).view(graph_height),
+                            )
                             .spacing(6)
                             .width(Length::Fill),
                     )
                     .padding(10)
-                    .class(theme::Container::custom(|theme| widget::container::Style {
-                        background: Some(Background::Color(
-                            theme.current_container().component.base.into(),
-                        )),
-                        border: Border {
-                            color: theme.cosmic().bg_divider().into(),
-                            width: 1.0,
-                            radius: 8.0.into(),
-                        },
-                        ..Default::default()
+                    .class(theme::Container::custom(move |theme| {
+                        widget::container::Style {
+                            background: Some(Background::Color(
+                                theme.current_container().component.base.into(),
+                            )),
+                            border: Border {
+                                color: if ran_on {
+                                    CPU_ACCENT
+                                } else {
+                                    theme.cosmic().bg_divider().into()
+                                },
+                                width: if ran_on { 2.0 } else { 1.0 },
+                                radius: 8.0.into(),
+                            },
+                            ..Default::default()
+                        }
                     }))
                     .width(Length::FillPortion(1))
                     .height(Length::Fixed(tile_height));
@@ -1086,7 +1197,7 @@ impl AppModel {
             .push(
                 widget::container(stat_block(
                     "Last".to_string(),
-                    format!("{cpu_usage:.0}%"),
+                    units::format_percent(cpu_usage, 0),
                     true,
                 ))
                 .width(Length::FillPortion(1)),
@@ -1148,12 +1259,16 @@ impl AppModel {
                 .width(Length::Shrink)
         };
 
-        let stats_col_2 = widget::column::with_capacity(3)
+        let mut stats_col_2 = widget::column::with_capacity(3)
             .push(right_line(
                 "Base speed",
                 format!("{} GHz", Self::format_ghz(base_freq_mhz)),
             ))
             .push(right_line("Cores", logical_cores.to_string()))
+            .push(right_line(
+                fl!("cpu-architecture").as_str(),
+                env::consts::ARCH.to_string(),
+            ))
             .push(right_line(fl!("stat-temperature").as_str(), cpu_temp_text))
             .push(right_line(
                 "Virtualization",
@@ -1174,6 +1289,38 @@ impl AppModel {
             .spacing(6)
             .width(Length::FillPortion(1));
 
+        // Clock of each core type on hybrid and big.LITTLE CPUs, averaged over its cores.
+        let clusters = self
+            .cpu_topology
+            .clusters
+            .iter()
+            .filter(|_| self.cpu_topology.is_heterogeneous());
+        for cluster in clusters {
+            let current_mhz = cluster
+                .cpus
+                .iter()
+                .filter_map(|cpu| cores.get(*cpu))
+                .map(|core| core.frequency())
+                .sum::<u64>()
+                / cluster.cpus.len().max(1) as u64;
+            let speed = cluster.max_mhz.map_or_else(
+                || format!("{} GHz", Self::format_ghz(current_mhz)),
+                |max_mhz| {
+                    format!(
+                        "{} / {} GHz",
+                        Self::format_ghz(current_mhz),
+                        Self::format_ghz(max_mhz)
+                    )
+                },
+            );
+            let label = fl!(
+                "cpu-cluster",
+                kind = cluster.core_type.label(),
+                count = cluster.cpus.len()
+            );
+            stats_col_2 = stats_col_2.push(right_line(label.as_str(), speed));
+        }
+
         let stats = widget::row::with_capacity(2)
             .push(stats_col_1)
             .push(stats_col_2)
@@ -1246,7 +1393,7 @@ impl AppModel {
             .push(
                 widget::container(stat_block(
                     "In use".to_string(),
-                    Self::format_rss(used_memory),
+                    units::format_bytes(used_memory),
                     true,
                 ))
                 .width(Length::FillPortion(1)),
@@ -1254,7 +1401,7 @@ impl AppModel {
             .push(
                 widget::container(stat_block(
                     "Available".to_string(),
-                    Self::format_rss(available_memory),
+                    units::format_bytes(available_memory),
                     false,
                 ))
                 .width(Length::FillPortion(1)),
@@ -1266,7 +1413,7 @@ impl AppModel {
             .push(
                 widget::container(stat_block(
                     "Cached".to_string(),
-                    Self::format_rss(cached_memory),
+                    units::format_bytes(cached_memory),
                     false,
                 ))
                 .width(Length::FillPortion(1)),
@@ -1277,8 +1424,8 @@ impl AppModel {
                     if total_swap > 0 {
                         format!(
                             "{} / {}",
-                            Self::format_rss(used_swap),
-                            Self::format_rss(total_swap)
+                            units::format_bytes(used_swap),
+                            units::format_bytes(total_swap)
                         )
                     } else {
                         "N/A".to_string()
@@ -1306,7 +1453,7 @@ impl AppModel {
                     .push(widget::text::title1("Memory"))
                     .push(widget::horizontal_space())
                     .push(
-                        widget::text(Self::format_rss(total_memory))
+                        widget::text(units::format_bytes(total_memory))
                             .size(16)
                             .class(theme::Text::Color(RAM_ACCENT)),
                     )
@@ -1314,14 +1461,18 @@ impl AppModel {
                     .width(Length::Fill),
             )
             .push(widget::text("Speicherauslastung").size(14))
-            .push(self.sparkline_solid(&self.ram_usage_history, RAM_ACCENT, 240.0))
+            .push(
+                Chart::new(&self.ram_usage_history, ChartKind::Area, RAM_ACCENT)
+                    .axes(true)
+                    .view(240.0),
+            )
             .push(
                 widget::row::with_capacity(3)
                     .push(
                         widget::column::with_capacity(2)
                             .push(widget::text("Momentan").size(14))
                             .push(
-                                widget::text(Self::format_rss(used_memory))
+                                widget::text(units::format_bytes(used_memory))
                                     .size(20)
                                     .class(theme::Text::Color(RAM_ACCENT)),
                             )
@@ -1332,7 +1483,7 @@ impl AppModel {
                         widget::column::with_capacity(2)
                             .push(widget::text("Maximal").size(14))
                             .push(
-                                widget::text(Self::format_rss(total_memory))
+                                widget::text(units::format_bytes(total_memory))
                                     .size(20)
                                     .class(theme::Text::Color(RAM_ACCENT)),
                             )
@@ -1369,22 +1520,28 @@ impl AppModel {
     }
 
     fn gpu_detail_panel(&self, gpu_usage: Option<f32>, space_s: u16) -> Element<'_, Message> {
-        let usage_text =
-            gpu_usage.map_or_else(|| fl!("gpu-not-available"), |value| format!("{value:.1}%"));
+        let usage_text = gpu_usage.map_or_else(
+            || fl!("gpu-not-available"),
+            |value| units::format_percent(value, 1),
+        );
         let vram_used_text = self
             .gpu_runtime_info
             .vram_used_bytes
-            .map_or_else(|| fl!("gpu-not-available"), Self::format_rss);
+            .map_or_else(|| fl!("gpu-not-available"), units::format_bytes);
         let vram_total_text = self
             .gpu_runtime_info
             .vram_total_bytes
-            .map_or_else(|| fl!("gpu-not-available"), Self::format_rss);
+            .map_or_else(|| fl!("gpu-not-available"), units::format_bytes);
         let vram_combined = match (
             self.gpu_runtime_info.vram_used_bytes,
             self.gpu_runtime_info.vram_total_bytes,
         ) {
             (Some(used), Some(total)) if total > 0 => {
-                format!("{} / {}", Self::format_rss(used), Self::format_rss(total))
+                format!(
+                    "{} / {}",
+                    units::format_bytes(used),
+                    units::format_bytes(total)
+                )
             }
             _ => fl!("gpu-not-available"),
         };
@@ -1541,7 +1698,11 @@ impl AppModel {
         if self.gpu_usage_history.is_empty() {
             panel = panel.push(widget::text(fl!("gpu-monitoring-unavailable")).size(14));
         } else {
-            panel = panel.push(self.sparkline_solid(&self.gpu_usage_history, GPU_ACCENT, 160.0));
+            panel = panel.push(
+                Chart::new(&self.gpu_usage_history, ChartKind::Area, GPU_ACCENT)
+                    .axes(true)
+                    .view(160.0),
+            );
         }
 
         panel = panel.push(widget::text(fl!("gpu-vram-history")).size(14));
@@ -1549,8 +1710,11 @@ impl AppModel {
         if self.gpu_vram_usage_history.is_empty() {
             panel = panel.push(widget::text(fl!("gpu-vram-monitoring-unavailable")).size(14));
         } else {
-            panel =
-                panel.push(self.sparkline_solid(&self.gpu_vram_usage_history, RAM_ACCENT, 140.0));
+            panel = panel.push(
+                Chart::new(&self.gpu_vram_usage_history, ChartKind::Area, RAM_ACCENT)
+                    .axes(true)
+                    .view(140.0),
+            );
         }
 
         panel = panel.push(widget::Space::with_height(Length::Fixed(24.0)));
@@ -1578,128 +1742,4 @@ impl AppModel {
         .height(Length::Fill)
         .into()
     }
-
-    fn sparkline_solid(&self, samples: &[f32], accent: Color, height: f32) -> Element<'_, Message> {
-        let mut bars = widget::row::with_capacity(samples.len().max(1))
-            .spacing(0)
-            .height(Length::Fixed(height))
-            .width(Length::Fill)
-            .align_y(Alignment::End);
-
-        if samples.is_empty() {
-            bars = bars.push(
-                widget::container(widget::text(""))
-                    .width(Length::Fill)
-                    .height(Length::Fill),
-            );
-        } else {
-            for sample in samples {
-                let clamped = sample.clamp(0.0, 100.0);
-                let bar_height = ((clamped / 100.0) * height).max(1.0);
-                let top_space = (height - bar_height).max(0.0);
-                bars = bars.push(
-                    widget::container(
-                        widget::column::with_capacity(2)
-                            .push(widget::Space::with_height(Length::Fixed(top_space)))
-                            .push(
-                                widget::container(widget::text(""))
-                                    .class(theme::Container::custom(move |_theme| {
-                                        widget::container::Style {
-                                            background: Some(Background::Color(Color {
-                                                a: 0.78,
-                                                ..accent
-                                            })),
-                                            ..Default::default()
-                                        }
-                                    }))
-                                    .height(Length::Fixed(bar_height))
-                                    .width(Length::Fill),
-                            )
-                            .spacing(0),
-                    )
-                    .width(Length::FillPortion(1))
-                    .height(Length::Fill),
-                );
-            }
-        }
-
-        widget::container(bars)
-            .padding(0)
-            .class(theme::Container::custom(|theme| widget::container::Style {
-                background: Some(Background::Color(
-                    theme.current_container().component.base.into(),
-                )),
-                border: Border {
-                    color: Color::TRANSPARENT,
-                    width: 0.0,
-                    radius: 0.0.into(),
-                },
-                ..Default::default()
-            }))
-            .width(Length::Fill)
-            .height(Length::Fixed(height))
-            .into()
-    }
-
-    fn sparkline(&self, samples: &[f32], accent: Color, height: f32) -> Element<'_, Message> {
-        let mut bars = widget::row::with_capacity(samples.len().max(1))
-            .spacing(1)
-            .height(Length::Fixed(height))
-            .width(Length::Fill)
-            .align_y(Alignment::End);
-
-        if samples.is_empty() {
-            bars = bars.push(
-                widget::container(widget::text(""))
-                    .width(Length::Fill)
-                    .height(Length::Fill),
-            );
-        } else {
-            for sample in samples {
-                let clamped = sample.clamp(0.0, 100.0);
-                let bar_height = ((clamped / 100.0) * height).max(1.0);
-                let top_space = (height - bar_height).max(0.0);
-                bars = bars.push(
-                    widget::container(
-                        widget::column::with_capacity(2)
-                            .push(widget::Space::with_height(Length::Fixed(top_space)))
-                            .push(
-                                widget::container(widget::text(""))
-                                    .class(theme::Container::custom(move |_theme| {
-                                        widget::container::Style {
-                                            background: Some(Background::Color(Color {
-                                                a: 0.75,
-                                                ..accent
-                                            })),
-                                            ..Default::default()
-                                        }
-                                    }))
-                                    .height(Length::Fixed(bar_height))
-                                    .width(Length::Fill),
-                            )
-                            .spacing(0),
-                    )
-                    .width(Length::FillPortion(1))
-                    .height(Length::Fill),
-                );
-            }
-        }
-
-        widget::container(bars)
-            .padding(8)
-            .class(theme::Container::custom(|theme| widget::container::Style {
-                background: Some(Background::Color(
-                    theme.current_container().component.base.into(),
-                )),
-                border: Border {
-                    color: theme.cosmic().bg_divider().into(),
-                    width: 1.0,
-                    radius: 8.0.into(),
-                },
-                ..Default::default()
-            }))
-            .width(Length::Fill)
-            .height(Length::Fixed(height + 16.0))
-            .into()
-    }
 }
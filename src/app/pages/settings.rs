@@ -0,0 +1,1056 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Settings page for the appearance, custom columns, highlight rules, automation hooks,
+//! view profiles, apps kept visible, and the optional integrations that run beside the main
+//! window.
+//!
+//! Everything shown here is persisted in [`Config`]; the page only keeps dropdown labels
+//! and the column, rule, or hook being typed.
+
+use super::*;
+use crate::app::debug_tools::DEFAULT_DEBUGGER;
+use crate::app::expression::{EXPRESSION_FIELDS, Expression};
+use crate::app::highlight::{compile_pattern, highlight_color};
+use crate::config::{
+    AutomationHook, CpuPercentMode, CpuSmoothing, CustomColumn, Density, DoubleClickAction,
+    HighlightColor, HighlightRule, HistoryRetention, HookTrigger, MemoryGrowthAlert,
+    ProcessBackend, SnapshotInterval, SortDamping,
+};
+
+pub struct SettingsPage {
+    density_labels: Vec<String>,
+    cpu_percent_mode_labels: Vec<String>,
+    cpu_smoothing_labels: Vec<String>,
+    sort_damping_labels: Vec<String>,
+    history_retention_labels: Vec<String>,
+    double_click_action_labels: Vec<String>,
+    startup_page_labels: Vec<String>,
+    new_column_name: String,
+    new_column_expression: String,
+    column_error: Option<String>,
+    highlight_color_labels: Vec<String>,
+    highlight_target_labels: Vec<String>,
+    new_rule_pattern: String,
+    new_rule_badge: String,
+    new_rule_match_command_line: bool,
+    new_rule_color: HighlightColor,
+    rule_error: Option<String>,
+    hook_trigger_labels: Vec<String>,
+    new_hook_pattern: String,
+    /// Index into `hook_trigger_labels`: on start, CPU, or RAM.
+    new_hook_trigger: usize,
+    new_hook_threshold: String,
+    new_hook_command: String,
+    hook_error: Option<String>,
+    new_always_visible: String,
+    process_backend_labels: Vec<String>,
+    snapshot_interval_labels: Vec<String>,
+    memory_growth_alert_labels: Vec<String>,
+}
+
+impl Default for SettingsPage {
+    fn default() -> Self {
+        Self {
+            density_labels: Density::ALL.iter().copied().map(density_label).collect(),
+            cpu_percent_mode_labels: vec![fl!("cpu-percent-machine"), fl!("cpu-percent-core")],
+            cpu_smoothing_labels: vec![
+                fl!("cpu-smoothing-off"),
+                fl!("cpu-smoothing-light"),
+                fl!("cpu-smoothing-strong"),
+            ],
+            sort_damping_labels: vec![
+                fl!("sort-damping-off"),
+                fl!("sort-damping-light"),
+                fl!("sort-damping-strong"),
+            ],
+            history_retention_labels: vec![
+                fl!("history-retention-minute"),
+                fl!("history-retention-five-minutes"),
+                fl!("history-retention-fifteen-minutes"),
+                fl!("history-retention-hour"),
+            ],
+            startup_page_labels: Page::ALL.iter().map(|page| page.label()).collect(),
+            double_click_action_labels: vec![
+                fl!("double-click-details"),
+                fl!("double-click-focus"),
+                fl!("double-click-open-location"),
+                fl!("double-click-nothing"),
+            ],
+            new_column_name: String::new(),
+            new_column_expression: String::new(),
+            column_error: None,
+            highlight_color_labels: HighlightColor::ALL
+                .iter()
+                .copied()
+                .map(highlight_color_label)
+                .collect(),
+            highlight_target_labels: vec![
+                fl!("highlight-target-name"),
+                fl!("highlight-target-cmdline"),
+            ],
+            new_rule_pattern: String::new(),
+            new_rule_badge: String::new(),
+            new_rule_match_command_line: false,
+            new_rule_color: HighlightColor::default(),
+            rule_error: None,
+            hook_trigger_labels: vec![
+                fl!("automation-trigger-start"),
+                fl!("automation-trigger-cpu"),
+                fl!("automation-trigger-ram"),
+            ],
+            new_hook_pattern: String::new(),
+            new_hook_trigger: 0,
+            new_hook_threshold: String::new(),
+            new_hook_command: String::new(),
+            hook_error: None,
+            new_always_visible: String::new(),
+            process_backend_labels: vec![
+                fl!("process-backend-sysinfo"),
+                fl!("process-backend-procfs"),
+            ],
+            snapshot_interval_labels: vec![
+                fl!("snapshot-interval-off"),
+                fl!("snapshot-interval-five-minutes"),
+                fl!("snapshot-interval-fifteen-minutes"),
+                fl!("snapshot-interval-hour"),
+            ],
+            memory_growth_alert_labels: vec![
+                fl!("memory-growth-alert-off"),
+                fl!("memory-growth-alert-100mb"),
+                fl!("memory-growth-alert-500mb"),
+                fl!("memory-growth-alert-1gb"),
+            ],
+        }
+    }
+}
+
+fn highlight_color_label(color: HighlightColor) -> String {
+    match color {
+        HighlightColor::Purple => fl!("highlight-color-purple"),
+        HighlightColor::Red => fl!("highlight-color-red"),
+        HighlightColor::Orange => fl!("highlight-color-orange"),
+        HighlightColor::Yellow => fl!("highlight-color-yellow"),
+        HighlightColor::Green => fl!("highlight-color-green"),
+        HighlightColor::Blue => fl!("highlight-color-blue"),
+    }
+}
+
+fn density_label(density: Density) -> String {
+    match density {
+        Density::Compact => fl!("density-compact"),
+        Density::Comfortable => fl!("density-comfortable"),
+        Density::Spacious => fl!("density-spacious"),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SettingsMessage {
+    ToggleTrayIcon,
+    ToggleBackgroundCollector,
+    ToggleRemoteMode,
+    ToggleRemoteLan,
+    ToggleGlobalShortcut,
+    ToggleThresholdAlerts,
+    ToggleEncoderColumn,
+    ToggleWakeupsColumn,
+    ToggleFdColumn,
+    ToggleIncludeHelpers,
+    ToggleReduceMotion,
+    SetDensity(Density),
+    SetCpuPercentMode(CpuPercentMode),
+    SetCpuSmoothing(CpuSmoothing),
+    SetSortDamping(SortDamping),
+    SetHistoryRetention(HistoryRetention),
+    SetDoubleClickAction(DoubleClickAction),
+    SetStartupPage(Page),
+    SetNewColumnName(String),
+    SetNewColumnExpression(String),
+    AddCustomColumn,
+    RemoveCustomColumn(usize),
+    SetNewRulePattern(String),
+    SetNewRuleBadge(String),
+    SetNewRuleMatchCommandLine(bool),
+    SetNewRuleColor(HighlightColor),
+    AddHighlightRule,
+    RemoveHighlightRule(usize),
+    SetNewHookPattern(String),
+    SetNewHookTrigger(usize),
+    SetNewHookThreshold(String),
+    SetNewHookCommand(String),
+    AddAutomationHook,
+    RemoveAutomationHook(usize),
+    RemoveViewProfile(usize),
+    SetDebuggerCommand(String),
+    SetNewAlwaysVisible(String),
+    AddAlwaysVisible,
+    RemoveAlwaysVisible(usize),
+    SetProcessBackend(ProcessBackend),
+    SetSnapshotInterval(SnapshotInterval),
+    SetMemoryGrowthAlert(MemoryGrowthAlert),
+}
+
+impl AppModel {
+    pub(in crate::app) fn update_settings_page(
+        &mut self,
+        message: SettingsMessage,
+    ) -> Task<cosmic::Action<Message>> {
+        match message {
+            SettingsMessage::ToggleTrayIcon => self.toggle_tray_icon(),
+            SettingsMessage::ToggleBackgroundCollector => {
+                return self.toggle_background_collector();
+            }
+            SettingsMessage::ToggleRemoteMode => self.toggle_remote_mode(),
+            SettingsMessage::ToggleRemoteLan => self.toggle_remote_lan(),
+            SettingsMessage::ToggleGlobalShortcut => self.toggle_global_shortcut(),
+            SettingsMessage::ToggleThresholdAlerts => self.toggle_threshold_alerts(),
+            SettingsMessage::ToggleEncoderColumn => self.toggle_encoder_column(),
+            SettingsMessage::ToggleWakeupsColumn => self.toggle_wakeups_column(),
+            SettingsMessage::ToggleFdColumn => self.toggle_fd_column(),
+            SettingsMessage::ToggleIncludeHelpers => self.toggle_include_helpers(),
+            SettingsMessage::ToggleReduceMotion => self.toggle_reduce_motion(),
+            SettingsMessage::SetDensity(density) => self.set_density(density),
+            SettingsMessage::SetCpuPercentMode(mode) => self.set_cpu_percent_mode(mode),
+            SettingsMessage::SetCpuSmoothing(smoothing) => self.set_cpu_smoothing(smoothing),
+            SettingsMessage::SetSortDamping(damping) => self.set_sort_damping(damping),
+            SettingsMessage::SetHistoryRetention(retention) => {
+                self.set_history_retention(retention)
+            }
+            SettingsMessage::SetDoubleClickAction(action) => self.set_double_click_action(action),
+            SettingsMessage::SetStartupPage(page) => self.set_startup_page(page),
+            SettingsMessage::SetNewColumnName(name) => self.settings_page.new_column_name = name,
+            SettingsMessage::SetNewColumnExpression(expression) => {
+                self.settings_page.new_column_expression = expression;
+                self.settings_page.column_error = None;
+            }
+            SettingsMessage::AddCustomColumn => self.add_custom_column(),
+            SettingsMessage::RemoveCustomColumn(index) => {
+                let mut columns = self.config.custom_columns.clone();
+                if index < columns.len() {
+                    columns.remove(index);
+                    self.set_custom_columns(columns);
+                }
+            }
+            SettingsMessage::SetNewRulePattern(pattern) => {
+                self.settings_page.new_rule_pattern = pattern;
+                self.settings_page.rule_error = None;
+            }
+            SettingsMessage::SetNewRuleBadge(badge) => self.settings_page.new_rule_badge = badge,
+            SettingsMessage::SetNewRuleMatchCommandLine(enabled) => {
+                self.settings_page.new_rule_match_command_line = enabled;
+            }
+            SettingsMessage::SetNewRuleColor(color) => self.settings_page.new_rule_color = color,
+            SettingsMessage::AddHighlightRule => self.add_highlight_rule(),
+            SettingsMessage::SetNewHookPattern(pattern) => {
+                self.settings_page.new_hook_pattern = pattern;
+                self.settings_page.hook_error = None;
+            }
+            SettingsMessage::SetNewHookTrigger(index) => {
+                self.settings_page.new_hook_trigger = index;
+            }
+            SettingsMessage::SetNewHookThreshold(threshold) => {
+                self.settings_page.new_hook_threshold = threshold;
+                self.settings_page.hook_error = None;
+            }
+            SettingsMessage::SetNewHookCommand(command) => {
+                self.settings_page.new_hook_command = command;
+            }
+            SettingsMessage::AddAutomationHook => self.add_automation_hook(),
+            SettingsMessage::RemoveAutomationHook(index) => {
+                let mut hooks = self.config.automation_hooks.clone();
+                if index < hooks.len() {
+                    hooks.remove(index);
+                    self.set_automation_hooks(hooks);
+                }
+            }
+            SettingsMessage::RemoveViewProfile(index) => self.delete_view_profile(index),
+            SettingsMessage::SetDebuggerCommand(command) => self.set_debugger_command(command),
+            SettingsMessage::SetProcessBackend(backend) => self.set_process_backend(backend),
+            SettingsMessage::SetSnapshotInterval(interval) => self.set_snapshot_interval(interval),
+            SettingsMessage::SetMemoryGrowthAlert(alert) => self.set_memory_growth_alert(alert),
+            SettingsMessage::SetNewAlwaysVisible(name) => {
+                self.settings_page.new_always_visible = name;
+            }
+            SettingsMessage::AddAlwaysVisible => {
+                let name = self.settings_page.new_always_visible.trim().to_string();
+                if !name.is_empty() && !self.config.always_visible.contains(&name) {
+                    let mut names = self.config.always_visible.clone();
+                    names.push(name);
+                    self.set_always_visible(names);
+                }
+                self.settings_page.new_always_visible.clear();
+            }
+            SettingsMessage::RemoveAlwaysVisible(index) => {
+                let mut names = self.config.always_visible.clone();
+                if index < names.len() {
+                    names.remove(index);
+                    self.set_always_visible(names);
+                }
+            }
+            SettingsMessage::RemoveHighlightRule(index) => {
+                let mut rules = self.config.highlight_rules.clone();
+                if index < rules.len() {
+                    rules.remove(index);
+                    self.set_highlight_rules(rules);
+                }
+            }
+        }
+        Task::none()
+    }
+
+    fn add_highlight_rule(&mut self) {
+        let page = &mut self.settings_page;
+        let pattern = page.new_rule_pattern.trim().to_string();
+        if pattern.is_empty() {
+            page.rule_error = Some(fl!("highlight-pattern-missing"));
+            return;
+        }
+        if let Err(err) = compile_pattern(&pattern) {
+            page.rule_error = Some(fl!("highlight-pattern-invalid", error = err.to_string()));
+            return;
+        }
+
+        let rule = HighlightRule {
+            pattern,
+            match_command_line: page.new_rule_match_command_line,
+            color: page.new_rule_color,
+            badge: page.new_rule_badge.trim().to_string(),
+        };
+        page.new_rule_pattern.clear();
+        page.new_rule_badge.clear();
+        page.rule_error = None;
+        let mut rules = self.config.highlight_rules.clone();
+        rules.push(rule);
+        self.set_highlight_rules(rules);
+    }
+
+    fn set_highlight_rules(&mut self, rules: Vec<HighlightRule>) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_highlight_rules(handler, rules) {
+                eprintln!("failed to save highlight rules: {err}");
+            }
+        } else {
+            self.config.highlight_rules = rules;
+        }
+        self.refresh_highlights();
+    }
+
+    fn add_automation_hook(&mut self) {
+        let page = &mut self.settings_page;
+        let pattern = page.new_hook_pattern.trim().to_string();
+        let command = page.new_hook_command.trim().to_string();
+        if pattern.is_empty() {
+            page.hook_error = Some(fl!("highlight-pattern-missing"));
+            return;
+        }
+        if let Err(err) = compile_pattern(&pattern) {
+            page.hook_error = Some(fl!("highlight-pattern-invalid", error = err.to_string()));
+            return;
+        }
+        if command.is_empty() {
+            page.hook_error = Some(fl!("automation-command-missing"));
+            return;
+        }
+        let trigger = if page.new_hook_trigger == 0 {
+            HookTrigger::Start
+        } else {
+            let Some(threshold) = page
+                .new_hook_threshold
+                .trim()
+                .trim_end_matches('%')
+                .parse::<u8>()
+                .ok()
+                .filter(|threshold| (1..=100).contains(threshold))
+            else {
+                page.hook_error = Some(fl!("automation-threshold-invalid"));
+                return;
+            };
+            if page.new_hook_trigger == 1 {
+                HookTrigger::CpuAbove(threshold)
+            } else {
+                HookTrigger::RamAbove(threshold)
+            }
+        };
+
+        let hook = AutomationHook {
+            pattern,
+            trigger,
+            command,
+        };
+        page.new_hook_pattern.clear();
+        page.new_hook_threshold.clear();
+        page.new_hook_command.clear();
+        page.hook_error = None;
+        let mut hooks = self.config.automation_hooks.clone();
+        hooks.push(hook);
+        self.set_automation_hooks(hooks);
+    }
+
+    fn set_automation_hooks(&mut self, hooks: Vec<AutomationHook>) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_automation_hooks(handler, hooks) {
+                eprintln!("failed to save automation hooks: {err}");
+            }
+        } else {
+            self.config.automation_hooks = hooks;
+        }
+    }
+
+    fn add_custom_column(&mut self) {
+        let page = &mut self.settings_page;
+        let name = page.new_column_name.trim().to_string();
+        let expression = page.new_column_expression.trim().to_string();
+        if name.is_empty() {
+            page.column_error = Some(fl!("custom-column-name-missing"));
+            return;
+        }
+        if let Err(err) = Expression::parse(&expression) {
+            page.column_error = Some(fl!("custom-column-invalid", error = err));
+            return;
+        }
+
+        page.new_column_name.clear();
+        page.new_column_expression.clear();
+        page.column_error = None;
+        let mut columns = self.config.custom_columns.clone();
+        columns.push(CustomColumn { name, expression });
+        self.set_custom_columns(columns);
+    }
+
+    pub(in crate::app) fn set_custom_columns(&mut self, columns: Vec<CustomColumn>) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_custom_columns(handler, columns) {
+                eprintln!("failed to save custom columns: {err}");
+            }
+        } else {
+            self.config.custom_columns = columns;
+        }
+        // Values are computed during aggregation, so the table needs a fresh pass.
+        self.refresh_processes();
+    }
+
+    fn set_cpu_percent_mode(&mut self, mode: CpuPercentMode) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_cpu_percent_mode(handler, mode) {
+                eprintln!("failed to save CPU percentage mode: {err}");
+            }
+        } else {
+            self.config.cpu_percent_mode = mode;
+        }
+    }
+
+    fn set_cpu_smoothing(&mut self, smoothing: CpuSmoothing) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_cpu_smoothing(handler, smoothing) {
+                eprintln!("failed to save CPU smoothing: {err}");
+            }
+        } else {
+            self.config.cpu_smoothing = smoothing;
+        }
+    }
+
+    fn set_sort_damping(&mut self, damping: SortDamping) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_sort_damping(handler, damping) {
+                eprintln!("failed to save sort damping: {err}");
+            }
+        } else {
+            self.config.sort_damping = damping;
+        }
+    }
+
+    fn set_history_retention(&mut self, retention: HistoryRetention) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_history_retention(handler, retention) {
+                eprintln!("failed to save history retention: {err}");
+            }
+        } else {
+            self.config.history_retention = retention;
+        }
+    }
+
+    fn set_snapshot_interval(&mut self, interval: SnapshotInterval) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_snapshot_interval(handler, interval) {
+                eprintln!("failed to save snapshot interval: {err}");
+            }
+        } else {
+            self.config.snapshot_interval = interval;
+        }
+    }
+
+    fn set_memory_growth_alert(&mut self, alert: MemoryGrowthAlert) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_memory_growth_alert(handler, alert) {
+                eprintln!("failed to save memory growth alert: {err}");
+            }
+        } else {
+            self.config.memory_growth_alert = alert;
+        }
+    }
+
+    fn toggle_reduce_motion(&mut self) {
+        let enabled = !self.config.reduce_motion;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_reduce_motion(handler, enabled) {
+                eprintln!("failed to save reduce motion setting: {err}");
+            }
+        } else {
+            self.config.reduce_motion = enabled;
+        }
+    }
+
+    fn set_density(&mut self, density: Density) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_density(handler, density) {
+                eprintln!("failed to save density setting: {err}");
+            }
+        } else {
+            self.config.density = density;
+        }
+    }
+
+    pub(in crate::app) fn settings_view(&self, space_s: u16) -> Element<'_, Message> {
+        let density_index = Density::ALL
+            .iter()
+            .position(|density| *density == self.config.density);
+        let appearance = widget::settings::section()
+            .title(fl!("settings-appearance"))
+            .add(widget::settings::item(
+                fl!("density"),
+                widget::dropdown(&self.settings_page.density_labels, density_index, |index| {
+                    Message::Settings(SettingsMessage::SetDensity(Density::ALL[index]))
+                }),
+            ))
+            .add(Self::settings_toggle(
+                fl!("reduce-motion"),
+                self.config.reduce_motion,
+                SettingsMessage::ToggleReduceMotion,
+            ))
+            .add(widget::settings::item(
+                fl!("cpu-percent-mode"),
+                widget::dropdown(
+                    &self.settings_page.cpu_percent_mode_labels,
+                    CpuPercentMode::ALL
+                        .iter()
+                        .position(|mode| *mode == self.config.cpu_percent_mode),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetCpuPercentMode(
+                            CpuPercentMode::ALL[index],
+                        ))
+                    },
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("cpu-smoothing"),
+                widget::dropdown(
+                    &self.settings_page.cpu_smoothing_labels,
+                    CpuSmoothing::ALL
+                        .iter()
+                        .position(|smoothing| *smoothing == self.config.cpu_smoothing),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetCpuSmoothing(
+                            CpuSmoothing::ALL[index],
+                        ))
+                    },
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("sort-damping"),
+                widget::dropdown(
+                    &self.settings_page.sort_damping_labels,
+                    SortDamping::ALL
+                        .iter()
+                        .position(|damping| *damping == self.config.sort_damping),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetSortDamping(SortDamping::ALL[index]))
+                    },
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("history-retention"),
+                widget::dropdown(
+                    &self.settings_page.history_retention_labels,
+                    HistoryRetention::ALL
+                        .iter()
+                        .position(|retention| *retention == self.config.history_retention),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetHistoryRetention(
+                            HistoryRetention::ALL[index],
+                        ))
+                    },
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("startup-page"),
+                widget::dropdown(
+                    &self.settings_page.startup_page_labels,
+                    Page::ALL
+                        .iter()
+                        .position(|page| *page == self.config.startup_page),
+                    |index| Message::Settings(SettingsMessage::SetStartupPage(Page::ALL[index])),
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("double-click-action"),
+                widget::dropdown(
+                    &self.settings_page.double_click_action_labels,
+                    DoubleClickAction::ALL
+                        .iter()
+                        .position(|action| *action == self.config.double_click_action),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetDoubleClickAction(
+                            DoubleClickAction::ALL[index],
+                        ))
+                    },
+                ),
+            ))
+            .add(Self::settings_toggle(
+                fl!("show-encoder-column"),
+                self.config.show_encoder_column,
+                SettingsMessage::ToggleEncoderColumn,
+            ))
+            .add(Self::settings_toggle(
+                fl!("show-wakeups-column"),
+                self.config.show_wakeups_column,
+                SettingsMessage::ToggleWakeupsColumn,
+            ))
+            .add(Self::settings_toggle(
+                fl!("show-fd-column"),
+                self.config.show_fd_column,
+                SettingsMessage::ToggleFdColumn,
+            ))
+            .add(Self::settings_toggle(
+                fl!("include-helpers"),
+                self.config.include_helpers,
+                SettingsMessage::ToggleIncludeHelpers,
+            ));
+        let mut integrations = widget::settings::section()
+            .title(fl!("settings-integrations"))
+            .add(Self::settings_toggle(
+                fl!("show-tray-icon"),
+                self.config.show_tray_icon,
+                SettingsMessage::ToggleTrayIcon,
+            ))
+            .add(Self::settings_toggle(
+                fl!("background-collector"),
+                self.config.background_collector,
+                SettingsMessage::ToggleBackgroundCollector,
+            ))
+            .add(Self::settings_toggle(
+                fl!("remote-mode"),
+                self.config.remote_mode,
+                SettingsMessage::ToggleRemoteMode,
+            ))
+            .add(Self::settings_toggle(
+                fl!("remote-lan"),
+                self.config.remote_lan,
+                SettingsMessage::ToggleRemoteLan,
+            ));
+        if let Some(url) = self.remote_lan_url() {
+            integrations = integrations.add(widget::settings::item(
+                fl!("remote-lan-address"),
+                widget::text::body(url),
+            ));
+        }
+        let integrations = integrations
+            .add(self.global_shortcut_item())
+            .add(Self::settings_toggle(
+                fl!("threshold-alerts"),
+                self.config.threshold_alerts,
+                SettingsMessage::ToggleThresholdAlerts,
+            ))
+            .add(widget::settings::item(
+                fl!("memory-growth-alert"),
+                widget::dropdown(
+                    &self.settings_page.memory_growth_alert_labels,
+                    MemoryGrowthAlert::ALL
+                        .iter()
+                        .position(|alert| *alert == self.config.memory_growth_alert),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetMemoryGrowthAlert(
+                            MemoryGrowthAlert::ALL[index],
+                        ))
+                    },
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("snapshot-interval"),
+                widget::dropdown(
+                    &self.settings_page.snapshot_interval_labels,
+                    SnapshotInterval::ALL
+                        .iter()
+                        .position(|interval| *interval == self.config.snapshot_interval),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetSnapshotInterval(
+                            SnapshotInterval::ALL[index],
+                        ))
+                    },
+                ),
+            ))
+            .add(widget::settings::item(
+                fl!("debugger-command"),
+                widget::text_input(DEFAULT_DEBUGGER, self.config.debugger_command.as_str())
+                    .on_input(|command| {
+                        Message::Settings(SettingsMessage::SetDebuggerCommand(command))
+                    })
+                    .width(Length::Fixed(240.0)),
+            ))
+            .add(widget::settings::item(
+                fl!("process-backend"),
+                widget::dropdown(
+                    &self.settings_page.process_backend_labels,
+                    ProcessBackend::ALL
+                        .iter()
+                        .position(|backend| *backend == self.config.process_backend),
+                    |index| {
+                        Message::Settings(SettingsMessage::SetProcessBackend(
+                            ProcessBackend::ALL[index],
+                        ))
+                    },
+                ),
+            ));
+
+        let content = widget::column::with_capacity(8)
+            .push(widget::text::title2(fl!("settings-title")))
+            .push(appearance)
+            .push(self.custom_columns_section())
+            .push(self.highlight_rules_section())
+            .push(self.automation_hooks_section())
+            .push(self.view_profiles_section())
+            .push(self.always_visible_section())
+            .push(integrations)
+            .spacing(space_s)
+            .width(Length::Fill);
+
+        widget::container(widget::scrollable(content).height(Length::Fill))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn custom_columns_section(&self) -> Element<'_, Message> {
+        let page = &self.settings_page;
+        let mut section = widget::settings::section().title(fl!("custom-columns"));
+        for (index, column) in self.config.custom_columns.iter().enumerate() {
+            section = section.add(widget::settings::item(
+                column.name.clone(),
+                widget::row::with_capacity(2)
+                    .push(widget::text(column.expression.clone()).size(12))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .on_press(Message::Settings(SettingsMessage::RemoveCustomColumn(
+                                index,
+                            ))),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(8),
+            ));
+        }
+
+        let add_row = widget::row::with_capacity(3)
+            .push(
+                widget::text_input(fl!("custom-column-name"), page.new_column_name.as_str())
+                    .on_input(|name| Message::Settings(SettingsMessage::SetNewColumnName(name)))
+                    .width(Length::FillPortion(1)),
+            )
+            .push(
+                widget::text_input(
+                    fl!("custom-column-expression"),
+                    page.new_column_expression.as_str(),
+                )
+                .on_input(|expression| {
+                    Message::Settings(SettingsMessage::SetNewColumnExpression(expression))
+                })
+                .on_submit(|_| Message::Settings(SettingsMessage::AddCustomColumn))
+                .width(Length::FillPortion(2)),
+            )
+            .push(
+                widget::button::standard(fl!("custom-column-add"))
+                    .on_press(Message::Settings(SettingsMessage::AddCustomColumn)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8);
+        let hint = page.column_error.clone().unwrap_or_else(|| {
+            fl!(
+                "custom-column-fields",
+                fields = EXPRESSION_FIELDS.join(", ")
+            )
+        });
+
+        section
+            .add(
+                widget::column::with_capacity(2)
+                    .push(add_row)
+                    .push(widget::text(hint).size(12))
+                    .spacing(4),
+            )
+            .into()
+    }
+
+    fn always_visible_section(&self) -> Element<'_, Message> {
+        let mut section = widget::settings::section().title(fl!("always-visible"));
+        for (index, name) in self.config.always_visible.iter().enumerate() {
+            section = section.add(widget::settings::item(
+                name.clone(),
+                widget::button::icon(widget::icon::from_name("edit-delete-symbolic")).on_press(
+                    Message::Settings(SettingsMessage::RemoveAlwaysVisible(index)),
+                ),
+            ));
+        }
+
+        let add_row = widget::row::with_capacity(2)
+            .push(
+                widget::text_input(
+                    fl!("always-visible-placeholder"),
+                    self.settings_page.new_always_visible.as_str(),
+                )
+                .on_input(|name| Message::Settings(SettingsMessage::SetNewAlwaysVisible(name)))
+                .on_submit(|_| Message::Settings(SettingsMessage::AddAlwaysVisible))
+                .width(Length::Fill),
+            )
+            .push(
+                widget::button::standard(fl!("always-visible-add"))
+                    .on_press(Message::Settings(SettingsMessage::AddAlwaysVisible)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8);
+        section
+            .add(
+                widget::column::with_capacity(2)
+                    .push(add_row)
+                    .push(widget::text(fl!("always-visible-hint")).size(12))
+                    .spacing(4),
+            )
+            .into()
+    }
+
+    fn view_profiles_section(&self) -> Element<'_, Message> {
+        let section = widget::settings::section().title(fl!("profiles"));
+        if self.config.view_profiles.is_empty() {
+            return section
+                .add(widget::text(fl!("profiles-empty")).size(12))
+                .into();
+        }
+
+        self.config
+            .view_profiles
+            .iter()
+            .enumerate()
+            .fold(section, |section, (index, profile)| {
+                section.add(widget::settings::item(
+                    profile.name.clone(),
+                    widget::row::with_capacity(2)
+                        .push(
+                            widget::button::standard(fl!("profile-apply"))
+                                .on_press(Message::ApplyViewProfile(index)),
+                        )
+                        .push(
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::Settings(SettingsMessage::RemoveViewProfile(
+                                    index,
+                                ))),
+                        )
+                        .align_y(Alignment::Center)
+                        .spacing(8),
+                ))
+            })
+            .into()
+    }
+
+    fn highlight_rules_section(&self) -> Element<'_, Message> {
+        let page = &self.settings_page;
+        let mut section = widget::settings::section().title(fl!("highlight-rules"));
+        for (index, rule) in self.config.highlight_rules.iter().enumerate() {
+            let color = highlight_color(rule.color);
+            let target = if rule.match_command_line {
+                &page.highlight_target_labels[1]
+            } else {
+                &page.highlight_target_labels[0]
+            };
+            let swatch = widget::container(widget::text(""))
+                .width(Length::Fixed(12.0))
+                .height(Length::Fixed(12.0))
+                .class(theme::Container::custom(move |_theme| {
+                    widget::container::Style {
+                        background: Some(Background::Color(color)),
+                        border: Border {
+                            radius: 6.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }
+                }));
+            let summary = if rule.badge.is_empty() {
+                target.clone()
+            } else {
+                format!("{target} · {}", rule.badge)
+            };
+
+            section = section.add(widget::settings::item(
+                rule.pattern.clone(),
+                widget::row::with_capacity(3)
+                    .push(swatch)
+                    .push(widget::text(summary).size(12))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .on_press(Message::Settings(SettingsMessage::RemoveHighlightRule(
+                                index,
+                            ))),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(8),
+            ));
+        }
+
+        let add_row = widget::row::with_capacity(5)
+            .push(
+                widget::text_input(fl!("highlight-pattern"), page.new_rule_pattern.as_str())
+                    .on_input(|pattern| {
+                        Message::Settings(SettingsMessage::SetNewRulePattern(pattern))
+                    })
+                    .on_submit(|_| Message::Settings(SettingsMessage::AddHighlightRule))
+                    .width(Length::FillPortion(2)),
+            )
+            .push(widget::dropdown(
+                &page.highlight_target_labels,
+                Some(usize::from(page.new_rule_match_command_line)),
+                |index| Message::Settings(SettingsMessage::SetNewRuleMatchCommandLine(index == 1)),
+            ))
+            .push(widget::dropdown(
+                &page.highlight_color_labels,
+                HighlightColor::ALL
+                    .iter()
+                    .position(|color| *color == page.new_rule_color),
+                |index| {
+                    Message::Settings(SettingsMessage::SetNewRuleColor(HighlightColor::ALL[index]))
+                },
+            ))
+            .push(
+                widget::text_input(fl!("highlight-badge"), page.new_rule_badge.as_str())
+                    .on_input(|badge| Message::Settings(SettingsMessage::SetNewRuleBadge(badge)))
+                    .width(Length::FillPortion(1)),
+            )
+            .push(
+                widget::button::standard(fl!("highlight-add"))
+                    .on_press(Message::Settings(SettingsMessage::AddHighlightRule)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8);
+        let hint = page
+            .rule_error
+            .clone()
+            .unwrap_or_else(|| fl!("highlight-hint"));
+
+        section
+            .add(
+                widget::column::with_capacity(2)
+                    .push(add_row)
+                    .push(widget::text(hint).size(12))
+                    .spacing(4),
+            )
+            .into()
+    }
+
+    fn automation_hooks_section(&self) -> Element<'_, Message> {
+        let page = &self.settings_page;
+        let mut section = widget::settings::section().title(fl!("automation-hooks"));
+        for (index, hook) in self.config.automation_hooks.iter().enumerate() {
+            let trigger = match hook.trigger {
+                HookTrigger::Start => fl!("automation-on-start"),
+                HookTrigger::CpuAbove(threshold) => fl!("automation-on-cpu", percent = threshold),
+                HookTrigger::RamAbove(threshold) => fl!("automation-on-ram", percent = threshold),
+            };
+            section = section.add(widget::settings::item(
+                hook.pattern.clone(),
+                widget::row::with_capacity(2)
+                    .push(widget::text(format!("{trigger} · {}", hook.command)).size(12))
+                    .push(
+                        widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                            .on_press(Message::Settings(SettingsMessage::RemoveAutomationHook(
+                                index,
+                            ))),
+                    )
+                    .align_y(Alignment::Center)
+                    .spacing(8),
+            ));
+        }
+
+        let add_row = widget::row::with_capacity(5)
+            .push(
+                widget::text_input(fl!("highlight-pattern"), page.new_hook_pattern.as_str())
+                    .on_input(|pattern| {
+                        Message::Settings(SettingsMessage::SetNewHookPattern(pattern))
+                    })
+                    .width(Length::FillPortion(1)),
+            )
+            .push(widget::dropdown(
+                &page.hook_trigger_labels,
+                Some(page.new_hook_trigger),
+                |index| Message::Settings(SettingsMessage::SetNewHookTrigger(index)),
+            ))
+            .push_maybe((page.new_hook_trigger != 0).then(|| {
+                widget::text_input(
+                    fl!("automation-threshold"),
+                    page.new_hook_threshold.as_str(),
+                )
+                .on_input(|threshold| {
+                    Message::Settings(SettingsMessage::SetNewHookThreshold(threshold))
+                })
+                .width(Length::Fixed(80.0))
+            }))
+            .push(
+                widget::text_input(fl!("automation-command"), page.new_hook_command.as_str())
+                    .on_input(|command| {
+                        Message::Settings(SettingsMessage::SetNewHookCommand(command))
+                    })
+                    .on_submit(|_| Message::Settings(SettingsMessage::AddAutomationHook))
+                    .width(Length::FillPortion(2)),
+            )
+            .push(
+                widget::button::standard(fl!("automation-add"))
+                    .on_press(Message::Settings(SettingsMessage::AddAutomationHook)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(8);
+        let hint = page
+            .hook_error
+            .clone()
+            .unwrap_or_else(|| fl!("automation-hint"));
+
+        section
+            .add(
+                widget::column::with_capacity(2)
+                    .push(add_row)
+                    .push(widget::text(hint).size(12))
+                    .spacing(4),
+            )
+            .into()
+    }
+
+    /// The global shortcut toggle, with why the last change failed.
+    fn global_shortcut_item(&self) -> Element<'_, Message> {
+        widget::column::with_capacity(2)
+            .push(Self::settings_toggle(
+                fl!("global-shortcut"),
+                self.global_shortcut.registered,
+                SettingsMessage::ToggleGlobalShortcut,
+            ))
+            .push_maybe(self.global_shortcut.error.as_ref().map(|error| {
+                widget::text(fl!("global-shortcut-failed", error = error.as_str())).size(12)
+            }))
+            .spacing(4)
+            .into()
+    }
+
+    fn settings_toggle<'a>(
+        label: String,
+        enabled: bool,
+        message: SettingsMessage,
+    ) -> Element<'a, Message> {
+        widget::settings::item(
+            label,
+            widget::toggler(enabled).on_toggle(move |_| Message::Settings(message.clone())),
+        )
+        .into()
+    }
+}
@@ -0,0 +1,231 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Playtime of detected games: how long each has been running since it was started, and
+//! how long it ran today and in total across sessions. Totals live in the config and are
+//! saved every minute while a game runs, and as soon as one exits.
+
+use super::*;
+use crate::config::GamePlaytime;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PLAYTIME_SAVE_INTERVAL: Duration = Duration::from_secs(60);
+/// Longest gap between two refreshes that still counts as playtime, so time with sampling
+/// paused, e.g. while the machine was suspended, is left out.
+const MAX_PLAYTIME_STEP: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Default)]
+pub(super) struct PlaytimeTracker {
+    /// Running time of each game since it was started.
+    session: HashMap<String, Duration>,
+    /// Totals including time not saved yet, copied from the config on the first refresh.
+    records: Option<Vec<GamePlaytime>>,
+    ticked_at: Option<Instant>,
+    saved_at: Option<Instant>,
+}
+
+/// The local time zone's offset from UTC in seconds at `unix_seconds`. It is looked up for
+/// each time, so times on either side of a daylight saving change get their own offset.
+pub(super) fn local_utc_offset(unix_seconds: u64) -> i64 {
+    let Ok(time) = libc::time_t::try_from(unix_seconds) else {
+        return 0;
+    };
+    // SAFETY: `libc::tm` is plain data, for which all zeroes is a valid value.
+    let mut local = unsafe { std::mem::zeroed::<libc::tm>() };
+    // SAFETY: both pointers come from references that live for the whole call, and
+    // `localtime_r` writes only to `local`.
+    let converted = unsafe { libc::localtime_r(&time, &mut local) };
+    if converted.is_null() {
+        0
+    } else {
+        i64::from(local.tm_gmtoff)
+    }
+}
+
+/// Days since the Unix epoch in local time.
+fn local_day(unix_seconds: u64, offset_seconds: i64) -> i64 {
+    (unix_seconds as i64 + offset_seconds).div_euclid(86_400)
+}
+
+fn today() -> i64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    local_day(now, local_utc_offset(now))
+}
+
+/// Adds played seconds to a game's totals, starting today's count over on a new day.
+fn add_playtime(record: &mut GamePlaytime, day: i64, seconds: u64) {
+    if record.day != day {
+        record.day = day;
+        record.today_secs = 0;
+    }
+    record.total_secs += seconds;
+    record.today_secs += seconds;
+}
+
+/// Whole hours and minutes, e.g. `2 h 5 min`.
+pub(super) fn format_playtime(seconds: u64) -> String {
+    fl!(
+        "playtime-duration",
+        hours = seconds / 3_600,
+        minutes = (seconds % 3_600) / 60
+    )
+}
+
+impl AppModel {
+    pub(super) fn refresh_playtime(&mut self) {
+        let now = Instant::now();
+        let step = self
+            .playtime
+            .ticked_at
+            .replace(now)
+            .map_or(Duration::ZERO, |ticked| {
+                now.duration_since(ticked).min(MAX_PLAYTIME_STEP)
+            });
+        let running = self
+            .process_entries
+            .iter()
+            .filter(|entry| gamemode::is_game(entry))
+            .map(|entry| (entry.app_id.clone(), entry.display_name.clone()))
+            .collect::<HashMap<_, _>>();
+        let session_count = self.playtime.session.len();
+        self.playtime
+            .session
+            .retain(|app_id, _| running.contains_key(app_id));
+        let stopped = self.playtime.session.len() < session_count;
+
+        let day = today();
+        let records = self
+            .playtime
+            .records
+            .get_or_insert_with(|| self.config.game_playtime.clone());
+        for (app_id, name) in &running {
+            let session = self.playtime.session.entry(app_id.clone()).or_default();
+            let before = session.as_secs();
+            *session += step;
+            let seconds = session.as_secs() - before;
+            if seconds == 0 {
+                continue;
+            }
+            let index = records
+                .iter()
+                .position(|record| record.app_id == *app_id)
+                .unwrap_or_else(|| {
+                    records.push(GamePlaytime {
+                        app_id: app_id.clone(),
+                        name: name.clone(),
+                        total_secs: 0,
+                        day,
+                        today_secs: 0,
+                    });
+                    records.len() - 1
+                });
+            records[index].name.clone_from(name);
+            add_playtime(&mut records[index], day, seconds);
+        }
+
+        let save_due = self
+            .playtime
+            .saved_at
+            .is_none_or(|saved| saved.elapsed() >= PLAYTIME_SAVE_INTERVAL);
+        if (stopped || save_due) && *records != self.config.game_playtime {
+            let records = records.clone();
+            self.playtime.saved_at = Some(now);
+            if let Some(handler) = self.config_handler.as_ref() {
+                if let Err(err) = self.config.set_game_playtime(handler, records) {
+                    eprintln!("failed to save game playtime: {err}");
+                }
+            } else {
+                self.config.game_playtime = records;
+            }
+        }
+    }
+
+    /// Playtime of every game played so far, including time not saved yet.
+    pub(super) fn playtime_records(&self) -> &[GamePlaytime] {
+        self.playtime
+            .records
+            .as_ref()
+            .unwrap_or(&self.config.game_playtime)
+    }
+
+    fn playtime_record(&self, app_id: &str) -> Option<&GamePlaytime> {
+        self.playtime_records()
+            .iter()
+            .find(|record| record.app_id == app_id)
+    }
+
+    /// How long a running game has been running since it was started.
+    pub(super) fn session_playtime(&self, app_id: &str) -> Option<Duration> {
+        self.playtime.session.get(app_id).copied()
+    }
+
+    /// Playtime of all games today.
+    pub(super) fn playtime_today(&self) -> u64 {
+        let today = today();
+        self.playtime_records()
+            .iter()
+            .filter(|record| record.day == today)
+            .map(|record| record.today_secs)
+            .sum()
+    }
+
+    /// This session's, today's, and the total playtime of the selected game.
+    pub(super) fn selected_app_playtime(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let session = self.playtime.session.get(&selected.app_id)?;
+        let record = self.playtime_record(&selected.app_id);
+        let today_secs = record
+            .filter(|record| record.day == today())
+            .map_or(0, |record| record.today_secs);
+        let total_secs = record.map_or(0, |record| record.total_secs);
+
+        let line = |label: String, seconds: u64| {
+            widget::row::with_capacity(2)
+                .push(widget::text(label).width(Length::Fill))
+                .push(widget::text(format_playtime(seconds)))
+        };
+        Some(
+            widget::column::with_capacity(4)
+                .push(widget::text::heading(fl!("playtime")))
+                .push(line(fl!("playtime-session"), session.as_secs()))
+                .push(line(fl!("playtime-today"), today_secs))
+                .push(line(fl!("playtime-total"), total_secs))
+                .spacing(4)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_playtime, local_day};
+    use crate::config::GamePlaytime;
+
+    #[test]
+    fn counts_local_days() {
+        // 2024-03-01 23:30 UTC is already the next day at UTC+2.
+        let late_evening = 1_709_335_800;
+        assert_eq!(local_day(late_evening, 0), 19_783);
+        assert_eq!(local_day(late_evening, 7_200), 19_784);
+        assert_eq!(local_day(0, -3_600), -1);
+    }
+
+    #[test]
+    fn resets_todays_playtime_on_a_new_day() {
+        let mut record = GamePlaytime {
+            app_id: "steam-app-570".to_string(),
+            name: "Dota 2".to_string(),
+            total_secs: 3_600,
+            day: 19_783,
+            today_secs: 600,
+        };
+        add_playtime(&mut record, 19_783, 60);
+        assert_eq!((record.total_secs, record.today_secs), (3_660, 660));
+        add_playtime(&mut record, 19_784, 30);
+        assert_eq!(
+            (record.total_secs, record.day, record.today_secs),
+            (3_690, 19_784, 30)
+        );
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Pressure stall information (PSI) from `/proc/pressure`, which tells a busy system apart
+//! from one where tasks are stalled waiting on CPU, memory, or I/O.
+
+use super::*;
+
+/// `some avg10` at or above this share of time stalled is shown as a warning.
+const PRESSURE_WARNING_PERCENT: f32 = 10.0;
+/// `some avg10` at or above this, or any notable `full` stall, is shown as critical.
+const PRESSURE_CRITICAL_PERCENT: f32 = 40.0;
+const PRESSURE_FULL_CRITICAL_PERCENT: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PressureReading {
+    /// Share of the last 10 s in which at least one task was stalled.
+    some: f32,
+    /// Share of the last 10 s in which all non-idle tasks were stalled at once.
+    full: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum PressureLevel {
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl PressureReading {
+    fn level(self) -> PressureLevel {
+        if self.some >= PRESSURE_CRITICAL_PERCENT
+            || self
+                .full
+                .is_some_and(|full| full >= PRESSURE_FULL_CRITICAL_PERCENT)
+        {
+            PressureLevel::Critical
+        } else if self.some >= PRESSURE_WARNING_PERCENT {
+            PressureLevel::Warning
+        } else {
+            PressureLevel::Normal
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct PressureStats {
+    cpu: Option<PressureReading>,
+    memory: Option<PressureReading>,
+    io: Option<PressureReading>,
+}
+
+/// Parses the `avg10` values from a `/proc/pressure/*` file, e.g.
+/// `some avg10=1.53 avg60=0.87 avg300=0.21 total=123456`.
+fn parse_pressure(contents: &str) -> Option<PressureReading> {
+    let mut some = None;
+    let mut full = None;
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+        let avg10 = fields
+            .find_map(|field| field.strip_prefix("avg10="))
+            .and_then(|value| value.parse::<f32>().ok());
+        match kind {
+            Some("some") => some = avg10,
+            Some("full") => full = avg10,
+            _ => {}
+        }
+    }
+
+    Some(PressureReading { some: some?, full })
+}
+
+fn read_pressure(resource: &str) -> Option<PressureReading> {
+    fs::read_to_string(format!("/proc/pressure/{resource}"))
+        .ok()
+        .and_then(|contents| parse_pressure(&contents))
+}
+
+impl AppModel {
+    pub(super) fn refresh_pressure_stats(&mut self) {
+        self.pressure = PressureStats {
+            cpu: read_pressure("cpu"),
+            memory: read_pressure("memory"),
+            io: read_pressure("io"),
+        };
+    }
+
+    /// Stall percentages for the Resources header, or `None` if the kernel lacks PSI.
+    pub(super) fn pressure_header(&self) -> Option<Element<'_, Message>> {
+        let readings = [
+            (fl!("pressure-cpu"), self.pressure.cpu),
+            (fl!("pressure-memory"), self.pressure.memory),
+            (fl!("pressure-io"), self.pressure.io),
+        ];
+        if readings.iter().all(|(_, reading)| reading.is_none()) {
+            return None;
+        }
+
+        let mut header = widget::row::with_capacity(readings.len() + 1)
+            .push(widget::text(fl!("pressure-title")).size(14))
+            .align_y(Alignment::Center)
+            .spacing(8)
+            .width(Length::Fill);
+        for (label, reading) in readings {
+            let Some(reading) = reading else {
+                continue;
+            };
+            let text = match reading.full {
+                // The system-wide CPU `full` line is always zero, so only show non-zero values.
+                Some(full) if full > 0.0 => fl!(
+                    "pressure-reading-full",
+                    resource = label,
+                    some = units::format_decimal(f64::from(reading.some), 1),
+                    full = units::format_decimal(f64::from(full), 1)
+                ),
+                _ => fl!(
+                    "pressure-reading",
+                    resource = label,
+                    some = units::format_decimal(f64::from(reading.some), 1)
+                ),
+            };
+            let level = reading.level();
+            header = header.push(
+                widget::container(widget::text(text).size(13))
+                    .padding([4, 10])
+                    .class(theme::Container::custom(move |theme| {
+                        let cosmic = theme.cosmic();
+                        let color: Color = match level {
+                            PressureLevel::Normal => cosmic.bg_divider().into(),
+                            PressureLevel::Warning => cosmic.warning_color().into(),
+                            PressureLevel::Critical => cosmic.destructive_color().into(),
+                        };
+                        widget::container::Style {
+                            background: (level != PressureLevel::Normal)
+                                .then_some(Background::Color(Color { a: 0.14, ..color })),
+                            border: Border {
+                                color,
+                                width: 1.0,
+                                radius: 10.0.into(),
+                            },
+                            ..Default::default()
+                        }
+                    })),
+            );
+        }
+
+        Some(header.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PressureLevel, PressureReading, parse_pressure};
+
+    #[test]
+    fn parses_some_and_full_avg10() {
+        let memory = "some avg10=12.50 avg60=4.00 avg300=1.00 total=900\n\
+                      full avg10=3.25 avg60=1.00 avg300=0.20 total=300\n";
+        let reading = parse_pressure(memory).unwrap();
+        assert_eq!(reading.some, 12.5);
+        assert_eq!(reading.full, Some(3.25));
+        assert_eq!(reading.level(), PressureLevel::Warning);
+
+        let old_cpu = "some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n";
+        assert_eq!(
+            parse_pressure(old_cpu),
+            Some(PressureReading {
+                some: 0.0,
+                full: None
+            })
+        );
+        assert_eq!(parse_pressure(""), None);
+    }
+}
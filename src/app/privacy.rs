@@ -0,0 +1,220 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Camera, microphone, and screen-share usage per process, shown as privacy badges.
+//!
+//! Cameras are found through open `/dev/video*` nodes and PipeWire camera links, the
+//! microphone through PulseAudio/PipeWire recording streams, and screen sharing through
+//! PipeWire video streams fed by a non-camera source such as the screencast portal.
+//!
+//! `pw-dump`, `pactl`, and scanning every process' file descriptors are too slow for the
+//! window's thread, so they run in the background every few refreshes.
+
+use super::*;
+
+const PRIVACY_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct PrivacyUsage {
+    camera: HashSet<u32>,
+    microphone: HashSet<u32>,
+    screencast: HashSet<u32>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct PrivacyMonitor {
+    usage: PrivacyUsage,
+    /// When the last read started.
+    requested_at: Option<Instant>,
+    /// Whether a read is running in the background.
+    reading: bool,
+}
+
+fn json_u32(value: &serde_json::Value) -> Option<u32> {
+    value
+        .as_u64()
+        .map(|value| value as u32)
+        .or_else(|| value.as_str()?.parse().ok())
+}
+
+/// Splits the video streams in `pw-dump` output into camera and screencast consumers.
+fn parse_pipewire_video_streams(json: &str) -> (HashSet<u32>, HashSet<u32>) {
+    let mut cameras = HashSet::new();
+    let mut screencasts = HashSet::new();
+    let Ok(serde_json::Value::Array(objects)) = serde_json::from_str(json) else {
+        return (cameras, screencasts);
+    };
+
+    let mut camera_nodes = HashSet::new();
+    let mut video_streams = HashMap::new();
+    let mut links = Vec::new();
+    for object in &objects {
+        let id = object.get("id").and_then(json_u32);
+        let info = object.get("info");
+        match object.get("type").and_then(|value| value.as_str()) {
+            Some("PipeWire:Interface:Node") => {
+                let (Some(id), Some(props)) = (id, info.and_then(|info| info.get("props"))) else {
+                    continue;
+                };
+                let prop = |key: &str| props.get(key).and_then(|value| value.as_str());
+                if matches!(prop("device.api"), Some("v4l2" | "libcamera"))
+                    || prop("media.role") == Some("Camera")
+                {
+                    camera_nodes.insert(id);
+                }
+                let stream_pid = props
+                    .get("application.process.id")
+                    .and_then(json_u32)
+                    .filter(|_| prop("media.class") == Some("Stream/Input/Video"));
+                if let Some(pid) = stream_pid {
+                    video_streams.insert(id, pid);
+                }
+            }
+            Some("PipeWire:Interface:Link") => {
+                let Some(info) = info else {
+                    continue;
+                };
+                if let (Some(output), Some(input)) = (
+                    info.get("output-node-id").and_then(json_u32),
+                    info.get("input-node-id").and_then(json_u32),
+                ) {
+                    links.push((output, input));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (output, input) in links {
+        let Some(pid) = video_streams.get(&input) else {
+            continue;
+        };
+        if camera_nodes.contains(&output) {
+            cameras.insert(*pid);
+        } else {
+            screencasts.insert(*pid);
+        }
+    }
+
+    (cameras, screencasts)
+}
+
+/// Whether a process' `/proc` directory has a `/dev/video*` node among its open files.
+fn has_open_video_device(proc_dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(proc_dir.join("fd")) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        fs::read_link(entry.path())
+            .is_ok_and(|target| target.to_string_lossy().starts_with("/dev/video"))
+    })
+}
+
+/// Reads the usage of the processes with the given `/proc` directories.
+fn read_privacy_usage(proc_dirs: Vec<(u32, PathBuf)>) -> PrivacyUsage {
+    let (mut camera, screencast) = Command::new("pw-dump")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_pipewire_video_streams(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default();
+    camera.extend(
+        proc_dirs
+            .into_iter()
+            .filter(|(_, dir)| has_open_video_device(dir))
+            .map(|(pid, _)| pid),
+    );
+    let microphone = audio::read_pactl_streams("source-outputs")
+        .into_iter()
+        .filter(|stream| stream.is_playing)
+        .filter_map(|stream| stream.pid)
+        .collect();
+    PrivacyUsage {
+        camera,
+        microphone,
+        screencast,
+    }
+}
+
+impl AppModel {
+    /// Reads the usage again in the background once the interval has passed.
+    pub(super) fn request_privacy_usage(&mut self) -> Task<cosmic::Action<Message>> {
+        let privacy = &mut self.privacy;
+        if privacy.reading
+            || privacy
+                .requested_at
+                .is_some_and(|requested| requested.elapsed() < PRIVACY_REFRESH_INTERVAL)
+        {
+            return Task::none();
+        }
+        privacy.reading = true;
+        privacy.requested_at = Some(Instant::now());
+
+        let proc_dirs = self
+            .process_entries
+            .iter()
+            .flat_map(|entry| entry.pids.iter().copied())
+            .filter_map(|pid| Some((pid, self.process_source.proc_dir(pid)?)))
+            .collect::<Vec<_>>();
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || read_privacy_usage(proc_dirs))
+                    .await
+                    .unwrap_or_default()
+            },
+            |usage| cosmic::Action::App(Message::PrivacyUsageRead(usage)),
+        )
+    }
+
+    pub(super) fn set_privacy_usage(&mut self, usage: PrivacyUsage) {
+        self.privacy.usage = usage;
+        self.privacy.reading = false;
+    }
+
+    /// Symbolic icon names of the privacy-sensitive devices the entry is using.
+    pub(super) fn privacy_badge_icons(&self, entry: &ProcessEntry) -> Vec<&'static str> {
+        let usage = &self.privacy.usage;
+        let uses = |pids: &HashSet<u32>| entry.pids.iter().any(|pid| pids.contains(pid));
+        let mut icons = Vec::new();
+        if uses(&usage.camera) {
+            icons.push("camera-web-symbolic");
+        }
+        if uses(&usage.microphone) {
+            icons.push("audio-input-microphone-symbolic");
+        }
+        if uses(&usage.screencast) {
+            icons.push("video-display-symbolic");
+        }
+        icons
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_pipewire_video_streams;
+
+    #[test]
+    fn splits_camera_and_screencast_consumers() {
+        let json = r#"[
+            {"id": 40, "type": "PipeWire:Interface:Node",
+             "info": {"props": {"media.class": "Video/Source", "device.api": "v4l2"}}},
+            {"id": 41, "type": "PipeWire:Interface:Node",
+             "info": {"props": {"media.class": "Video/Source", "node.name": "xdpw_screencast"}}},
+            {"id": 50, "type": "PipeWire:Interface:Node",
+             "info": {"props": {"media.class": "Stream/Input/Video", "application.process.id": 1001}}},
+            {"id": 51, "type": "PipeWire:Interface:Node",
+             "info": {"props": {"media.class": "Stream/Input/Video", "application.process.id": "2002"}}},
+            {"id": 60, "type": "PipeWire:Interface:Link",
+             "info": {"output-node-id": 40, "input-node-id": 50}},
+            {"id": 61, "type": "PipeWire:Interface:Link",
+             "info": {"output-node-id": 41, "input-node-id": 51}}
+        ]"#;
+
+        let (cameras, screencasts) = parse_pipewire_video_streams(json);
+        assert!(cameras.contains(&1001));
+        assert!(!cameras.contains(&2002));
+        assert!(screencasts.contains(&2002));
+        assert_eq!(screencasts.len(), 1);
+    }
+}
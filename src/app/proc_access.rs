@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Detection of a `/proc` the task monitor can only partly read: mounted with `hidepid`,
+//! private to a Flatpak sandbox, or with command lines withheld by a hardened kernel. Apps
+//! are then matched by process name alone, which is what process matching falls back to
+//! when a command line and executable are missing, and a banner explains what is missing.
+
+use super::*;
+
+/// Why the processes, or their command lines, cannot all be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ProcRestriction {
+    /// `/proc` is mounted with `hidepid`, which hides other users' processes or their details.
+    HidePid,
+    /// The task monitor runs in a Flatpak sandbox, which has a `/proc` of its own, and may
+    /// not read the host's.
+    Sandbox,
+    /// Most command lines could not be read for another reason.
+    Unreadable,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ProcAccess {
+    checked: bool,
+    restriction: Option<ProcRestriction>,
+    dismissed: bool,
+}
+
+/// Whether `/proc` is mounted with a `hidepid` option that hides anything, from the lines of
+/// `/proc/self/mountinfo`.
+fn mounted_with_hidepid(mountinfo: &str) -> bool {
+    mountinfo.lines().any(|line| {
+        let Some((mount, filesystem)) = line.split_once(" - ") else {
+            return false;
+        };
+        let mut filesystem = filesystem.split_whitespace();
+        mount.split_whitespace().nth(4) == Some("/proc")
+            && filesystem.next() == Some("proc")
+            && filesystem.nth(1).is_some_and(|options| {
+                options.split(',').any(|option| {
+                    option
+                        .strip_prefix("hidepid=")
+                        .is_some_and(|value| !matches!(value, "0" | "off"))
+                })
+            })
+    })
+}
+
+/// Whether most processes have no command line. Kernel threads and zombies never have one,
+/// so they are not counted.
+fn cmdlines_unreadable(processes: &HashMap<Pid, ProcessSample>) -> bool {
+    let (mut user_space, mut unreadable) = (0, 0);
+    for process in processes.values() {
+        if process.pid == 2 || process.parent == Some(2) || process.state == 'Z' {
+            continue;
+        }
+        user_space += 1;
+        if process.cmd.is_empty() {
+            unreadable += 1;
+        }
+    }
+    unreadable * 2 > user_space
+}
+
+impl AppModel {
+    /// Looks for a restricted `/proc` once, after the first refresh.
+    pub(super) fn check_proc_access(&mut self) {
+        if self.proc_access.checked {
+            return;
+        }
+        self.proc_access.checked = true;
+        // Reading the host's processes failed if the sandbox's `bwrap` is still pid 1.
+        let sandboxed = self
+            .process_source
+            .processes()
+            .get(&Pid::from_u32(1))
+            .is_some_and(|init| init.name == "bwrap");
+        self.proc_access.restriction = if sandboxed {
+            Some(ProcRestriction::Sandbox)
+        } else if fs::read_to_string("/proc/self/mountinfo")
+            .is_ok_and(|mountinfo| mounted_with_hidepid(&mountinfo))
+        {
+            Some(ProcRestriction::HidePid)
+        } else if cmdlines_unreadable(self.process_source.processes()) {
+            Some(ProcRestriction::Unreadable)
+        } else {
+            None
+        };
+    }
+
+    pub(super) fn dismiss_proc_access_warning(&mut self) {
+        self.proc_access.dismissed = true;
+    }
+
+    pub(super) fn proc_access_banner(&self) -> Option<Element<'_, Message>> {
+        if self.proc_access.dismissed {
+            return None;
+        }
+        let warning = match self.proc_access.restriction? {
+            ProcRestriction::HidePid => fl!("proc-access-hidepid"),
+            ProcRestriction::Sandbox => fl!("proc-access-sandbox"),
+            ProcRestriction::Unreadable => fl!("proc-access-unreadable"),
+        };
+        Some(Self::warning_banner(
+            warning,
+            None,
+            Message::DismissProcAccessWarning,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cmdlines_unreadable, mounted_with_hidepid};
+    use crate::app::source::ProcessSample;
+    use std::collections::HashMap;
+    use sysinfo::Pid;
+
+    #[test]
+    fn detects_restricted_proc() {
+        let mountinfo = "22 28 0:20 / /sys rw,nosuid - sysfs sysfs rw\n\
+                         23 28 0:21 / /proc rw,nosuid,nodev,noexec - proc proc rw,hidepid=2\n";
+        assert!(mounted_with_hidepid(mountinfo));
+        assert!(mounted_with_hidepid(
+            "23 28 0:21 / /proc rw - proc proc rw,gid=10,hidepid=invisible"
+        ));
+        assert!(!mounted_with_hidepid(
+            "23 28 0:21 / /proc rw - proc proc rw,hidepid=off"
+        ));
+        assert!(!mounted_with_hidepid(
+            "23 28 0:21 / /proc rw - proc proc rw"
+        ));
+
+        let sample = |pid: u32, parent: Option<u32>, cmd: &[&str]| ProcessSample {
+            pid,
+            parent,
+            start_time: 0,
+            name: format!("process-{pid}").into(),
+            exe: None,
+            cmd: cmd.iter().map(ToString::to_string).collect(),
+            user_id: None,
+            state: 'S',
+            cpu_usage: 0.0,
+            memory: 0,
+            threads: None,
+            read_bytes: 0,
+            written_bytes: 0,
+        };
+        let mut processes = [
+            sample(2, None, &[]),
+            sample(3, Some(2), &[]),
+            sample(4, Some(2), &[]),
+            sample(100, Some(1), &["/usr/bin/bash"]),
+            sample(101, Some(1), &[]),
+        ]
+        .into_iter()
+        .map(|process| (Pid::from_u32(process.pid), process))
+        .collect::<HashMap<_, _>>();
+        assert!(!cmdlines_unreadable(&processes));
+
+        processes.insert(Pid::from_u32(102), sample(102, Some(1), &[]));
+        assert!(cmdlines_unreadable(&processes));
+    }
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Instant app start and exit detection through the kernel's proc connector, a netlink
+//! socket that reports every exec and exit. A burst of events refreshes the process list
+//! right away, so new apps show up without waiting for the next tick, while history samples
+//! stay on the fixed interval. Listening needs `CAP_NET_ADMIN`; without it the kernel sends
+//! nothing and the interval is all there is.
+
+use super::*;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+const NETLINK_CONNECTOR: libc::c_int = 11;
+const CN_IDX_PROC: u32 = 1;
+const CN_VAL_PROC: u32 = 1;
+const PROC_CN_MCAST_LISTEN: u32 = 1;
+const PROC_EVENT_EXEC: u32 = 0x0000_0002;
+const PROC_EVENT_EXIT: u32 = 0x8000_0000;
+/// `struct nlmsghdr` and `struct cn_msg`, which precede the `struct proc_event`.
+const NLMSG_HEADER_LEN: usize = 16;
+const CN_MSG_HEADER_LEN: usize = 20;
+/// Events arriving this soon after the first are handled by the same refresh.
+const PROC_EVENT_DEBOUNCE: Duration = Duration::from_millis(150);
+/// Least time between a refresh and one triggered by events, so CPU shares are still
+/// measured over a useful span.
+pub(super) const MIN_EVENT_REFRESH_GAP: Duration = Duration::from_millis(250);
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_ne_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// Whether a netlink message from the proc connector reports a process, not a thread,
+/// executing a program or exiting. The `proc_event` starts with `what`, `cpu`, and a
+/// timestamp, followed by the pid and thread group id for both kinds.
+fn is_process_exec_or_exit(message: &[u8]) -> bool {
+    let connector_id = (
+        u32_at(message, NLMSG_HEADER_LEN),
+        u32_at(message, NLMSG_HEADER_LEN + 4),
+    );
+    if connector_id != (Some(CN_IDX_PROC), Some(CN_VAL_PROC)) {
+        return false;
+    }
+    let Some(event) = message.get(NLMSG_HEADER_LEN + CN_MSG_HEADER_LEN..) else {
+        return false;
+    };
+    let (Some(what), Some(pid), Some(tgid)) =
+        (u32_at(event, 0), u32_at(event, 16), u32_at(event, 20))
+    else {
+        return false;
+    };
+    pid == tgid && (what == PROC_EVENT_EXEC || what == PROC_EVENT_EXIT)
+}
+
+/// The message that subscribes a connector socket to process events.
+fn listen_message() -> Vec<u8> {
+    let payload_len = 4;
+    let total_len = NLMSG_HEADER_LEN + CN_MSG_HEADER_LEN + payload_len;
+    let mut message = Vec::with_capacity(total_len);
+    // nlmsghdr: length, type NLMSG_DONE, flags, sequence, port id.
+    message.extend_from_slice(&(total_len as u32).to_ne_bytes());
+    message.extend_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+    message.extend_from_slice(&0u16.to_ne_bytes());
+    message.extend_from_slice(&0u32.to_ne_bytes());
+    message.extend_from_slice(&0u32.to_ne_bytes());
+    // cn_msg: connector id, sequence, ack, payload length, flags.
+    message.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+    message.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+    message.extend_from_slice(&0u32.to_ne_bytes());
+    message.extend_from_slice(&0u32.to_ne_bytes());
+    message.extend_from_slice(&(payload_len as u16).to_ne_bytes());
+    message.extend_from_slice(&0u16.to_ne_bytes());
+    message.extend_from_slice(&PROC_CN_MCAST_LISTEN.to_ne_bytes());
+    message
+}
+
+fn open_proc_connector() -> io::Result<OwnedFd> {
+    // SAFETY: a plain socket call, whose descriptor is owned right below.
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_NETLINK,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            NETLINK_CONNECTOR,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `fd` is a fresh descriptor that nothing else owns.
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // SAFETY: all zeroes is a valid `sockaddr_nl`.
+    let mut address: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+    address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+    address.nl_groups = CN_IDX_PROC;
+    // SAFETY: the address points to a live `sockaddr_nl` of the given size.
+    let bound = unsafe {
+        libc::bind(
+            socket.as_raw_fd(),
+            (&address as *const libc::sockaddr_nl).cast(),
+            std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+        )
+    };
+    if bound < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let message = listen_message();
+    // SAFETY: the buffer is valid for `message.len()` bytes.
+    let sent = unsafe {
+        libc::send(
+            socket.as_raw_fd(),
+            message.as_ptr().cast(),
+            message.len(),
+            0,
+        )
+    };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(socket)
+}
+
+/// Blocks reading the socket, passing on each process exec or exit until the receiver is
+/// gone.
+fn read_proc_events(socket: OwnedFd, sender: UnboundedSender<()>) {
+    let mut buffer = [0u8; 1024];
+    loop {
+        // SAFETY: the buffer is valid for writes of `buffer.len()` bytes.
+        let received = unsafe {
+            libc::recv(
+                socket.as_raw_fd(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len(),
+                0,
+            )
+        };
+        let relevant = if received < 0 {
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(libc::EINTR) => continue,
+                // Events were dropped, so refresh to catch up on them.
+                Some(libc::ENOBUFS) => true,
+                _ => {
+                    eprintln!("failed to read process events: {err}");
+                    return;
+                }
+            }
+        } else {
+            is_process_exec_or_exit(&buffer[..received as usize])
+        };
+        if relevant && sender.send(()).is_err() {
+            return;
+        }
+    }
+}
+
+/// Subscribes to process events, or returns `None` if the proc connector is unavailable.
+pub(super) fn listen_for_process_events() -> Option<UnboundedReceiver<()>> {
+    let socket = match open_proc_connector() {
+        Ok(socket) => socket,
+        Err(err) => {
+            eprintln!("failed to listen for process events: {err}");
+            return None;
+        }
+    };
+    let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+    std::thread::spawn(move || read_proc_events(socket, sender));
+    Some(receiver)
+}
+
+/// Waits for the next burst of process events, which never comes without a listener.
+pub(super) async fn next_process_event_burst(receiver: &mut Option<UnboundedReceiver<()>>) {
+    let Some(events) = receiver.as_mut() else {
+        return std::future::pending().await;
+    };
+    if events.recv().await.is_none() {
+        *receiver = None;
+        return std::future::pending().await;
+    }
+    tokio::time::sleep(PROC_EVENT_DEBOUNCE).await;
+    while events.try_recv().is_ok() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NLMSG_HEADER_LEN, is_process_exec_or_exit, listen_message};
+
+    fn proc_event(what: u32, pid: u32, tgid: u32) -> Vec<u8> {
+        let mut message = vec![0; NLMSG_HEADER_LEN];
+        for value in [1u32, 1, 0, 0] {
+            message.extend_from_slice(&value.to_ne_bytes());
+        }
+        message.extend_from_slice(&[0; 4]);
+        message.extend_from_slice(&what.to_ne_bytes());
+        message.extend_from_slice(&[0; 12]);
+        message.extend_from_slice(&pid.to_ne_bytes());
+        message.extend_from_slice(&tgid.to_ne_bytes());
+        message
+    }
+
+    #[test]
+    fn recognizes_process_exec_and_exit() {
+        assert!(is_process_exec_or_exit(&proc_event(0x2, 42, 42)));
+        assert!(is_process_exec_or_exit(&proc_event(0x8000_0000, 42, 42)));
+        // A thread exiting, and a fork.
+        assert!(!is_process_exec_or_exit(&proc_event(0x8000_0000, 43, 42)));
+        assert!(!is_process_exec_or_exit(&proc_event(0x1, 42, 42)));
+        assert!(!is_process_exec_or_exit(&proc_event(0x2, 42, 42)[..40]));
+    }
+
+    #[test]
+    fn builds_listen_message() {
+        let message = listen_message();
+        assert_eq!(message.len(), 40);
+        assert_eq!(&message[..4], &40u32.to_ne_bytes());
+        assert_eq!(&message[36..], &1u32.to_ne_bytes());
+    }
+}
@@ -2,6 +2,10 @@
 
 use super::*;
 
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod benches;
+
 impl AppModel {
     pub fn update_title(&mut self) -> Task<cosmic::Action<Message>> {
         let mut window_title = crate::fl!("app-title");
@@ -20,8 +24,18 @@ impl AppModel {
 
     pub(super) fn refresh_processes(&mut self) {
         self.clear_expired_autostart_feedback();
-        self.desktop_apps_by_exec = Self::load_desktop_app_map();
+        let desktop_apps_by_exec = Self::load_desktop_app_map(&mut self.icon_cache);
+        // Installing or removing an app can change what running processes match.
+        if !same_desktop_apps(&self.desktop_apps_by_exec, &desktop_apps_by_exec) {
+            self.process_matches.clear();
+        }
+        self.desktop_apps_by_exec = desktop_apps_by_exec;
         self.refresh_autostart_state();
+        self.history_ticks += 1;
+        let (retained, tick) = (
+            history::retained_samples(self.config.history_retention),
+            self.history_ticks,
+        );
         self.disks.refresh(true);
         let mut read_by_disk: HashMap<String, u64> = HashMap::new();
         let mut write_by_disk: HashMap<String, u64> = HashMap::new();
@@ -39,16 +53,10 @@ impl AppModel {
             let write_mib_s = (write_bytes as f32 / (1024.0 * 1024.0)) / refresh_secs;
 
             let read_history = self.disk_read_history.entry(disk_key.clone()).or_default();
-            read_history.push(read_mib_s.max(0.0));
-            if read_history.len() > PERFORMANCE_HISTORY_POINTS {
-                read_history.remove(0);
-            }
+            history::push_sample(read_history, read_mib_s.max(0.0), retained, tick);
 
             let write_history = self.disk_write_history.entry(disk_key.clone()).or_default();
-            write_history.push(write_mib_s.max(0.0));
-            if write_history.len() > PERFORMANCE_HISTORY_POINTS {
-                write_history.remove(0);
-            }
+            history::push_sample(write_history, write_mib_s.max(0.0), retained, tick);
         }
         self.disk_read_history
             .retain(|key, _| read_by_disk.contains_key(key));
@@ -116,10 +124,7 @@ impl AppModel {
             .iter_mut()
             .zip(core_usages.iter().copied())
         {
-            history.push(usage);
-            if history.len() > PERFORMANCE_HISTORY_POINTS {
-                history.remove(0);
-            }
+            history::push_sample(history, usage, retained, tick);
         }
         let total_memory = self.system.total_memory();
         let used_memory = self.system.used_memory().min(total_memory);
@@ -128,28 +133,20 @@ impl AppModel {
         } else {
             0.0
         };
-        self.ram_usage_history.push(ram_usage);
-        if self.ram_usage_history.len() > PERFORMANCE_HISTORY_POINTS {
-            self.ram_usage_history.remove(0);
-        }
+        history::push_sample(&mut self.ram_usage_history, ram_usage, retained, tick);
+        self.refresh_load_average(retained, tick);
         let gpu_runtime = Self::read_gpu_runtime_info();
         self.gpu_runtime_info = gpu_runtime.clone();
 
         if let Some(gpu_usage) = gpu_runtime.utilization_percent {
-            self.gpu_usage_history.push(gpu_usage);
-            if self.gpu_usage_history.len() > PERFORMANCE_HISTORY_POINTS {
-                self.gpu_usage_history.remove(0);
-            }
+            history::push_sample(&mut self.gpu_usage_history, gpu_usage, retained, tick);
         }
         if let (Some(vram_used), Some(vram_total)) =
             (gpu_runtime.vram_used_bytes, gpu_runtime.vram_total_bytes)
         {
             if vram_total > 0 {
                 let vram_usage = (vram_used as f32 / vram_total as f32 * 100.0).clamp(0.0, 100.0);
-                self.gpu_vram_usage_history.push(vram_usage);
-                if self.gpu_vram_usage_history.len() > PERFORMANCE_HISTORY_POINTS {
-                    self.gpu_vram_usage_history.remove(0);
-                }
+                history::push_sample(&mut self.gpu_vram_usage_history, vram_usage, retained, tick);
             }
         }
         let active_networks = Self::list_active_network_interfaces();
@@ -179,19 +176,13 @@ impl AppModel {
                 .network_rx_history
                 .entry(interface.name.clone())
                 .or_default();
-            rx_history.push(rx_mib_s.max(0.0));
-            if rx_history.len() > PERFORMANCE_HISTORY_POINTS {
-                rx_history.remove(0);
-            }
+            history::push_sample(rx_history, rx_mib_s.max(0.0), retained, tick);
 
             let tx_history = self
                 .network_tx_history
                 .entry(interface.name.clone())
                 .or_default();
-            tx_history.push(tx_mib_s.max(0.0));
-            if tx_history.len() > PERFORMANCE_HISTORY_POINTS {
-                tx_history.remove(0);
-            }
+            history::push_sample(tx_history, tx_mib_s.max(0.0), retained, tick);
 
             self.network_previous_snapshots
                 .insert(interface.name.clone(), current);
@@ -202,151 +193,434 @@ impl AppModel {
             .retain(|key, _| known_networks.contains(key));
         self.network_previous_snapshots
             .retain(|key, _| known_networks.contains(key));
-        self.system.refresh_processes_specifics(
-            ProcessesToUpdate::All,
-            true,
-            ProcessRefreshKind::nothing()
-                .with_memory()
-                .with_cpu()
-                .with_disk_usage()
-                .with_user(UpdateKind::OnlyIfNotSet)
-                .with_exe(UpdateKind::OnlyIfNotSet)
-                // New processes need cmdline to match Flatpak/wrapper launches correctly.
-                .with_cmd(UpdateKind::OnlyIfNotSet),
-        );
-        let cpu_core_count = self.system.cpus().len().max(1) as f32;
 
-        let current_user_id = self
-            .system
-            .process(Pid::from_u32(std::process::id()))
-            .and_then(|process| process.user_id().cloned());
+        self.refresh_process_list();
+        self.record_selected_app_history();
+        self.record_comparison_history();
+        self.refresh_selected_shared_memory();
+        self.refresh_selected_browser_processes();
+        self.refresh_selected_trim_method();
+        self.refresh_selected_thread_placement();
+        self.refresh_captured_log();
+        self.poll_screen_recording();
+        self.refresh_steam_maintenance();
+        self.refresh_value_flashes();
+        self.refresh_stuck_states();
+        self.refresh_pressure_stats();
+        self.refresh_data_sources();
+
+        if self.config.threshold_alerts {
+            self.check_alerts();
+        }
+        self.refresh_memory_growth();
+        if self.config.show_tray_icon {
+            self.publish_tray_snapshot();
+        }
+        if self.config.remote_mode {
+            self.publish_remote_snapshot();
+        }
+    }
+
+    /// Reads the processes again and rebuilds the list, without taking history samples.
+    /// Apps starting or exiting between two ticks are shown this way right away.
+    pub(super) fn refresh_process_list(&mut self) {
+        self.process_source.refresh();
+        self.check_proc_access();
+        let cpu_core_count = self.process_source.cpu_count().max(1) as f32;
+        let current_user_id = self.process_source.current_user_id();
 
         let eligible_pids: HashSet<Pid> = {
-            let processes = self.system.processes();
+            let processes = self.process_source.processes();
             processes
                 .iter()
                 .filter_map(|(pid, process)| {
-                    if Self::is_program_process(process, current_user_id.as_ref()) {
-                        Some(*pid)
+                    let eligible = if self.processes_page.group_by == GroupBy::User {
+                        Self::is_user_space_process(process)
                     } else {
-                        None
-                    }
+                        self.is_program_process(process, current_user_id)
+                    };
+                    if eligible { Some(*pid) } else { None }
                 })
                 .collect()
         };
-        let processes = self.system.processes();
+        let processes = self.process_source.processes();
+        let source = self.process_source.as_ref();
 
         #[derive(Default)]
         struct Aggregate {
-            name: String,
+            name: Arc<str>,
             icon_handle: Option<icon::Handle>,
             is_background: bool,
             pid: u32,
+            pids: Vec<u32>,
+            exe_path: Option<Arc<Path>>,
+            category: Option<&'static str>,
             cpu_percent: f32,
             rss_bytes: u64,
+            swap_bytes: u64,
             threads: u32,
+            read_bytes: u64,
+            write_bytes: u64,
+            fds: FdUsage,
+            state_codes: Vec<char>,
+        }
+
+        impl Aggregate {
+            fn add_member(
+                &mut self,
+                process: &ProcessSample,
+                source: &dyn SystemSource,
+                cpu_core_count: f32,
+                swap_in_use: bool,
+                count_fds: bool,
+            ) {
+                self.pids.push(process.pid);
+                self.state_codes.push(process.state);
+                self.cpu_percent += (process.cpu_usage / cpu_core_count).clamp(0.0, 100.0);
+                self.rss_bytes = self.rss_bytes.max(process.memory);
+                if swap_in_use {
+                    self.swap_bytes += memory::read_swap_bytes(source, process.pid);
+                }
+                if count_fds {
+                    self.fds.add_process(source, process.pid);
+                }
+                self.threads += process.threads.unwrap_or(1);
+                self.read_bytes += process.read_bytes;
+                self.write_bytes += process.written_bytes;
+            }
+
+            /// Counts a hidden helper towards the group. Unlike the app's own processes,
+            /// which share most of their pages, a helper's memory is its own, so it adds up.
+            fn add_helper(
+                &mut self,
+                process: &ProcessSample,
+                source: &dyn SystemSource,
+                cpu_core_count: f32,
+                swap_in_use: bool,
+                count_fds: bool,
+            ) {
+                let rss_bytes = self.rss_bytes;
+                self.add_member(process, source, cpu_core_count, swap_in_use, count_fds);
+                self.rss_bytes = rss_bytes + process.memory;
+            }
         }
 
-        let mut groups: HashMap<String, Aggregate> = HashMap::new();
+        // Without swap in use nothing can be paged out, so the status files are not read.
+        let swap_in_use = self.process_source.used_swap() > 0;
+        let count_fds = self.config.show_fd_column;
+        let mut groups: HashMap<Arc<str>, Aggregate> = HashMap::new();
         let mut steam_apps_by_id = std::mem::take(&mut self.steam_apps_by_id);
         let steam_icon_handle = self
             .desktop_apps_by_exec
             .get("steam")
-            .and_then(|meta| meta.icon_handle.clone());
+            .and_then(|meta| meta.icon_handle.as_deref().cloned());
         for pid in &eligible_pids {
             let Some(process) = processes.get(pid) else {
                 continue;
             };
-            let candidate_keys = Self::process_candidate_keys(process);
-            if candidate_keys.is_empty() {
-                continue;
-            }
-
-            let (app_id, app_name, app_is_background, app_icon_handle) = if let Some(app_meta) =
-                Self::desktop_app_for_process(process, &self.desktop_apps_by_exec)
-            {
-                (
-                    app_meta.app_id.clone(),
-                    app_meta.name.clone(),
-                    false,
-                    app_meta.icon_handle.clone(),
-                )
-            } else if let Some(steam_app_id) = Self::steam_app_id_for_process(process, processes) {
-                let steam_meta =
-                    steam_apps_by_id
-                        .entry(steam_app_id.clone())
-                        .or_insert_with(|| {
-                            Self::load_steam_app_meta(&steam_app_id, steam_icon_handle.clone())
-                        });
-
-                (
-                    format!("steam-app-{steam_app_id}"),
-                    steam_meta.name.clone(),
-                    true,
-                    steam_meta.icon_handle.clone(),
-                )
+            // Only new processes, or ones that ran another program since, are matched. The
+            // sample shares its executable and command line with the cached match while they
+            // are unchanged, so comparing them only compares pointers.
+            let cached = self
+                .process_matches
+                .get(&process.pid)
+                .filter(|cached| {
+                    cached.start_time == process.start_time
+                        && cached.exe == process.exe
+                        && cached.cmd == process.cmd
+                })
+                .cloned();
+            let process_match = if let Some(cached) = cached {
+                cached
             } else {
-                (
-                    Self::fallback_app_id_for_process(process),
-                    Self::fallback_app_name_for_process(process),
-                    true,
-                    None,
-                )
+                let candidate_keys = Self::process_candidate_keys(process);
+                if candidate_keys.is_empty() {
+                    continue;
+                }
+                // Placeholder names of Steam games still being looked up are not cached.
+                let mut placeholder = false;
+                let (app_id, name, is_background, icon_handle, category) = if let Some(app_meta) =
+                    Self::desktop_app_for_keys(&candidate_keys, &self.desktop_apps_by_exec)
+                {
+                    (
+                        app_meta.app_id.clone(),
+                        app_meta.name.clone(),
+                        false,
+                        app_meta.icon_handle.as_deref().cloned(),
+                        Self::primary_desktop_category(&app_meta.categories),
+                    )
+                } else if Self::shader_precache_for_process(process).is_some() {
+                    let (app_id, name, icon_handle, category) = self.steam_client_identity();
+                    (app_id, name, false, icon_handle, category)
+                } else if let Some(steam_app_id) =
+                    Self::steam_app_id_for_process(process, processes)
+                {
+                    let steam_meta = self.steam_resolver.meta(
+                        &mut steam_apps_by_id,
+                        &steam_app_id,
+                        steam_icon_handle.as_ref(),
+                    );
+                    placeholder = self.steam_resolver.is_pending(&steam_app_id);
+                    if steam_meta.compat_prefix.is_some() && steam_meta.proton_tool.is_none() {
+                        steam_meta.proton_tool = Self::proton_tool_for_process(process);
+                    }
+
+                    (
+                        format!("steam-app-{steam_app_id}"),
+                        steam_meta.name.clone(),
+                        true,
+                        steam_meta.icon_handle.clone(),
+                        Some("Game"),
+                    )
+                } else if let Some(game) = self.heroic.game_for_process(process, source) {
+                    let title = self.heroic.title(&game);
+                    (game.app_id(), title, true, None, Some("Game"))
+                } else if let Some(sandbox) =
+                    self.namespaces.sandbox_for(process, processes, source)
+                {
+                    let (app_id, name, icon_handle) = sandbox.app_identity();
+                    (app_id, name, true, icon_handle, None)
+                } else {
+                    (
+                        Self::fallback_app_id_for_process(process),
+                        Self::fallback_app_name_for_process(process),
+                        true,
+                        None,
+                        None,
+                    )
+                };
+                let process_match = ProcessMatch {
+                    start_time: process.start_time,
+                    exe: process.exe.clone(),
+                    cmd: process.cmd.clone(),
+                    app_id: app_id.into(),
+                    name: name.into(),
+                    is_background,
+                    icon_handle,
+                    category,
+                };
+                if !placeholder {
+                    self.process_matches
+                        .insert(process.pid, process_match.clone());
+                }
+                process_match
             };
-            if Self::is_excluded_app_id(&app_id) {
+            let ProcessMatch {
+                app_id,
+                name: app_name,
+                is_background: app_is_background,
+                icon_handle: app_icon_handle,
+                category: app_category,
+                ..
+            } = process_match;
+            if self.is_excluded_app_id(&app_id) {
                 continue;
             }
+            let (group_key, group_name, group_icon_handle) =
+                self.group_for_process(process, app_id, app_name, app_icon_handle, app_category);
 
-            let entry = groups.entry(app_id).or_insert_with(|| Aggregate {
-                name: app_name,
-                icon_handle: app_icon_handle,
+            let entry = groups.entry(group_key).or_insert_with(|| Aggregate {
+                name: group_name,
+                icon_handle: group_icon_handle,
                 is_background: app_is_background,
-                pid: process.pid().as_u32(),
-                rss_bytes: process.memory(),
+                pid: process.pid,
+                exe_path: process.exe.clone(),
+                category: app_category,
+                rss_bytes: process.memory,
                 ..Aggregate::default()
             });
 
-            entry.cpu_percent += (process.cpu_usage() / cpu_core_count).clamp(0.0, 100.0);
+            entry.add_member(process, source, cpu_core_count, swap_in_use, count_fds);
             entry.is_background |= app_is_background;
-            entry.pid = entry.pid.min(process.pid().as_u32());
-            entry.rss_bytes = entry.rss_bytes.max(process.memory());
-            entry.threads += process.tasks().map_or(1, |tasks| tasks.len() as u32);
+            // The lowest pid represents the group, so its executable is the one shown.
+            if process.pid < entry.pid {
+                entry.pid = process.pid;
+                entry.exe_path = process.exe.clone();
+            }
+        }
+        self.heroic.retain_pids(&eligible_pids);
+        self.namespaces.retain_pids(&eligible_pids);
+        self.process_matches
+            .retain(|pid, _| eligible_pids.contains(&Pid::from_u32(*pid)));
+        let member_groups = groups
+            .iter()
+            .flat_map(|(group_key, entry)| entry.pids.iter().map(move |pid| (*pid, &**group_key)))
+            .collect::<HashMap<_, _>>();
+        self.spawned_helpers = self.collect_spawned_helpers(
+            processes,
+            &eligible_pids,
+            current_user_id,
+            &member_groups,
+            cpu_core_count,
+        );
+        if self.config.include_helpers {
+            for (group_key, helpers) in &self.spawned_helpers {
+                let Some(entry) = groups.get_mut(group_key.as_str()) else {
+                    continue;
+                };
+                for helper in helpers {
+                    if let Some(process) = processes.get(&Pid::from_u32(helper.pid)) {
+                        entry.add_helper(process, source, cpu_core_count, swap_in_use, count_fds);
+                    }
+                }
+            }
         }
 
+        let custom_columns = self
+            .config
+            .custom_columns
+            .iter()
+            .map(|column| Expression::parse(&column.expression).ok())
+            .collect::<Vec<_>>();
+
+        let previous_positions = self
+            .process_entries
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| (entry.app_id.clone(), position))
+            .collect::<HashMap<_, _>>();
+        let smoothing_samples = self.config.cpu_smoothing.samples();
+        let encoder_usage = self.encoder_usage();
+        let wakeups = self.sample_wakeups();
+        let previous_cpu = std::mem::take(&mut self.smoothed_cpu);
+
         self.process_entries = groups
             .into_iter()
-            .map(|(app_id, entry)| ProcessEntry {
-                app_id,
-                display_name: entry.name.clone(),
-                name: entry.name,
-                is_background: entry.is_background,
-                pid: entry.pid,
-                icon_handle: entry.icon_handle,
-                cpu_percent: entry.cpu_percent.clamp(0.0, 100.0),
-                rss_bytes: entry.rss_bytes,
-                threads: entry.threads.max(1),
+            .map(|(app_id, entry)| {
+                let cpu_percent = smooth_cpu(
+                    previous_cpu.get(&*app_id).copied(),
+                    entry.cpu_percent.clamp(0.0, 100.0),
+                    smoothing_samples,
+                );
+                let threads = entry.threads.max(1);
+                let encoder_percent = entry
+                    .pids
+                    .iter()
+                    .filter_map(|pid| encoder_usage.get(pid).copied())
+                    .reduce(|total, usage| total + usage);
+                let wakeups_per_sec = entry
+                    .pids
+                    .iter()
+                    .filter_map(|pid| wakeups.get(pid).copied())
+                    .reduce(|total, rate| total + rate);
+                let field = |name: &str| match name {
+                    "cpu" => Some(f64::from(cpu_percent)),
+                    "rss" => Some(entry.rss_bytes as f64),
+                    "threads" => Some(f64::from(threads)),
+                    "processes" => Some(entry.pids.len() as f64),
+                    "read_bytes" => Some(entry.read_bytes as f64 / f64::from(refresh_secs)),
+                    "write_bytes" => Some(entry.write_bytes as f64 / f64::from(refresh_secs)),
+                    "wakeups" => wakeups_per_sec.map(f64::from),
+                    "fds" => count_fds.then(|| f64::from(entry.fds.count)),
+                    _ => None,
+                };
+                let custom_values = custom_columns
+                    .iter()
+                    .map(|expression| expression.as_ref()?.eval(&field))
+                    .collect();
+
+                ProcessEntry {
+                    app_id: app_id.to_string(),
+                    display_name: entry.name.to_string(),
+                    name: entry.name.to_string(),
+                    is_background: entry.is_background,
+                    pid: entry.pid,
+                    pids: entry.pids,
+                    exe_path: entry.exe_path.as_deref().map(Path::to_path_buf),
+                    category: entry.category,
+                    icon_handle: entry.icon_handle,
+                    cpu_percent,
+                    rss_bytes: entry.rss_bytes,
+                    swap_bytes: entry.swap_bytes,
+                    threads,
+                    encoder_percent,
+                    wakeups_per_sec,
+                    fds: count_fds.then_some(entry.fds),
+                    states: states::count_states(&entry.state_codes),
+                    custom_values,
+                }
             })
             .collect();
 
+        self.smoothed_cpu = self
+            .process_entries
+            .iter()
+            .map(|entry| (entry.app_id.clone(), entry.cpu_percent))
+            .collect();
+
+        naming::disambiguate_display_names(&mut self.process_entries);
+        self.icon_resolver
+            .resolve_missing(&mut self.process_entries);
         self.steam_apps_by_id = steam_apps_by_id;
-        self.sort_process_entries();
+        self.settle_process_entries(&previous_positions);
+        self.refresh_paused_apps();
+        self.refresh_search_matches();
+        self.record_app_events();
+        self.run_automation_hooks();
+        self.refresh_playtime();
+        self.refresh_games();
+        self.refresh_highlights();
+        self.refresh_restart_loops();
+        self.refresh_cgroup_tree();
+        self.refresh_nav_counters();
     }
 
-    pub(super) fn load_desktop_app_map() -> HashMap<String, DesktopAppMeta> {
+    pub(super) fn load_desktop_app_map(
+        icon_cache: &mut IconCache,
+    ) -> HashMap<String, DesktopAppMeta> {
         let locales = Self::desktop_locales();
         let xdg_current_desktop = env::var("XDG_CURRENT_DESKTOP")
             .ok()
             .and_then(|desktop| desktop.split(':').next().map(ToString::to_string));
 
+        let apps = desktop::load_applications(&locales, false, xdg_current_desktop.as_deref())
+            .into_iter()
+            .filter_map(|app| {
+                let app_id = Self::normalize_exec_key(&app.id)?;
+                let icon_handle =
+                    icon_cache.get_or_insert(&app_id, &format!("{:?}", app.icon), || {
+                        app.icon.as_cosmic_icon()
+                    });
+                let other_names = app
+                    .wm_class
+                    .iter()
+                    .cloned()
+                    .chain(app.mime_types.iter().filter_map(|mime| {
+                        mime.essence_str()
+                            .rsplit('/')
+                            .next()
+                            .map(ToString::to_string)
+                    }))
+                    .collect();
+                let meta = DesktopAppMeta {
+                    app_id,
+                    name: app.name.clone(),
+                    icon_handle: Some(icon_handle),
+                    primary_exec_keys: HashSet::new(),
+                    desktop_entry_id: Some(app.id.clone()),
+                    desktop_entry_path: app.path.clone(),
+                    exec_command: app.exec.clone(),
+                    categories: app.categories.clone(),
+                };
+                Some((meta, other_names))
+            })
+            .collect::<Vec<_>>();
+        Self::desktop_app_map(apps)
+    }
+
+    /// Keys each app by every name its processes may go by: the keys of its `Exec` line,
+    /// its normalized id, and `other_names`, its `StartupWMClass` and MIME type suffixes. A
+    /// key several apps share goes to the closest one. The apps' `primary_exec_keys` are
+    /// filled in.
+    pub(super) fn desktop_app_map(
+        apps: Vec<(DesktopAppMeta, Vec<String>)>,
+    ) -> HashMap<String, DesktopAppMeta> {
         let mut candidates_by_key: HashMap<String, Vec<DesktopAppMeta>> = HashMap::new();
-        for app in desktop::load_applications(&locales, false, xdg_current_desktop.as_deref()) {
+        for (mut meta, other_names) in apps {
             let mut candidates = HashSet::new();
             let mut primary_exec_keys = HashSet::new();
-            let Some(app_id) = Self::normalize_exec_key(&app.id) else {
-                continue;
-            };
 
-            if let Some(exec) = app.exec.as_deref() {
+            if let Some(exec) = meta.exec_command.as_deref() {
                 for key in Self::exec_candidate_keys(exec) {
                     candidates.insert(key);
                 }
@@ -357,43 +631,17 @@ impl AppModel {
                     primary_exec_keys.insert(key);
                 }
             }
-            if let Some(id_key) = Self::normalize_exec_key(&app.id) {
-                candidates.insert(id_key);
-            }
-            if let Some(wm_class) = app.wm_class.as_deref() {
-                for key in Self::exec_candidate_keys(wm_class) {
+            candidates.insert(meta.app_id.clone());
+            for name in &other_names {
+                for key in Self::exec_candidate_keys(name) {
                     candidates.insert(key.clone());
                     primary_exec_keys.insert(key);
                 }
             }
-            for mime in &app.mime_types {
-                let mime = mime.essence_str();
-                if let Some(suffix) = mime.rsplit('/').next() {
-                    for key in Self::exec_candidate_keys(suffix) {
-                        candidates.insert(key.clone());
-                        primary_exec_keys.insert(key);
-                    }
-                }
-            }
-
-            if candidates.is_empty() {
-                continue;
-            }
             if primary_exec_keys.is_empty() {
-                if let Some(id_key) = Self::normalize_exec_key(&app.id) {
-                    primary_exec_keys.insert(id_key);
-                }
+                primary_exec_keys.insert(meta.app_id.clone());
             }
-
-            let meta = DesktopAppMeta {
-                app_id,
-                name: app.name.clone(),
-                icon_handle: Some(app.icon.as_cosmic_icon()),
-                primary_exec_keys,
-                desktop_entry_id: Some(app.id.clone()),
-                desktop_entry_path: app.path.clone(),
-                exec_command: app.exec.clone(),
-            };
+            meta.primary_exec_keys = primary_exec_keys;
 
             for key in candidates {
                 candidates_by_key.entry(key).or_default().push(meta.clone());
@@ -440,7 +688,7 @@ impl AppModel {
         apps
     }
 
-    fn desktop_locales() -> Vec<String> {
+    pub(super) fn desktop_locales() -> Vec<String> {
         let mut locales = Vec::new();
 
         if let Ok(language) = env::var("LANGUAGE") {
@@ -466,33 +714,28 @@ impl AppModel {
         locales
     }
 
-    fn desktop_app_for_process<'a>(
-        process: &sysinfo::Process,
+    /// The desktop app of the first of a process's candidate keys that names one.
+    fn desktop_app_for_keys<'a>(
+        keys: &[String],
         desktop_apps: &'a HashMap<String, DesktopAppMeta>,
     ) -> Option<&'a DesktopAppMeta> {
-        for key in Self::process_candidate_keys(process) {
-            if let Some(app) = desktop_apps.get(&key) {
-                return Some(app);
-            }
-        }
-        None
+        keys.iter().find_map(|key| desktop_apps.get(&**key))
     }
 
-    pub(super) fn restart_selected_application(&mut self) {
+    /// Restarts the selected app, asking first if it is protected.
+    pub(super) fn restart_selected_application(&mut self) -> Task<cosmic::Action<Message>> {
         let Some(selected) = self.selected_process.as_ref().cloned() else {
-            return;
+            return Task::none();
         };
-        let launch_candidates = self.launch_candidates_for_selected(&selected);
-
-        self.signal_selected_application(Signal::Term);
-        self.wait_for_app_exit(&selected.app_id, Duration::from_secs(3));
-
-        if !Self::launch_from_candidates(&launch_candidates) {
-            // Some apps ignore SIGTERM, so try one hard stop before relaunch.
-            self.signal_selected_application(Signal::Kill);
-            self.wait_for_app_exit(&selected.app_id, Duration::from_secs(1));
-            let _ = Self::launch_from_candidates(&launch_candidates);
-        }
+        let candidates = self.launch_candidates_for_selected(&selected);
+        let settings = self.launch_settings(&selected.app_id);
+        self.request_stop(
+            selected.app_id,
+            StopKind::Restart(Relaunch::Launch {
+                candidates,
+                settings,
+            }),
+        )
     }
 
     pub(super) fn focus_selected_application(&mut self) {
@@ -505,22 +748,30 @@ impl AppModel {
         };
 
         let launch_candidates = self.launch_candidates_for_selected(&selected);
-        Self::launch_from_candidates(&launch_candidates)
+        Self::launch_from_candidates(&launch_candidates, &LaunchSettings::default())
     }
 
-    fn launch_candidates_for_selected(
-        &mut self,
-        selected: &SelectedProcess,
-    ) -> Vec<LaunchCandidate> {
+    /// Ways to launch an app that need no running process: a launcher URI for Steam and
+    /// Heroic games, then its desktop entry.
+    fn launch_candidates_for_app_id(&self, app_id: &str) -> Vec<LaunchCandidate> {
         let mut candidates = Vec::with_capacity(6);
 
-        if let Some(steam_app_id) = selected.app_id.strip_prefix("steam-app-") {
-            candidates.push(LaunchCandidate::SteamUri(format!(
+        if let Some(steam_app_id) = app_id.strip_prefix("steam-app-") {
+            candidates.push(LaunchCandidate::Uri(format!(
                 "steam://rungameid/{steam_app_id}"
             )));
         }
 
-        if let Some(meta) = self.desktop_meta_for_app_id(&selected.app_id) {
+        if let Some((source, app_name)) = app_id
+            .strip_prefix("heroic-")
+            .and_then(|game| game.split_once('-'))
+        {
+            candidates.push(LaunchCandidate::Uri(format!(
+                "heroic://launch/{source}/{app_name}"
+            )));
+        }
+
+        if let Some(meta) = self.desktop_meta_for_app_id(app_id) {
             if let Some(entry_id) = meta.desktop_entry_id.as_deref() {
                 let launch_id = entry_id.strip_suffix(".desktop").unwrap_or(entry_id);
                 if !launch_id.trim().is_empty() {
@@ -539,30 +790,38 @@ impl AppModel {
             }
         }
 
-        let pid = Pid::from_u32(selected.pid);
-        self.system.refresh_processes_specifics(
-            ProcessesToUpdate::Some(&[pid]),
-            false,
-            ProcessRefreshKind::nothing()
-                .with_cmd(UpdateKind::OnlyIfNotSet)
-                .with_exe(UpdateKind::OnlyIfNotSet),
-        );
+        candidates
+    }
 
-        if let Some(process) = self.system.process(pid) {
-            if let Some(cmd0) = process.cmd().first() {
-                let program = cmd0.to_string_lossy().trim().to_string();
+    /// Launches an app that is not running, such as a recently played game.
+    pub(super) fn launch_app(&self, app_id: &str) {
+        let candidates = self.launch_candidates_for_app_id(app_id);
+        if !Self::launch_from_candidates(&candidates, &LaunchSettings::default()) {
+            eprintln!("failed to launch `{app_id}`");
+        }
+    }
+
+    pub(super) fn launch_candidates_for_selected(
+        &self,
+        selected: &SelectedProcess,
+    ) -> Vec<LaunchCandidate> {
+        let mut candidates = self.launch_candidates_for_app_id(&selected.app_id);
+
+        // The command line and executable as the process source saw them, which in a
+        // Flatpak are the host's.
+        let processes = self.process_source.processes();
+        if let Some(process) = processes.get(&Pid::from_u32(selected.pid)) {
+            if let Some((cmd0, args)) = process.cmd.split_first() {
+                let program = cmd0.trim().to_string();
                 if !program.is_empty() {
-                    let args = process
-                        .cmd()
-                        .iter()
-                        .skip(1)
-                        .map(|arg| arg.to_string_lossy().to_string())
-                        .collect::<Vec<_>>();
-                    candidates.push(LaunchCandidate::Command { program, args });
+                    candidates.push(LaunchCandidate::Command {
+                        program,
+                        args: args.to_vec(),
+                    });
                 }
             }
 
-            if let Some(exe_path) = process.exe() {
+            if let Some(exe_path) = process.exe.as_deref() {
                 if !exe_path.as_os_str().is_empty() {
                     candidates.push(LaunchCandidate::Executable(exe_path.to_path_buf()));
                 }
@@ -572,30 +831,38 @@ impl AppModel {
         candidates
     }
 
-    fn launch_from_candidates(candidates: &[LaunchCandidate]) -> bool {
+    /// Launches the first candidate that starts, with the environment and extra arguments of
+    /// `settings`. A launcher URI carries neither, and `gtk-launch` and `gio launch` take no
+    /// arguments for the app, so they are skipped while there are extra arguments.
+    pub(super) fn launch_from_candidates(
+        candidates: &[LaunchCandidate],
+        settings: &LaunchSettings,
+    ) -> bool {
         for candidate in candidates {
             let launched = match candidate {
-                LaunchCandidate::SteamUri(uri) => open::that_detached(uri).is_ok(),
+                LaunchCandidate::Uri(uri) => open::that_detached(uri).is_ok(),
+                LaunchCandidate::GtkLaunch(_) | LaunchCandidate::GioLaunch(_)
+                    if !settings.args.is_empty() =>
+                {
+                    continue;
+                }
                 LaunchCandidate::GtkLaunch(entry_id) => {
                     let mut command = Command::new("gtk-launch");
-                    command.arg(entry_id);
+                    command.arg(entry_id).envs(settings.env.iter().cloned());
                     Self::spawn_detached(&mut command)
                 }
                 LaunchCandidate::GioLaunch(entry_path) => {
                     let mut command = Command::new("gio");
-                    command.arg("launch").arg(entry_path);
-                    Self::spawn_detached(&mut command)
-                }
-                LaunchCandidate::DesktopExec(exec) => Self::spawn_desktop_exec(exec),
-                LaunchCandidate::Command { program, args } => {
-                    let mut command = Command::new(program);
-                    command.args(args);
-                    Self::spawn_detached(&mut command)
-                }
-                LaunchCandidate::Executable(exe_path) => {
-                    let mut command = Command::new(exe_path);
+                    command
+                        .arg("launch")
+                        .arg(entry_path)
+                        .envs(settings.env.iter().cloned());
                     Self::spawn_detached(&mut command)
                 }
+                LaunchCandidate::DesktopExec(_)
+                | LaunchCandidate::Command { .. }
+                | LaunchCandidate::Executable(_) => Self::candidate_command(candidate, settings)
+                    .is_some_and(|mut command| Self::spawn_detached(&mut command)),
             };
 
             if launched {
@@ -606,52 +873,65 @@ impl AppModel {
         false
     }
 
-    fn wait_for_app_exit(&mut self, app_id: &str, timeout: Duration) {
-        let deadline = Instant::now() + timeout;
-        while Instant::now() < deadline {
-            if !self.is_app_id_running(app_id) {
-                return;
-            }
-            std::thread::sleep(Duration::from_millis(100));
-        }
+    pub(super) fn is_app_id_running(&mut self, app_id: &str) -> bool {
+        !self.app_pids(app_id).is_empty()
     }
 
-    fn is_app_id_running(&mut self, app_id: &str) -> bool {
-        self.system.refresh_processes_specifics(
-            ProcessesToUpdate::All,
-            false,
-            ProcessRefreshKind::nothing()
-                .with_user(UpdateKind::OnlyIfNotSet)
-                .with_exe(UpdateKind::OnlyIfNotSet)
-                .with_cmd(UpdateKind::OnlyIfNotSet),
-        );
+    /// The current user's processes that belong to the app, read from the process source
+    /// again, so processes the app started since the last refresh are included.
+    pub(super) fn app_pids(&mut self, app_id: &str) -> Vec<u32> {
+        self.process_source.refresh();
+        let processes = self.process_source.processes();
+        let current_user_id = self.process_source.current_user_id();
 
-        let current_user_id = self
-            .system
-            .process(Pid::from_u32(std::process::id()))
-            .and_then(|process| process.user_id().cloned());
-        let processes = self.system.processes();
-
-        processes.values().any(|process| {
-            if let Some(uid) = current_user_id.as_ref() {
-                if process.user_id() != Some(uid) {
-                    return false;
+        processes
+            .values()
+            .filter(|process| {
+                if let Some(uid) = current_user_id {
+                    if process.user_id != Some(uid) {
+                        return false;
+                    }
                 }
-            }
 
-            Self::resolved_app_id_for_process(process, processes, &self.desktop_apps_by_exec)
-                .is_some_and(|resolved| resolved == app_id)
-        })
+                Self::resolved_app_id_for_process(process, processes, &self.desktop_apps_by_exec)
+                    .is_some_and(|resolved| resolved == app_id)
+            })
+            .map(|process| process.pid)
+            .collect()
     }
 
-    fn desktop_meta_for_app_id(&self, app_id: &str) -> Option<DesktopAppMeta> {
+    pub(super) fn desktop_meta_for_app_id(&self, app_id: &str) -> Option<DesktopAppMeta> {
         self.desktop_apps_by_exec
             .values()
             .find(|meta| meta.app_id == app_id)
             .cloned()
     }
 
-    fn spawn_desktop_exec(exec: &str) -> bool {
+    /// The command for a candidate the task monitor runs itself rather than handing to a
+    /// launcher, with the environment and extra arguments of `settings`.
+    pub(super) fn candidate_command(
+        candidate: &LaunchCandidate,
+        settings: &LaunchSettings,
+    ) -> Option<Command> {
+        let mut command = match candidate {
+            LaunchCandidate::DesktopExec(exec) => {
+                return Self::desktop_exec_command(exec, settings);
+            }
+            LaunchCandidate::Command { program, args } => {
+                let mut command = Command::new(program);
+                command.args(args);
+                command
+            }
+            LaunchCandidate::Executable(exe_path) => Command::new(exe_path),
+            LaunchCandidate::Uri(_)
+            | LaunchCandidate::GtkLaunch(_)
+            | LaunchCandidate::GioLaunch(_) => return None,
+        };
+        settings.apply(&mut command);
+        Some(command)
+    }
+
+    fn desktop_exec_command(exec: &str, settings: &LaunchSettings) -> Option<Command> {
         let mut sanitized = exec.to_string();
         for field_code in [
             "%f", "%F", "%u", "%U", "%d", "%D", "%n", "%N", "%k", "%v", "%m", "%i", "%c",
@@ -661,15 +941,25 @@ impl AppModel {
         sanitized = sanitized.replace("%%", "%");
         let command = sanitized.trim();
         if command.is_empty() {
-            return false;
+            return None;
         }
 
+        let command = settings
+            .args
+            .iter()
+            .fold(command.to_string(), |command, arg| {
+                format!("{command} {}", debug_tools::shell_quote(arg))
+            });
+
         let mut shell_command = Command::new("sh");
-        shell_command.arg("-lc").arg(command);
-        Self::spawn_detached(&mut shell_command)
+        shell_command
+            .arg("-lc")
+            .arg(command)
+            .envs(settings.env.iter().cloned());
+        Some(shell_command)
     }
 
-    fn spawn_detached(command: &mut Command) -> bool {
+    pub(super) fn spawn_detached(command: &mut Command) -> bool {
         command
             .stdin(Stdio::null())
             .stdout(Stdio::null())
@@ -684,42 +974,11 @@ impl AppModel {
         command.spawn().is_ok()
     }
 
-    pub(super) fn signal_selected_application(&mut self, signal: Signal) {
-        let Some(selected) = self.selected_process.as_ref().cloned() else {
-            return;
-        };
-
-        self.system.refresh_processes_specifics(
-            ProcessesToUpdate::All,
-            false,
-            ProcessRefreshKind::nothing()
-                .with_user(UpdateKind::OnlyIfNotSet)
-                .with_exe(UpdateKind::OnlyIfNotSet)
-                .with_cmd(UpdateKind::OnlyIfNotSet),
-        );
-
-        let current_user_id = self
-            .system
-            .process(Pid::from_u32(std::process::id()))
-            .and_then(|process| process.user_id().cloned());
-        let processes = self.system.processes();
-
-        for process in processes.values() {
-            if let Some(uid) = current_user_id.as_ref() {
-                if process.user_id() != Some(uid) {
-                    continue;
-                }
-            }
-
-            let Some(app_id) =
-                Self::resolved_app_id_for_process(process, processes, &self.desktop_apps_by_exec)
-            else {
-                continue;
-            };
-
-            if app_id == selected.app_id {
-                let _ = process.kill_with(signal);
-            }
+    /// Signals the app's processes through the process source, which reaches the host's
+    /// processes from inside a Flatpak.
+    pub(super) fn signal_application(&mut self, target_app_id: &str, signal: Signal) {
+        for pid in self.app_pids(target_app_id) {
+            self.process_source.signal(pid, signal);
         }
 
         self.refresh_processes();
@@ -739,17 +998,12 @@ impl AppModel {
             }
         }
 
-        let pid = Pid::from_u32(selected.pid);
-        self.system.refresh_processes_specifics(
-            ProcessesToUpdate::Some(&[pid]),
-            false,
-            ProcessRefreshKind::nothing().with_exe(UpdateKind::OnlyIfNotSet),
-        );
-
-        let Some(process) = self.system.process(pid) else {
-            return;
-        };
-        let Some(exe_path) = process.exe() else {
+        let Some(exe_path) = self
+            .process_source
+            .processes()
+            .get(&Pid::from_u32(selected.pid))
+            .and_then(|process| process.exe.as_deref())
+        else {
             return;
         };
 
@@ -763,6 +1017,29 @@ impl AppModel {
         }
     }
 
+    /// Opens a terminal in the selected process's working directory. A process in another
+    /// mount namespace may report a directory that does not exist here, so the terminal then
+    /// starts in its default directory.
+    pub(super) fn open_selected_terminal(&self) {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return;
+        };
+        let cwd = self
+            .process_source
+            .proc_dir(selected.pid)
+            .and_then(|dir| fs::read_link(dir.join("cwd")).ok())
+            .filter(|cwd| cwd.is_dir());
+        if cwd.is_none() {
+            eprintln!(
+                "failed to read the working directory of process {}",
+                selected.pid
+            );
+        }
+        if !terminal::spawn_in_terminal(&[], cwd.as_deref()) {
+            eprintln!("failed to open a terminal");
+        }
+    }
+
     pub(super) fn copy_selected_application_info(&self) {
         let Some(selected) = self.selected_process.as_ref() else {
             return;
@@ -808,12 +1085,13 @@ impl AppModel {
         false
     }
 
-    fn resolved_app_id_for_process(
-        process: &sysinfo::Process,
-        processes: &HashMap<Pid, sysinfo::Process>,
+    pub(super) fn resolved_app_id_for_process(
+        process: &ProcessSample,
+        processes: &HashMap<Pid, ProcessSample>,
         desktop_apps: &HashMap<String, DesktopAppMeta>,
     ) -> Option<String> {
-        if let Some(app_meta) = Self::desktop_app_for_process(process, desktop_apps) {
+        let candidate_keys = Self::process_candidate_keys(process);
+        if let Some(app_meta) = Self::desktop_app_for_keys(&candidate_keys, desktop_apps) {
             return Some(app_meta.app_id.clone());
         }
 
@@ -824,25 +1102,26 @@ impl AppModel {
         Some(Self::fallback_app_id_for_process(process))
     }
 
-    fn fallback_app_name_for_process(process: &sysinfo::Process) -> String {
-        process.name().to_string_lossy().trim().to_string()
+    fn fallback_app_name_for_process(process: &ProcessSample) -> String {
+        process.name.trim().to_string()
     }
 
-    fn fallback_app_id_for_process(process: &sysinfo::Process) -> String {
+    fn fallback_app_id_for_process(process: &ProcessSample) -> String {
         let name = Self::fallback_app_name_for_process(process);
         if let Some(normalized) = Self::normalize_exec_key(&name) {
             normalized
         } else {
-            format!("pid-{}", process.pid().as_u32())
+            format!("pid-{}", process.pid)
         }
     }
 
-    fn process_candidate_keys(process: &sysinfo::Process) -> Vec<String> {
+    fn process_candidate_keys(process: &ProcessSample) -> Vec<String> {
         let mut keys = Vec::new();
         let mut seen = HashSet::new();
 
         if let Some(exe_name) = process
-            .exe()
+            .exe
+            .as_deref()
             .and_then(|exe| exe.file_stem().or_else(|| exe.file_name()))
             .map(|name| name.to_string_lossy().to_string())
         {
@@ -853,25 +1132,18 @@ impl AppModel {
             }
         }
 
-        if !process.cmd().is_empty() {
-            let cmdline = process
-                .cmd()
-                .iter()
-                .map(|part| part.to_string_lossy())
-                .collect::<Vec<_>>()
-                .join(" ");
-            for key in Self::exec_candidate_keys(&cmdline) {
+        if !process.cmd.is_empty() {
+            for key in Self::exec_candidate_keys(&process.cmdline()) {
                 if seen.insert(key.clone()) {
                     keys.push(key);
                 }
             }
 
-            for arg in process.cmd() {
-                let arg = arg.to_string_lossy();
-                if !Self::is_exec_like_arg(arg.as_ref()) {
+            for arg in process.cmd.iter() {
+                if !Self::is_exec_like_arg(arg) {
                     continue;
                 }
-                for key in Self::exec_candidate_keys(arg.as_ref()) {
+                for key in Self::exec_candidate_keys(arg) {
                     if seen.insert(key.clone()) {
                         keys.push(key);
                     }
@@ -879,9 +1151,8 @@ impl AppModel {
             }
         }
 
-        if let Some(cmd0) = process.cmd().first() {
-            let cmd0 = cmd0.to_string_lossy();
-            for key in Self::exec_candidate_keys(cmd0.as_ref()) {
+        if let Some(cmd0) = process.cmd.first() {
+            for key in Self::exec_candidate_keys(cmd0) {
                 if seen.insert(key.clone()) {
                     keys.push(key);
                 }
@@ -889,8 +1160,7 @@ impl AppModel {
         }
 
         if keys.is_empty() {
-            let process_name = process.name().to_string_lossy();
-            for key in Self::exec_candidate_keys(process_name.as_ref()) {
+            for key in Self::exec_candidate_keys(&process.name) {
                 if seen.insert(key.clone()) {
                     keys.push(key);
                 }
@@ -1043,13 +1313,14 @@ impl AppModel {
     }
 
     pub(super) fn toggle_sort(&mut self, column: SortColumn) {
-        if self.sort_state.column == column {
-            self.sort_state.direction = match self.sort_state.direction {
-                SortDirection::Asc => SortDirection::Desc,
-                SortDirection::Desc => SortDirection::Asc,
-            };
+        if self.processes_page.sort_state.column == column {
+            self.processes_page.sort_state.direction =
+                match self.processes_page.sort_state.direction {
+                    SortDirection::Asc => SortDirection::Desc,
+                    SortDirection::Desc => SortDirection::Asc,
+                };
         } else {
-            self.sort_state = SortState {
+            self.processes_page.sort_state = SortState {
                 column,
                 direction: Self::default_direction(column),
             };
@@ -1059,7 +1330,7 @@ impl AppModel {
 
     fn sort_process_entries(&mut self) {
         self.process_entries.sort_by(|a, b| {
-            let primary = match self.sort_state.column {
+            let primary = match self.processes_page.sort_state.column {
                 SortColumn::Name => a
                     .name
                     .to_lowercase()
@@ -1071,10 +1342,11 @@ impl AppModel {
                     .unwrap_or(Ordering::Equal),
                 SortColumn::Pid => a.pid.cmp(&b.pid),
                 SortColumn::Ram => a.rss_bytes.cmp(&b.rss_bytes),
+                SortColumn::Swap => a.swap_bytes.cmp(&b.swap_bytes),
                 SortColumn::Threads => a.threads.cmp(&b.threads),
             };
 
-            let primary = match self.sort_state.direction {
+            let primary = match self.processes_page.sort_state.direction {
                 SortDirection::Asc => primary,
                 SortDirection::Desc => primary.reverse(),
             };
@@ -1090,142 +1362,220 @@ impl AppModel {
         });
     }
 
+    /// Sorts after a refresh. With sort damping on, rows sorted by CPU or memory keep their
+    /// previous order until one clearly overtakes its neighbour.
+    fn settle_process_entries(&mut self, previous_positions: &HashMap<String, usize>) {
+        self.sort_process_entries();
+
+        let threshold = self.config.sort_damping.threshold();
+        let SortState { column, direction } = self.processes_page.sort_state;
+        if threshold <= 0.0 || !matches!(column, SortColumn::Cpu | SortColumn::Ram) {
+            return;
+        }
+
+        // New rows go after the known ones, in sorted order, and move up from there.
+        self.process_entries.sort_by_key(|entry| {
+            previous_positions
+                .get(&entry.app_id)
+                .copied()
+                .unwrap_or(usize::MAX)
+        });
+        let total_memory = self.system.total_memory().max(1) as f32;
+        settle_order(
+            &mut self.process_entries,
+            |entry| match column {
+                SortColumn::Cpu => entry.cpu_percent,
+                _ => entry.rss_bytes as f32 / total_memory * 100.0,
+            },
+            direction == SortDirection::Desc,
+            threshold,
+        );
+    }
+
     fn default_direction(column: SortColumn) -> SortDirection {
         match column {
             SortColumn::Name => SortDirection::Asc,
-            SortColumn::Cpu | SortColumn::Pid | SortColumn::Ram | SortColumn::Threads => {
-                SortDirection::Desc
-            }
+            SortColumn::Cpu
+            | SortColumn::Pid
+            | SortColumn::Ram
+            | SortColumn::Swap
+            | SortColumn::Threads => SortDirection::Desc,
         }
     }
 
-    pub(super) fn header_button_content(
+    /// A header cell that sorts by its column. The whole cell is the button, so it can be
+    /// clicked anywhere, focused with Tab, and toggled with Enter or Space, and screen
+    /// readers hear the column's sort state.
+    pub(super) fn sortable_header(
         &self,
         label: String,
         column: SortColumn,
+        portion: u16,
     ) -> Element<'static, Message> {
         let mut row = widget::row::with_capacity(2)
-            .push(widget::text(label))
+            .push(widget::text(label.clone()))
             .align_y(Alignment::Center)
             .spacing(6);
 
-        if self.sort_state.column == column {
-            let arrow_icon_name = match self.sort_state.direction {
-                SortDirection::Asc => "pan-up-symbolic",
-                SortDirection::Desc => "pan-down-symbolic",
+        let sort_state = &self.processes_page.sort_state;
+        let description = if sort_state.column == column {
+            let (arrow_icon_name, description) = match sort_state.direction {
+                SortDirection::Asc => ("pan-up-symbolic", fl!("sort-state-ascending")),
+                SortDirection::Desc => ("pan-down-symbolic", fl!("sort-state-descending")),
             };
             row = row.push(
                 widget::icon::from_name(arrow_icon_name)
                     .icon()
                     .size(14)
-                    .class(theme::Svg::custom(|_| cosmic::iced_widget::svg::Style {
-                        color: Some(Color::WHITE),
+                    .class(theme::Svg::custom(|theme| {
+                        cosmic::iced_widget::svg::Style {
+                            color: Some(theme.cosmic().accent_color().into()),
+                        }
                     })),
             );
-        }
+            description
+        } else {
+            fl!("sort-state-none")
+        };
 
-        widget::container(row)
+        let content = widget::container(row)
+            .padding(self.config.density.cell_padding())
             .width(Length::Fill)
-            .align_x(Horizontal::Center)
+            .align_x(Horizontal::Center);
+        widget::button::custom(content)
+            .on_press(Message::Processes(ProcessesMessage::ToggleSort(column)))
+            .name(label)
+            .description(description)
+            .padding(0)
+            .class(table_header_button_style())
+            .width(Length::FillPortion(portion))
             .into()
     }
 
-    fn is_program_process(
-        process: &sysinfo::Process,
-        current_user_id: Option<&sysinfo::Uid>,
-    ) -> bool {
+    fn is_program_process(&self, process: &ProcessSample, current_user_id: Option<u32>) -> bool {
         if let Some(uid) = current_user_id {
-            if process.user_id() != Some(uid) {
+            if process.user_id != Some(uid) {
                 return false;
             }
         }
 
-        let Some(exe) = process.exe() else {
-            return false;
-        };
-
-        if exe.as_os_str().is_empty() {
+        let has_exe = process
+            .exe
+            .as_ref()
+            .is_some_and(|exe| !exe.as_os_str().is_empty());
+        // A zombie has no executable left, but still belongs to its app.
+        if !has_exe && process.state != 'Z' {
             return false;
         }
 
-        let name = process.name().to_string_lossy();
+        let name = &process.name;
         if name.trim().is_empty() || name.starts_with('[') {
             return false;
         }
 
-        if Self::is_background_component_process(process) {
+        if self.is_hidden_background_process(process) {
             return false;
         }
 
         true
     }
 
-    fn is_background_component_process(process: &sysinfo::Process) -> bool {
-        if let Some(exe_name) = process
-            .exe()
-            .and_then(|exe| exe.file_stem().or_else(|| exe.file_name()))
-        {
-            if Self::looks_like_background_component(exe_name.to_string_lossy().as_ref()) {
-                return true;
-            }
-        }
+    /// Eligibility used when grouping by user, where other users' executables are unreadable.
+    fn is_user_space_process(process: &ProcessSample) -> bool {
+        let name = &process.name;
+        !name.trim().is_empty() && !name.starts_with('[') && !process.cmd.is_empty()
+    }
+}
 
-        if let Some(cmd0) = process.cmd().first() {
-            let cmd0 = cmd0.to_string_lossy();
-            let cmd0_name = Path::new(cmd0.as_ref())
-                .file_stem()
-                .or_else(|| Path::new(cmd0.as_ref()).file_name())
-                .map(|name| name.to_string_lossy().to_string())
-                .unwrap_or_else(|| cmd0.to_string());
+/// Exponential moving average spanning roughly `samples` refreshes; one sample keeps the
+/// new value as is.
+fn smooth_cpu(previous: Option<f32>, sample: f32, samples: u8) -> f32 {
+    let alpha = 2.0 / (f32::from(samples) + 1.0);
+    previous.map_or(sample, |previous| previous + alpha * (sample - previous))
+}
 
-            if Self::looks_like_background_component(&cmd0_name) {
-                return true;
-            }
+/// Moves items forward only past neighbours they beat by more than `threshold`, so values
+/// that wobble around each other keep their current order.
+fn settle_order<T>(items: &mut [T], key: impl Fn(&T) -> f32, descending: bool, threshold: f32) {
+    let ahead = |value: f32, neighbour: f32| {
+        if descending {
+            value - neighbour > threshold
+        } else {
+            neighbour - value > threshold
+        }
+    };
+    for index in 1..items.len() {
+        let mut position = index;
+        while position > 0 && ahead(key(&items[position]), key(&items[position - 1])) {
+            items.swap(position, position - 1);
+            position -= 1;
         }
-
-        Self::looks_like_background_component(process.name().to_string_lossy().as_ref())
     }
+}
 
-    fn looks_like_background_component(token: &str) -> bool {
-        let token = token.trim().to_ascii_lowercase();
-        if token.is_empty() {
-            return false;
-        }
+/// Whether both desktop app maps match the same keys to the same apps.
+fn same_desktop_apps(
+    left: &HashMap<String, DesktopAppMeta>,
+    right: &HashMap<String, DesktopAppMeta>,
+) -> bool {
+    left.len() == right.len()
+        && left.iter().all(|(key, app)| {
+            right
+                .get(key)
+                .is_some_and(|other| other.app_id == app.app_id && other.name == app.name)
+        })
+}
 
-        token.contains("daemon")
-            || token.contains("applet")
-            || token.contains("helper")
-            || token.contains("service")
-    }
+#[cfg(test)]
+mod tests {
+    use super::{AppModel, SystemSource, settle_order, smooth_cpu};
+    use crate::app::fixture::FixtureSource;
+    use sysinfo::Pid;
 
-    fn is_excluded_app_id(app_id: &str) -> bool {
-        app_id.contains("cosmicapplet")
-            || app_id.contains("cosmic-applet")
-            || app_id.contains("cosmic-panel-button")
-            || app_id.contains("cosmic-status-area")
-            || app_id.contains("cosmic-notifications")
-            || app_id.contains("cosmic-osd")
-            || app_id.contains("cosmic-workspaces")
-            || app_id.contains("cosmic-launcher")
-            || app_id.contains("cosmic-greeter")
-            || app_id.contains("xdg-desktop-portal")
-            || app_id.contains("daemon")
+    const DESKTOP_SESSION: &str = include_str!("../../tests/fixtures/desktop-session.json");
+
+    #[test]
+    fn matches_recorded_processes_to_apps() {
+        let source = FixtureSource::from_json(DESKTOP_SESSION).expect("fixture parses");
+        let desktop_apps = source.desktop_apps();
+        let processes = source.processes();
+        let app_id = |pid: u32| {
+            AppModel::resolved_app_id_for_process(
+                &processes[&Pid::from_u32(pid)],
+                processes,
+                &desktop_apps,
+            )
+        };
+
+        // The browser and its content processes share one entry.
+        assert_eq!(app_id(2100).as_deref(), Some("firefox"));
+        assert_eq!(app_id(2150).as_deref(), Some("firefox"));
+        assert_eq!(app_id(3090).as_deref(), Some("com-system76-cosmicterm"));
+        assert_eq!(app_id(1500).as_deref(), Some("steam"));
+        // A game is found through the app id on its reaper parent's command line.
+        assert_eq!(app_id(4200).as_deref(), Some("steam-app-620"));
+        assert_eq!(app_id(4210).as_deref(), Some("steam-app-620"));
+        assert_eq!(app_id(3100).as_deref(), Some("bash"));
     }
 
-    pub(super) fn format_rss(bytes: u64) -> String {
-        let gib = bytes as f64 / 1024.0 / 1024.0 / 1024.0;
-        if gib >= 1.0 {
-            format!("{gib:.1}GB")
-        } else {
-            let mib = bytes as f64 / 1024.0 / 1024.0;
-            format!("{mib:.1}MB")
-        }
+    #[test]
+    fn reorders_only_past_clear_differences() {
+        let mut values = [10.0, 10.5, 30.0, 2.0];
+        settle_order(&mut values, |value| *value, true, 1.0);
+        assert_eq!(values, [30.0, 10.0, 10.5, 2.0]);
+
+        let mut values = [3.0, 2.5, 0.5];
+        settle_order(&mut values, |value| *value, false, 1.0);
+        assert_eq!(values, [0.5, 3.0, 2.5]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::AppModel;
+    #[test]
+    fn smooths_cpu_towards_new_samples() {
+        assert_eq!(smooth_cpu(Some(40.0), 10.0, 1), 10.0);
+        assert_eq!(smooth_cpu(None, 10.0, 5), 10.0);
+        assert_eq!(smooth_cpu(Some(40.0), 10.0, 3), 25.0);
+        assert_eq!(smooth_cpu(Some(40.0), 10.0, 5), 30.0);
+    }
 
     #[test]
     fn extracts_steam_app_id_from_reaper_cmdline() {
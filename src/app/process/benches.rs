@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The matching that runs on every process refresh, on the recorded desktop session scaled
+//! up to a busy desktop, for the criterion benchmarks in `benches/process_matching.rs`. Each
+//! function returns a count so the benchmark has a result to keep from being optimized away.
+//! Only built with the `bench` feature.
+
+use super::*;
+use crate::app::fixture::FixtureSource;
+use std::collections::BTreeMap;
+
+const DESKTOP_SESSION: &str = include_str!("../../../tests/fixtures/desktop-session.json");
+
+/// How often the recorded session is repeated, which gives a snapshot of 2000 processes.
+const SNAPSHOT_COPIES: u32 = 200;
+/// How often the recorded desktop apps are repeated under new ids, which gives 300 apps.
+const DESKTOP_COPIES: usize = 100;
+
+/// The recorded session repeated with shifted pids, and the desktop apps recorded with it.
+pub struct Session {
+    processes: HashMap<Pid, ProcessSample>,
+    desktop_apps: HashMap<String, DesktopAppMeta>,
+    /// The recorded desktop apps repeated, as the desktop map is built from them.
+    desktop_entries: Vec<(DesktopAppMeta, Vec<String>)>,
+    current_user_id: Option<u32>,
+    /// Every command line and desktop entry `Exec` line.
+    commands: Vec<String>,
+}
+
+impl Session {
+    pub fn load() -> Self {
+        let source = FixtureSource::from_json(DESKTOP_SESSION).expect("fixture parses");
+        let mut processes = HashMap::new();
+        for copy in 0..SNAPSHOT_COPIES {
+            let offset = copy * 100_000;
+            for sample in source.processes().values() {
+                let mut sample = sample.clone();
+                sample.pid += offset;
+                sample.parent = sample.parent.map(|parent| parent + offset);
+                processes.insert(Pid::from_u32(sample.pid), sample);
+            }
+        }
+        let desktop_apps = source.desktop_apps();
+        let recorded_apps = desktop_apps
+            .values()
+            .map(|app| (app.app_id.clone(), app.clone()))
+            .collect::<BTreeMap<_, _>>();
+        let desktop_entries = (0..DESKTOP_COPIES)
+            .flat_map(|copy| {
+                recorded_apps.values().map(move |app| {
+                    let mut app = app.clone();
+                    app.app_id = format!("{}-{copy}", app.app_id);
+                    app.primary_exec_keys.clear();
+                    (app, Vec::new())
+                })
+            })
+            .collect();
+        let commands = processes
+            .values()
+            .map(ProcessSample::cmdline)
+            .chain(
+                desktop_apps
+                    .values()
+                    .filter_map(|app| app.exec_command.clone()),
+            )
+            .collect();
+        Self {
+            processes,
+            desktop_apps,
+            desktop_entries,
+            current_user_id: source.current_user_id(),
+            commands,
+        }
+    }
+
+    /// Derives the candidate keys of every process.
+    pub fn candidate_keys(&self) -> usize {
+        self.processes
+            .values()
+            .map(|process| AppModel::process_candidate_keys(process).len())
+            .sum()
+    }
+
+    /// Derives the keys of every command line and `Exec` line.
+    pub fn exec_keys(&self) -> usize {
+        self.commands
+            .iter()
+            .map(|command| AppModel::exec_candidate_keys(command).len())
+            .sum()
+    }
+
+    /// Matches every process of the user to its app and sums each app's usage, as a refresh
+    /// does.
+    pub fn aggregate(&self) -> usize {
+        let mut groups: HashMap<String, (f32, u64, Vec<u32>)> = HashMap::new();
+        for process in self.processes.values() {
+            if process.user_id != self.current_user_id {
+                continue;
+            }
+            let Some(app_id) =
+                AppModel::resolved_app_id_for_process(process, &self.processes, &self.desktop_apps)
+            else {
+                continue;
+            };
+            let (cpu, rss, pids) = groups.entry(app_id).or_default();
+            *cpu += process.cpu_usage;
+            *rss = (*rss).max(process.memory);
+            pids.push(process.pid);
+        }
+        groups.len()
+    }
+
+    /// Builds the desktop map from the recorded apps, including copying them in.
+    pub fn desktop_map(&self) -> usize {
+        AppModel::desktop_app_map(self.desktop_entries.clone()).len()
+    }
+}
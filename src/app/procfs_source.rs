@@ -0,0 +1,622 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A process source that reads `/proc` directly. Each refresh reads only `stat`, `statm`
+//! and `io` of every process; the command line, executable and owner are read once when a
+//! process first shows up, since they do not change. sysinfo gathers more than the table
+//! needs, so on busy systems this cuts the syscalls and allocations of every refresh.
+//!
+//! Inside a Flatpak sandbox, `/proc` only holds the sandbox's own processes. There the files
+//! are read from the host's `/proc` instead, copied out with `flatpak-spawn --host`, which
+//! needs the sandbox to be allowed to talk to `org.freedesktop.Flatpak`. The copy takes long
+//! enough to stall the window, so it is made on a thread of its own and a refresh uses the
+//! last finished one. Signals go to the host's `kill`, and of the per-process files only
+//! the copied ones can be read; directories such as `fd` cannot.
+
+use super::*;
+use std::os::unix::fs::MetadataExt;
+use std::sync::mpsc;
+
+/// The fields of `/proc/<pid>/stat` the table uses.
+#[derive(Debug, PartialEq)]
+struct StatFields {
+    name: String,
+    state: char,
+    parent: Option<u32>,
+    /// User plus system time, in clock ticks.
+    cpu_ticks: u64,
+    threads: u32,
+    /// Clock ticks after boot, which tells a reused pid apart from the process before it.
+    start_time: u64,
+}
+
+/// What is kept about a process between refreshes, besides its last sample.
+#[derive(Debug)]
+struct KnownProcess {
+    cpu_ticks: u64,
+    /// Bytes read and written in total.
+    io: (u64, u64),
+}
+
+/// Fields are numbered as in proc(5). The name may itself contain spaces and parentheses,
+/// so the fields after it are split at the last `)`.
+fn parse_stat(stat: &str) -> Option<StatFields> {
+    let (head, tail) = stat.rsplit_once(')')?;
+    let (_, name) = head.split_once('(')?;
+    let mut fields = tail.split_whitespace();
+    let state = fields.next()?.chars().next()?; // 3
+    let parent = fields.next()?.parse::<u32>().ok()?; // 4
+    let utime = fields.nth(9)?.parse::<u64>().ok()?; // 14
+    let stime = fields.next()?.parse::<u64>().ok()?; // 15
+    let threads = fields.nth(4)?.parse().ok()?; // 20
+    let start_time = fields.nth(1)?.parse().ok()?; // 22
+    Some(StatFields {
+        name: name.to_string(),
+        state,
+        parent: (parent != 0).then_some(parent),
+        cpu_ticks: utime + stime,
+        threads,
+        start_time,
+    })
+}
+
+/// Resident pages, the second field of `/proc/<pid>/statm`.
+fn parse_statm_resident(statm: &str) -> Option<u64> {
+    statm.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Arguments of `/proc/<pid>/cmdline`, which are separated and ended by NUL bytes.
+fn parse_cmdline(cmdline: &[u8]) -> Vec<String> {
+    let cmdline = cmdline.strip_suffix(&[0]).unwrap_or(cmdline);
+    if cmdline.is_empty() {
+        return Vec::new();
+    }
+    cmdline
+        .split(|byte| *byte == 0)
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect()
+}
+
+/// Bytes a process has read from and written to storage, from `/proc/<pid>/io`.
+fn parse_io(io: &str) -> (u64, u64) {
+    let mut read_bytes = 0;
+    let mut write_bytes = 0;
+    for line in io.lines() {
+        if let Some(value) = line.strip_prefix("read_bytes:") {
+            read_bytes = value.trim().parse().unwrap_or(0);
+        } else if let Some(value) = line.strip_prefix("write_bytes:") {
+            write_bytes = value.trim().parse().unwrap_or(0);
+        }
+    }
+    (read_bytes, write_bytes)
+}
+
+/// Clock ticks all CPUs have spent since boot, and the number of CPUs, from `/proc/stat`.
+/// Guest time is already counted in user time, so it is left out.
+fn parse_cpu_times(stat: &str) -> Option<(u64, usize)> {
+    let mut lines = stat.lines();
+    let total = lines
+        .next()?
+        .strip_prefix("cpu ")?
+        .split_whitespace()
+        .take(8)
+        .filter_map(|ticks| ticks.parse::<u64>().ok())
+        .sum();
+    let cpu_count = lines.take_while(|line| line.starts_with("cpu")).count();
+    Some((total, cpu_count))
+}
+
+/// Swap in use in bytes, from the totals in `/proc/meminfo`.
+fn parse_used_swap(meminfo: &str) -> Option<u64> {
+    let kib = |key: &str| -> Option<u64> {
+        let line = meminfo.lines().find(|line| line.starts_with(key))?;
+        line[key.len()..].split_whitespace().next()?.parse().ok()
+    };
+    Some(kib("SwapTotal:")?.saturating_sub(kib("SwapFree:")?) * 1024)
+}
+
+/// Prints the host's `/proc` files the table and the app details need, each after a
+/// `==> path <==` line as `head -v` writes them, then a line with the executable and owner
+/// of each process.
+const HOST_PROC_SCRIPT: &str = "head -v -c 1048576 /proc/stat /proc/meminfo \
+    /proc/[0-9]*/stat /proc/[0-9]*/statm /proc/[0-9]*/io /proc/[0-9]*/cmdline \
+    /proc/[0-9]*/status /proc/[0-9]*/cgroup 2>/dev/null; \
+    printf '\\n==> exe <==\\n'; \
+    find /proc/[0-9]*/exe -maxdepth 0 -printf '%p\\t%l\\t%U\\n' 2>/dev/null";
+
+/// Whether the task monitor runs as a Flatpak.
+pub(super) fn in_flatpak_sandbox() -> bool {
+    Path::new("/.flatpak-info").exists()
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// The files in the output of `head -v`, by path. `head` puts an empty line between a file
+/// and the next header.
+fn split_head_output(output: &[u8]) -> HashMap<String, Vec<u8>> {
+    let mut files = HashMap::new();
+    let output = output.strip_prefix(b"\n").unwrap_or(output);
+    let mut rest = output.strip_prefix(b"==> ");
+    while let Some(section) = rest {
+        let Some(path_end) = find_bytes(section, b" <==\n") else {
+            break;
+        };
+        let path = String::from_utf8_lossy(&section[..path_end]).into_owned();
+        let content = &section[path_end + 5..];
+        let (content, next) = match find_bytes(content, b"\n==> ") {
+            Some(end) => (&content[..end], Some(&content[end + 5..])),
+            None => (content, None),
+        };
+        files.insert(path, content.to_vec());
+        rest = next;
+    }
+    files
+}
+
+/// Executable and owner by pid, from `/proc/<pid>/exe<TAB>target<TAB>uid` lines. The target
+/// is empty when the link cannot be read.
+fn parse_exe_lines(lines: &str) -> HashMap<u32, (Option<PathBuf>, Option<u32>)> {
+    lines
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let pid = fields
+                .next()?
+                .strip_prefix("/proc/")?
+                .strip_suffix("/exe")?
+                .parse()
+                .ok()?;
+            let exe = fields
+                .next()
+                .filter(|exe| !exe.is_empty())
+                .map(PathBuf::from);
+            let owner = fields.next().and_then(|uid| uid.parse().ok());
+            Some((pid, (exe, owner)))
+        })
+        .collect()
+}
+
+/// The host's `/proc` as copied by the last refresh.
+struct HostProc {
+    files: HashMap<String, Vec<u8>>,
+    /// Executable and owner by pid.
+    exes: HashMap<u32, (Option<PathBuf>, Option<u32>)>,
+}
+
+impl HostProc {
+    /// Copies the files, or returns `None` when the sandbox may not run commands on the host.
+    fn copy() -> Option<Self> {
+        // `head` fails for the `io` files of other users' processes, so its status is ignored.
+        let output = Command::new("flatpak-spawn")
+            .args(["--host", "sh", "-c", HOST_PROC_SCRIPT])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let mut files = split_head_output(&output.stdout);
+        let exes = parse_exe_lines(&String::from_utf8_lossy(&files.remove("exe")?));
+        (!exes.is_empty()).then_some(Self { files, exes })
+    }
+}
+
+/// Copies the host's `/proc` on a thread of its own, one copy per request.
+struct HostCopier {
+    requests: mpsc::Sender<()>,
+    copies: mpsc::Receiver<Option<HostProc>>,
+    /// Whether a copy was asked for and has not arrived yet.
+    pending: bool,
+}
+
+impl HostCopier {
+    fn start() -> Self {
+        let (requests, request_receiver) = mpsc::channel::<()>();
+        let (copy_sender, copies) = mpsc::channel();
+        std::thread::spawn(move || {
+            for () in request_receiver {
+                if copy_sender.send(HostProc::copy()).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            requests,
+            copies,
+            pending: false,
+        }
+    }
+
+    /// The copy that arrived since the last call, if any, after asking for the next one.
+    /// A copy of `None` means the host cannot be reached.
+    fn poll(&mut self) -> Option<Option<HostProc>> {
+        let copy = self.copies.try_recv().ok();
+        if copy.is_some() {
+            self.pending = false;
+        }
+        if !self.pending && self.requests.send(()).is_ok() {
+            self.pending = true;
+        }
+        copy
+    }
+}
+
+/// The number of a signal for `kill(2)`, and its name for `kill -s`.
+fn signal_id(signal: Signal) -> Option<(i32, &'static str)> {
+    Some(match signal {
+        Signal::Hangup => (libc::SIGHUP, "HUP"),
+        Signal::Interrupt => (libc::SIGINT, "INT"),
+        Signal::Kill => (libc::SIGKILL, "KILL"),
+        Signal::Term => (libc::SIGTERM, "TERM"),
+        Signal::Stop => (libc::SIGSTOP, "STOP"),
+        Signal::Continue => (libc::SIGCONT, "CONT"),
+        _ => return None,
+    })
+}
+
+/// Sends a signal with the host's `kill`, which is waited for on a thread of its own.
+fn signal_host_process(pid: u32, signal_name: &str) -> bool {
+    let child = Command::new("flatpak-spawn")
+        .args(["--host", "kill", "-s", signal_name, &pid.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match child {
+        Ok(mut child) => {
+            std::thread::spawn(move || child.wait());
+            true
+        }
+        Err(err) => {
+            eprintln!("failed to signal host process {pid}: {err}");
+            false
+        }
+    }
+}
+
+/// Where `/proc` is read from.
+enum ProcReader {
+    /// The `/proc` of the system the task monitor runs on.
+    Local,
+    /// The host's `/proc`, from inside a Flatpak sandbox, as of the last finished copy.
+    Host { copy: HostProc, copier: HostCopier },
+}
+
+impl ProcReader {
+    /// Takes the host's files of the copy that finished since the last refresh, returning
+    /// whether there is anything new to read. Without access to the host, the sandbox's own
+    /// `/proc` is read from then on.
+    fn refresh(&mut self) -> bool {
+        let Self::Host { copy, copier } = self else {
+            return true;
+        };
+        match copier.poll() {
+            Some(Some(latest)) => {
+                *copy = latest;
+                true
+            }
+            Some(None) => {
+                eprintln!("failed to read the host's processes, showing the sandbox's instead");
+                *self = Self::Local;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn pids(&self) -> Vec<u32> {
+        match self {
+            Self::Local => fs::read_dir("/proc")
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Self::Host { copy, .. } => copy.exes.keys().copied().collect(),
+        }
+    }
+
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Local => fs::read(path).ok(),
+            Self::Host { copy, .. } => copy.files.get(path).cloned(),
+        }
+    }
+
+    fn read_to_string(&self, path: &str) -> Option<String> {
+        match self {
+            Self::Local => fs::read_to_string(path).ok(),
+            Self::Host { copy, .. } => copy
+                .files
+                .get(path)
+                .map(|file| String::from_utf8_lossy(file).into_owned()),
+        }
+    }
+
+    fn exe(&self, pid: u32) -> Option<PathBuf> {
+        match self {
+            Self::Local => fs::read_link(format!("/proc/{pid}/exe")).ok(),
+            Self::Host { copy, .. } => copy.exes.get(&pid)?.0.clone(),
+        }
+    }
+
+    fn owner(&self, pid: u32) -> Option<u32> {
+        match self {
+            Self::Local => fs::metadata(format!("/proc/{pid}"))
+                .ok()
+                .map(|metadata| metadata.uid()),
+            Self::Host { copy, .. } => copy.exes.get(&pid)?.1,
+        }
+    }
+}
+
+pub(super) struct ProcfsSource {
+    reader: ProcReader,
+    known: HashMap<u32, KnownProcess>,
+    processes: HashMap<Pid, ProcessSample>,
+    total_ticks: u64,
+    cpu_count: usize,
+    used_swap: u64,
+    page_size: u64,
+}
+
+impl ProcfsSource {
+    pub(super) fn new() -> Self {
+        Self::with_reader(ProcReader::Local)
+    }
+
+    /// Reads the host's processes from inside a Flatpak sandbox, or the sandbox's own once
+    /// the host turns out to be out of reach. The table is empty until the first copy.
+    pub(super) fn host() -> Self {
+        Self::with_reader(ProcReader::Host {
+            copy: HostProc {
+                files: HashMap::new(),
+                exes: HashMap::new(),
+            },
+            copier: HostCopier::start(),
+        })
+    }
+
+    fn with_reader(reader: ProcReader) -> Self {
+        // SAFETY: sysconf has no preconditions.
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        Self {
+            reader,
+            known: HashMap::new(),
+            processes: HashMap::new(),
+            total_ticks: 0,
+            cpu_count: 0,
+            used_swap: 0,
+            page_size: u64::try_from(page_size).unwrap_or(4096),
+        }
+    }
+}
+
+impl SystemSource for ProcfsSource {
+    fn refresh(&mut self) {
+        // CPU usage is measured between two copies, so a refresh without a new one keeps
+        // the last samples.
+        if !self.reader.refresh() {
+            return;
+        }
+        let previous_total_ticks = self.total_ticks;
+        if let Some((total_ticks, cpu_count)) = self
+            .reader
+            .read_to_string("/proc/stat")
+            .and_then(|stat| parse_cpu_times(&stat))
+        {
+            self.total_ticks = total_ticks;
+            self.cpu_count = cpu_count;
+        }
+        let elapsed_ticks = self.total_ticks.saturating_sub(previous_total_ticks);
+        self.used_swap = self
+            .reader
+            .read_to_string("/proc/meminfo")
+            .and_then(|meminfo| parse_used_swap(&meminfo))
+            .unwrap_or(0);
+
+        let mut known = HashMap::with_capacity(self.known.len());
+        let mut processes = HashMap::with_capacity(self.processes.len());
+        for pid in self.reader.pids() {
+            let dir = format!("/proc/{pid}");
+            let Some(stat) = self
+                .reader
+                .read_to_string(&format!("{dir}/stat"))
+                .and_then(|stat| parse_stat(&stat))
+            else {
+                continue;
+            };
+
+            // The command line, executable and owner carry over from the last sample, unless
+            // the pid now belongs to a new process.
+            let previous = self
+                .known
+                .remove(&pid)
+                .zip(self.processes.remove(&Pid::from_u32(pid)))
+                .filter(|(_, sample)| sample.start_time == stat.start_time);
+            let (name, exe, cmd, user_id, previous) = match previous {
+                Some((process, sample)) => (
+                    Some(sample.name).filter(|name| **name == stat.name),
+                    sample.exe,
+                    sample.cmd,
+                    sample.user_id,
+                    Some(process),
+                ),
+                None => (
+                    None,
+                    self.reader.exe(pid).map(Arc::from),
+                    self.reader
+                        .read(&format!("{dir}/cmdline"))
+                        .map(|cmdline| parse_cmdline(&cmdline))
+                        .unwrap_or_default()
+                        .into(),
+                    self.reader.owner(pid),
+                    None,
+                ),
+            };
+            let io = self
+                .reader
+                .read_to_string(&format!("{dir}/io"))
+                .map(|io| parse_io(&io))
+                .unwrap_or_default();
+            let cpu_usage = match &previous {
+                Some(process) if elapsed_ticks > 0 => {
+                    stat.cpu_ticks.saturating_sub(process.cpu_ticks) as f32 / elapsed_ticks as f32
+                        * self.cpu_count as f32
+                        * 100.0
+                }
+                _ => 0.0,
+            };
+            let (read_bytes, written_bytes) = previous.map_or((0, 0), |process| {
+                (
+                    io.0.saturating_sub(process.io.0),
+                    io.1.saturating_sub(process.io.1),
+                )
+            });
+            let memory = self
+                .reader
+                .read_to_string(&format!("{dir}/statm"))
+                .and_then(|statm| parse_statm_resident(&statm))
+                .unwrap_or(0)
+                * self.page_size;
+
+            known.insert(
+                pid,
+                KnownProcess {
+                    cpu_ticks: stat.cpu_ticks,
+                    io,
+                },
+            );
+            processes.insert(
+                Pid::from_u32(pid),
+                ProcessSample {
+                    pid,
+                    parent: stat.parent,
+                    start_time: stat.start_time,
+                    name: name.unwrap_or_else(|| stat.name.into()),
+                    exe,
+                    cmd,
+                    user_id,
+                    state: stat.state,
+                    cpu_usage,
+                    memory,
+                    threads: Some(stat.threads),
+                    read_bytes,
+                    written_bytes,
+                },
+            );
+        }
+        self.known = known;
+        self.processes = processes;
+    }
+
+    fn processes(&self) -> &HashMap<Pid, ProcessSample> {
+        &self.processes
+    }
+
+    /// Read from the kernel, since in a sandbox the task monitor's own pid is not the one
+    /// the host knows it by.
+    fn current_user_id(&self) -> Option<u32> {
+        // SAFETY: getuid has no preconditions and cannot fail.
+        Some(unsafe { libc::getuid() })
+    }
+
+    fn cpu_count(&self) -> usize {
+        self.cpu_count
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.used_swap
+    }
+
+    fn signal(&self, pid: u32, signal: Signal) -> bool {
+        let Some((number, name)) = signal_id(signal) else {
+            return false;
+        };
+        match &self.reader {
+            ProcReader::Local => {
+                let Ok(pid) = i32::try_from(pid) else {
+                    return false;
+                };
+                // SAFETY: kill has no memory preconditions.
+                unsafe { libc::kill(pid, number) == 0 }
+            }
+            ProcReader::Host { .. } => signal_host_process(pid, name),
+        }
+    }
+
+    fn proc_dir(&self, pid: u32) -> Option<PathBuf> {
+        match self.reader {
+            ProcReader::Local => Some(PathBuf::from(format!("/proc/{pid}"))),
+            ProcReader::Host { .. } => None,
+        }
+    }
+
+    fn read_proc_file(&self, pid: u32, file: &str) -> Option<Vec<u8>> {
+        self.reader.read(&format!("/proc/{pid}/{file}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        StatFields, parse_cmdline, parse_cpu_times, parse_exe_lines, parse_stat, parse_used_swap,
+        split_head_output,
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_proc_files() {
+        let stat = "4242 (Web Content (x)) S 1 4242 4242 0 -1 4194560 12 0 0 0 5 3 0 0 20 0 \
+                    31 0 8123 3221225472 51234 18446744073709551615 1 1 0 0 0 0 0 4096";
+        assert_eq!(
+            parse_stat(stat),
+            Some(StatFields {
+                name: "Web Content (x)".to_string(),
+                state: 'S',
+                parent: Some(1),
+                cpu_ticks: 8,
+                threads: 31,
+                start_time: 8123,
+            })
+        );
+        assert_eq!(
+            parse_stat("1 (systemd) S 0 1").map(|stat| stat.parent),
+            None
+        );
+
+        assert_eq!(
+            parse_cmdline(b"/usr/bin/gedit\0--new-window\0\0notes.txt\0"),
+            vec!["/usr/bin/gedit", "--new-window", "", "notes.txt"]
+        );
+        assert!(parse_cmdline(b"").is_empty());
+
+        let proc_stat = "cpu  100 5 50 1000 20 3 2 1 40 0\ncpu0 50 2 25 500 10 1 1 0 20 0\n\
+                         cpu1 50 3 25 500 10 2 1 1 20 0\nintr 12345\n";
+        assert_eq!(parse_cpu_times(proc_stat), Some((1181, 2)));
+
+        let meminfo = "MemTotal: 16000000 kB\nSwapTotal: 8388604 kB\nSwapFree: 8388092 kB\n";
+        assert_eq!(parse_used_swap(meminfo), Some(512 * 1024));
+    }
+
+    #[test]
+    fn parses_host_proc_copy() {
+        let output = b"==> /proc/stat <==\ncpu  1 2 3\n\n==> /proc/42/cmdline <==\nbash\0-l\0\n\
+                       ==> /proc/42/io <==\nread_bytes: 7\n\n\
+                       ==> exe <==\n/proc/42/exe\t/usr/bin/bash\t1000\n/proc/1/exe\t\t0\n";
+        let mut files = split_head_output(output);
+        assert_eq!(files["/proc/stat"], b"cpu  1 2 3\n");
+        assert_eq!(files["/proc/42/cmdline"], b"bash\0-l\0");
+        assert_eq!(files["/proc/42/io"], b"read_bytes: 7\n");
+
+        let exes = parse_exe_lines(&String::from_utf8_lossy(&files.remove("exe").unwrap()));
+        assert_eq!(
+            exes[&42],
+            (Some(PathBuf::from("/usr/bin/bash")), Some(1000))
+        );
+        assert_eq!(exes[&1], (None, Some(0)));
+    }
+}
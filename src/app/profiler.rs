@@ -0,0 +1,411 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! "Profile for 10 s": samples where the selected app spends its time and lists the hottest
+//! symbols. `perf record` is used when it is installed and allowed to attach; otherwise the
+//! threads' kernel stacks in `/proc/<pid>/task/*/stack` are sampled, falling back to
+//! `wchan` where the stacks need root, which still shows what a stuck app is waiting on.
+
+use super::*;
+
+pub(super) const PROFILE_DURATION: Duration = Duration::from_secs(10);
+const STACK_SAMPLE_INTERVAL: Duration = Duration::from_millis(20);
+/// Kernel frames kept per sampled stack, innermost first.
+const STACK_DEPTH: usize = 3;
+const TOP_SYMBOLS: usize = 30;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum ProfileSource {
+    Perf,
+    KernelStacks,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ProfileEntry {
+    name: String,
+    percent: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProfileReport {
+    source: ProfileSource,
+    entries: Vec<ProfileEntry>,
+    /// Why perf could not be used, when it is installed but failed.
+    perf_error: Option<String>,
+}
+
+#[derive(Debug)]
+enum ProfileStatus {
+    Running,
+    Done(ProfileReport),
+}
+
+/// The profile of the app whose results are open.
+#[derive(Debug)]
+pub(super) struct Profile {
+    app_id: String,
+    name: String,
+    status: ProfileStatus,
+}
+
+/// Symbols and their share of samples from `perf report --stdio --sort symbol`, whose lines
+/// look like `    23.45%  [k] do_syscall_64`.
+fn parse_perf_report(report: &str) -> Vec<ProfileEntry> {
+    report
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (percent, rest) = line.split_once('%')?;
+            let percent = percent.trim().parse::<f32>().ok()?;
+            let rest = rest.trim();
+            let name = rest
+                .strip_prefix("[.]")
+                .or_else(|| rest.strip_prefix("[k]"))
+                .unwrap_or(rest)
+                .trim();
+            (!name.is_empty()).then(|| ProfileEntry {
+                name: name.to_string(),
+                percent,
+            })
+        })
+        .take(TOP_SYMBOLS)
+        .collect()
+}
+
+/// Function names from a kernel stack, innermost first, e.g. `do_sys_poll` from
+/// `[<0>] do_sys_poll+0x3c1/0x5b0`. Scheduler frames, on top of every sleeping thread's
+/// stack, are left out.
+fn parse_kernel_stack(stack: &str) -> Vec<String> {
+    stack
+        .lines()
+        .filter_map(|line| {
+            let frame = line.split_once(']').map_or(line, |(_, frame)| frame).trim();
+            let name = frame.split_once('+').map_or(frame, |(name, _)| name);
+            (!name.is_empty() && !name.contains("schedule")).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// The state letter of a thread's stat line, e.g. `R` or `S`.
+fn parse_stat_state(stat: &str) -> Option<char> {
+    stat.rsplit_once(')')?.1.trim_start().chars().next()
+}
+
+/// Shares of samples per stack, hottest first.
+fn rank_samples(counts: HashMap<String, usize>) -> Vec<ProfileEntry> {
+    let total = counts.values().sum::<usize>().max(1) as f32;
+    let mut entries = counts
+        .into_iter()
+        .map(|(name, count)| ProfileEntry {
+            name,
+            percent: count as f32 * 100.0 / total,
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|left, right| {
+        right
+            .percent
+            .total_cmp(&left.percent)
+            .then_with(|| left.name.cmp(&right.name))
+    });
+    entries.truncate(TOP_SYMBOLS);
+    entries
+}
+
+/// What a thread is doing right now: running, its innermost kernel frames, or the kernel
+/// function it waits in.
+fn sample_thread(task: &Path) -> Option<String> {
+    let stat = fs::read_to_string(task.join("stat")).ok()?;
+    if parse_stat_state(&stat)? == 'R' {
+        return Some("[running]".to_string());
+    }
+    let frames = fs::read_to_string(task.join("stack"))
+        .map(|stack| parse_kernel_stack(&stack))
+        .unwrap_or_default();
+    if !frames.is_empty() {
+        return Some(
+            frames
+                .into_iter()
+                .take(STACK_DEPTH)
+                .collect::<Vec<_>>()
+                .join(" ← "),
+        );
+    }
+    fs::read_to_string(task.join("wchan"))
+        .ok()
+        .map(|wchan| wchan.trim().to_string())
+        .filter(|wchan| !wchan.is_empty() && wchan != "0")
+}
+
+/// Samples the threads below each process's `/proc` directory.
+fn sample_kernel_stacks(proc_dirs: &[(u32, PathBuf)]) -> Vec<ProfileEntry> {
+    let mut counts = HashMap::<String, usize>::new();
+    let started = Instant::now();
+    while started.elapsed() < PROFILE_DURATION {
+        for (_, proc_dir) in proc_dirs {
+            let Ok(tasks) = fs::read_dir(proc_dir.join("task")) else {
+                continue;
+            };
+            for task in tasks.flatten() {
+                if let Some(sample) = sample_thread(&task.path()) {
+                    *counts.entry(sample).or_default() += 1;
+                }
+            }
+        }
+        std::thread::sleep(STACK_SAMPLE_INTERVAL);
+    }
+    rank_samples(counts)
+}
+
+/// Records the processes with perf for the profile's duration, then reports the hottest
+/// symbols. Fails with perf's last error line, e.g. when `perf_event_paranoid` forbids it.
+fn profile_with_perf(pids: &[u32]) -> Result<Vec<ProfileEntry>, String> {
+    // The recording goes into a private directory with a new name each run, so no one else
+    // can plant or read it. The directory is deleted when it goes out of scope.
+    let directory = tempfile::Builder::new()
+        .prefix("cosmic-task-monitor-perf-")
+        .tempdir_in(
+            env::var_os("XDG_RUNTIME_DIR")
+                .filter(|runtime_dir| !runtime_dir.is_empty())
+                .map_or_else(env::temp_dir, PathBuf::from),
+        )
+        .map_err(|err| err.to_string())?;
+    let data = directory.path().join("perf.data");
+    let pid_list = pids
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let last_error_line = |stderr: &[u8]| {
+        String::from_utf8_lossy(stderr)
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .unwrap_or("perf failed")
+            .to_string()
+    };
+
+    let record = Command::new("perf")
+        .args(["record", "-F", "99", "-g", "-o"])
+        .arg(&data)
+        .args(["-p", &pid_list, "--", "sleep"])
+        .arg(PROFILE_DURATION.as_secs().to_string())
+        .stdout(Stdio::null())
+        .output()
+        .map_err(|err| err.to_string())?;
+    if !record.status.success() {
+        return Err(last_error_line(&record.stderr));
+    }
+
+    let report = Command::new("perf")
+        .args(["report", "-i"])
+        .arg(&data)
+        .args(["--stdio", "--no-children", "--sort", "symbol", "-g", "none"])
+        .output()
+        .map_err(|err| err.to_string())?;
+    if !report.status.success() {
+        return Err(last_error_line(&report.stderr));
+    }
+    Ok(parse_perf_report(&String::from_utf8_lossy(&report.stdout)))
+}
+
+fn run_profile(proc_dirs: Vec<(u32, PathBuf)>) -> ProfileReport {
+    let pids = proc_dirs.iter().map(|(pid, _)| *pid).collect::<Vec<_>>();
+    let perf_installed = Command::new("perf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    let perf_error = if perf_installed {
+        match profile_with_perf(&pids) {
+            Ok(entries) => {
+                return ProfileReport {
+                    source: ProfileSource::Perf,
+                    entries,
+                    perf_error: None,
+                };
+            }
+            Err(err) => Some(err),
+        }
+    } else {
+        None
+    };
+    ProfileReport {
+        source: ProfileSource::KernelStacks,
+        entries: sample_kernel_stacks(&proc_dirs),
+        perf_error,
+    }
+}
+
+impl AppModel {
+    /// Starts profiling the selected app in the background and shows the results drawer.
+    pub(super) fn profile_selected_application(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            return Task::none();
+        };
+        let app_id = entry.app_id.clone();
+        // perf and the kernel stacks need the processes' /proc, which a sandbox lacks for
+        // host processes.
+        let proc_dirs = entry
+            .pids
+            .iter()
+            .filter_map(|pid| Some((*pid, self.process_source.proc_dir(*pid)?)))
+            .collect::<Vec<_>>();
+        self.profile = Some(Profile {
+            app_id: app_id.clone(),
+            name: entry.display_name.clone(),
+            status: ProfileStatus::Running,
+        });
+        self.context_page = ContextPage::Profile;
+        self.core.window.show_context = true;
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || run_profile(proc_dirs))
+                    .await
+                    .ok()
+            },
+            move |report| cosmic::Action::App(Message::ProfileFinished { app_id, report }),
+        )
+    }
+
+    /// Shows the results, unless the drawer was closed or another app is profiled by now.
+    pub(super) fn set_profile_report(&mut self, app_id: String, report: Option<ProfileReport>) {
+        let Some(profile) = self
+            .profile
+            .as_mut()
+            .filter(|profile| profile.app_id == app_id)
+        else {
+            return;
+        };
+        profile.status = ProfileStatus::Done(report.unwrap_or(ProfileReport {
+            source: ProfileSource::KernelStacks,
+            entries: Vec::new(),
+            perf_error: None,
+        }));
+    }
+
+    /// Returns to the app's actions, or closes the drawer when the split view shows them.
+    pub(super) fn close_profile(&mut self) {
+        self.profile = None;
+        if self.config.split_view {
+            self.core.window.show_context = false;
+        } else {
+            self.context_page = ContextPage::ProcessActions;
+        }
+    }
+
+    /// Whether the selected app is being profiled right now.
+    pub(super) fn selected_app_profiling(&self) -> bool {
+        self.profile.as_ref().is_some_and(|profile| {
+            matches!(profile.status, ProfileStatus::Running)
+                && self
+                    .selected_process
+                    .as_ref()
+                    .is_some_and(|selected| selected.app_id == profile.app_id)
+        })
+    }
+
+    pub(super) fn profile_drawer(&self) -> Element<'_, Message> {
+        let Some(profile) = self.profile.as_ref() else {
+            return widget::text(fl!("process-none-selected")).into();
+        };
+        let report = match &profile.status {
+            ProfileStatus::Running => {
+                return widget::text(fl!(
+                    "profile-running",
+                    name = profile.name.clone(),
+                    seconds = PROFILE_DURATION.as_secs()
+                ))
+                .into();
+            }
+            ProfileStatus::Done(report) => report,
+        };
+
+        let source = match report.source {
+            ProfileSource::Perf => fl!("profile-source-perf"),
+            ProfileSource::KernelStacks => fl!("profile-source-stacks"),
+        };
+        let mut content =
+            widget::column::with_capacity(3 + report.entries.len())
+                .push(widget::text(source).size(12))
+                .push_maybe(report.perf_error.as_ref().map(|err| {
+                    widget::text(fl!("profile-perf-failed", error = err.clone())).size(12)
+                }))
+                .spacing(8)
+                .width(Length::Fill);
+        if report.entries.is_empty() {
+            content = content.push(widget::text(fl!("profile-no-samples")));
+        }
+        for entry in &report.entries {
+            content = content.push(
+                widget::row::with_capacity(2)
+                    .push(
+                        widget::text(units::format_percent(entry.percent, 1))
+                            .size(12)
+                            .width(Length::Fixed(56.0)),
+                    )
+                    .push(
+                        widget::text(entry.name.clone())
+                            .size(12)
+                            .width(Length::Fill),
+                    )
+                    .spacing(8),
+            );
+        }
+        content.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProfileEntry, parse_kernel_stack, parse_perf_report, parse_stat_state};
+
+    #[test]
+    fn parses_perf_report_symbols() {
+        let report = "# Samples: 987  of event 'cpu-clock:ppp'\n\
+                      #\n\
+                      # Overhead  Symbol\n\
+                      # ........  ......................\n\
+                      #\n\
+                      \x20   41.20%  [.] vkQueueSubmit\n\
+                      \x20    7.05%  [k] do_syscall_64\n\
+                      \x20    0.10%  [.] 0x00007f2a1c0281d0\n\
+                      \n";
+        assert_eq!(
+            parse_perf_report(report),
+            vec![
+                ProfileEntry {
+                    name: "vkQueueSubmit".to_string(),
+                    percent: 41.2,
+                },
+                ProfileEntry {
+                    name: "do_syscall_64".to_string(),
+                    percent: 7.05,
+                },
+                ProfileEntry {
+                    name: "0x00007f2a1c0281d0".to_string(),
+                    percent: 0.1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_kernel_stacks_without_scheduler_frames() {
+        let stack = "[<0>] __schedule+0x2b1/0x6f0\n\
+                     [<0>] schedule+0x5e/0xd0\n\
+                     [<0>] schedule_hrtimeout_range+0x9c/0x100\n\
+                     [<0>] do_sys_poll+0x3c1/0x5b0\n\
+                     [<0>] __x64_sys_poll+0xb6/0x140\n";
+        assert_eq!(
+            parse_kernel_stack(stack),
+            vec!["do_sys_poll", "__x64_sys_poll"]
+        );
+        assert_eq!(parse_stat_state("812 (Web (x) R) S 1 812"), Some('S'));
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Named view profiles, such as "Gaming" or "Development", that save how the Apps page is
+//! laid out and restore it from the View menu.
+
+use super::*;
+use crate::config::ViewProfile;
+
+impl AppModel {
+    fn current_view_profile(&self, name: String) -> ViewProfile {
+        let sort_state = self.processes_page.sort_state;
+        ViewProfile {
+            name,
+            view_mode: self.apps_view_mode,
+            group_by: self.processes_page.group_by,
+            sort_column: sort_state.column,
+            sort_descending: sort_state.direction == SortDirection::Desc,
+            category_filter: self.config.category_filter.clone(),
+            custom_columns: self.config.custom_columns.clone(),
+        }
+    }
+
+    fn set_view_profiles(&mut self, profiles: Vec<ViewProfile>) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_view_profiles(handler, profiles) {
+                eprintln!("failed to save view profiles: {err}");
+            }
+        } else {
+            self.config.view_profiles = profiles;
+        }
+    }
+
+    /// Saves the current layout under the name typed in the dialog, replacing a profile
+    /// with the same name.
+    pub(super) fn save_view_profile(&mut self) {
+        let Some(name) = self.profile_dialog_name.take() else {
+            return;
+        };
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+
+        let profile = self.current_view_profile(name);
+        let mut profiles = self.config.view_profiles.clone();
+        match profiles.iter_mut().find(|saved| saved.name == profile.name) {
+            Some(saved) => *saved = profile,
+            None => profiles.push(profile),
+        }
+        self.set_view_profiles(profiles);
+    }
+
+    pub(super) fn delete_view_profile(&mut self, index: usize) {
+        let mut profiles = self.config.view_profiles.clone();
+        if index < profiles.len() {
+            profiles.remove(index);
+            self.set_view_profiles(profiles);
+        }
+    }
+
+    pub(super) fn apply_view_profile(&mut self, index: usize) {
+        let Some(profile) = self.config.view_profiles.get(index).cloned() else {
+            return;
+        };
+
+        self.apps_view_mode = profile.view_mode;
+        if self.processes_page.group_by != profile.group_by {
+            if profile.group_by == GroupBy::User {
+                self.users.refresh();
+            }
+            self.processes_page.group_by = profile.group_by;
+            self.processes_page.baseline = None;
+        }
+        self.processes_page.sort_state = SortState {
+            column: profile.sort_column,
+            direction: if profile.sort_descending {
+                SortDirection::Desc
+            } else {
+                SortDirection::Asc
+            },
+        };
+        self.set_category_filter(profile.category_filter);
+        // Sets the columns and refreshes, which also applies grouping and sort.
+        self.set_custom_columns(profile.custom_columns);
+    }
+
+    pub(super) fn view_profile_menu_items(&self) -> Vec<menu::Item<MenuAction, String>> {
+        let mut items = self
+            .config
+            .view_profiles
+            .iter()
+            .enumerate()
+            .map(|(index, profile)| {
+                menu::Item::Button(profile.name.clone(), None, MenuAction::ApplyProfile(index))
+            })
+            .collect::<Vec<_>>();
+        items.push(menu::Item::Button(
+            fl!("profile-save"),
+            None,
+            MenuAction::SaveProfile,
+        ));
+        items
+    }
+
+    pub(super) fn save_profile_dialog(&self) -> Option<Element<'_, Message>> {
+        let name = self.profile_dialog_name.as_ref()?;
+        let save_button = widget::button::suggested(fl!("profile-save-confirm"))
+            .on_press_maybe((!name.trim().is_empty()).then_some(Message::SaveViewProfile));
+
+        Some(
+            widget::dialog()
+                .title(fl!("profile-save-title"))
+                .body(fl!("profile-save-description"))
+                .control(
+                    widget::text_input(fl!("profile-name"), name.as_str())
+                        .on_input(Message::SetProfileName)
+                        .on_submit(|_| Message::SaveViewProfile),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("autostart-modal-cancel"))
+                        .on_press(Message::CancelSaveViewProfile),
+                )
+                .primary_action(save_button)
+                .into(),
+        )
+    }
+}
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Protected apps, such as a password manager or an unsaved editor session. Stopping,
+//! killing, or restarting one asks for its name to be typed first, and batch actions leave
+//! them alone.
+
+use super::*;
+
+#[derive(Debug)]
+pub(super) enum StopKind {
+    /// Close the window, then escalate to SIGTERM and SIGKILL.
+    Stop,
+    Kill,
+    /// SIGTERM, then SIGKILL, and start the app again once it has exited.
+    Restart(Relaunch),
+}
+
+#[derive(Debug)]
+pub(super) struct ProtectedStop {
+    app_id: String,
+    display_name: String,
+    kind: StopKind,
+    typed: String,
+}
+
+impl AppModel {
+    pub(super) fn is_protected(&self, app_id: &str) -> bool {
+        self.config.protected_apps.iter().any(|id| id == app_id)
+    }
+
+    pub(super) fn toggle_selected_app_protected(&mut self) {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return;
+        };
+        let mut protected_apps = self.config.protected_apps.clone();
+        if let Some(index) = protected_apps.iter().position(|id| *id == selected.app_id) {
+            protected_apps.remove(index);
+        } else {
+            protected_apps.push(selected.app_id.clone());
+        }
+
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_protected_apps(handler, protected_apps) {
+                eprintln!("failed to save protected apps: {err}");
+            }
+        } else {
+            self.config.protected_apps = protected_apps;
+        }
+    }
+
+    /// Stops or kills an app, or asks for confirmation first if it is protected.
+    pub(super) fn request_stop(
+        &mut self,
+        app_id: String,
+        kind: StopKind,
+    ) -> Task<cosmic::Action<Message>> {
+        if !self.is_protected(&app_id) {
+            return self.stop_app(app_id, kind);
+        }
+
+        let display_name = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == app_id)
+            .map_or_else(|| app_id.clone(), |entry| entry.display_name.clone());
+        self.protected_stop = Some(ProtectedStop {
+            app_id,
+            display_name,
+            kind,
+            typed: String::new(),
+        });
+        Task::none()
+    }
+
+    pub(super) fn request_stop_selected(
+        &mut self,
+        kind: StopKind,
+    ) -> Task<cosmic::Action<Message>> {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return Task::none();
+        };
+        self.request_stop(selected.app_id.clone(), kind)
+    }
+
+    fn stop_app(&mut self, app_id: String, kind: StopKind) -> Task<cosmic::Action<Message>> {
+        match kind {
+            StopKind::Stop => self.quit_application(app_id),
+            StopKind::Kill => {
+                self.signal_application(&app_id, Signal::Kill);
+                Task::none()
+            }
+            StopKind::Restart(relaunch) => self.restart_app(app_id, relaunch),
+        }
+    }
+
+    pub(super) fn set_protected_confirmation(&mut self, typed: String) {
+        if let Some(pending) = self.protected_stop.as_mut() {
+            pending.typed = typed;
+        }
+    }
+
+    pub(super) fn confirm_protected_stop(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(pending) = self.protected_stop.take() else {
+            return Task::none();
+        };
+        if pending.typed.trim() != pending.display_name {
+            self.protected_stop = Some(pending);
+            return Task::none();
+        }
+        self.stop_app(pending.app_id, pending.kind)
+    }
+
+    pub(super) fn selected_app_protected_toggle(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        Some(
+            widget::settings::item(
+                fl!("protected-app"),
+                widget::toggler(self.is_protected(&selected.app_id))
+                    .on_toggle(|_| Message::ToggleSelectedAppProtected),
+            )
+            .into(),
+        )
+    }
+
+    pub(super) fn protected_stop_dialog(&self) -> Option<Element<'_, Message>> {
+        let pending = self.protected_stop.as_ref()?;
+        let confirmed = pending.typed.trim() == pending.display_name;
+        let (title, confirm_label) = match &pending.kind {
+            StopKind::Stop => (
+                fl!("protected-stop-title", name = pending.display_name.as_str()),
+                fl!("process-action-stop"),
+            ),
+            StopKind::Kill => (
+                fl!("protected-kill-title", name = pending.display_name.as_str()),
+                fl!("process-action-kill"),
+            ),
+            StopKind::Restart(_) => (
+                fl!(
+                    "protected-restart-title",
+                    name = pending.display_name.as_str()
+                ),
+                fl!("process-action-restart"),
+            ),
+        };
+
+        Some(
+            widget::dialog()
+                .title(title)
+                .body(fl!(
+                    "protected-confirm-description",
+                    name = pending.display_name.as_str()
+                ))
+                .control(
+                    widget::text_input(pending.display_name.as_str(), pending.typed.as_str())
+                        .on_input(Message::SetProtectedConfirmation)
+                        .on_submit(|_| Message::ConfirmProtectedStop),
+                )
+                .secondary_action(
+                    widget::button::standard(fl!("autostart-modal-cancel"))
+                        .on_press(Message::CancelProtectedStop),
+                )
+                .primary_action(
+                    widget::button::destructive(confirm_label)
+                        .on_press_maybe(confirmed.then_some(Message::ConfirmProtectedStop)),
+                )
+                .into(),
+        )
+    }
+}
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Proton details of Steam games: the compatibility tool a game runs under, from the
+//! `.../<tool>/proton waitforexitandrun` part of its launch command line, and its Wine
+//! prefix in `steamapps/compatdata/<appid>`, whose `config_info` records the Proton build
+//! that last set it up.
+
+use super::*;
+
+/// The compatibility tool's directory name from a launch command line, e.g. `GE-Proton9-20`
+/// from `.../compatibilitytools.d/GE-Proton9-20/proton waitforexitandrun game.exe`. Steam's
+/// own builds live in `steamapps/common` under names with spaces, like `Proton 9.0 (Beta)`.
+fn proton_tool_from_cmdline(cmdline: &str) -> Option<String> {
+    let (end, _) = cmdline.match_indices("/proton").find(|(index, marker)| {
+        let rest = &cmdline[index + marker.len()..];
+        rest.is_empty() || rest.starts_with(' ')
+    })?;
+    let tool = cmdline[..end].rsplit('/').next()?;
+    (!tool.trim().is_empty()).then(|| tool.to_string())
+}
+
+/// The Proton build from the first line of a prefix's `config_info`, e.g.
+/// `1712160582 proton-9.0-1`.
+fn parse_proton_build(config_info: &str) -> Option<String> {
+    let build = config_info.lines().next()?.split_whitespace().last()?;
+    Some(build.to_string())
+}
+
+impl AppModel {
+    /// The prefix directory and Proton build of a Steam game that runs through Proton.
+    pub(super) fn steam_compat_prefix(app_id: &str) -> Option<(PathBuf, Option<String>)> {
+        Self::steam_library_roots()
+            .into_iter()
+            .find_map(|library_root| {
+                let compatdata = Self::steamapps_dir(&library_root)
+                    .join("compatdata")
+                    .join(app_id);
+                let prefix = compatdata.join("pfx");
+                if !prefix.is_dir() {
+                    return None;
+                }
+                let build = fs::read_to_string(compatdata.join("config_info"))
+                    .ok()
+                    .and_then(|config_info| parse_proton_build(&config_info));
+                Some((prefix, build))
+            })
+    }
+
+    /// The compatibility tool named on a game process's command line. Only the Proton
+    /// script's command line names it, but the script is grouped with the game.
+    pub(super) fn proton_tool_for_process(process: &ProcessSample) -> Option<String> {
+        proton_tool_from_cmdline(&process.cmdline())
+    }
+
+    fn selected_steam_meta(&self) -> Option<&SteamAppMeta> {
+        let selected = self.selected_process.as_ref()?;
+        let steam_app_id = selected.app_id.strip_prefix("steam-app-")?;
+        self.steam_apps_by_id.get(steam_app_id)
+    }
+
+    pub(super) fn open_selected_proton_prefix(&self) {
+        let Some(prefix) = self
+            .selected_steam_meta()
+            .and_then(|meta| meta.compat_prefix.as_ref())
+        else {
+            return;
+        };
+        if let Err(err) = open::that_detached(prefix) {
+            eprintln!("failed to open Proton prefix: {err}");
+        }
+    }
+
+    pub(super) fn selected_app_proton(&self) -> Option<Element<'_, Message>> {
+        let meta = self.selected_steam_meta()?;
+        let prefix = meta.compat_prefix.as_ref()?;
+        let tool = meta
+            .proton_tool
+            .clone()
+            .or_else(|| meta.proton_build.clone())
+            .unwrap_or_else(|| fl!("proton-unknown"));
+
+        Some(
+            widget::column::with_capacity(4)
+                .push(widget::text::heading(fl!("proton")))
+                .push(widget::text(tool))
+                .push_maybe(
+                    meta.proton_build
+                        .as_ref()
+                        .filter(|_| meta.proton_tool.is_some())
+                        .map(|build| {
+                            widget::text(fl!("proton-prefix-build", build = build.clone())).size(12)
+                        }),
+                )
+                .push(widget::text(prefix.display().to_string()).size(12))
+                .push(
+                    widget::button::standard(fl!("proton-open-prefix"))
+                        .on_press(Message::OpenSelectedProtonPrefix)
+                        .width(Length::Fill)
+                        .height(Length::Fixed(38.0)),
+                )
+                .spacing(4)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_proton_build, proton_tool_from_cmdline};
+
+    #[test]
+    fn finds_proton_tool_on_command_line() {
+        assert_eq!(
+            proton_tool_from_cmdline(
+                "/usr/bin/python3 /home/user/.local/share/Steam/steamapps/common/\
+                 Proton 9.0 (Beta)/proton waitforexitandrun /games/Game/game.exe"
+            )
+            .as_deref(),
+            Some("Proton 9.0 (Beta)")
+        );
+        assert_eq!(
+            proton_tool_from_cmdline(
+                "/home/user/.steam/root/compatibilitytools.d/GE-Proton9-20/proton"
+            )
+            .as_deref(),
+            Some("GE-Proton9-20")
+        );
+        assert_eq!(
+            proton_tool_from_cmdline("/games/protonmail/protonmail-bridge --noninteractive"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_prefix_proton_build() {
+        assert_eq!(
+            parse_proton_build("1712160582 proton-9.0-1\n/home/user/fonts\n").as_deref(),
+            Some("proton-9.0-1")
+        );
+        assert_eq!(parse_proton_build(""), None);
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Quick actions in the header bar for when the desktop turns sluggish: ending the app that
+//! uses the most CPU, after a confirmation, and dropping the filesystem caches through
+//! `pkexec`, whose password prompt confirms it.
+
+use super::*;
+
+/// Exit code of `pkexec` when the password prompt was dismissed.
+const PKEXEC_DISMISSED: i32 = 126;
+
+#[derive(Debug)]
+pub(super) enum QuickAction {
+    /// Ending the top CPU consumer, waiting for confirmation.
+    ConfirmEnd {
+        app_id: String,
+        display_name: String,
+        cpu_percent: f32,
+    },
+    DroppingCaches,
+    /// The outcome of dropping the caches.
+    Outcome(String),
+}
+
+/// Page cache in bytes, from the `Cached:` line of `/proc/meminfo`.
+fn parse_cached_bytes(meminfo: &str) -> Option<u64> {
+    let line = meminfo.lines().find(|line| line.starts_with("Cached:"))?;
+    let kib = line["Cached:".len()..]
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+fn cached_bytes() -> u64 {
+    fs::read_to_string("/proc/meminfo")
+        .ok()
+        .and_then(|meminfo| parse_cached_bytes(&meminfo))
+        .unwrap_or(0)
+}
+
+/// Writes dirty pages back, then drops the page cache, dentries, and inodes as root. Returns
+/// the bytes of page cache freed, or `None` when the password prompt was dismissed.
+fn drop_caches() -> Result<Option<u64>, String> {
+    let cached_before = cached_bytes();
+    // SAFETY: sync has no preconditions and cannot fail.
+    unsafe { libc::sync() };
+    let mut child = Command::new("pkexec")
+        .args(["tee", "/proc/sys/vm/drop_caches"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| err.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(b"3\n").map_err(|err| err.to_string())?;
+    }
+    let status = child.wait().map_err(|err| err.to_string())?;
+    match status.code() {
+        Some(0) => Ok(Some(cached_before.saturating_sub(cached_bytes()))),
+        Some(PKEXEC_DISMISSED) => Ok(None),
+        _ => Err(fl!("quick-drop-caches-denied")),
+    }
+}
+
+fn quick_action_button(
+    icon_name: &'static str,
+    tooltip: String,
+    message: Option<Message>,
+) -> Element<'static, Message> {
+    widget::tooltip(
+        widget::button::icon(widget::icon::from_name(icon_name)).on_press_maybe(message),
+        widget::text(tooltip),
+        widget::tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+impl AppModel {
+    pub(super) fn quick_action_buttons(&self) -> Vec<Element<'_, Message>> {
+        let idle = self.quick_action.is_none();
+        vec![
+            quick_action_button(
+                "process-stop-symbolic",
+                fl!("quick-end-top-cpu"),
+                idle.then_some(Message::EndTopCpuConsumer),
+            ),
+            quick_action_button(
+                "edit-clear-all-symbolic",
+                fl!("quick-drop-caches"),
+                idle.then_some(Message::DropCaches),
+            ),
+        ]
+    }
+
+    /// Asks to end the app using the most CPU, other than the task monitor itself.
+    pub(super) fn end_top_cpu_consumer(&mut self) {
+        let own_pid = std::process::id();
+        let top = self
+            .process_entries
+            .iter()
+            .filter(|entry| !entry.pids.contains(&own_pid))
+            .max_by(|a, b| a.cpu_percent.total_cmp(&b.cpu_percent));
+        self.quick_action = Some(match top {
+            Some(entry) => QuickAction::ConfirmEnd {
+                app_id: entry.app_id.clone(),
+                display_name: entry.display_name.clone(),
+                cpu_percent: entry.cpu_percent,
+            },
+            None => QuickAction::Outcome(fl!("quick-end-top-cpu-none")),
+        });
+    }
+
+    pub(super) fn confirm_quick_action(&mut self) -> Task<cosmic::Action<Message>> {
+        match self.quick_action.take() {
+            Some(QuickAction::ConfirmEnd { app_id, .. }) => {
+                self.request_stop(app_id, StopKind::Stop)
+            }
+            other => {
+                self.quick_action = other;
+                Task::none()
+            }
+        }
+    }
+
+    pub(super) fn start_drop_caches(&mut self) -> Task<cosmic::Action<Message>> {
+        self.quick_action = Some(QuickAction::DroppingCaches);
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(drop_caches)
+                    .await
+                    .unwrap_or_else(|err| Err(err.to_string()))
+            },
+            |result| cosmic::Action::App(Message::CachesDropped(result)),
+        )
+    }
+
+    pub(super) fn finish_drop_caches(&mut self, result: Result<Option<u64>, String>) {
+        self.quick_action = match result {
+            Ok(Some(freed)) => Some(QuickAction::Outcome(fl!(
+                "quick-drop-caches-done",
+                size = units::format_bytes(freed)
+            ))),
+            Ok(None) => None,
+            Err(error) => Some(QuickAction::Outcome(fl!(
+                "quick-drop-caches-failed",
+                error = error
+            ))),
+        };
+    }
+
+    pub(super) fn close_quick_action(&mut self) {
+        // Closing while the caches are dropped would re-enable the buttons too early.
+        if !matches!(self.quick_action, Some(QuickAction::DroppingCaches)) {
+            self.quick_action = None;
+        }
+    }
+
+    pub(super) fn quick_action_dialog(&self) -> Option<Element<'_, Message>> {
+        let close =
+            |label: String| widget::button::standard(label).on_press(Message::CloseQuickAction);
+        let dialog = match self.quick_action.as_ref()? {
+            QuickAction::ConfirmEnd {
+                display_name,
+                cpu_percent,
+                ..
+            } => widget::dialog()
+                .title(fl!("quick-end-top-cpu-title", name = display_name.as_str()))
+                .body(fl!(
+                    "quick-end-top-cpu-body",
+                    name = display_name.as_str(),
+                    cpu = units::format_percent(*cpu_percent, 1)
+                ))
+                .secondary_action(close(fl!("autostart-modal-cancel")))
+                .primary_action(
+                    widget::button::destructive(fl!("process-action-stop"))
+                        .on_press(Message::ConfirmQuickAction),
+                ),
+            // The password prompt is on screen meanwhile, so there is nothing to show.
+            QuickAction::DroppingCaches => return None,
+            QuickAction::Outcome(outcome) => widget::dialog()
+                .title(fl!("quick-actions-title"))
+                .body(outcome.clone())
+                .primary_action(close(fl!("settings-transfer-close"))),
+        };
+        Some(dialog.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_cached_bytes;
+
+    #[test]
+    fn parses_page_cache_size() {
+        let meminfo = "MemTotal:       32594260 kB\nBuffers:          412340 kB\n\
+                       Cached:          8123456 kB\nSwapCached:            0 kB\n";
+        assert_eq!(parse_cached_bytes(meminfo), Some(8_123_456 * 1024));
+        assert_eq!(parse_cached_bytes("MemTotal: 1 kB\n"), None);
+    }
+}
@@ -0,0 +1,265 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Graceful quit: ask the compositor to close the app's windows first, then escalate to
+//! SIGTERM and finally SIGKILL if the app is still running after each timeout.
+//!
+//! Restarts stop the app from SIGTERM on, checking on a timer whether it has exited, and
+//! start it again once it has, so the window is not blocked while the app shuts down.
+
+use super::install_size::command_stdout;
+use super::*;
+
+const CLOSE_WINDOW_TIMEOUT: Duration = Duration::from_secs(5);
+const TERMINATE_TIMEOUT: Duration = Duration::from_secs(3);
+/// How long a restart waits after SIGKILL before starting the app again regardless.
+const KILL_TIMEOUT: Duration = Duration::from_secs(1);
+/// How often a restarting app is checked for having exited.
+const RESTART_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum QuitStage {
+    Terminate,
+    Kill,
+}
+
+/// What a restart starts once the app has exited.
+#[derive(Debug)]
+pub(super) enum Relaunch {
+    /// The first way of launching the app that starts, with its launch settings.
+    Launch {
+        candidates: Vec<LaunchCandidate>,
+        settings: LaunchSettings,
+    },
+    /// The app's own commands, with their output captured to a new log.
+    CaptureLog {
+        candidates: Vec<LaunchCandidate>,
+        settings: LaunchSettings,
+    },
+    /// A command in a terminal, such as strace running the app's command line.
+    InTerminal {
+        command: Vec<String>,
+        cwd: Option<PathBuf>,
+    },
+}
+
+/// A restart waiting for the app to exit.
+#[derive(Debug)]
+pub(super) struct PendingRestart {
+    relaunch: Relaunch,
+    /// When the app is killed, or, once it was, started again regardless.
+    deadline: Instant,
+    killed: bool,
+}
+
+/// Whether `wlrctl toplevel close app_id:<app_id>` closes only the app's windows, given
+/// `wlrctl toplevel list` output, whose lines look like `app_id: title`. wlrctl matches app
+/// ids by substring, so an id that is part of another listed one, like `foot` of
+/// `footclient`, would close that app's windows too.
+fn closes_only_app(listing: &str, app_id: &str) -> bool {
+    let mut listed = listing
+        .lines()
+        .filter_map(|line| line.split_once(": ").map(|(listed, _)| listed));
+    let mut found = false;
+    listed.all(|listed| {
+        found |= listed == app_id;
+        listed == app_id || !listed.contains(app_id)
+    }) && found
+}
+
+/// The ids of the windows owned by one of `pids` in `wmctrl -lp` output, whose lines start
+/// with the window id, desktop, and pid.
+fn x11_windows_for_pids(listing: &str, pids: &[u32]) -> Vec<String> {
+    listing
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let window = fields.next()?;
+            let pid = fields.nth(1)?.parse::<u32>().ok()?;
+            pids.contains(&pid).then(|| window.to_string())
+        })
+        .collect()
+}
+
+/// Requests a window close through the compositor's foreign-toplevel protocol for the
+/// Wayland windows with the app's exact app id, and through the X11 window manager for the
+/// XWayland windows of its processes. Returns whether any request was accepted.
+fn request_window_close(window_app_id: Option<&str>, pids: &[u32]) -> bool {
+    let wayland = window_app_id.is_some_and(|app_id| {
+        command_stdout("wlrctl", &["toplevel", "list"])
+            .is_some_and(|listing| closes_only_app(&listing, app_id))
+            && command_stdout(
+                "wlrctl",
+                &["toplevel", "close", &format!("app_id:{app_id}")],
+            )
+            .is_some()
+    });
+    let x11 = command_stdout("wmctrl", &["-lp"]).is_some_and(|listing| {
+        x11_windows_for_pids(&listing, pids)
+            .iter()
+            .fold(false, |closed, window| {
+                command_stdout("wmctrl", &["-i", "-c", window]).is_some() || closed
+            })
+    });
+    wayland || x11
+}
+
+fn schedule_quit_stage(
+    app_id: String,
+    stage: QuitStage,
+    delay: Duration,
+) -> Task<cosmic::Action<Message>> {
+    Task::perform(tokio::time::sleep(delay), move |_| {
+        cosmic::Action::App(Message::ContinueQuit { app_id, stage })
+    })
+}
+
+fn schedule_restart_check(app_id: String) -> Task<cosmic::Action<Message>> {
+    Task::perform(tokio::time::sleep(RESTART_POLL_INTERVAL), move |_| {
+        cosmic::Action::App(Message::ContinueRestart(app_id))
+    })
+}
+
+impl AppModel {
+    pub(super) fn quit_application(&mut self, app_id: String) -> Task<cosmic::Action<Message>> {
+        // Only desktop apps have a known Wayland app id; X11 windows are found by pid, and an
+        // app without either starts at SIGTERM.
+        let window_app_id = self
+            .desktop_meta_for_app_id(&app_id)
+            .and_then(|meta| meta.desktop_entry_id)
+            .map(|entry_id| {
+                entry_id
+                    .strip_suffix(".desktop")
+                    .unwrap_or(&entry_id)
+                    .to_string()
+            });
+
+        // wlrctl and wmctrl run off the UI thread; the Terminate stage follows right away
+        // when no window could be asked to close.
+        let pids = self.app_pids(&app_id);
+        Task::perform(
+            async move {
+                let closing = tokio::task::spawn_blocking(move || {
+                    request_window_close(window_app_id.as_deref(), &pids)
+                })
+                .await
+                .unwrap_or(false);
+                if closing {
+                    tokio::time::sleep(CLOSE_WINDOW_TIMEOUT).await;
+                }
+            },
+            move |()| {
+                cosmic::Action::App(Message::ContinueQuit {
+                    app_id,
+                    stage: QuitStage::Terminate,
+                })
+            },
+        )
+    }
+
+    pub(super) fn continue_quit(
+        &mut self,
+        app_id: String,
+        stage: QuitStage,
+    ) -> Task<cosmic::Action<Message>> {
+        if !self.is_app_id_running(&app_id) {
+            self.refresh_processes();
+            return Task::none();
+        }
+
+        match stage {
+            QuitStage::Terminate => {
+                self.signal_application(&app_id, Signal::Term);
+                schedule_quit_stage(app_id, QuitStage::Kill, TERMINATE_TIMEOUT)
+            }
+            QuitStage::Kill => {
+                self.signal_application(&app_id, Signal::Kill);
+                Task::none()
+            }
+        }
+    }
+
+    /// Stops the app with SIGTERM and starts it again once it has exited.
+    pub(super) fn restart_app(
+        &mut self,
+        app_id: String,
+        relaunch: Relaunch,
+    ) -> Task<cosmic::Action<Message>> {
+        self.signal_application(&app_id, Signal::Term);
+        self.pending_restarts.insert(
+            app_id.clone(),
+            PendingRestart {
+                relaunch,
+                deadline: Instant::now() + TERMINATE_TIMEOUT,
+                killed: false,
+            },
+        );
+        schedule_restart_check(app_id)
+    }
+
+    /// Starts a restarting app again once it has exited, killing it if SIGTERM was not
+    /// enough.
+    pub(super) fn continue_restart(&mut self, app_id: String) -> Task<cosmic::Action<Message>> {
+        let running = self.is_app_id_running(&app_id);
+        let Some(pending) = self.pending_restarts.get_mut(&app_id) else {
+            return Task::none();
+        };
+        if running && Instant::now() < pending.deadline {
+            return schedule_restart_check(app_id);
+        }
+        if running && !pending.killed {
+            pending.killed = true;
+            pending.deadline = Instant::now() + KILL_TIMEOUT;
+            self.signal_application(&app_id, Signal::Kill);
+            return schedule_restart_check(app_id);
+        }
+
+        // A killed app still listed is most likely exiting, so it is started again anyway.
+        let Some(pending) = self.pending_restarts.remove(&app_id) else {
+            return Task::none();
+        };
+        match pending.relaunch {
+            Relaunch::Launch {
+                candidates,
+                settings,
+            } => {
+                if !Self::launch_from_candidates(&candidates, &settings) {
+                    eprintln!("failed to start `{app_id}` again");
+                }
+            }
+            Relaunch::CaptureLog {
+                candidates,
+                settings,
+            } => self.launch_with_log_capture(app_id, &candidates, &settings),
+            Relaunch::InTerminal { command, cwd } => self.run_debug_tool(&command, cwd.as_deref()),
+        }
+        self.refresh_processes();
+        Task::none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closes_only_app, x11_windows_for_pids};
+
+    #[test]
+    fn closes_wayland_windows_by_exact_app_id() {
+        let listing = "foot: ~\nfootclient: ~/src\nfirefox: Mozilla Firefox\n";
+        assert!(closes_only_app(listing, "firefox"));
+        assert!(!closes_only_app(listing, "foot"));
+        assert!(!closes_only_app(listing, "fire"));
+        assert!(!closes_only_app(listing, "vkcube"));
+        assert!(!closes_only_app("", "firefox"));
+    }
+
+    #[test]
+    fn closes_x11_windows_by_pid() {
+        let listing = "0x03a00003  0 4242   host Steam\n\
+                       0x03a00009  0 4242   host Friends List\n\
+                       0x04200007  0 42     host Portal 2\n";
+        assert_eq!(
+            x11_windows_for_pids(listing, &[4242]),
+            ["0x03a00003", "0x03a00009"]
+        );
+        assert!(x11_windows_for_pids(listing, &[424]).is_empty());
+    }
+}
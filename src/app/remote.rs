@@ -0,0 +1,321 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Opt-in remote monitoring over HTTP with server-sent events.
+//!
+//! Serves a small read-only page that renders the task list from the same JSON snapshot
+//! used by the exports, streamed to the browser on every refresh. By default it only
+//! listens on the loopback interface, and other machines reach it through a tunnel such as
+//! `ssh -L`. Listening on the local network is a separate opt-in, and then every request has
+//! to carry the random pairing token shown in Settings.
+
+use super::*;
+use std::sync::Mutex;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, tcp::OwnedReadHalf, tcp::OwnedWriteHalf};
+
+const REMOTE_PORT: u16 = 9847;
+/// Most bytes read of a request line and its headers, so a client cannot grow them forever.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+/// Time a client has to send its request before it is dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Clients served at once; further connections are closed right away.
+const MAX_REMOTE_CLIENTS: usize = 16;
+const REMOTE_PAGE: &str = include_str!("../../resources/remote.html");
+/// Header a client can send the pairing token in instead of the `token` query parameter.
+const TOKEN_HEADER: &str = "x-remote-token";
+
+/// Latest snapshot JSON published by the main refresh loop.
+static REMOTE_SNAPSHOT: OnceLock<Mutex<String>> = OnceLock::new();
+
+fn shared_remote_snapshot() -> &'static Mutex<String> {
+    REMOTE_SNAPSHOT.get_or_init(|| Mutex::new("{}".to_string()))
+}
+
+fn current_remote_snapshot() -> String {
+    shared_remote_snapshot()
+        .lock()
+        .map(|snapshot| snapshot.clone())
+        .unwrap_or_else(|_| "{}".to_string())
+}
+
+async fn write_response(writer: &mut OwnedWriteHalf, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = writer.write_all(response.as_bytes()).await;
+}
+
+async fn stream_remote_events(writer: &mut OwnedWriteHalf) {
+    let header = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: keep-alive\r\n\r\n";
+    if writer.write_all(header.as_bytes()).await.is_err() {
+        return;
+    }
+
+    let mut interval = tokio::time::interval(PROCESS_REFRESH_INTERVAL);
+    loop {
+        interval.tick().await;
+        let event = format!("data: {}\n\n", current_remote_snapshot());
+        if writer.write_all(event.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// 32 hex digits from the kernel's random source, for pairing LAN clients.
+pub(super) fn generate_remote_token() -> std::io::Result<String> {
+    use std::io::Read;
+
+    let mut bytes = [0u8; 16];
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Address other machines on the network most likely reach this one at: the source address
+/// of the default route. Connecting a UDP socket sends nothing.
+pub(super) fn local_network_address() -> Option<std::net::IpAddr> {
+    let socket = std::net::UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("192.0.2.1", 9)).ok()?;
+    socket.local_addr().ok().map(|address| address.ip())
+}
+
+/// Compares without returning early, so response times do not reveal how much of a guessed
+/// token was right.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The request line and the pairing token header, after consuming the headers. `None` when
+/// the request ends early or exceeds `MAX_REQUEST_BYTES`.
+async fn read_request(
+    reader: &mut BufReader<tokio::io::Take<OwnedReadHalf>>,
+) -> Option<(String, Option<String>)> {
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).await.ok()? == 0 {
+        return None;
+    }
+    let mut token = None;
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line).await {
+            Ok(0) | Err(_) => return None,
+            Ok(_) if line.trim().is_empty() => return Some((request_line, token)),
+            Ok(_) => {
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.trim().eq_ignore_ascii_case(TOKEN_HEADER) {
+                        token = Some(value.trim().to_string());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Splits `target` into its path and the value of its `token` query parameter.
+fn split_target(target: &str) -> (&str, Option<&str>) {
+    let Some((path, query)) = target.split_once('?') else {
+        return (target, None);
+    };
+    let token = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="));
+    (path, token)
+}
+
+/// Serves one client. `token` is the pairing token every request has to carry, or `None`
+/// when the server only listens on loopback.
+async fn serve_remote_client(stream: TcpStream, token: Option<Arc<str>>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader.take(MAX_REQUEST_BYTES));
+    let Ok(Some((request_line, header_token))) =
+        tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader)).await
+    else {
+        return;
+    };
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let (path, query_token) = split_target(parts.next().unwrap_or("/"));
+    if let Some(expected) = token.as_deref() {
+        let given = header_token.as_deref().or(query_token).unwrap_or_default();
+        if !token_matches(given, expected) {
+            write_response(
+                &mut writer,
+                "403 Forbidden",
+                "text/plain",
+                "pairing token required",
+            )
+            .await;
+            return;
+        }
+    }
+    if method != "GET" {
+        write_response(
+            &mut writer,
+            "405 Method Not Allowed",
+            "text/plain",
+            "read-only",
+        )
+        .await;
+        return;
+    }
+
+    match path {
+        "/" | "/index.html" => {
+            write_response(
+                &mut writer,
+                "200 OK",
+                "text/html; charset=utf-8",
+                REMOTE_PAGE,
+            )
+            .await;
+        }
+        "/snapshot.json" => {
+            write_response(
+                &mut writer,
+                "200 OK",
+                "application/json",
+                &current_remote_snapshot(),
+            )
+            .await;
+        }
+        "/events" => stream_remote_events(&mut writer).await,
+        _ => write_response(&mut writer, "404 Not Found", "text/plain", "not found").await,
+    }
+}
+
+impl AppModel {
+    /// Serves on loopback, or on every interface behind the pairing token when LAN access
+    /// is on. Changing either restarts the server.
+    pub(super) fn remote_subscription(&self) -> Subscription<Message> {
+        let token = (self.config.remote_lan && !self.config.remote_token.is_empty())
+            .then(|| Arc::<str>::from(self.config.remote_token.as_str()));
+        Subscription::run_with(token, |token| {
+            let token = token.clone();
+            iced_futures::stream::channel(1, |_emitter| async move {
+                let host = if token.is_some() {
+                    "0.0.0.0"
+                } else {
+                    "127.0.0.1"
+                };
+                let listener = match TcpListener::bind((host, REMOTE_PORT)).await {
+                    Ok(listener) => listener,
+                    Err(err) => {
+                        eprintln!("failed to start remote monitoring on port {REMOTE_PORT}: {err}");
+                        return;
+                    }
+                };
+
+                // Client tasks live in the set so they are aborted when remote mode is turned off.
+                let mut clients = tokio::task::JoinSet::new();
+                loop {
+                    tokio::select! {
+                        accepted = listener.accept() => {
+                            if let Some((stream, _)) =
+                                accepted.ok().filter(|_| clients.len() < MAX_REMOTE_CLIENTS)
+                            {
+                                clients.spawn(serve_remote_client(stream, token.clone()));
+                            }
+                        }
+                        Some(_) = clients.join_next() => {}
+                    }
+                }
+            })
+        })
+    }
+
+    pub(super) fn publish_remote_snapshot(&self) {
+        let json = match serde_json::to_string(&self.process_snapshot()) {
+            Ok(json) => json,
+            Err(err) => {
+                eprintln!("failed to serialize remote snapshot: {err}");
+                return;
+            }
+        };
+        if let Ok(mut shared) = shared_remote_snapshot().lock() {
+            *shared = json;
+        }
+    }
+
+    pub(super) fn toggle_remote_mode(&mut self) {
+        let enabled = !self.config.remote_mode;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_remote_mode(handler, enabled) {
+                eprintln!("failed to save remote mode setting: {err}");
+            }
+        } else {
+            self.config.remote_mode = enabled;
+        }
+
+        if enabled {
+            self.publish_remote_snapshot();
+        }
+    }
+
+    pub(super) fn toggle_remote_lan(&mut self) {
+        let enabled = !self.config.remote_lan;
+        // Each time LAN access is turned on, a new token unpairs the clients of last time.
+        let token = if enabled {
+            match generate_remote_token() {
+                Ok(token) => token,
+                Err(err) => {
+                    eprintln!("failed to generate remote pairing token: {err}");
+                    return;
+                }
+            }
+        } else {
+            String::new()
+        };
+
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_remote_token(handler, token) {
+                eprintln!("failed to save remote pairing token: {err}");
+            }
+            if let Err(err) = self.config.set_remote_lan(handler, enabled) {
+                eprintln!("failed to save remote LAN setting: {err}");
+            }
+        } else {
+            self.config.remote_token = token;
+            self.config.remote_lan = enabled;
+        }
+    }
+
+    /// Address to open on another device, with the pairing token, while LAN access is on.
+    pub(super) fn remote_lan_url(&self) -> Option<String> {
+        if !self.config.remote_mode || !self.config.remote_lan {
+            return None;
+        }
+        let host = local_network_address()
+            .map(|address| address.to_string())
+            .unwrap_or_else(|| "localhost".to_string());
+        Some(format!(
+            "http://{host}:{REMOTE_PORT}/?token={}",
+            self.config.remote_token
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_remote_token, split_target, token_matches};
+
+    #[test]
+    fn lan_requests_need_the_pairing_token() {
+        let token = generate_remote_token().expect("kernel random source is readable");
+        assert_eq!(token.len(), 32);
+        assert!(token_matches(&token, &token));
+        assert!(!token_matches(&token[..31], &token));
+        assert!(!token_matches("", &token));
+
+        assert_eq!(split_target("/events"), ("/events", None));
+        assert_eq!(
+            split_target("/events?view=apps&token=abc"),
+            ("/events", Some("abc"))
+        );
+    }
+}
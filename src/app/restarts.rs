@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Detection of apps caught in a crash-restart loop, such as a service systemd or a
+//! supervisor keeps restarting. An app whose process exited and was replaced by a new one,
+//! seen as its pid changing to one the app did not have before, was restarted. Three
+//! restarts within two minutes flag the app with a badge.
+
+use super::*;
+use std::collections::VecDeque;
+
+/// How long restarts count towards a loop, and how long an app that disappeared is
+/// remembered so its return counts as a restart.
+const RESTART_WINDOW: Duration = Duration::from_secs(120);
+
+/// Restarts within [`RESTART_WINDOW`] that make a loop.
+const RESTART_LOOP_COUNT: usize = 3;
+
+#[derive(Debug)]
+struct AppRestarts {
+    /// Members at the last refresh the app was listed.
+    pids: Vec<u32>,
+    representative: u32,
+    last_seen: Instant,
+    /// Restarts within the window, oldest first.
+    recent: VecDeque<Instant>,
+    /// Restarts seen since the task monitor started.
+    total: u32,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct RestartMonitor {
+    apps: HashMap<String, AppRestarts>,
+}
+
+impl RestartMonitor {
+    /// Records the representative pid and members of every listed app at `now`.
+    fn record<'a>(
+        &mut self,
+        apps: impl IntoIterator<Item = (&'a str, u32, &'a [u32])>,
+        now: Instant,
+    ) {
+        for (app_id, representative, pids) in apps {
+            let Some(restarts) = self.apps.get_mut(app_id) else {
+                self.apps.insert(
+                    app_id.to_string(),
+                    AppRestarts {
+                        pids: pids.to_vec(),
+                        representative,
+                        last_seen: now,
+                        recent: VecDeque::new(),
+                        total: 0,
+                    },
+                );
+                continue;
+            };
+            // A new representative that was already a member is a helper taking over, not
+            // a restart, and neither is the old one still running.
+            if representative != restarts.representative
+                && !restarts.pids.contains(&representative)
+                && !pids.contains(&restarts.representative)
+            {
+                restarts.recent.push_back(now);
+                restarts.total += 1;
+            }
+            restarts.pids = pids.to_vec();
+            restarts.representative = representative;
+            restarts.last_seen = now;
+        }
+
+        self.apps
+            .retain(|_, restarts| now.duration_since(restarts.last_seen) <= RESTART_WINDOW);
+        for restarts in self.apps.values_mut() {
+            while restarts
+                .recent
+                .front()
+                .is_some_and(|restarted_at| now.duration_since(*restarted_at) > RESTART_WINDOW)
+            {
+                restarts.recent.pop_front();
+            }
+        }
+    }
+
+    fn is_looping(&self, app_id: &str) -> bool {
+        self.apps
+            .get(app_id)
+            .is_some_and(|restarts| restarts.recent.len() >= RESTART_LOOP_COUNT)
+    }
+}
+
+impl AppModel {
+    pub(super) fn refresh_restart_loops(&mut self) {
+        self.restarts.record(
+            self.process_entries
+                .iter()
+                .map(|entry| (entry.app_id.as_str(), entry.pid, entry.pids.as_slice())),
+            Instant::now(),
+        );
+    }
+
+    pub(super) fn restart_badge_icons(&self, entry: &ProcessEntry) -> Vec<&'static str> {
+        if self.restarts.is_looping(&entry.app_id) {
+            vec!["view-refresh-symbolic"]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// How often the selected app was restarted, with a warning while it keeps restarting.
+    pub(super) fn selected_app_restarts(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let restarts = self
+            .restarts
+            .apps
+            .get(&selected.app_id)
+            .filter(|restarts| restarts.total > 0)?;
+
+        let mut column = widget::column::with_capacity(2).push(widget::text(fl!(
+            "process-restarts",
+            count = restarts.total
+        )));
+        if self.restarts.is_looping(&selected.app_id) {
+            column = column.push(
+                widget::text(fl!("process-restart-loop", count = restarts.recent.len())).size(12),
+            );
+        }
+        Some(column.spacing(4).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RESTART_WINDOW, RestartMonitor};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn flags_apps_restarting_repeatedly() {
+        let mut monitor = RestartMonitor::default();
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        monitor.record(
+            [("editor", 10, &[10, 11][..]), ("daemon", 20, &[20][..])],
+            at(0),
+        );
+        // A helper becoming the representative is not a restart.
+        monitor.record(
+            [("editor", 11, &[11][..]), ("daemon", 21, &[21][..])],
+            at(1),
+        );
+        // The daemon is briefly gone between restarts.
+        monitor.record([("editor", 11, &[11][..])], at(5));
+        monitor.record(
+            [("editor", 11, &[11][..]), ("daemon", 22, &[22][..])],
+            at(10),
+        );
+        assert!(!monitor.is_looping("daemon"));
+        monitor.record(
+            [("editor", 11, &[11][..]), ("daemon", 23, &[23][..])],
+            at(20),
+        );
+        assert!(monitor.is_looping("daemon"));
+        assert!(!monitor.is_looping("editor"));
+        assert_eq!(monitor.apps["editor"].total, 0);
+
+        let later = RESTART_WINDOW.as_secs() + 25;
+        monitor.record([("daemon", 23, &[23][..])], at(later));
+        assert!(!monitor.is_looping("daemon"));
+        assert_eq!(monitor.apps["daemon"].total, 3);
+        assert!(!monitor.apps.contains_key("editor"));
+    }
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Click tracking for Apps table rows and tiles. A press selects the app as before, and a
+//! second press on the same app within [`DOUBLE_CLICK_INTERVAL`] runs the double-click
+//! action chosen in Settings. While a comparison waits for its second app, a press picks it
+//! instead.
+
+use super::*;
+use crate::config::DoubleClickAction;
+
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+#[derive(Debug)]
+pub(super) struct RowClick {
+    app_id: String,
+    pressed_at: Instant,
+}
+
+impl AppModel {
+    pub(super) fn press_process_row(
+        &mut self,
+        app_id: String,
+        display_name: String,
+        pid: u32,
+    ) -> Task<cosmic::Action<Message>> {
+        if self.pick_compared_app(&app_id, &display_name) {
+            return Task::none();
+        }
+        let is_double_click = self.last_row_click.as_ref().is_some_and(|click| {
+            click.app_id == app_id && click.pressed_at.elapsed() <= DOUBLE_CLICK_INTERVAL
+        });
+        // A third press starts a new double-click rather than completing another one.
+        self.last_row_click = (!is_double_click).then(|| RowClick {
+            app_id: app_id.clone(),
+            pressed_at: Instant::now(),
+        });
+
+        let select = cosmic::Application::update(
+            self,
+            Message::OpenProcessMenu {
+                app_id,
+                display_name,
+                pid,
+            },
+        );
+        if !is_double_click {
+            return select;
+        }
+
+        let action = match self.config.double_click_action {
+            DoubleClickAction::Details => {
+                self.core.window.show_context = true;
+                Task::none()
+            }
+            DoubleClickAction::FocusWindow => {
+                cosmic::Application::update(self, Message::FocusSelectedApplication)
+            }
+            DoubleClickAction::OpenFileLocation => {
+                cosmic::Application::update(self, Message::OpenSelectedApplicationPath)
+            }
+            DoubleClickAction::Nothing => Task::none(),
+        };
+        Task::batch([select, action])
+    }
+
+    pub(super) fn set_double_click_action(&mut self, action: DoubleClickAction) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_double_click_action(handler, action) {
+                eprintln!("failed to save double-click action: {err}");
+            }
+        } else {
+            self.config.double_click_action = action;
+        }
+    }
+}
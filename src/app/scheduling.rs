@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Which cores the selected app's threads last ran on, from the `processor` field of
+//! `/proc/<pid>/task/*/stat`. On hybrid and big.LITTLE CPUs this shows whether the scheduler
+//! keeps an app on the performance or the efficiency cores.
+
+use super::*;
+use std::collections::BTreeSet;
+
+/// Cores the selected app's threads last ran on, sampled on each refresh.
+#[derive(Debug, Default)]
+pub(super) struct ThreadPlacement {
+    app_id: String,
+    cpus: BTreeSet<usize>,
+}
+
+/// The CPU a thread last ran on, field 39 of its stat line. Fields are counted after the
+/// command name, which may itself contain spaces and parentheses.
+fn parse_stat_processor(stat: &str) -> Option<usize> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    // The state, field 3, is the first one after the command name.
+    fields.split_whitespace().nth(39 - 3)?.parse().ok()
+}
+
+fn read_thread_cpus(source: &dyn SystemSource, pids: &[u32]) -> BTreeSet<usize> {
+    let mut cpus = BTreeSet::new();
+    for pid in pids {
+        let Some(Ok(tasks)) = source
+            .proc_dir(*pid)
+            .map(|dir| fs::read_dir(dir.join("task")))
+        else {
+            continue;
+        };
+        for task in tasks.flatten() {
+            if let Some(cpu) = fs::read_to_string(task.path().join("stat"))
+                .ok()
+                .and_then(|stat| parse_stat_processor(&stat))
+            {
+                cpus.insert(cpu);
+            }
+        }
+    }
+    cpus
+}
+
+/// CPU indexes as the CPU panel numbers its cores, from 1, e.g. `1, 3, 8`.
+fn format_core_numbers<'a>(cpus: impl Iterator<Item = &'a usize>) -> String {
+    cpus.map(|cpu| (cpu + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl AppModel {
+    pub(super) fn refresh_selected_thread_placement(&mut self) {
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            self.thread_placement = ThreadPlacement::default();
+            return;
+        };
+        self.thread_placement = ThreadPlacement {
+            app_id: entry.app_id.clone(),
+            cpus: read_thread_cpus(self.process_source.as_ref(), &entry.pids),
+        };
+    }
+
+    /// Whether one of the selected app's threads last ran on the CPU.
+    pub(super) fn selected_app_ran_on(&self, cpu: usize) -> bool {
+        self.selected_process
+            .as_ref()
+            .is_some_and(|selected| selected.app_id == self.thread_placement.app_id)
+            && self.thread_placement.cpus.contains(&cpu)
+    }
+
+    /// The cores the selected app's threads last ran on, grouped by core type on CPUs that
+    /// mix them.
+    pub(super) fn selected_app_cores(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let placement = &self.thread_placement;
+        if placement.app_id != selected.app_id || placement.cpus.is_empty() {
+            return None;
+        }
+
+        let mut section = widget::column::with_capacity(1 + self.cpu_topology.clusters.len())
+            .push(widget::text::heading(fl!("cores-last-ran-on")))
+            .spacing(4);
+        if self.cpu_topology.is_heterogeneous() {
+            for cluster in &self.cpu_topology.clusters {
+                let mut cpus = cluster
+                    .cpus
+                    .iter()
+                    .filter(|cpu| placement.cpus.contains(cpu))
+                    .peekable();
+                if cpus.peek().is_none() {
+                    continue;
+                }
+                section = section.push(
+                    widget::row::with_capacity(2)
+                        .push(widget::text(cluster.core_type.label()).width(Length::Fill))
+                        .push(widget::text(format_core_numbers(cpus))),
+                );
+            }
+        } else {
+            section = section.push(widget::text(format_core_numbers(placement.cpus.iter())));
+        }
+        Some(section.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_stat_processor;
+
+    #[test]
+    fn parses_last_cpu_from_stat() {
+        let stat = "4242 (Web Content (x)) S 1 4242 4242 0 -1 4194560 12 0 0 0 5 3 0 0 20 0 \
+                    31 0 8123 3221225472 51234 18446744073709551615 1 1 0 0 0 0 0 4096 \
+                    17663 0 0 0 17 11 0 0 0 0 0 0 0 0 0 0 0 0 0";
+        assert_eq!(parse_stat_processor(stat), Some(11));
+        assert_eq!(parse_stat_processor("4242 (short) S 1 2 3"), None);
+        assert_eq!(parse_stat_processor("garbage"), None);
+    }
+}
@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Recording the screen while an app misbehaves, to attach to a bug report next to its
+//! resource data. gpu-screen-recorder records through the ScreenCast portal, whose dialog
+//! lets the user pick the app's window, and the video is saved to the Videos folder. The
+//! portal offers no way to choose a window for the user, so the dialog always asks.
+
+use super::*;
+use std::process::Child;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const RECORDER: &str = "gpu-screen-recorder";
+
+#[derive(Debug)]
+struct ActiveRecording {
+    app_id: String,
+    path: PathBuf,
+    child: Child,
+}
+
+impl Drop for ActiveRecording {
+    /// Stops a recording still running when the task monitor quits, so the video is saved.
+    fn drop(&mut self) {
+        if matches!(self.child.try_wait(), Ok(None)) {
+            stop_recorder(&self.child);
+        }
+    }
+}
+
+/// How the last recording ended: where it was saved, or why it was not.
+#[derive(Debug)]
+struct FinishedRecording {
+    app_id: String,
+    path: PathBuf,
+    error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ScreenRecorder {
+    active: Option<ActiveRecording>,
+    last: Option<FinishedRecording>,
+}
+
+/// Whether gpu-screen-recorder is installed, checked once.
+fn recorder_installed() -> bool {
+    static INSTALLED: OnceLock<bool> = OnceLock::new();
+    *INSTALLED.get_or_init(|| debug_tools::is_installed(RECORDER))
+}
+
+/// The Videos folder from `user-dirs.dirs`, e.g. `XDG_VIDEOS_DIR="$HOME/Videos"`, with
+/// `$HOME` replaced by `home`. Entries that are not absolute paths are ignored.
+fn parse_videos_dir(user_dirs: &str, home: &Path) -> Option<PathBuf> {
+    let value = user_dirs
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("XDG_VIDEOS_DIR="))?
+        .trim()
+        .trim_matches('"');
+    let path = match value.strip_prefix("$HOME") {
+        Some(rest) => home.join(rest.trim_start_matches('/')),
+        None => PathBuf::from(value),
+    };
+    path.is_absolute().then_some(path)
+}
+
+fn videos_directory() -> PathBuf {
+    let home = env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir);
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|config_home| !config_home.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+    fs::read_to_string(config_home.join("user-dirs.dirs"))
+        .ok()
+        .and_then(|user_dirs| parse_videos_dir(&user_dirs, &home))
+        .unwrap_or_else(|| home.join("Videos"))
+}
+
+/// Asks the recorder to finish the video, as Ctrl+C in a terminal would.
+fn stop_recorder(child: &Child) {
+    let Ok(pid) = i32::try_from(child.id()) else {
+        return;
+    };
+    // SAFETY: kill has no memory preconditions. The pid is our own child's, and a recording
+    // is dropped as soon as its child is reaped, so the pid cannot belong to another process.
+    if unsafe { libc::kill(pid, libc::SIGINT) } != 0 {
+        eprintln!(
+            "failed to stop {RECORDER}: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+fn spawn_recorder(path: &Path) -> std::io::Result<Child> {
+    Command::new(RECORDER)
+        .args(["-w", "portal", "-f", "60", "-o"])
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+impl AppModel {
+    pub(super) fn start_screen_recording(&mut self) {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return;
+        };
+        if self.screen_recorder.active.is_some() {
+            return;
+        }
+        let app_id = selected.app_id.clone();
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        let directory = videos_directory();
+        let path = directory.join(log_capture::capture_file_name(&app_id, started_at, "mp4"));
+
+        let started = fs::create_dir_all(&directory).and_then(|()| spawn_recorder(&path));
+        match started {
+            Ok(child) => {
+                self.screen_recorder.active = Some(ActiveRecording {
+                    app_id,
+                    path,
+                    child,
+                });
+            }
+            Err(err) => {
+                eprintln!("failed to start {RECORDER}: {err}");
+                self.screen_recorder.last = Some(FinishedRecording {
+                    app_id,
+                    path,
+                    error: Some(err.to_string()),
+                });
+            }
+        }
+    }
+
+    pub(super) fn stop_screen_recording(&mut self) {
+        if let Some(active) = self.screen_recorder.active.as_ref() {
+            stop_recorder(&active.child);
+        }
+    }
+
+    /// Notices the recorder exiting, after it was stopped or when the portal dialog was
+    /// cancelled, which leaves no video behind.
+    pub(super) fn poll_screen_recording(&mut self) {
+        let Some(active) = self.screen_recorder.active.as_mut() else {
+            return;
+        };
+        let error = match active.child.try_wait() {
+            Ok(None) => return,
+            Ok(Some(_)) if active.path.is_file() => None,
+            Ok(Some(status)) => Some(fl!(
+                "screen-recording-not-saved",
+                status = status.to_string()
+            )),
+            Err(err) => Some(err.to_string()),
+        };
+        let Some(active) = self.screen_recorder.active.take() else {
+            return;
+        };
+        if let Some(error) = error.as_ref() {
+            eprintln!("{RECORDER} did not save {}: {error}", active.path.display());
+        }
+        self.screen_recorder.last = Some(FinishedRecording {
+            app_id: active.app_id.clone(),
+            path: active.path.clone(),
+            error,
+        });
+    }
+
+    pub(super) fn open_screen_recording(&self) {
+        if let Some(last) = self.screen_recorder.last.as_ref() {
+            if let Err(err) = open::that_detached(&last.path) {
+                eprintln!("failed to open {}: {err}", last.path.display());
+            }
+        }
+    }
+
+    /// Recording controls in the selected app's actions, when the recorder is installed.
+    pub(super) fn selected_app_screen_recording(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        if !recorder_installed() {
+            return None;
+        }
+        let recorder = &self.screen_recorder;
+        let button_height = Length::Fixed(38.0);
+        let recording_this = recorder
+            .active
+            .as_ref()
+            .is_some_and(|active| active.app_id == selected.app_id);
+        let last = recorder
+            .last
+            .as_ref()
+            .filter(|last| last.app_id == selected.app_id && recorder.active.is_none());
+
+        let button = if recording_this {
+            widget::button::destructive(fl!("screen-recording-stop"))
+                .on_press(Message::StopScreenRecording)
+        } else {
+            widget::button::standard(fl!("screen-recording-start")).on_press_maybe(
+                recorder
+                    .active
+                    .is_none()
+                    .then_some(Message::StartScreenRecording),
+            )
+        };
+        Some(
+            widget::column::with_capacity(4)
+                .push(button.width(Length::Fill).height(button_height))
+                .push_maybe(
+                    recording_this.then(|| widget::text(fl!("screen-recording-running")).size(12)),
+                )
+                .push_maybe(last.map(|last| {
+                    match last.error.as_ref() {
+                        Some(error) => {
+                            widget::text(fl!("screen-recording-failed", error = error.as_str()))
+                                .size(12)
+                        }
+                        None => widget::text(fl!(
+                            "screen-recording-saved",
+                            path = last.path.display().to_string()
+                        ))
+                        .size(12),
+                    }
+                }))
+                .push_maybe(last.filter(|last| last.error.is_none()).map(|_| {
+                    widget::button::standard(fl!("screen-recording-open"))
+                        .on_press(Message::OpenScreenRecording)
+                        .width(Length::Fill)
+                        .height(button_height)
+                }))
+                .spacing(8)
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_videos_dir;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn reads_the_videos_folder() {
+        let home = Path::new("/home/ada");
+        let user_dirs = "# written by xdg-user-dirs-update\n\
+                         XDG_DOWNLOAD_DIR=\"$HOME/Downloads\"\n\
+                         XDG_VIDEOS_DIR=\"$HOME/Filme\"\n";
+        assert_eq!(
+            parse_videos_dir(user_dirs, home),
+            Some(PathBuf::from("/home/ada/Filme"))
+        );
+        assert_eq!(
+            parse_videos_dir("XDG_VIDEOS_DIR=\"/data/Videos\"", home),
+            Some(PathBuf::from("/data/Videos"))
+        );
+        assert_eq!(parse_videos_dir("XDG_VIDEOS_DIR=\"Videos\"", home), None);
+        assert_eq!(
+            parse_videos_dir("XDG_MUSIC_DIR=\"$HOME/Music\"", home),
+            None
+        );
+    }
+}
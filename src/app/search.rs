@@ -0,0 +1,337 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Process search. Plain text matches app names, while the `file:`, `port:`, and `title:`
+//! prefixes look up open files, listening sockets, and window titles and return the apps
+//! that own them. Lookups run in the background, since they read every process' open
+//! files or ask the window manager.
+
+use super::*;
+
+/// How often a `file:`, `port:`, or `title:` lookup runs again while its query stays.
+const LOOKUP_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum SearchQuery {
+    Name(String),
+    File(String),
+    Port(u16),
+    Title(String),
+}
+
+pub(super) fn parse_search_query(input: &str) -> Option<SearchQuery> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    let query = match input.split_once(':') {
+        Some((prefix, value)) if !value.trim().is_empty() => {
+            let value = value.trim().to_string();
+            match prefix.to_ascii_lowercase().as_str() {
+                "file" => Some(SearchQuery::File(value)),
+                "port" => value.parse().ok().map(SearchQuery::Port),
+                "title" => Some(SearchQuery::Title(value.to_lowercase())),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    Some(query.unwrap_or_else(|| SearchQuery::Name(input.to_lowercase())))
+}
+
+/// Socket inodes bound to `port` in a `/proc/net/{tcp,tcp6,udp,udp6}` table. TCP sockets
+/// only count while listening, since UDP has no listen state.
+fn parse_listening_inodes(table: &str, port: u16, is_tcp: bool) -> HashSet<u64> {
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let local_port = fields.get(1)?.rsplit_once(':')?.1;
+            let is_listening = !is_tcp || *fields.get(3)? == "0A";
+            (u16::from_str_radix(local_port, 16).ok()? == port && is_listening)
+                .then(|| fields.get(9)?.parse().ok())
+                .flatten()
+        })
+        .collect()
+}
+
+/// Window titles with the owning pid (X11/XWayland) or app id (Wayland toplevels).
+fn read_window_titles() -> Vec<(Option<u32>, Option<String>, String)> {
+    let run = |program: &str, args: &[&str]| {
+        Command::new(program)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default()
+    };
+
+    let mut titles = Vec::new();
+    // `wmctrl -lp`: window id, desktop, pid, host, then the title.
+    for line in run("wmctrl", &["-lp"]).lines() {
+        let mut fields = line.split_whitespace();
+        let pid = fields.nth(2).and_then(|pid| pid.parse().ok());
+        let title = fields.skip(1).collect::<Vec<_>>().join(" ");
+        titles.push((pid, None, title));
+    }
+    // `wlrctl toplevel list`: `app_id: title`.
+    for line in run("wlrctl", &["toplevel", "list"]).lines() {
+        if let Some((app_id, title)) = line.split_once(": ") {
+            titles.push((None, Some(app_id.to_string()), title.to_string()));
+        }
+    }
+    titles
+}
+
+/// The targets of a process' open file descriptors, from its `/proc` directory.
+fn open_fd_targets(proc_dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(proc_dir.join("fd"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| fs::read_link(entry.path()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// What a `file:`, `port:`, or `title:` lookup found. It is matched against the entries on
+/// every refresh, while the lookup itself only runs again every few seconds.
+#[derive(Debug, Clone, Default)]
+pub(super) struct SearchLookup {
+    pub(super) pids: HashSet<u32>,
+    /// Lowercase app ids of Wayland windows with a matching title.
+    window_app_ids: HashSet<String>,
+}
+
+/// Looks up the processes of `proc_dirs` that own what `query` asks for. Name queries
+/// need no lookup and find nothing.
+pub(super) fn look_up_search(query: &SearchQuery, proc_dirs: &[(u32, PathBuf)]) -> SearchLookup {
+    let pids_with_target = |matches: &dyn Fn(&Path) -> bool| {
+        proc_dirs
+            .iter()
+            .filter(|(_, dir)| {
+                open_fd_targets(dir)
+                    .iter()
+                    .any(|target| matches(target.as_path()))
+            })
+            .map(|(pid, _)| *pid)
+            .collect()
+    };
+    match query {
+        SearchQuery::Name(_) => SearchLookup::default(),
+        SearchQuery::File(path) => SearchLookup {
+            pids: pids_with_target(&|target| target.to_string_lossy().contains(path.as_str())),
+            window_app_ids: HashSet::new(),
+        },
+        SearchQuery::Port(port) => {
+            let inodes = [
+                ("tcp", true),
+                ("tcp6", true),
+                ("udp", false),
+                ("udp6", false),
+            ]
+            .into_iter()
+            .filter_map(|(table, is_tcp)| {
+                fs::read_to_string(format!("/proc/net/{table}"))
+                    .ok()
+                    .map(|contents| parse_listening_inodes(&contents, *port, is_tcp))
+            })
+            .flatten()
+            .map(|inode| PathBuf::from(format!("socket:[{inode}]")))
+            .collect::<HashSet<_>>();
+            if inodes.is_empty() {
+                return SearchLookup::default();
+            }
+            SearchLookup {
+                pids: pids_with_target(&|target| inodes.contains(target)),
+                window_app_ids: HashSet::new(),
+            }
+        }
+        SearchQuery::Title(text) => {
+            let mut lookup = SearchLookup::default();
+            for (pid, app_id, title) in read_window_titles() {
+                if !title.to_lowercase().contains(text) {
+                    continue;
+                }
+                lookup.pids.extend(pid);
+                lookup
+                    .window_app_ids
+                    .extend(app_id.map(|app_id| app_id.to_ascii_lowercase()));
+            }
+            lookup
+        }
+    }
+}
+
+/// The lookup for the current query, run in the background when the query changes and
+/// again every [`LOOKUP_REFRESH_INTERVAL`].
+#[derive(Debug, Default)]
+pub(super) struct SearchLookups {
+    query: Option<SearchQuery>,
+    /// What the last finished lookup for `query` found.
+    found: Option<SearchLookup>,
+    requested_at: Option<Instant>,
+    running: bool,
+}
+
+impl AppModel {
+    /// The `/proc` directories of the listed processes, for a lookup to read in the
+    /// background.
+    pub(super) fn entry_proc_dirs(&self) -> Vec<(u32, PathBuf)> {
+        self.process_entries
+            .iter()
+            .flat_map(|entry| entry.pids.iter().copied())
+            .filter_map(|pid| Some((pid, self.process_source.proc_dir(pid)?)))
+            .collect()
+    }
+
+    /// Starts a lookup for the search when its query changed or the last one is stale,
+    /// unless one is still running.
+    pub(super) fn request_search_lookup(&mut self) -> Task<cosmic::Action<Message>> {
+        let query = self
+            .processes_page
+            .search_query()
+            .filter(|query| !matches!(query, SearchQuery::Name(_)));
+        let lookups = &mut self.search_lookups;
+        if lookups.query != query {
+            lookups.query.clone_from(&query);
+            lookups.found = None;
+            lookups.requested_at = None;
+        }
+        let Some(query) = query else {
+            return Task::none();
+        };
+        if lookups.running
+            || lookups
+                .requested_at
+                .is_some_and(|requested| requested.elapsed() < LOOKUP_REFRESH_INTERVAL)
+        {
+            return Task::none();
+        }
+        lookups.running = true;
+        lookups.requested_at = Some(Instant::now());
+
+        let proc_dirs = self.entry_proc_dirs();
+        Task::perform(
+            async move {
+                let lookup_query = query.clone();
+                let found =
+                    tokio::task::spawn_blocking(move || look_up_search(&lookup_query, &proc_dirs))
+                        .await
+                        .unwrap_or_default();
+                (query, found)
+            },
+            |(query, found)| cosmic::Action::App(Message::SearchLookedUp { query, found }),
+        )
+    }
+
+    /// Keeps the lookup if the search has not changed since it started, and starts the
+    /// lookup for the new query if it has.
+    pub(super) fn set_search_lookup(
+        &mut self,
+        query: SearchQuery,
+        found: SearchLookup,
+    ) -> Task<cosmic::Action<Message>> {
+        self.search_lookups.running = false;
+        if self.search_lookups.query.as_ref() == Some(&query) {
+            self.search_lookups.found = Some(found);
+            self.refresh_search_matches();
+        }
+        self.request_search_lookup()
+    }
+
+    /// App ids of the entries matching the query. Queries that need a lookup match nothing
+    /// until the first one for them has finished.
+    pub(super) fn search_matches(&self, query: &SearchQuery) -> HashSet<String> {
+        let entries = self.process_entries.iter();
+        if let SearchQuery::Name(text) = query {
+            return entries
+                .filter(|entry| {
+                    entry.display_name.to_lowercase().contains(text)
+                        || entry.app_id.to_lowercase().contains(text)
+                })
+                .map(|entry| entry.app_id.clone())
+                .collect();
+        }
+        let Some(lookup) = self
+            .search_lookups
+            .found
+            .as_ref()
+            .filter(|_| self.search_lookups.query.as_ref() == Some(query))
+        else {
+            return HashSet::new();
+        };
+        entries
+            .filter(|entry| {
+                entry.pids.iter().any(|pid| lookup.pids.contains(pid))
+                    || (!lookup.window_app_ids.is_empty()
+                        && lookup
+                            .window_app_ids
+                            .contains(&self.window_app_id(&entry.app_id)))
+            })
+            .map(|entry| entry.app_id.clone())
+            .collect()
+    }
+
+    /// The app id the entry's windows carry: its desktop entry's id, or its own.
+    fn window_app_id(&self, app_id: &str) -> String {
+        self.desktop_meta_for_app_id(app_id)
+            .and_then(|meta| meta.desktop_entry_id)
+            .map(|entry_id| {
+                entry_id
+                    .strip_suffix(".desktop")
+                    .unwrap_or(&entry_id)
+                    .to_ascii_lowercase()
+            })
+            .unwrap_or_else(|| app_id.to_ascii_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SearchQuery, parse_listening_inodes, parse_search_query};
+
+    #[test]
+    fn parses_search_prefixes() {
+        assert_eq!(parse_search_query("  "), None);
+        assert_eq!(
+            parse_search_query("Fire"),
+            Some(SearchQuery::Name("fire".to_string()))
+        );
+        assert_eq!(
+            parse_search_query("file:/home/me/video.mkv"),
+            Some(SearchQuery::File("/home/me/video.mkv".to_string()))
+        );
+        assert_eq!(
+            parse_search_query("PORT: 8080"),
+            Some(SearchQuery::Port(8080))
+        );
+        assert_eq!(
+            parse_search_query("title:Inbox"),
+            Some(SearchQuery::Title("inbox".to_string()))
+        );
+        assert_eq!(
+            parse_search_query("port:http"),
+            Some(SearchQuery::Name("port:http".to_string()))
+        );
+    }
+
+    #[test]
+    fn finds_listening_socket_inodes() {
+        // Columns: sl, local, remote, state, tx:rx queue, timer, retransmits, uid, timeout, inode.
+        let table = "  sl  local_address rem_address   st ... inode\n\
+            0: 00000000:1F90 00000000:0000 0A 0:0 00:0 0 1000 0 41234 1\n\
+            1: 0100007F:1F90 0100007F:9C40 01 0:0 00:0 0 1000 0 41299 1\n\
+            2: 00000000:0016 00000000:0000 0A 0:0 00:0 0    0 0 17001 1\n";
+
+        let inodes = parse_listening_inodes(table, 8080, true);
+        assert_eq!(inodes.len(), 1);
+        assert!(inodes.contains(&41234));
+        assert_eq!(parse_listening_inodes(table, 8080, false).len(), 2);
+    }
+}
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Pauses sampling while the session is locked or idle, e.g. with the screen blanked
+//! overnight, by following the `LockedHint` and `IdleHint` properties and the `Lock` and
+//! `Unlock` signals of the logind session over `gdbus monitor`. Remote monitoring keeps
+//! sampling, since its clients are not behind the lock screen.
+
+use super::*;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+const LOGIND_DESTINATION: &str = "org.freedesktop.login1";
+
+/// Lock and idle state of the session, as last reported by logind.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub(super) struct SessionState {
+    pub(super) locked: bool,
+    pub(super) idle: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub struct SessionChange {
+    locked: Option<bool>,
+    idle: Option<bool>,
+}
+
+impl SessionState {
+    fn apply(&mut self, change: SessionChange) {
+        self.locked = change.locked.unwrap_or(self.locked);
+        self.idle = change.idle.unwrap_or(self.idle);
+    }
+}
+
+/// The object path of a `GetSession` reply, e.g. `o "/org/freedesktop/login1/session/_32"`.
+fn parse_session_path(reply: &str) -> Option<String> {
+    let path = reply.trim().strip_prefix("o ")?.trim().trim_matches('"');
+    path.starts_with('/').then(|| path.to_string())
+}
+
+fn parse_hint(line: &str, property: &str) -> Option<bool> {
+    let value = line.split_once(&format!("'{property}': <"))?.1;
+    if value.starts_with("true>") {
+        Some(true)
+    } else if value.starts_with("false>") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// A lock or idle change from a line of `gdbus monitor` output, e.g.
+/// `/org/freedesktop/login1/session/_32: org.freedesktop.login1.Session.Unlock ()` or
+/// `...PropertiesChanged ('org.freedesktop.login1.Session', {'LockedHint': <true>}, @as [])`.
+fn parse_session_signal(line: &str) -> Option<SessionChange> {
+    let (_, signal) = line.split_once(": ")?;
+    let change = if signal.starts_with("org.freedesktop.login1.Session.Lock ") {
+        SessionChange {
+            locked: Some(true),
+            idle: None,
+        }
+    } else if signal.starts_with("org.freedesktop.login1.Session.Unlock ") {
+        SessionChange {
+            locked: Some(false),
+            idle: None,
+        }
+    } else if signal.starts_with("org.freedesktop.DBus.Properties.PropertiesChanged ") {
+        SessionChange {
+            locked: parse_hint(signal, "LockedHint"),
+            idle: parse_hint(signal, "IdleHint"),
+        }
+    } else {
+        return None;
+    };
+    (change != SessionChange::default()).then_some(change)
+}
+
+/// Path of the session the task monitor runs in. Signals are sent on this path, not on
+/// the `session/auto` alias.
+async fn session_path() -> Option<String> {
+    let output = tokio::process::Command::new("busctl")
+        .args([
+            "call",
+            LOGIND_DESTINATION,
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+            "GetSession",
+            "s",
+            "auto",
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_session_path(&String::from_utf8_lossy(&output.stdout))
+}
+
+impl AppModel {
+    pub(super) fn session_subscription() -> Subscription<Message> {
+        Subscription::run(|| {
+            iced_futures::stream::channel(1, |mut emitter| async move {
+                let Some(path) = session_path().await else {
+                    eprintln!("failed to find the logind session to watch its lock state");
+                    return;
+                };
+                let child = tokio::process::Command::new("gdbus")
+                    .args([
+                        "monitor",
+                        "--system",
+                        "--dest",
+                        LOGIND_DESTINATION,
+                        "--object-path",
+                        &path,
+                    ])
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn();
+                let mut child = match child {
+                    Ok(child) => child,
+                    Err(err) => {
+                        eprintln!("failed to watch the session lock state: {err}");
+                        return;
+                    }
+                };
+                let Some(stdout) = child.stdout.take() else {
+                    return;
+                };
+
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if let Some(change) = parse_session_signal(&line) {
+                        _ = emitter.send(Message::SessionChanged(change)).await;
+                    }
+                }
+            })
+        })
+    }
+
+    /// Whether sampling is paused for a locked or idle session.
+    pub(super) fn sampling_paused(&self) -> bool {
+        !self.config.remote_mode && (self.session.locked || self.session.idle)
+    }
+
+    /// Applies a lock or idle change. Returns whether sampling resumes, so everything can be
+    /// refreshed at once instead of waiting for the next tick.
+    pub(super) fn update_session(&mut self, change: SessionChange) -> bool {
+        let was_paused = self.sampling_paused();
+        self.session.apply(change);
+        was_paused && !self.sampling_paused()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SessionChange, parse_session_path, parse_session_signal};
+
+    #[test]
+    fn parses_session_path() {
+        assert_eq!(
+            parse_session_path("o \"/org/freedesktop/login1/session/_32\"\n").as_deref(),
+            Some("/org/freedesktop/login1/session/_32")
+        );
+        assert_eq!(parse_session_path("s \"auto\""), None);
+    }
+
+    #[test]
+    fn parses_lock_and_idle_signals() {
+        let path = "/org/freedesktop/login1/session/_32";
+        assert_eq!(
+            parse_session_signal(&format!("{path}: org.freedesktop.login1.Session.Lock ()")),
+            Some(SessionChange {
+                locked: Some(true),
+                idle: None,
+            })
+        );
+        assert_eq!(
+            parse_session_signal(&format!(
+                "{path}: org.freedesktop.DBus.Properties.PropertiesChanged \
+                 ('org.freedesktop.login1.Session', {{'IdleHint': <false>, \
+                 'LockedHint': <false>}}, @as [])"
+            )),
+            Some(SessionChange {
+                locked: Some(false),
+                idle: Some(false),
+            })
+        );
+        assert_eq!(
+            parse_session_signal(&format!(
+                "{path}: org.freedesktop.DBus.Properties.PropertiesChanged \
+                 ('org.freedesktop.login1.Session', {{'Active': <true>}}, @as [])"
+            )),
+            None
+        );
+        assert_eq!(
+            parse_session_signal("Monitoring signals on object /org/freedesktop/login1"),
+            None
+        );
+    }
+}
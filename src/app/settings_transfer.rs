@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Exporting the settings to a JSON file and importing them on another machine. An import
+//! is checked against the current settings first and lists what it would change; keys this
+//! version does not know, or whose values it cannot read, are left out rather than failing
+//! the whole file.
+
+use super::*;
+use serde_json::{Map, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Format version of the settings file, raised when keys are renamed or change meaning.
+const SETTINGS_FILE_VERSION: u32 = 1;
+
+/// Keys that describe this machine rather than preferences, so they are neither exported
+/// nor imported.
+const MACHINE_KEYS: [&str; 4] = ["demo", "game_playtime", "remote_lan", "remote_token"];
+
+/// Keys holding commands, or environment and arguments of commands, that run without
+/// asking, so a settings file from elsewhere cannot bring them in unseen. They are neither
+/// exported nor imported either.
+const COMMAND_KEYS: [&str; 3] = ["automation_hooks", "debugger_command", "launch_overrides"];
+
+/// Whether `key` is written to and read from settings files.
+fn is_transferred(key: &str) -> bool {
+    !MACHINE_KEYS.contains(&key) && !COMMAND_KEYS.contains(&key)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsFile {
+    app: String,
+    version: u32,
+    settings: Map<String, Value>,
+}
+
+#[derive(Debug)]
+enum ImportError {
+    Read(std::io::Error),
+    Parse(serde_json::Error),
+    NotSettings,
+    NewerVersion(u32),
+}
+
+/// An import checked against the current settings, waiting to be confirmed.
+#[derive(Debug)]
+pub(super) struct SettingsImport {
+    file_name: String,
+    config: Config,
+    /// Keys whose values the import changes.
+    changed: Vec<String>,
+    /// Keys left out because they are unknown or their values are invalid.
+    skipped: Vec<String>,
+}
+
+#[derive(Debug)]
+pub(super) enum SettingsTransfer {
+    Preview(SettingsImport),
+    /// The outcome of an export, or why an import failed.
+    Outcome(String),
+}
+
+fn settings_map(config: &Config) -> Map<String, Value> {
+    match serde_json::to_value(config) {
+        Ok(Value::Object(settings)) => settings,
+        _ => Map::new(),
+    }
+}
+
+/// Applies the settings in `contents` over `current`, key by key.
+fn plan_import(
+    current: &Config,
+    contents: &str,
+) -> Result<(Config, Vec<String>, Vec<String>), ImportError> {
+    let file = serde_json::from_str::<SettingsFile>(contents).map_err(ImportError::Parse)?;
+    if file.app != <AppModel as cosmic::Application>::APP_ID {
+        return Err(ImportError::NotSettings);
+    }
+    if file.version > SETTINGS_FILE_VERSION {
+        return Err(ImportError::NewerVersion(file.version));
+    }
+
+    let mut merged = settings_map(current);
+    let mut changed = Vec::new();
+    let mut skipped = Vec::new();
+    for (key, value) in file.settings {
+        if !is_transferred(&key) || !merged.contains_key(&key) {
+            skipped.push(key);
+            continue;
+        }
+        let mut candidate = merged.clone();
+        candidate.insert(key.clone(), value.clone());
+        if serde_json::from_value::<Config>(Value::Object(candidate)).is_err() {
+            skipped.push(key);
+            continue;
+        }
+        if merged[&key] != value {
+            changed.push(key.clone());
+        }
+        merged.insert(key, value);
+    }
+    changed.sort();
+    skipped.sort();
+    let config = serde_json::from_value(Value::Object(merged)).map_err(ImportError::Parse)?;
+    Ok((config, changed, skipped))
+}
+
+fn pick_settings_file() -> std::io::Result<Option<PathBuf>> {
+    let title = fl!("settings-import-title");
+    let zenity_title = format!("--title={title}");
+    match AppModel::pick_file_with_command(
+        "zenity",
+        &[
+            "--file-selection",
+            &zenity_title,
+            "--file-filter=JSON | *.json",
+        ],
+    ) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => AppModel::pick_file_with_command(
+            "kdialog",
+            &[
+                "--title",
+                &title,
+                "--getopenfilename",
+                ".",
+                "*.json|JSON (*.json)",
+            ],
+        ),
+        result => result,
+    }
+}
+
+impl AppModel {
+    fn write_settings_export(&self) -> std::io::Result<PathBuf> {
+        let mut settings = settings_map(&self.config);
+        settings.retain(|key, _| is_transferred(key));
+        let file = SettingsFile {
+            app: Self::APP_ID.to_string(),
+            version: SETTINGS_FILE_VERSION,
+            settings,
+        };
+        let generated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        let path = alerts::export_directory()
+            .join(format!("cosmic-task-monitor-settings-{generated_at}.json"));
+        fs::write(&path, serde_json::to_vec_pretty(&file)?)?;
+        Ok(path)
+    }
+
+    pub(super) fn export_settings(&mut self) {
+        let outcome = match self.write_settings_export() {
+            Ok(path) => fl!("settings-export-success", path = path.display().to_string()),
+            Err(err) => fl!("settings-export-failed", error = err.to_string()),
+        };
+        self.settings_transfer = Some(SettingsTransfer::Outcome(outcome));
+    }
+
+    /// Asks for a settings file and shows what importing it would change.
+    pub(super) fn import_settings(&mut self) {
+        let path = match pick_settings_file() {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(err) => {
+                let error = err.to_string();
+                self.settings_transfer = Some(SettingsTransfer::Outcome(fl!(
+                    "settings-import-failed",
+                    error = error
+                )));
+                return;
+            }
+        };
+        let planned = fs::read_to_string(&path)
+            .map_err(ImportError::Read)
+            .and_then(|contents| plan_import(&self.config, &contents));
+        self.settings_transfer = Some(match planned {
+            Ok((config, changed, skipped)) => SettingsTransfer::Preview(SettingsImport {
+                file_name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                config,
+                changed,
+                skipped,
+            }),
+            Err(err) => {
+                let error = match err {
+                    ImportError::Read(err) => err.to_string(),
+                    ImportError::Parse(err) => err.to_string(),
+                    ImportError::NotSettings => fl!("settings-import-not-settings"),
+                    ImportError::NewerVersion(version) => {
+                        fl!("settings-import-newer", version = version)
+                    }
+                };
+                SettingsTransfer::Outcome(fl!("settings-import-failed", error = error))
+            }
+        });
+    }
+
+    pub(super) fn confirm_settings_import(&mut self) {
+        let Some(SettingsTransfer::Preview(import)) = self.settings_transfer.take() else {
+            return;
+        };
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = import.config.write_entry(handler) {
+                eprintln!("failed to save imported settings: {err}");
+            }
+        }
+        let backend_changed = import.config.process_backend != self.config.process_backend;
+        self.config = import.config;
+        if backend_changed {
+            self.process_source = source::source_for(self.config.process_backend);
+        }
+        self.refresh_processes();
+    }
+
+    pub(super) fn close_settings_transfer(&mut self) {
+        self.settings_transfer = None;
+    }
+
+    pub(super) fn settings_transfer_dialog(&self) -> Option<Element<'_, Message>> {
+        let close_button = |label: String| {
+            widget::button::standard(label).on_press(Message::CloseSettingsTransfer)
+        };
+        match self.settings_transfer.as_ref()? {
+            SettingsTransfer::Outcome(outcome) => Some(
+                widget::dialog()
+                    .title(fl!("settings-transfer-title"))
+                    .body(outcome.clone())
+                    .primary_action(close_button(fl!("settings-transfer-close")))
+                    .into(),
+            ),
+            SettingsTransfer::Preview(import) => {
+                let changes = if import.changed.is_empty() {
+                    fl!("settings-import-unchanged")
+                } else {
+                    import
+                        .changed
+                        .iter()
+                        .map(|key| format!("• {}", key.replace('_', " ")))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                let mut preview = widget::column::with_capacity(2)
+                    .push(widget::text(changes).size(14))
+                    .spacing(8);
+                if !import.skipped.is_empty() {
+                    preview = preview.push(
+                        widget::text(fl!(
+                            "settings-import-skipped",
+                            keys = import.skipped.join(", ")
+                        ))
+                        .size(12),
+                    );
+                }
+                Some(
+                    widget::dialog()
+                        .title(fl!("settings-import-title"))
+                        .body(fl!(
+                            "settings-import-description",
+                            file = import.file_name.clone()
+                        ))
+                        .control(widget::scrollable(preview).height(Length::Shrink))
+                        .secondary_action(close_button(fl!("autostart-modal-cancel")))
+                        .primary_action(
+                            widget::button::suggested(fl!("settings-import-confirm"))
+                                .on_press_maybe(
+                                    (!import.changed.is_empty())
+                                        .then_some(Message::ConfirmSettingsImport),
+                                ),
+                        )
+                        .into(),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, plan_import};
+    use crate::config::Density;
+
+    #[test]
+    fn imports_known_valid_settings_only() {
+        let current = Config {
+            debugger_command: "lldb --attach-pid {pid}".to_string(),
+            ..Config::default()
+        };
+        let file = r#"{
+            "app": "com.github.exepta.cosmic-task-monitor",
+            "version": 1,
+            "settings": {
+                "density": "Compact",
+                "show_fd_column": false,
+                "threshold_alerts": "yes",
+                "game_playtime": [],
+                "automation_hooks": [],
+                "debugger_command": "gdb -x /tmp/commands -p {pid}",
+                "launch_overrides": [
+                    {"app_id": "firefox", "env": "LD_PRELOAD=/tmp/hook.so", "args": ""}
+                ],
+                "future_setting": 3
+            }
+        }"#;
+        let (config, changed, skipped) = plan_import(&current, file).expect("file is valid");
+        assert_eq!(config.density, Density::Compact);
+        assert_eq!(config.debugger_command, current.debugger_command);
+        assert_eq!(changed, ["density"]);
+        assert_eq!(
+            skipped,
+            [
+                "automation_hooks",
+                "debugger_command",
+                "future_setting",
+                "game_playtime",
+                "launch_overrides",
+                "threshold_alerts"
+            ]
+        );
+
+        assert!(
+            plan_import(
+                &current,
+                r#"{"app": "other", "version": 1, "settings": {}}"#
+            )
+            .is_err()
+        );
+        let newer =
+            r#"{"app": "com.github.exepta.cosmic-task-monitor", "version": 9, "settings": {}}"#;
+        assert!(plan_import(&current, newer).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Serializable snapshot of the process table, shared by every JSON export, and the
+//! snapshots written to disk on a schedule so the processes running before a freeze or
+//! crash can be looked up afterwards.
+
+use super::*;
+use serde::Serialize;
+use std::fs::File;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bumped whenever a field is renamed or removed so consumers can detect old payloads.
+const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+/// Scheduled snapshots kept on disk, a day's worth at the shortest interval. The oldest are
+/// deleted as new ones are written.
+const SCHEDULED_SNAPSHOTS_KEPT: usize = 288;
+
+/// How often the snapshot subscription checks whether the next snapshot is due.
+const SNAPSHOT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct SnapshotApp {
+    app_id: String,
+    name: String,
+    pid: u32,
+    is_background: bool,
+    cpu_percent: f32,
+    rss_bytes: u64,
+    threads: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct ProcessSnapshot {
+    schema_version: u32,
+    generated_at: u64,
+    hostname: String,
+    cpu_percent: f32,
+    ram_used_bytes: u64,
+    ram_total_bytes: u64,
+    apps: Vec<SnapshotApp>,
+}
+
+/// The task monitor's state directory, `$XDG_STATE_HOME/cosmic-task-monitor`.
+pub(super) fn state_directory() -> PathBuf {
+    env::var_os("XDG_STATE_HOME")
+        .filter(|state_home| !state_home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .unwrap_or_else(env::temp_dir)
+        .join("cosmic-task-monitor")
+}
+
+/// Where scheduled snapshots are written: `$XDG_STATE_HOME/cosmic-task-monitor/snapshots`.
+fn scheduled_snapshot_directory() -> PathBuf {
+    state_directory().join("snapshots")
+}
+
+fn is_scheduled_snapshot(file_name: &str) -> bool {
+    file_name.starts_with("snapshot-") && file_name.ends_with(".json")
+}
+
+/// The snapshot files to delete so only the newest `kept` remain. File names hold the unix
+/// time they were written at, so they sort by age.
+fn expired_snapshots(mut file_names: Vec<String>, kept: usize) -> Vec<String> {
+    file_names.retain(|file_name| is_scheduled_snapshot(file_name));
+    file_names.sort();
+    let expired = file_names.len().saturating_sub(kept);
+    file_names.truncate(expired);
+    file_names
+}
+
+/// Writes `snapshot` into `directory` and deletes the oldest snapshots beyond the limit. The
+/// file is synced and then renamed into place, so a freeze right after leaves no partial file.
+fn write_scheduled_snapshot_file(
+    directory: &Path,
+    snapshot: &ProcessSnapshot,
+) -> std::io::Result<()> {
+    fs::create_dir_all(directory)?;
+    let path = directory.join(format!("snapshot-{}.json", snapshot.generated_at));
+    let partial = path.with_extension("json.partial");
+    let mut file = File::create(&partial)?;
+    file.write_all(&serde_json::to_vec(snapshot)?)?;
+    file.sync_all()?;
+    fs::rename(&partial, &path)?;
+
+    let file_names = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect();
+    for file_name in expired_snapshots(file_names, SCHEDULED_SNAPSHOTS_KEPT) {
+        fs::remove_file(directory.join(file_name))?;
+    }
+    Ok(())
+}
+
+impl AppModel {
+    /// Checks for a due snapshot on its own timer. Unlike the refresh interval it keeps
+    /// running while the session is locked or idle, when a freeze is no less likely.
+    pub(super) fn snapshot_subscription() -> Subscription<Message> {
+        Subscription::run(|| {
+            iced_futures::stream::channel(1, |mut emitter| async move {
+                let mut interval = tokio::time::interval(SNAPSHOT_CHECK_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    _ = emitter.send(Message::WriteScheduledSnapshot).await;
+                }
+            })
+        })
+    }
+
+    /// Writes a snapshot in the background once the configured interval has passed since
+    /// the last one. While sampling is paused the process list is read again first, so the
+    /// snapshot is not as old as the pause.
+    pub(super) fn write_scheduled_snapshot(&mut self) -> Task<cosmic::Action<Message>> {
+        let Some(interval) = self.config.snapshot_interval.seconds() else {
+            return Task::none();
+        };
+        if self.scheduled_snapshot_at.elapsed() < Duration::from_secs(interval) {
+            return Task::none();
+        }
+        self.scheduled_snapshot_at = Instant::now();
+        if self.sampling_paused() {
+            self.system.refresh_cpu_usage();
+            self.system.refresh_memory();
+            self.refresh_process_list();
+        }
+
+        let snapshot = self.process_snapshot();
+        Task::perform(
+            async move {
+                _ = tokio::task::spawn_blocking(move || {
+                    let directory = scheduled_snapshot_directory();
+                    if let Err(err) = write_scheduled_snapshot_file(&directory, &snapshot) {
+                        eprintln!("failed to write snapshot to {}: {err}", directory.display());
+                    }
+                })
+                .await;
+            },
+            |()| cosmic::Action::None,
+        )
+    }
+
+    pub(super) fn process_snapshot(&self) -> ProcessSnapshot {
+        let generated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+
+        ProcessSnapshot {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            generated_at,
+            hostname: System::host_name().unwrap_or_default(),
+            cpu_percent: self.system.global_cpu_usage().clamp(0.0, 100.0),
+            ram_used_bytes: self.system.used_memory(),
+            ram_total_bytes: self.system.total_memory(),
+            apps: self
+                .process_entries
+                .iter()
+                .map(|entry| SnapshotApp {
+                    app_id: entry.app_id.clone(),
+                    name: entry.display_name.clone(),
+                    pid: entry.pid,
+                    is_background: entry.is_background,
+                    cpu_percent: entry.cpu_percent,
+                    rss_bytes: entry.rss_bytes,
+                    threads: entry.threads,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expired_snapshots;
+
+    #[test]
+    fn expires_oldest_snapshots_beyond_limit() {
+        let file_names = [
+            "snapshot-1700000600.json",
+            "snapshot-1700000000.json",
+            "snapshot-1700000300.json",
+            "snapshot-1700000900.json.partial",
+            "notes.txt",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(
+            expired_snapshots(file_names.clone(), 2),
+            ["snapshot-1700000000.json"]
+        );
+        assert!(expired_snapshots(file_names, 3).is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! What the process table is built from: a plain-data sample of each process, read from a
+//! [`SystemSource`]. The app samples the live system through sysinfo or by reading `/proc`
+//! itself, as configured; tests replay a recorded fixture instead. Samples own their data,
+//! so a scan can be saved and compared. Names, executables, and command lines are shared
+//! with the previous sample of the same process, so a refresh only allocates them for
+//! processes that are new or ran another program.
+
+use super::procfs_source::{self, ProcfsSource};
+use super::*;
+use crate::config::ProcessBackend;
+use std::ffi::OsStr;
+use sysinfo::MemoryRefreshKind;
+
+/// One process as it was seen by the last refresh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct ProcessSample {
+    pub(super) pid: u32,
+    pub(super) parent: Option<u32>,
+    /// When the process started, in the source's own unit. Together with the pid it tells a
+    /// process apart from a later one that reuses its pid.
+    #[serde(default)]
+    pub(super) start_time: u64,
+    pub(super) name: Arc<str>,
+    pub(super) exe: Option<Arc<Path>>,
+    pub(super) cmd: Arc<[String]>,
+    pub(super) user_id: Option<u32>,
+    /// The state letter from `/proc/<pid>/stat`, e.g. `S` for sleeping.
+    pub(super) state: char,
+    /// Percent of one core.
+    pub(super) cpu_usage: f32,
+    /// Resident memory in bytes.
+    pub(super) memory: u64,
+    pub(super) threads: Option<u32>,
+    /// Bytes read and written since the previous refresh.
+    pub(super) read_bytes: u64,
+    pub(super) written_bytes: u64,
+}
+
+impl ProcessSample {
+    /// Samples a process, sharing what has not changed with `previous`, its last sample.
+    pub(super) fn from_process(process: &sysinfo::Process, previous: Option<&Self>) -> Self {
+        let disk_usage = process.disk_usage();
+        let previous = previous.filter(|previous| previous.start_time == process.start_time());
+        let name = match previous {
+            Some(previous) if process.name() == &*previous.name => previous.name.clone(),
+            _ => process.name().to_string_lossy().into(),
+        };
+        let exe = match previous {
+            Some(previous) if process.exe() == previous.exe.as_deref() => previous.exe.clone(),
+            _ => process.exe().map(Arc::from),
+        };
+        let same_cmd = |previous: &Self| {
+            process
+                .cmd()
+                .iter()
+                .map(|arg| arg.as_os_str())
+                .eq(previous.cmd.iter().map(OsStr::new))
+        };
+        let cmd = match previous {
+            Some(previous) if same_cmd(previous) => previous.cmd.clone(),
+            _ => process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect(),
+        };
+        Self {
+            pid: process.pid().as_u32(),
+            parent: process.parent().map(|parent| parent.as_u32()),
+            start_time: process.start_time(),
+            name,
+            exe,
+            cmd,
+            user_id: process.user_id().map(|uid| **uid),
+            state: states::state_code(process.status()),
+            cpu_usage: process.cpu_usage(),
+            memory: process.memory(),
+            threads: process.tasks().map(|tasks| tasks.len() as u32),
+            read_bytes: disk_usage.read_bytes,
+            written_bytes: disk_usage.written_bytes,
+        }
+    }
+
+    /// The command line joined with spaces, as matched against app and game ids.
+    pub(super) fn cmdline(&self) -> String {
+        self.cmd.join(" ")
+    }
+}
+
+pub(super) fn sample_processes(
+    processes: &HashMap<Pid, sysinfo::Process>,
+    previous: &HashMap<Pid, ProcessSample>,
+) -> HashMap<Pid, ProcessSample> {
+    processes
+        .iter()
+        .map(|(pid, process)| {
+            (
+                *pid,
+                ProcessSample::from_process(process, previous.get(pid)),
+            )
+        })
+        .collect()
+}
+
+/// What each refresh reads about every process.
+fn process_refresh_kind() -> ProcessRefreshKind {
+    ProcessRefreshKind::nothing()
+        .with_memory()
+        .with_cpu()
+        .with_disk_usage()
+        .with_user(UpdateKind::OnlyIfNotSet)
+        .with_exe(UpdateKind::OnlyIfNotSet)
+        // New processes need cmdline to match Flatpak/wrapper launches correctly.
+        .with_cmd(UpdateKind::OnlyIfNotSet)
+}
+
+/// Where process samples come from.
+pub(super) trait SystemSource {
+    /// Samples every process again.
+    fn refresh(&mut self);
+
+    /// The processes seen by the last refresh.
+    fn processes(&self) -> &HashMap<Pid, ProcessSample>;
+
+    /// The user the task monitor runs as.
+    fn current_user_id(&self) -> Option<u32>;
+
+    /// Logical CPUs, which a process's CPU usage is spread over.
+    fn cpu_count(&self) -> usize;
+
+    /// Swap in use, in bytes.
+    fn used_swap(&self) -> u64;
+
+    /// Sends a signal to a process seen by the last refresh.
+    fn signal(&self, pid: u32, signal: Signal) -> bool;
+
+    /// The `/proc` directory of a process seen by the last refresh, when the `/proc` here
+    /// holds it. Processes read from the host in a Flatpak have none, since the sandbox's
+    /// `/proc` holds other processes under the same pids.
+    fn proc_dir(&self, pid: u32) -> Option<PathBuf> {
+        Some(PathBuf::from(format!("/proc/{pid}")))
+    }
+
+    /// A file in the `/proc` directory of a process seen by the last refresh, e.g. `status`.
+    fn read_proc_file(&self, pid: u32, file: &str) -> Option<Vec<u8>> {
+        fs::read(self.proc_dir(pid)?.join(file)).ok()
+    }
+}
+
+/// The live source for the configured backend.
+pub(super) fn source_for(backend: ProcessBackend) -> Box<dyn SystemSource> {
+    // A Flatpak only sees its own processes through sysinfo, so it reads the host's `/proc`.
+    if procfs_source::in_flatpak_sandbox() {
+        return Box::new(ProcfsSource::host());
+    }
+    match backend {
+        ProcessBackend::Sysinfo => Box::new(SysinfoSource::new()),
+        ProcessBackend::Procfs => Box::new(ProcfsSource::new()),
+    }
+}
+
+impl AppModel {
+    /// Switches where the process table is read from. CPU usage starts over, since it is
+    /// measured between two refreshes of the same source.
+    pub(super) fn set_process_backend(&mut self, backend: ProcessBackend) {
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_process_backend(handler, backend) {
+                eprintln!("failed to save process backend: {err}");
+            }
+        } else {
+            self.config.process_backend = backend;
+        }
+        self.process_source = source_for(backend);
+        self.refresh_processes();
+    }
+}
+
+/// The live system, read through sysinfo. It keeps its own [`System`] so refreshing the
+/// process table does not disturb the CPU and memory graphs.
+pub(super) struct SysinfoSource {
+    system: System,
+    processes: HashMap<Pid, ProcessSample>,
+}
+
+impl SysinfoSource {
+    pub(super) fn new() -> Self {
+        Self {
+            system: System::new(),
+            processes: HashMap::new(),
+        }
+    }
+}
+
+impl SystemSource for SysinfoSource {
+    fn refresh(&mut self) {
+        self.system
+            .refresh_memory_specifics(MemoryRefreshKind::nothing().with_swap());
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            process_refresh_kind(),
+        );
+        self.processes = sample_processes(self.system.processes(), &self.processes);
+    }
+
+    fn processes(&self) -> &HashMap<Pid, ProcessSample> {
+        &self.processes
+    }
+
+    fn current_user_id(&self) -> Option<u32> {
+        self.processes
+            .get(&Pid::from_u32(std::process::id()))
+            .and_then(|process| process.user_id)
+    }
+
+    fn cpu_count(&self) -> usize {
+        self.system.cpus().len()
+    }
+
+    fn used_swap(&self) -> u64 {
+        self.system.used_swap()
+    }
+
+    fn signal(&self, pid: u32, signal: Signal) -> bool {
+        self.system
+            .process(Pid::from_u32(pid))
+            .and_then(|process| process.kill_with(signal))
+            .unwrap_or(false)
+    }
+}
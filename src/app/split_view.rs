@@ -0,0 +1,222 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Optional split view for the Apps page: the table on the left and the selected app's
+//! graphs and actions in a pane on the right, instead of the context drawer. The splitter
+//! can be dragged, and the pane's share of the width is saved when the drag ends.
+
+use super::*;
+use cosmic::iced::mouse;
+
+const DEFAULT_DETAIL_PERCENT: u16 = 35;
+const MIN_DETAIL_PERCENT: u16 = 20;
+const MAX_DETAIL_PERCENT: u16 = 60;
+const SPLITTER_WIDTH: f32 = 6.0;
+const DETAIL_GRAPH_HEIGHT: f32 = 72.0;
+
+#[derive(Debug, Default)]
+struct SelectedAppHistory {
+    app_id: String,
+    cpu: Vec<f32>,
+    ram: Vec<f32>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct SplitView {
+    /// Detail pane share while the splitter is dragged; `None` when not dragging.
+    drag_percent: Option<u16>,
+    history: SelectedAppHistory,
+}
+
+impl AppModel {
+    fn detail_pane_percent(&self) -> u16 {
+        let saved = match self.config.detail_pane_percent {
+            0 => DEFAULT_DETAIL_PERCENT,
+            percent => percent,
+        };
+        self.split_view
+            .drag_percent
+            .unwrap_or(saved)
+            .clamp(MIN_DETAIL_PERCENT, MAX_DETAIL_PERCENT)
+    }
+
+    pub(super) fn toggle_split_view(&mut self) {
+        let enabled = !self.config.split_view;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_split_view(handler, enabled) {
+                eprintln!("failed to save split view setting: {err}");
+            }
+        } else {
+            self.config.split_view = enabled;
+        }
+
+        // The selection moves between the drawer and the pane.
+        if self.context_page == ContextPage::ProcessActions && self.selected_process.is_some() {
+            self.core.window.show_context = !enabled;
+        }
+    }
+
+    pub(super) fn start_splitter_drag(&mut self) {
+        self.split_view.drag_percent = Some(self.detail_pane_percent());
+    }
+
+    /// Follows the cursor, given as a fraction of the split view's width.
+    pub(super) fn drag_splitter(&mut self, position: f32) {
+        if self.split_view.drag_percent.is_some() {
+            let percent = ((1.0 - position.clamp(0.0, 1.0)) * 100.0).round() as u16;
+            self.split_view.drag_percent =
+                Some(percent.clamp(MIN_DETAIL_PERCENT, MAX_DETAIL_PERCENT));
+        }
+    }
+
+    pub(super) fn end_splitter_drag(&mut self) {
+        let Some(percent) = self.split_view.drag_percent.take() else {
+            return;
+        };
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_detail_pane_percent(handler, percent) {
+                eprintln!("failed to save split view position: {err}");
+            }
+        } else {
+            self.config.detail_pane_percent = percent;
+        }
+    }
+
+    /// Samples the selected app for the detail pane's graphs.
+    pub(super) fn record_selected_app_history(&mut self) {
+        if !self.config.split_view {
+            return;
+        }
+        let Some(selected) = self.selected_process.as_ref() else {
+            return;
+        };
+        let Some(entry) = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == selected.app_id)
+        else {
+            return;
+        };
+
+        let history = &mut self.split_view.history;
+        if history.app_id != entry.app_id {
+            *history = SelectedAppHistory {
+                app_id: entry.app_id.clone(),
+                ..Default::default()
+            };
+        }
+        let total_memory = self.system.total_memory().max(1);
+        let retained = history::retained_samples(self.config.history_retention);
+        let tick = self.history_ticks;
+        history::push_sample(&mut history.cpu, entry.cpu_percent, retained, tick);
+        history::push_sample(
+            &mut history.ram,
+            entry.rss_bytes as f32 / total_memory as f32 * 100.0,
+            retained,
+            tick,
+        );
+    }
+
+    pub(super) fn split_view(&self, space_s: u16) -> Element<'_, Message> {
+        cosmic::iced::widget::responsive(move |size| {
+            let detail_percent = self.detail_pane_percent();
+            let splitter = cosmic::iced::widget::mouse_area(
+                widget::container(widget::text(""))
+                    .width(Length::Fixed(SPLITTER_WIDTH))
+                    .height(Length::Fill)
+                    .class(theme::Container::custom(|theme| widget::container::Style {
+                        background: Some(Background::Color(theme.cosmic().bg_divider().into())),
+                        border: Border {
+                            radius: 3.0.into(),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })),
+            )
+            .on_press(Message::StartSplitterDrag)
+            .interaction(mouse::Interaction::ResizingHorizontally);
+
+            let panes = widget::row::with_capacity(3)
+                .push(
+                    widget::container(self.apps_list_view(space_s))
+                        .width(Length::FillPortion(100 - detail_percent)),
+                )
+                .push(splitter)
+                .push(
+                    widget::container(self.detail_pane(space_s))
+                        .width(Length::FillPortion(detail_percent)),
+                )
+                .spacing(space_s)
+                .height(Length::Fill);
+
+            // Moves are only tracked during a drag, so hovering stays free of updates.
+            let area = cosmic::iced::widget::mouse_area(panes);
+            if self.split_view.drag_percent.is_some() {
+                let width = size.width.max(1.0);
+                area.on_move(move |point| Message::DragSplitter(point.x / width))
+                    .on_release(Message::EndSplitterDrag)
+                    .into()
+            } else {
+                area.into()
+            }
+        })
+        .into()
+    }
+
+    fn detail_pane(&self, space_s: u16) -> Element<'_, Message> {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return widget::container(widget::text(fl!("split-view-empty")))
+                .center(Length::Fill)
+                .into();
+        };
+
+        let history = &self.split_view.history;
+        let (cpu, ram): (&[f32], &[f32]) = if history.app_id == selected.app_id {
+            (history.cpu.as_slice(), history.ram.as_slice())
+        } else {
+            (&[], &[])
+        };
+        let header = widget::row::with_capacity(2)
+            .push(
+                widget::text::title4(selected.display_name.clone())
+                    .width(Length::Fill)
+                    .wrapping(cosmic::iced::widget::text::Wrapping::None)
+                    .ellipsize(cosmic::iced::widget::text::Ellipsize::End(
+                        cosmic::iced_core::text::EllipsizeHeightLimit::Lines(1),
+                    )),
+            )
+            .push(
+                widget::button::icon(widget::icon::from_name("window-close-symbolic"))
+                    .on_press(Message::CloseProcessMenu),
+            )
+            .align_y(Alignment::Center);
+
+        // The graph is always a share of the machine; the label follows the CPU mode.
+        let current_cpu = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == selected.app_id)
+            .map(|entry| self.display_cpu(entry))
+            .unwrap_or_default();
+        let content = widget::column::with_capacity(6)
+            .push(header)
+            .push(widget::text(format!(
+                "{}: {}",
+                self.cpu_column_label(),
+                units::format_percent(current_cpu, 1)
+            )))
+            .push(Chart::new(cpu, ChartKind::Area, CPU_ACCENT).view(DETAIL_GRAPH_HEIGHT))
+            .push(widget::text(format!(
+                "{}: {}",
+                fl!("table-ram"),
+                units::format_percent(ram.last().copied().unwrap_or_default(), 1)
+            )))
+            .push(Chart::new(ram, ChartKind::Area, RAM_ACCENT).view(DETAIL_GRAPH_HEIGHT))
+            .push(self.process_actions_content())
+            .spacing(space_s)
+            .width(Length::Fill);
+
+        widget::scrollable(widget::container(content).padding([0, 12, 0, 0]))
+            .height(Length::Fill)
+            .into()
+    }
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Process states per app, with the single-letter codes `ps` uses (R, S, D, Z, T, ...).
+//! An app with a zombie, or with a member stuck in uninterruptible sleep (D) for two
+//! refreshes in a row, gets a warning badge. A single D sample is usually just disk I/O.
+
+use super::*;
+use std::collections::BTreeMap;
+use sysinfo::ProcessStatus;
+
+#[derive(Debug, Default)]
+pub(super) struct StateMonitor {
+    /// Apps with a member in D state at the previous refresh.
+    uninterruptible: HashSet<String>,
+    /// Apps with a zombie or a member that stayed in D state.
+    stuck: HashSet<String>,
+}
+
+pub(super) fn state_code(status: ProcessStatus) -> char {
+    match status {
+        ProcessStatus::Run => 'R',
+        ProcessStatus::Sleep => 'S',
+        ProcessStatus::UninterruptibleDiskSleep => 'D',
+        ProcessStatus::Zombie => 'Z',
+        ProcessStatus::Stop => 'T',
+        ProcessStatus::Tracing => 't',
+        ProcessStatus::Idle => 'I',
+        ProcessStatus::Dead => 'X',
+        ProcessStatus::Wakekill => 'K',
+        ProcessStatus::Waking => 'W',
+        ProcessStatus::Parked => 'P',
+        ProcessStatus::LockBlocked => 'L',
+        ProcessStatus::Unknown(_) => '?',
+    }
+}
+
+/// Number of members per state code, in code order.
+pub(super) fn count_states(codes: &[char]) -> Vec<(char, u32)> {
+    let mut counts = BTreeMap::new();
+    for code in codes {
+        *counts.entry(*code).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+fn has_state(entry: &ProcessEntry, code: char) -> bool {
+    entry.states.iter().any(|(state, _)| *state == code)
+}
+
+impl AppModel {
+    pub(super) fn refresh_stuck_states(&mut self) {
+        let monitor = &mut self.states;
+        let uninterruptible = self
+            .process_entries
+            .iter()
+            .filter(|entry| has_state(entry, 'D'))
+            .map(|entry| entry.app_id.clone())
+            .collect::<HashSet<_>>();
+        monitor.stuck = self
+            .process_entries
+            .iter()
+            .filter(|entry| {
+                has_state(entry, 'Z')
+                    || (uninterruptible.contains(&entry.app_id)
+                        && monitor.uninterruptible.contains(&entry.app_id))
+            })
+            .map(|entry| entry.app_id.clone())
+            .collect();
+        monitor.uninterruptible = uninterruptible;
+    }
+
+    pub(super) fn state_badge_icons(&self, entry: &ProcessEntry) -> Vec<&'static str> {
+        if self.states.stuck.contains(&entry.app_id) {
+            vec!["dialog-warning-symbolic"]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The selected app's members per state, e.g. `S 12 · R 1 · Z 1`.
+    pub(super) fn selected_app_states(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let entry = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == selected.app_id)?;
+        let states = entry
+            .states
+            .iter()
+            .map(|(code, count)| format!("{code} {count}"))
+            .collect::<Vec<_>>()
+            .join(" · ");
+
+        let mut column = widget::column::with_capacity(2)
+            .push(widget::text(fl!("process-states", states = states)));
+        if self.states.stuck.contains(&entry.app_id) {
+            let warning = if has_state(entry, 'Z') {
+                fl!("process-state-zombie")
+            } else {
+                fl!("process-state-uninterruptible")
+            };
+            column = column.push(widget::text(warning).size(12));
+        }
+        Some(column.spacing(4).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count_states;
+
+    #[test]
+    fn counts_states_in_code_order() {
+        assert_eq!(
+            count_states(&['S', 'R', 'S', 'Z', 'S']),
+            vec![('R', 1), ('S', 3), ('Z', 1)]
+        );
+        assert!(count_states(&[]).is_empty());
+    }
+}
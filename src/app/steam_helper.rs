@@ -4,15 +4,15 @@ use super::*;
 
 impl AppModel {
     pub(super) fn steam_app_id_for_process(
-        process: &sysinfo::Process,
-        processes: &HashMap<Pid, sysinfo::Process>,
+        process: &ProcessSample,
+        processes: &HashMap<Pid, ProcessSample>,
     ) -> Option<String> {
         if let Some(app_id) = Self::extract_steam_app_id_from_process(process) {
             return Some(app_id);
         }
 
         let mut visited = HashSet::new();
-        let mut parent = process.parent();
+        let mut parent = process.parent.map(Pid::from_u32);
         let mut depth = 0usize;
 
         while let Some(parent_pid) = parent {
@@ -28,38 +28,31 @@ impl AppModel {
                 return Some(app_id);
             }
 
-            parent = parent_process.parent();
+            parent = parent_process.parent.map(Pid::from_u32);
             depth += 1;
         }
 
         None
     }
 
-    pub(super) fn extract_steam_app_id_from_process(process: &sysinfo::Process) -> Option<String> {
-        if let Some(app_id) = Self::extract_steam_app_id(process.name().to_string_lossy().as_ref())
-        {
+    pub(super) fn extract_steam_app_id_from_process(process: &ProcessSample) -> Option<String> {
+        if let Some(app_id) = Self::extract_steam_app_id(&process.name) {
             return Some(app_id);
         }
 
-        if let Some(cmd0) = process.cmd().first() {
-            if let Some(app_id) = Self::extract_steam_app_id(cmd0.to_string_lossy().as_ref()) {
+        if let Some(cmd0) = process.cmd.first() {
+            if let Some(app_id) = Self::extract_steam_app_id(cmd0) {
                 return Some(app_id);
             }
         }
 
-        if !process.cmd().is_empty() {
-            let cmdline = process
-                .cmd()
-                .iter()
-                .map(|part| part.to_string_lossy())
-                .collect::<Vec<_>>()
-                .join(" ");
-            if let Some(app_id) = Self::extract_steam_app_id(&cmdline) {
+        if !process.cmd.is_empty() {
+            if let Some(app_id) = Self::extract_steam_app_id(&process.cmdline()) {
                 return Some(app_id);
             }
 
-            for arg in process.cmd() {
-                if let Some(app_id) = Self::extract_steam_app_id(arg.to_string_lossy().as_ref()) {
+            for arg in process.cmd.iter() {
+                if let Some(app_id) = Self::extract_steam_app_id(arg) {
                     return Some(app_id);
                 }
             }
@@ -134,13 +127,65 @@ impl AppModel {
         app_id: &str,
         default_icon: Option<icon::Handle>,
     ) -> SteamAppMeta {
-        let name = Self::steam_manifest_name(app_id)
-            .unwrap_or_else(|| crate::fl!("steam-app-fallback", app_id = app_id));
-        let icon_handle = Self::steam_icon_path(app_id)
-            .map(icon::from_path)
-            .or(default_icon);
+        let manifest_name = Self::steam_manifest_name(app_id);
+        // Non-Steam games added to the library have no manifest, only a shortcut.
+        let shortcut = manifest_name
+            .is_none()
+            .then(|| Self::steam_shortcut(app_id))
+            .flatten();
+        let (name, icon_path) = match (manifest_name, shortcut) {
+            (Some(name), _) => (name, Self::steam_icon_path(app_id)),
+            (None, Some((name, icon_path))) => (name, icon_path),
+            (None, None) => (
+                crate::fl!("steam-app-fallback", app_id = app_id),
+                Self::steam_icon_path(app_id),
+            ),
+        };
+        let icon_handle = icon_path.map(icon::from_path).or(default_icon);
+        let (compat_prefix, proton_build) = Self::steam_compat_prefix(app_id).unzip();
+
+        SteamAppMeta {
+            name,
+            icon_handle,
+            compat_prefix,
+            proton_build: proton_build.flatten(),
+            proton_tool: None,
+        }
+    }
+
+    /// Name and icon of a non-Steam game from the `shortcuts.vdf` of each Steam user. The
+    /// icon is the one set in the shortcut's properties, or else its grid artwork.
+    pub(super) fn steam_shortcut(app_id: &str) -> Option<(String, Option<PathBuf>)> {
+        let app_id = app_id.parse::<u32>().ok()?;
+        for steam_root in Self::steam_root_paths() {
+            let Ok(users) = fs::read_dir(steam_root.join("userdata")) else {
+                continue;
+            };
+            for user in users.flatten() {
+                let config = user.path().join("config");
+                let Ok(data) = fs::read(config.join("shortcuts.vdf")) else {
+                    continue;
+                };
+                let Some(shortcut) = vdf::parse_shortcuts(&data)
+                    .into_iter()
+                    .find(|shortcut| shortcut.app_id == app_id)
+                else {
+                    continue;
+                };
+
+                let grid = config.join("grid");
+                let icon_path = std::iter::once(PathBuf::from(shortcut.icon.trim_matches('"')))
+                    .chain(
+                        ["_icon.png", "_icon.jpg", "p.png", "p.jpg", ".png", ".jpg"]
+                            .into_iter()
+                            .map(|suffix| grid.join(format!("{app_id}{suffix}"))),
+                    )
+                    .find(|path| path.is_file());
+                return Some((shortcut.name, icon_path));
+            }
+        }
 
-        SteamAppMeta { name, icon_handle }
+        None
     }
 
     pub(super) fn steam_manifest_name(app_id: &str) -> Option<String> {
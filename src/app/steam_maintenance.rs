@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Background work Steam does between games: shader pre-caching, where `fossilize_replay`
+//! compiles a game's Vulkan pipelines, and downloads staged in `steamapps/downloading`.
+//! Shader compiles count towards the Steam client's entry instead of showing up as unmatched
+//! processes, and while either is going on, Steam gets a maintenance badge and its details
+//! list the games concerned.
+
+use super::*;
+
+const STEAM_DOWNLOAD_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+const STEAM_MAINTENANCE_BADGE: &str = "emblem-system-symbolic";
+/// App ids of the Steam client's desktop entry, native and Flatpak.
+const STEAM_CLIENT_APP_IDS: [&str; 2] = ["steam", "com.valvesoftware.Steam"];
+
+#[derive(Debug, Default)]
+pub(super) struct SteamMaintenance {
+    /// Names of the games with a download staged.
+    downloads: Vec<String>,
+    downloads_refreshed_at: Option<Instant>,
+    /// App ids of the games shaders are compiled for, and their names.
+    shader_app_ids: Vec<String>,
+    shader_games: Vec<String>,
+}
+
+fn is_fossilize_replay(name: &str) -> bool {
+    // The kernel truncates process names to 15 bytes.
+    "fossilize_replay".starts_with(name) && name.len() >= "fossilize_repl".len()
+}
+
+/// The game a shader compile is for, from the cache path on its command line, e.g.
+/// `.../steamapps/shadercache/1091500/fozpipelinesv6/steamapp_pipeline_cache.foz`.
+fn shader_cache_app_id(cmdline: &str) -> Option<String> {
+    let lower = cmdline.to_ascii_lowercase();
+    AppModel::extract_decimal_after_marker(cmdline, &lower, "shadercache/")
+}
+
+fn is_steam_client(entry: &ProcessEntry) -> bool {
+    STEAM_CLIENT_APP_IDS.contains(&entry.app_id.as_str())
+}
+
+impl AppModel {
+    /// For a `fossilize_replay` process, the game it compiles shaders for, if the cache
+    /// path names one. `None` for every other process.
+    pub(super) fn shader_precache_for_process(process: &ProcessSample) -> Option<Option<String>> {
+        if !is_fossilize_replay(&process.name) {
+            return None;
+        }
+        Some(shader_cache_app_id(&process.cmdline()))
+    }
+
+    /// App id, name, icon, and category of the Steam client's entry, which shader compiles
+    /// count towards. Without a desktop entry for Steam they still share one entry.
+    pub(super) fn steam_client_identity(
+        &self,
+    ) -> (String, String, Option<icon::Handle>, Option<&'static str>) {
+        match self.desktop_apps_by_exec.get("steam") {
+            Some(meta) => (
+                meta.app_id.clone(),
+                meta.name.clone(),
+                meta.icon_handle.as_deref().cloned(),
+                Self::primary_desktop_category(&meta.categories),
+            ),
+            None => (
+                STEAM_CLIENT_APP_IDS[0].to_string(),
+                "Steam".to_string(),
+                None,
+                None,
+            ),
+        }
+    }
+
+    /// A Steam game's name from the running games, or else from its manifest.
+    fn steam_game_name(&self, app_id: &str) -> String {
+        self.steam_apps_by_id.get(app_id).map_or_else(
+            || {
+                Self::steam_manifest_name(app_id)
+                    .unwrap_or_else(|| fl!("steam-app-fallback", app_id = app_id))
+            },
+            |meta| meta.name.clone(),
+        )
+    }
+
+    /// Looks for shader compiles on every refresh, and for staged downloads every few
+    /// seconds while Steam runs, since that walks the Steam libraries.
+    pub(super) fn refresh_steam_maintenance(&mut self) {
+        if !self.process_entries.iter().any(is_steam_client) {
+            self.steam_maintenance = SteamMaintenance::default();
+            return;
+        }
+
+        let mut shader_app_ids = self
+            .process_source
+            .processes()
+            .values()
+            .filter_map(|process| Self::shader_precache_for_process(process).flatten())
+            .collect::<Vec<_>>();
+        shader_app_ids.sort();
+        shader_app_ids.dedup();
+        if shader_app_ids != self.steam_maintenance.shader_app_ids {
+            self.steam_maintenance.shader_games = shader_app_ids
+                .iter()
+                .map(|app_id| self.steam_game_name(app_id))
+                .collect();
+            self.steam_maintenance.shader_app_ids = shader_app_ids;
+        }
+
+        if self
+            .steam_maintenance
+            .downloads_refreshed_at
+            .is_some_and(|refreshed| refreshed.elapsed() < STEAM_DOWNLOAD_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        let mut app_ids = Self::steam_library_roots()
+            .iter()
+            .filter_map(|root| fs::read_dir(Self::steamapps_dir(root).join("downloading")).ok())
+            .flat_map(|entries| entries.flatten())
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .filter(|name| !name.is_empty() && name.bytes().all(|byte| byte.is_ascii_digit()))
+            .collect::<Vec<_>>();
+        app_ids.sort();
+        app_ids.dedup();
+        self.steam_maintenance.downloads = app_ids
+            .iter()
+            .map(|app_id| self.steam_game_name(app_id))
+            .collect();
+        self.steam_maintenance.downloads_refreshed_at = Some(Instant::now());
+    }
+
+    pub(super) fn steam_maintenance_badge_icons(&self, entry: &ProcessEntry) -> Vec<&'static str> {
+        let maintenance = &self.steam_maintenance;
+        if is_steam_client(entry)
+            && !(maintenance.downloads.is_empty() && maintenance.shader_games.is_empty())
+        {
+            vec![STEAM_MAINTENANCE_BADGE]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The games Steam compiles shaders for or is downloading, shown with the Steam
+    /// client's actions.
+    pub(super) fn selected_steam_maintenance(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let entry = self
+            .process_entries
+            .iter()
+            .find(|entry| entry.app_id == selected.app_id)?;
+        let maintenance = &self.steam_maintenance;
+        if !is_steam_client(entry)
+            || (maintenance.shader_games.is_empty() && maintenance.downloads.is_empty())
+        {
+            return None;
+        }
+
+        let sections = [
+            (fl!("steam-shader-precache"), &maintenance.shader_games),
+            (fl!("steam-downloads"), &maintenance.downloads),
+        ];
+        let mut column = widget::column::with_capacity(4).spacing(4);
+        for (heading, games) in sections {
+            if !games.is_empty() {
+                column = column
+                    .push(widget::text::heading(heading))
+                    .push(widget::text(games.join("\n")));
+            }
+        }
+        Some(column.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_fossilize_replay, shader_cache_app_id};
+
+    #[test]
+    fn recognizes_truncated_fossilize_names() {
+        assert!(is_fossilize_replay("fossilize_replay"));
+        assert!(is_fossilize_replay("fossilize_repla"));
+        assert!(!is_fossilize_replay("fossil"));
+        assert!(!is_fossilize_replay("steam"));
+    }
+
+    #[test]
+    fn finds_game_in_shader_cache_path() {
+        let cmdline = "/home/user/.local/share/Steam/ubuntu12_64/fossilize_replay \
+                       --master-process --quiet-slave \
+                       /home/user/.local/share/Steam/steamapps/shadercache/1091500/\
+                       fozpipelinesv6/steamapp_pipeline_cache.foz";
+        assert_eq!(shader_cache_app_id(cmdline).as_deref(), Some("1091500"));
+        assert_eq!(shader_cache_app_id("fossilize_replay --help"), None);
+    }
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Steam app names and icons, looked up in the background. Reading the app manifests and
+//! the users' `shortcuts.vdf` walks every Steam library, which is too slow for a refresh, so
+//! a game seen for the first time shows a placeholder name until the lookup finishes. Its
+//! processes are matched again once the lookup is done.
+
+use super::*;
+
+#[derive(Debug, Default)]
+pub(super) struct SteamResolver {
+    /// App ids shown with a placeholder until their lookup finishes.
+    pending: HashSet<String>,
+    lookup_running: bool,
+}
+
+impl SteamResolver {
+    pub(super) fn is_pending(&self, app_id: &str) -> bool {
+        self.pending.contains(app_id)
+    }
+
+    /// The metadata of `app_id`, or a placeholder that queues its lookup.
+    pub(super) fn meta<'a>(
+        &mut self,
+        steam_apps_by_id: &'a mut HashMap<String, SteamAppMeta>,
+        app_id: &str,
+        default_icon: Option<&icon::Handle>,
+    ) -> &'a mut SteamAppMeta {
+        steam_apps_by_id
+            .entry(app_id.to_string())
+            .or_insert_with(|| {
+                self.pending.insert(app_id.to_string());
+                SteamAppMeta {
+                    name: fl!("steam-app-loading", app_id = app_id),
+                    icon_handle: default_icon.cloned(),
+                    compat_prefix: None,
+                    proton_build: None,
+                    proton_tool: None,
+                }
+            })
+    }
+}
+
+impl AppModel {
+    pub(super) fn request_steam_lookups(&mut self) -> Task<cosmic::Action<Message>> {
+        let resolver = &mut self.steam_resolver;
+        if resolver.lookup_running || resolver.pending.is_empty() {
+            return Task::none();
+        }
+
+        resolver.lookup_running = true;
+        let pending = resolver.pending.iter().cloned().collect::<Vec<_>>();
+        let steam_icon_handle = self
+            .desktop_apps_by_exec
+            .get("steam")
+            .and_then(|meta| meta.icon_handle.as_deref().cloned());
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    pending
+                        .into_iter()
+                        .map(|app_id| {
+                            let meta =
+                                Self::load_steam_app_meta(&app_id, steam_icon_handle.clone());
+                            (app_id, meta)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await
+                .unwrap_or_default()
+            },
+            |apps| cosmic::Action::App(Message::SteamAppsResolved(apps)),
+        )
+    }
+
+    pub(super) fn set_steam_apps(&mut self, apps: Vec<(String, SteamAppMeta)>) {
+        self.steam_resolver.lookup_running = false;
+        for (app_id, mut meta) in apps {
+            self.steam_resolver.pending.remove(&app_id);
+            // The tool is read from the game's command line during the refresh.
+            if let Some(placeholder) = self.steam_apps_by_id.get(&app_id) {
+                meta.proton_tool = placeholder.proton_tool.clone();
+            }
+            self.steam_apps_by_id.insert(app_id, meta);
+        }
+    }
+}
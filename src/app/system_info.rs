@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The system summary on the Info page: kernel, distribution, boot time, and the installed
+//! memory modules. The modules come from the SMBIOS tables through `dmidecode`, which most
+//! distributions only let root read, so a second attempt goes through `pkexec`.
+
+use super::*;
+
+/// Exit code of `pkexec` when the password prompt was dismissed.
+const PKEXEC_DISMISSED: i32 = 126;
+
+/// What does not change while the task monitor runs, read once at startup.
+#[derive(Debug, Clone)]
+pub(super) struct SystemSummary {
+    pub(super) kernel: Option<String>,
+    pub(super) distribution: Option<String>,
+    pub(super) host_name: Option<String>,
+    pub(super) architecture: String,
+    /// Seconds since the Unix epoch.
+    pub(super) boot_time: u64,
+}
+
+impl SystemSummary {
+    pub(super) fn read() -> Self {
+        Self {
+            kernel: System::kernel_version(),
+            distribution: System::long_os_version(),
+            host_name: System::host_name(),
+            architecture: System::cpu_arch(),
+            boot_time: System::boot_time(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) struct MemoryModule {
+    pub(super) locator: String,
+    pub(super) size: String,
+    pub(super) kind: Option<String>,
+    pub(super) speed: Option<String>,
+    pub(super) manufacturer: Option<String>,
+    pub(super) part_number: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum MemoryModules {
+    NotRead,
+    Reading,
+    Read(Vec<MemoryModule>),
+    /// Reading the firmware tables needs administrator rights.
+    Denied,
+    Failed(String),
+}
+
+/// Values `dmidecode` prints for fields the firmware left empty.
+fn dmi_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    let placeholder = value.is_empty()
+        || matches!(
+            value,
+            "Unknown" | "Not Specified" | "None" | "Undefined" | "<OUT OF SPEC>"
+        );
+    (!placeholder).then(|| value.to_string())
+}
+
+/// The populated slots in `dmidecode -t memory` output; empty slots are skipped.
+fn parse_memory_modules(output: &str) -> Vec<MemoryModule> {
+    let mut modules = Vec::new();
+    for device in output.split("\n\n") {
+        let mut lines = device
+            .lines()
+            .skip_while(|line| line.starts_with("Handle "));
+        if lines.next().map(str::trim) != Some("Memory Device") {
+            continue;
+        }
+        let mut fields = HashMap::new();
+        for line in lines {
+            if let Some((key, value)) = line.trim().split_once(':') {
+                fields.entry(key).or_insert(value);
+            }
+        }
+        let field = |key: &str| fields.get(key).and_then(|value| dmi_value(value));
+        let Some(size) = field("Size").filter(|size| *size != "No Module Installed") else {
+            continue;
+        };
+        modules.push(MemoryModule {
+            locator: field("Locator").unwrap_or_default(),
+            size,
+            kind: field("Type"),
+            speed: field("Configured Memory Speed").or_else(|| field("Speed")),
+            manufacturer: field("Manufacturer"),
+            part_number: field("Part Number"),
+        });
+    }
+    modules
+}
+
+/// Runs `dmidecode`, through `pkexec` if `privileged`.
+fn read_memory_modules(privileged: bool) -> MemoryModules {
+    let mut command = if privileged {
+        let mut command = Command::new("pkexec");
+        command.arg("dmidecode");
+        command
+    } else {
+        Command::new("dmidecode")
+    };
+    let output = match command
+        .args(["--type", "memory"])
+        .stdin(Stdio::null())
+        .output()
+    {
+        Ok(output) => output,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return MemoryModules::Failed(fl!("info-dmidecode-missing"));
+        }
+        Err(err) => return MemoryModules::Failed(err.to_string()),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let modules = parse_memory_modules(&stdout);
+    if output.status.success() || !modules.is_empty() {
+        return MemoryModules::Read(modules);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.code() == Some(PKEXEC_DISMISSED) || stderr.contains("Permission denied") {
+        MemoryModules::Denied
+    } else {
+        MemoryModules::Failed(stderr.trim().to_string())
+    }
+}
+
+/// The year, month, and day of a day counted from the Unix epoch.
+pub(super) fn civil_date(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm, with eras of 400 years starting in March.
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Local date and time as `YYYY-MM-DD HH:MM:SS`.
+fn format_date_time(unix_seconds: u64, offset_seconds: i64) -> String {
+    let local_seconds = unix_seconds as i64 + offset_seconds;
+    let (year, month, day) = civil_date(local_seconds.div_euclid(86_400));
+    format!(
+        "{year:04}-{month:02}-{day:02} {}",
+        events::format_time_of_day(unix_seconds, offset_seconds)
+    )
+}
+
+impl AppModel {
+    /// Reads the memory modules in the background, through `pkexec` if `privileged`.
+    pub(super) fn request_memory_modules(
+        &mut self,
+        privileged: bool,
+    ) -> Task<cosmic::Action<Message>> {
+        self.info_page.memory_modules = MemoryModules::Reading;
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || read_memory_modules(privileged))
+                    .await
+                    .unwrap_or_else(|err| MemoryModules::Failed(err.to_string()))
+            },
+            |modules| cosmic::Action::App(Message::Info(InfoMessage::MemoryModulesRead(modules))),
+        )
+    }
+
+    pub(super) fn boot_time_label(&self) -> String {
+        let boot_time = self.system_summary.boot_time;
+        format_date_time(boot_time, playtime::local_utc_offset(boot_time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MemoryModule, civil_date, parse_memory_modules};
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+        assert_eq!(civil_date(20_741), (2026, 10, 15));
+        assert_eq!(civil_date(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn parses_populated_memory_slots() {
+        let output = "# dmidecode 3.5\nGetting SMBIOS data from sysfs.\n\n\
+                      Handle 0x0040, DMI type 17, 92 bytes\nMemory Device\n\
+                      \tSize: 16 GB\n\tLocator: DIMM A1\n\tType: DDR4\n\tSpeed: 3200 MT/s\n\
+                      \tManufacturer: Samsung\n\tPart Number: M471A2K43EB1-CWE\n\
+                      \tConfigured Memory Speed: 2933 MT/s\n\n\
+                      Handle 0x0041, DMI type 17, 92 bytes\nMemory Device\n\
+                      \tSize: No Module Installed\n\tLocator: DIMM A2\n\tType: Unknown\n";
+        assert_eq!(
+            parse_memory_modules(output),
+            vec![MemoryModule {
+                locator: "DIMM A1".to_string(),
+                size: "16 GB".to_string(),
+                kind: Some("DDR4".to_string()),
+                speed: Some("2933 MT/s".to_string()),
+                manufacturer: Some("Samsung".to_string()),
+                part_number: Some("M471A2K43EB1-CWE".to_string()),
+            }]
+        );
+    }
+}
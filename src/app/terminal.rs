@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Runs commands in the user's terminal emulator, trying `$TERMINAL` first and then the
+//! common emulators with the flag each uses to run a command.
+
+use super::*;
+
+const TERMINALS: [(&str, &str); 5] = [
+    ("cosmic-term", "-e"),
+    ("gnome-terminal", "--"),
+    ("konsole", "-e"),
+    ("x-terminal-emulator", "-e"),
+    ("xterm", "-e"),
+];
+
+/// Opens a terminal running `command`, or a plain shell when it is empty, in `working_dir`.
+/// Returns whether a terminal could be started.
+pub(super) fn spawn_in_terminal(command: &[&str], working_dir: Option<&Path>) -> bool {
+    let user_terminal = env::var("TERMINAL")
+        .ok()
+        .filter(|terminal| !terminal.trim().is_empty());
+    let candidates = user_terminal
+        .iter()
+        .map(|terminal| (terminal.as_str(), "-e"))
+        .chain(TERMINALS);
+
+    for (terminal, exec_flag) in candidates {
+        let mut launch = Command::new(terminal);
+        if !command.is_empty() {
+            launch.arg(exec_flag).args(command);
+        }
+        if let Some(dir) = working_dir {
+            launch.current_dir(dir);
+        }
+        if AppModel::spawn_detached(&mut launch) {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A small screenshot of the selected app's window at the top of its details, so the right
+//! app is picked among several with the same name. Wayland windows are captured by their
+//! toplevel with `grim -T`, found through `lswt`, which needs a compositor with the
+//! foreign-toplevel-list and image-copy-capture protocols such as COSMIC. XWayland windows
+//! are found by pid with `wmctrl` and captured with ImageMagick's `import`. The screenshot
+//! is taken again every few seconds while the details are shown.
+
+use super::*;
+
+/// How often the screenshot is taken again while the details are shown.
+const THUMBNAIL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Width the X11 capture is scaled to, and the scale of a Wayland capture.
+const THUMBNAIL_WIDTH: u32 = 480;
+const THUMBNAIL_SCALE: &str = "0.25";
+
+const THUMBNAIL_HEIGHT: f32 = 140.0;
+
+#[derive(Debug, Default)]
+pub(super) struct WindowThumbnail {
+    app_id: String,
+    handle: Option<icon::Handle>,
+    captured_at: Option<Instant>,
+    loading: bool,
+}
+
+/// Whether the tools for either kind of window are installed, checked once.
+fn capture_tools_installed() -> bool {
+    static INSTALLED: OnceLock<bool> = OnceLock::new();
+    *INSTALLED.get_or_init(|| {
+        let installed = |programs: &[&str]| {
+            programs
+                .iter()
+                .all(|program| debug_tools::is_installed(program))
+        };
+        installed(&["lswt", "grim"]) || installed(&["wmctrl", "import"])
+    })
+}
+
+/// The identifier of the first toplevel with one of `app_ids` in `lswt -j` output, e.g.
+/// `{"toplevels": [{"app-id": "firefox", "identifier": "a1b2", …}]}`.
+fn toplevel_identifier(listing: &str, app_ids: &[String]) -> Option<String> {
+    let listing = serde_json::from_str::<serde_json::Value>(listing).ok()?;
+    let toplevels = listing.get("toplevels")?.as_array()?;
+    app_ids.iter().find_map(|app_id| {
+        let toplevel = toplevels.iter().find(|toplevel| {
+            toplevel.get("app-id").and_then(serde_json::Value::as_str) == Some(app_id.as_str())
+        })?;
+        Some(toplevel.get("identifier")?.as_str()?.to_string())
+    })
+}
+
+/// The id of the first window owned by one of `pids` in `wmctrl -lp` output, whose lines
+/// start with the window id, desktop, and pid.
+fn x11_window_for_pids(listing: &str, pids: &[u32]) -> Option<String> {
+    listing.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let window = fields.next()?;
+        let pid = fields.nth(1)?.parse::<u32>().ok()?;
+        pids.contains(&pid).then(|| window.to_string())
+    })
+}
+
+/// Stdout of a program that succeeded.
+fn run_for_output(program: &str, args: &[&str]) -> Option<Vec<u8>> {
+    Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success() && !output.stdout.is_empty())
+        .map(|output| output.stdout)
+}
+
+/// A PNG of the app's window, trying its Wayland toplevel before an X11 window.
+fn capture_window(app_ids: &[String], pids: &[u32]) -> Option<Vec<u8>> {
+    let wayland = || {
+        let listing = run_for_output("lswt", &["-j"])?;
+        let identifier = toplevel_identifier(&String::from_utf8_lossy(&listing), app_ids)?;
+        run_for_output("grim", &["-s", THUMBNAIL_SCALE, "-T", &identifier, "-"])
+    };
+    let x11 = || {
+        let listing = run_for_output("wmctrl", &["-lp"])?;
+        let window = x11_window_for_pids(&String::from_utf8_lossy(&listing), pids)?;
+        let width = format!("{THUMBNAIL_WIDTH}x");
+        run_for_output("import", &["-window", &window, "-resize", &width, "png:-"])
+    };
+    wayland().or_else(x11)
+}
+
+impl AppModel {
+    /// Whether the selected app's details are on screen, in the drawer or the split view.
+    fn selected_details_shown(&self) -> bool {
+        self.selected_process.is_some()
+            && (self.config.split_view
+                || (self.core.window.show_context
+                    && self.context_page == ContextPage::ProcessActions))
+    }
+
+    /// Takes a new screenshot on a refresh when the last one is old or of another app.
+    pub(super) fn request_window_thumbnail(&mut self) -> Task<cosmic::Action<Message>> {
+        if !self.selected_details_shown() || !capture_tools_installed() {
+            return Task::none();
+        }
+        let Some(entry) = self.selected_process.as_ref().and_then(|selected| {
+            self.process_entries
+                .iter()
+                .find(|entry| entry.app_id == selected.app_id)
+        }) else {
+            return Task::none();
+        };
+        let thumbnail = &mut self.thumbnail;
+        if thumbnail.app_id != entry.app_id {
+            *thumbnail = WindowThumbnail {
+                app_id: entry.app_id.clone(),
+                ..Default::default()
+            };
+        }
+        if thumbnail.loading
+            || thumbnail
+                .captured_at
+                .is_some_and(|captured_at| captured_at.elapsed() < THUMBNAIL_INTERVAL)
+        {
+            return Task::none();
+        }
+        thumbnail.loading = true;
+
+        // Windows carry the desktop entry's id as their app id, which may differ from ours.
+        let mut app_ids = self
+            .desktop_meta_for_app_id(&entry.app_id)
+            .and_then(|meta| meta.desktop_entry_id)
+            .map(|entry_id| {
+                entry_id
+                    .strip_suffix(".desktop")
+                    .unwrap_or(&entry_id)
+                    .to_string()
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+        if !app_ids.contains(&entry.app_id) {
+            app_ids.push(entry.app_id.clone());
+        }
+        let app_id = entry.app_id.clone();
+        let pids = entry.pids.clone();
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || capture_window(&app_ids, &pids))
+                    .await
+                    .ok()
+                    .flatten()
+            },
+            move |png| cosmic::Action::App(Message::WindowThumbnailCaptured { app_id, png }),
+        )
+    }
+
+    /// Shows the screenshot, unless another app was selected meanwhile. Without one the
+    /// window was closed or could not be found, and the old screenshot is dropped.
+    pub(super) fn set_window_thumbnail(&mut self, app_id: String, png: Option<Vec<u8>>) {
+        let thumbnail = &mut self.thumbnail;
+        if thumbnail.app_id != app_id {
+            return;
+        }
+        thumbnail.loading = false;
+        thumbnail.captured_at = Some(Instant::now());
+        thumbnail.handle = png.map(icon::from_raster_bytes);
+    }
+
+    pub(super) fn selected_app_thumbnail(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        let handle = self
+            .thumbnail
+            .handle
+            .clone()
+            .filter(|_| self.thumbnail.app_id == selected.app_id)?;
+        Some(
+            widget::container(
+                icon::icon(handle)
+                    .width(Length::Fill)
+                    .height(Length::Fixed(THUMBNAIL_HEIGHT))
+                    .content_fit(cosmic::iced::ContentFit::Contain),
+            )
+            .width(Length::Fill)
+            .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{toplevel_identifier, x11_window_for_pids};
+
+    #[test]
+    fn finds_the_apps_window() {
+        let listing = r#"{"toplevels": [
+            {"title": "Files", "app-id": "com.system76.CosmicFiles", "identifier": "f00d"},
+            {"title": "Mozilla Firefox", "app-id": "firefox", "identifier": "beef"}
+        ]}"#;
+        let app_ids = ["org.mozilla.firefox".to_string(), "firefox".to_string()];
+        assert_eq!(
+            toplevel_identifier(listing, &app_ids),
+            Some("beef".to_string())
+        );
+        assert_eq!(toplevel_identifier(listing, &["vkcube".to_string()]), None);
+        assert_eq!(toplevel_identifier("not json", &app_ids), None);
+
+        let windows = "0x03a00003  0 4242   host Steam\n\
+                       0x04200007  0 5150   host Portal 2\n";
+        assert_eq!(
+            x11_window_for_pids(windows, &[5150, 5151]),
+            Some("0x04200007".to_string())
+        );
+        assert_eq!(x11_window_for_pids(windows, &[1]), None);
+    }
+}
@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Optional StatusNotifierItem tray icon with quick stats and app actions.
+
+use super::*;
+use ksni::TrayMethods;
+use std::sync::Mutex;
+use tokio::sync::mpsc;
+
+const TRAY_TOP_APPS: usize = 3;
+
+#[derive(Debug, Clone, Default)]
+struct TrayApp {
+    app_id: String,
+    display_name: String,
+    cpu_percent: f32,
+    rss_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TraySnapshot {
+    cpu_percent: f32,
+    ram_percent: f32,
+    top_apps: Vec<TrayApp>,
+}
+
+#[derive(Debug, Clone)]
+enum TrayAction {
+    OpenWindow,
+    QuitApp(String),
+}
+
+/// Latest stats published by the main refresh loop and mirrored into the tray.
+static TRAY_SNAPSHOT: OnceLock<Mutex<TraySnapshot>> = OnceLock::new();
+
+fn shared_tray_snapshot() -> &'static Mutex<TraySnapshot> {
+    TRAY_SNAPSHOT.get_or_init(|| Mutex::new(TraySnapshot::default()))
+}
+
+fn current_tray_snapshot() -> TraySnapshot {
+    shared_tray_snapshot()
+        .lock()
+        .map(|snapshot| snapshot.clone())
+        .unwrap_or_default()
+}
+
+struct MonitorTray {
+    snapshot: TraySnapshot,
+    actions: mpsc::UnboundedSender<TrayAction>,
+}
+
+impl ksni::Tray for MonitorTray {
+    fn id(&self) -> String {
+        <AppModel as cosmic::Application>::APP_ID.to_string()
+    }
+
+    fn title(&self) -> String {
+        fl!("app-title")
+    }
+
+    fn icon_name(&self) -> String {
+        <AppModel as cosmic::Application>::APP_ID.to_string()
+    }
+
+    fn tool_tip(&self) -> ksni::ToolTip {
+        ksni::ToolTip {
+            icon_name: self.icon_name(),
+            title: fl!("app-title"),
+            description: fl!(
+                "tray-tooltip",
+                cpu = units::format_percent(self.snapshot.cpu_percent, 1),
+                ram = units::format_percent(self.snapshot.ram_percent, 1)
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        let _ = self.actions.send(TrayAction::OpenWindow);
+    }
+
+    fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
+        use ksni::menu::{StandardItem, SubMenu};
+
+        let mut items = vec![
+            StandardItem {
+                label: fl!("tray-open"),
+                activate: Box::new(|tray: &mut Self| {
+                    let _ = tray.actions.send(TrayAction::OpenWindow);
+                }),
+                ..Default::default()
+            }
+            .into(),
+            ksni::MenuItem::Separator,
+        ];
+
+        if self.snapshot.top_apps.is_empty() {
+            items.push(
+                StandardItem {
+                    label: fl!("tray-no-apps"),
+                    enabled: false,
+                    ..Default::default()
+                }
+                .into(),
+            );
+            return items;
+        }
+
+        for app in &self.snapshot.top_apps {
+            items.push(
+                StandardItem {
+                    label: fl!(
+                        "tray-top-app",
+                        name = app.display_name.clone(),
+                        cpu = units::format_percent(app.cpu_percent, 1),
+                        ram = units::format_bytes(app.rss_bytes)
+                    ),
+                    enabled: false,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+
+        let quit_items = self
+            .snapshot
+            .top_apps
+            .iter()
+            .map(|app| {
+                let app_id = app.app_id.clone();
+                StandardItem {
+                    label: app.display_name.clone(),
+                    activate: Box::new(move |tray: &mut Self| {
+                        let _ = tray.actions.send(TrayAction::QuitApp(app_id.clone()));
+                    }),
+                    ..Default::default()
+                }
+                .into()
+            })
+            .collect();
+
+        items.push(ksni::MenuItem::Separator);
+        items.push(
+            SubMenu {
+                label: fl!("tray-quit-app"),
+                submenu: quit_items,
+                ..Default::default()
+            }
+            .into(),
+        );
+
+        items
+    }
+}
+
+/// Shuts the tray service down once the subscription driving it is dropped.
+struct TrayShutdownGuard(ksni::Handle<MonitorTray>);
+
+impl Drop for TrayShutdownGuard {
+    fn drop(&mut self) {
+        let _ = self.0.shutdown();
+    }
+}
+
+impl AppModel {
+    pub(super) fn tray_subscription() -> Subscription<Message> {
+        Subscription::run(|| {
+            iced_futures::stream::channel(8, |mut emitter| async move {
+                let (sender, mut actions) = mpsc::unbounded_channel();
+                let tray = MonitorTray {
+                    snapshot: current_tray_snapshot(),
+                    actions: sender,
+                };
+
+                let handle = match tray.spawn().await {
+                    Ok(handle) => TrayShutdownGuard(handle),
+                    Err(err) => {
+                        eprintln!("failed to start tray icon: {err}");
+                        return;
+                    }
+                };
+
+                let mut interval = tokio::time::interval(PROCESS_REFRESH_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            let snapshot = current_tray_snapshot();
+                            if handle.0.update(|tray| tray.snapshot = snapshot).await.is_none() {
+                                break;
+                            }
+                        }
+                        Some(action) = actions.recv() => {
+                            let message = match action {
+                                TrayAction::OpenWindow => Message::TrayOpenWindow,
+                                TrayAction::QuitApp(app_id) => Message::TrayQuitApp(app_id),
+                            };
+                            _ = emitter.send(message).await;
+                        }
+                    }
+                }
+            })
+        })
+    }
+
+    pub(super) fn publish_tray_snapshot(&self) {
+        let mut top_apps = self
+            .process_entries
+            .iter()
+            .map(|entry| TrayApp {
+                app_id: entry.app_id.clone(),
+                display_name: entry.display_name.clone(),
+                cpu_percent: entry.cpu_percent,
+                rss_bytes: entry.rss_bytes,
+            })
+            .collect::<Vec<_>>();
+        top_apps.sort_by(|a, b| {
+            b.cpu_percent
+                .partial_cmp(&a.cpu_percent)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| b.rss_bytes.cmp(&a.rss_bytes))
+        });
+        top_apps.truncate(TRAY_TOP_APPS);
+
+        let snapshot = TraySnapshot {
+            cpu_percent: self.system.global_cpu_usage().clamp(0.0, 100.0),
+            ram_percent: self.ram_usage_history.last().copied().unwrap_or(0.0),
+            top_apps,
+        };
+        if let Ok(mut shared) = shared_tray_snapshot().lock() {
+            *shared = snapshot;
+        }
+    }
+
+    pub(super) fn toggle_tray_icon(&mut self) {
+        let enabled = !self.config.show_tray_icon;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_show_tray_icon(handler, enabled) {
+                eprintln!("failed to save tray icon setting: {err}");
+            }
+        } else {
+            self.config.show_tray_icon = enabled;
+        }
+
+        if enabled {
+            self.publish_tray_snapshot();
+        }
+    }
+}
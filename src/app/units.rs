@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Human-readable byte and number formatting used across tables, tiles, and detail panels.
+//! Decimal and digit group separators follow the locale, e.g. `1,04 GB` and `1.234.567`
+//! in German.
+
+use super::AppModel;
+use std::env;
+use std::sync::OnceLock;
+
+const UNIT_LABELS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+/// Decimal and digit group separators of a locale.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+struct NumberFormat {
+    decimal: char,
+    grouping: char,
+}
+
+impl NumberFormat {
+    const POSIX: Self = Self::new('.', ',');
+
+    const fn new(decimal: char, grouping: char) -> Self {
+        Self { decimal, grouping }
+    }
+
+    /// Separators for a locale name such as `de_DE`, `fr_CA.UTF-8`, or `C`.
+    fn for_locale(locale: &str) -> Self {
+        let locale = locale.split(['.', '@']).next().unwrap_or_default();
+        let (language, region) = locale.split_once(['_', '-']).unwrap_or((locale, ""));
+        match (language, region) {
+            ("de" | "it", "CH") => Self::new('.', '\u{2019}'),
+            ("pt", "BR") => Self::new(',', '.'),
+            ("fr", _) => Self::new(',', '\u{202f}'),
+            (
+                "de" | "es" | "it" | "nl" | "da" | "id" | "tr" | "el" | "ro" | "hr" | "sl" | "sr",
+                _,
+            ) => Self::new(',', '.'),
+            (
+                "pt" | "ru" | "uk" | "be" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no"
+                | "hu" | "bg" | "lt" | "lv" | "et",
+                _,
+            ) => Self::new(',', '\u{a0}'),
+            _ => Self::POSIX,
+        }
+    }
+
+    /// The format of `LC_ALL` or `LC_NUMERIC` when set, otherwise of the locale desktop
+    /// entries are read in.
+    fn current() -> Self {
+        static CURRENT: OnceLock<NumberFormat> = OnceLock::new();
+        *CURRENT.get_or_init(|| {
+            let locale = ["LC_ALL", "LC_NUMERIC"]
+                .iter()
+                .filter_map(|key| env::var(key).ok())
+                .find(|value| !value.trim().is_empty())
+                .or_else(|| AppModel::desktop_locales().into_iter().next())
+                .unwrap_or_default();
+            Self::for_locale(&locale)
+        })
+    }
+
+    /// Swaps the separators of a number formatted with `.` and `,`.
+    fn localize(self, formatted: &str) -> String {
+        formatted
+            .chars()
+            .map(|character| match character {
+                '.' => self.decimal,
+                ',' => self.grouping,
+                other => other,
+            })
+            .collect()
+    }
+}
+
+/// Formats a byte count with an adaptive precision: two decimals below 10 units,
+/// none above, e.g. `1.04 GB`, `18 MB`, or `312 KB`.
+pub(super) fn format_bytes(bytes: u64) -> String {
+    format_bytes_in(NumberFormat::current(), bytes)
+}
+
+fn format_bytes_in(format: NumberFormat, bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNIT_LABELS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        return format!("{bytes} {}", UNIT_LABELS[0]);
+    }
+
+    // Rounding 9.999 up to "10.00" would show one digit too many.
+    let formatted = if value < 10.0 && (value * 100.0).round() < 1000.0 {
+        format!("{value:.2} {}", UNIT_LABELS[unit])
+    } else {
+        format!("{value:.0} {}", UNIT_LABELS[unit])
+    };
+    format.localize(&formatted)
+}
+
+/// Formats a byte difference with an explicit sign, e.g. `+18 MB` or `-1.04 GB`.
+pub(super) fn format_bytes_signed(delta: i64) -> String {
+    let sign = if delta < 0 { '-' } else { '+' };
+    format!("{sign}{}", format_bytes(delta.unsigned_abs()))
+}
+
+/// Formats a byte count with thousands separators, e.g. `1,234,567`.
+pub(super) fn format_bytes_grouped(bytes: u64) -> String {
+    format_bytes_grouped_in(NumberFormat::current(), bytes)
+}
+
+fn format_bytes_grouped_in(format: NumberFormat, bytes: u64) -> String {
+    let digits = bytes.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(format.grouping);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Formats a computed value with at most two decimals and no trailing zeros, e.g. `1024`,
+/// `0.5`, or `12.35`.
+pub(super) fn format_number(value: f64) -> String {
+    format_number_in(NumberFormat::current(), value)
+}
+
+fn format_number_in(format: NumberFormat, value: f64) -> String {
+    let formatted = format!("{value:.2}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        format.localize(trimmed)
+    }
+}
+
+/// Formats a value with a fixed number of decimals, e.g. `3.60`.
+pub(super) fn format_decimal(value: f64, decimals: usize) -> String {
+    NumberFormat::current().localize(&format!("{value:.decimals$}"))
+}
+
+/// Formats a percentage with a fixed number of decimals, e.g. `12.5%`.
+pub(super) fn format_percent(value: f32, decimals: usize) -> String {
+    format!("{}%", format_decimal(f64::from(value), decimals))
+}
+
+/// Formats a percentage change with an explicit sign, e.g. `+2.5%`.
+pub(super) fn format_percent_signed(value: f32, decimals: usize) -> String {
+    let sign = if value < 0.0 { '-' } else { '+' };
+    format!("{sign}{}", format_percent(value.abs(), decimals))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        NumberFormat, format_bytes_grouped_in, format_bytes_in, format_bytes_signed,
+        format_number_in,
+    };
+
+    #[test]
+    fn formats_bytes_with_adaptive_precision() {
+        let format = NumberFormat::POSIX;
+        assert_eq!(format_bytes_in(format, 512), "512 B");
+        assert_eq!(format_bytes_in(format, 312 * 1024), "312 KB");
+        assert_eq!(
+            format_bytes_in(format, 18 * 1024 * 1024 + 300 * 1024),
+            "18 MB"
+        );
+        assert_eq!(format_bytes_in(format, 1_116_691_496), "1.04 GB");
+        assert_eq!(format_bytes_in(format, 10 * 1024 * 1024 - 1), "10 MB");
+    }
+
+    #[test]
+    fn signs_byte_deltas() {
+        assert_eq!(format_bytes_signed(0), "+0 B");
+        assert_eq!(format_bytes_signed(18 * 1024 * 1024), "+18 MB");
+        assert_eq!(format_bytes_signed(-512 * 1024), "-512 KB");
+    }
+
+    #[test]
+    fn groups_exact_byte_counts() {
+        let format = NumberFormat::POSIX;
+        assert_eq!(format_bytes_grouped_in(format, 0), "0");
+        assert_eq!(format_bytes_grouped_in(format, 999), "999");
+        assert_eq!(format_bytes_grouped_in(format, 1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn trims_computed_numbers() {
+        let format = NumberFormat::POSIX;
+        assert_eq!(format_number_in(format, 1024.0), "1024");
+        assert_eq!(format_number_in(format, 0.5), "0.5");
+        assert_eq!(format_number_in(format, 12.3456), "12.35");
+        assert_eq!(format_number_in(format, -0.001), "0");
+    }
+
+    #[test]
+    fn follows_locale_separators() {
+        let german = NumberFormat::for_locale("de_DE.UTF-8");
+        assert_eq!(format_bytes_in(german, 1_116_691_496), "1,04 GB");
+        assert_eq!(format_bytes_grouped_in(german, 1_234_567), "1.234.567");
+        assert_eq!(format_number_in(german, 12.3456), "12,35");
+
+        let french = NumberFormat::for_locale("fr_CA");
+        assert_eq!(
+            format_bytes_grouped_in(french, 1_234_567),
+            "1\u{202f}234\u{202f}567"
+        );
+        assert_eq!(NumberFormat::for_locale("de_CH").decimal, '.');
+        assert_eq!(NumberFormat::for_locale("C"), NumberFormat::POSIX);
+        assert_eq!(NumberFormat::for_locale("en_US"), NumberFormat::POSIX);
+    }
+}
@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Pending updates per app from Flatpak remotes and PackageKit, checked in the background
+//! about once an hour, with a shortcut to update the app in the COSMIC Store.
+
+use super::install_size::command_stdout;
+use super::*;
+
+const UPDATE_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Default)]
+pub(super) struct UpdateChecker {
+    apps_with_updates: HashSet<String>,
+    checked_at: Option<Instant>,
+    check_running: bool,
+}
+
+struct UpdateCandidate {
+    app_id: String,
+    flatpak_id: Option<String>,
+    /// Executable of a distro-packaged app, whose owning package is looked up.
+    package_exe: Option<PathBuf>,
+}
+
+/// Package names from `pkcon --plain get-updates`, whose lines look like
+/// `Normal    firefox-120.0-1.fc39.x86_64 (updates)`.
+fn parse_pkcon_updates(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let package_id = line.split_whitespace().nth(1)?;
+            // The name ends at the first dash that starts the version.
+            let version_start = package_id
+                .char_indices()
+                .find(|(index, ch)| {
+                    *ch == '-'
+                        && package_id[index + 1..]
+                            .chars()
+                            .next()
+                            .is_some_and(|next| next.is_ascii_digit())
+                })?
+                .0;
+            Some(package_id[..version_start].to_string())
+        })
+        .collect()
+}
+
+fn owning_package(exe: &Path) -> Option<String> {
+    let exe = exe.to_str()?;
+    if let Some(name) = command_stdout("rpm", &["-qf", "--qf", "%{NAME}", exe]) {
+        return Some(name.trim().to_string());
+    }
+    if let Some(owner) = command_stdout("dpkg", &["-S", exe]) {
+        let package = owner.lines().next()?.split_once(": ")?.0;
+        return Some(package.split(':').next()?.to_string());
+    }
+    command_stdout("pacman", &["-Qoq", exe]).map(|name| name.trim().to_string())
+}
+
+fn find_apps_with_updates(candidates: Vec<UpdateCandidate>) -> HashSet<String> {
+    let flatpak_updates = command_stdout(
+        "flatpak",
+        &["remote-ls", "--updates", "--columns=application"],
+    )
+    .map(|output| {
+        output
+            .lines()
+            .map(|line| line.trim().to_string())
+            .collect::<HashSet<_>>()
+    })
+    .unwrap_or_default();
+    let package_updates = if candidates.iter().any(|c| c.package_exe.is_some()) {
+        command_stdout("pkcon", &["--plain", "get-updates"])
+            .map(|output| parse_pkcon_updates(&output))
+            .unwrap_or_default()
+    } else {
+        HashSet::new()
+    };
+
+    candidates
+        .into_iter()
+        .filter(|candidate| {
+            candidate
+                .flatpak_id
+                .as_ref()
+                .is_some_and(|flatpak_id| flatpak_updates.contains(flatpak_id))
+                || (!package_updates.is_empty()
+                    && candidate
+                        .package_exe
+                        .as_deref()
+                        .and_then(owning_package)
+                        .is_some_and(|package| package_updates.contains(&package)))
+        })
+        .map(|candidate| candidate.app_id)
+        .collect()
+}
+
+impl AppModel {
+    pub(super) fn request_update_check(&mut self) -> Task<cosmic::Action<Message>> {
+        let due = self
+            .updates
+            .checked_at
+            .is_none_or(|checked| checked.elapsed() >= UPDATE_CHECK_INTERVAL);
+        // Waiting for origins keeps distro-packaged apps from being skipped for a whole hour.
+        if !due
+            || self.updates.check_running
+            || !self.origins_settled()
+            || self.processes_page.group_by != GroupBy::App
+        {
+            return Task::none();
+        }
+
+        let candidates = self
+            .process_entries
+            .iter()
+            .map(|entry| UpdateCandidate {
+                app_id: entry.app_id.clone(),
+                flatpak_id: self.flatpak_app_id(&entry.app_id),
+                package_exe: entry
+                    .exe_path
+                    .clone()
+                    .filter(|_| self.app_origin(entry) == Some(AppOrigin::Distro)),
+            })
+            .filter(|candidate| candidate.flatpak_id.is_some() || candidate.package_exe.is_some())
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Task::none();
+        }
+
+        self.updates.check_running = true;
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || find_apps_with_updates(candidates))
+                    .await
+                    .unwrap_or_default()
+            },
+            |app_ids| cosmic::Action::App(Message::UpdatesChecked(app_ids)),
+        )
+    }
+
+    pub(super) fn set_apps_with_updates(&mut self, app_ids: HashSet<String>) {
+        self.updates = UpdateChecker {
+            apps_with_updates: app_ids,
+            checked_at: Some(Instant::now()),
+            check_running: false,
+        };
+    }
+
+    pub(super) fn has_pending_update(&self, entry: &ProcessEntry) -> bool {
+        self.updates.apps_with_updates.contains(&entry.app_id)
+    }
+
+    pub(super) fn update_selected_app_in_store(&self) {
+        let Some(selected) = self.selected_process.as_ref() else {
+            return;
+        };
+        let component_id = self.flatpak_app_id(&selected.app_id).or_else(|| {
+            self.desktop_meta_for_app_id(&selected.app_id)
+                .and_then(|meta| meta.desktop_entry_id)
+                .map(|entry_id| {
+                    entry_id
+                        .strip_suffix(".desktop")
+                        .unwrap_or(&entry_id)
+                        .to_string()
+                })
+        });
+        let uri = format!(
+            "appstream://{}",
+            component_id.unwrap_or_else(|| selected.app_id.clone())
+        );
+
+        let result = Command::new("cosmic-store")
+            .arg(&uri)
+            .spawn()
+            .map(drop)
+            .or_else(|_| open::that_detached(&uri));
+        if let Err(err) = result {
+            eprintln!("failed to open the store for {uri}: {err}");
+        }
+    }
+
+    pub(super) fn selected_app_update_button(&self) -> Option<Element<'_, Message>> {
+        let selected = self.selected_process.as_ref()?;
+        if !self.updates.apps_with_updates.contains(&selected.app_id) {
+            return None;
+        }
+
+        Some(
+            widget::button::suggested(fl!("process-action-update"))
+                .on_press(Message::UpdateSelectedAppInStore)
+                .width(Length::Fill)
+                .height(Length::Fixed(38.0))
+                .into(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_pkcon_updates;
+
+    #[test]
+    fn parses_package_names_from_pkcon() {
+        let output = "Getting updates               [=========================]\n\
+                      Results:\n\
+                      Normal      \tfirefox-120.0-1.fc39.x86_64 (updates)\n\
+                      Security    \topenssl-1:3.0.2-0ubuntu1.10.amd64 (jammy-security)\n\
+                      Bugfix      \tpython3-gobject-3.46.0-1.fc39.noarch (updates)\n";
+
+        let names = parse_pkcon_updates(output);
+        assert_eq!(names.len(), 3);
+        assert!(names.contains("firefox"));
+        assert!(names.contains("openssl"));
+        assert!(names.contains("python3-gobject"));
+    }
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Faint bars behind the CPU and RAM cells of the Apps table, as long as the value's share
+//! of the whole machine, so rows can be compared at a glance like the meters in htop.
+
+use super::*;
+use cosmic::iced::gradient::Linear;
+use cosmic::iced::{Gradient, Radians};
+use std::f32::consts::FRAC_PI_2;
+
+/// Alpha of the bar in the column's accent, faint enough to keep the text readable.
+const BAR_ALPHA: f32 = 0.14;
+
+/// The table cell style with a bar in `accent` over `share` of the cell's width, from 0 to
+/// 1, and the highlight of a changed value over the whole cell if there is one.
+pub(super) fn usage_bar_class(
+    share: f32,
+    accent: Color,
+    flash: Option<Color>,
+) -> theme::Container<'static> {
+    let share = share.clamp(0.0, 1.0);
+    let rest = flash.unwrap_or(Color::TRANSPARENT);
+    let bar = Color {
+        a: BAR_ALPHA + rest.a * (1.0 - BAR_ALPHA),
+        ..accent
+    };
+    let background = if share > 0.0 {
+        // Two stops at the same offset give the bar a hard edge. An angle of a quarter turn
+        // runs the gradient from left to right.
+        Background::Gradient(Gradient::Linear(
+            Linear::new(Radians(FRAC_PI_2))
+                .add_stop(0.0, bar)
+                .add_stop(share, bar)
+                .add_stop(share, rest)
+                .add_stop(1.0, rest),
+        ))
+    } else {
+        Background::Color(rest)
+    };
+    theme::Container::custom(move |theme| {
+        let mut style = table_cell_style(theme);
+        style.background = Some(background);
+        style
+    })
+}
+
+impl AppModel {
+    /// How much of the machine's memory `rss_bytes` is, for the RAM column's bar.
+    pub(super) fn memory_share(&self, rss_bytes: u64) -> f32 {
+        let total = self.system.total_memory();
+        if total == 0 {
+            return 0.0;
+        }
+        (rss_bytes as f64 / total as f64) as f32
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Brief highlights on the CPU and RAM cells of apps whose usage jumped since the last
+//! refresh, to draw the eye to what just became busy. A highlight fades out over a moment;
+//! with reduced motion it shows as a steady tint for the same time instead.
+
+use super::*;
+
+const FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// How often a fading highlight is redrawn, coarse enough not to rebuild the table per frame.
+const FLASH_FRAME_INTERVAL: Duration = Duration::from_millis(60);
+
+/// Percentage points CPU usage has to rise by between two refreshes.
+const CPU_JUMP_POINTS: f32 = 10.0;
+
+/// RAM has to grow by this share of what the app used before, and by at least
+/// [`RAM_JUMP_MIN_BYTES`], so small apps doubling a few megabytes are not highlighted.
+const RAM_JUMP_RATIO: f64 = 0.25;
+const RAM_JUMP_MIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Strongest tint of a highlight, in the alpha of the column's accent.
+const FLASH_ALPHA: f32 = 0.35;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum FlashColumn {
+    Cpu,
+    Ram,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ValueFlashes {
+    /// CPU and RAM per app id at the last refresh.
+    previous: HashMap<String, (f32, u64)>,
+    cpu: HashMap<String, Instant>,
+    ram: HashMap<String, Instant>,
+}
+
+fn is_cpu_jump(before: f32, after: f32) -> bool {
+    after - before >= CPU_JUMP_POINTS
+}
+
+fn is_ram_jump(before: u64, after: u64) -> bool {
+    let growth = after.saturating_sub(before);
+    growth >= RAM_JUMP_MIN_BYTES && growth as f64 >= before as f64 * RAM_JUMP_RATIO
+}
+
+/// How strongly a highlight started `elapsed` ago shows, from 1 when it starts down to 0, or
+/// `None` once it is over.
+fn flash_strength(elapsed: Duration, reduce_motion: bool) -> Option<f32> {
+    if elapsed >= FLASH_DURATION {
+        return None;
+    }
+    if reduce_motion {
+        return Some(1.0);
+    }
+    Some(1.0 - elapsed.as_secs_f32() / FLASH_DURATION.as_secs_f32())
+}
+
+impl ValueFlashes {
+    /// Starts a highlight for every cell that jumped since the last refresh. Apps that just
+    /// appeared are not highlighted, or every app would light up on startup.
+    fn record(&mut self, entries: &[ProcessEntry], now: Instant) {
+        self.expire(now);
+        for entry in entries {
+            let Some(&(cpu, rss)) = self.previous.get(&entry.app_id) else {
+                continue;
+            };
+            if is_cpu_jump(cpu, entry.cpu_percent) {
+                self.cpu.insert(entry.app_id.clone(), now);
+            }
+            if is_ram_jump(rss, entry.rss_bytes) {
+                self.ram.insert(entry.app_id.clone(), now);
+            }
+        }
+        self.previous = entries
+            .iter()
+            .map(|entry| (entry.app_id.clone(), (entry.cpu_percent, entry.rss_bytes)))
+            .collect();
+    }
+
+    fn expire(&mut self, now: Instant) {
+        let running = |started: &mut Instant| now.duration_since(*started) < FLASH_DURATION;
+        self.cpu.retain(|_, started| running(started));
+        self.ram.retain(|_, started| running(started));
+    }
+
+    fn started(&self, column: FlashColumn) -> &HashMap<String, Instant> {
+        match column {
+            FlashColumn::Cpu => &self.cpu,
+            FlashColumn::Ram => &self.ram,
+        }
+    }
+
+    pub(super) fn is_running(&self) -> bool {
+        !self.cpu.is_empty() || !self.ram.is_empty()
+    }
+}
+
+impl AppModel {
+    pub(super) fn refresh_value_flashes(&mut self) {
+        self.value_flashes
+            .record(&self.process_entries, Instant::now());
+    }
+
+    pub(super) fn fade_value_flashes(&mut self) {
+        self.value_flashes.expire(Instant::now());
+    }
+
+    /// The tint of a cell whose value jumped, fading as its highlight runs out.
+    pub(super) fn value_flash_tint(&self, app_id: &str, column: FlashColumn) -> Option<Color> {
+        let started = self.value_flashes.started(column).get(app_id)?;
+        let strength = flash_strength(started.elapsed(), self.config.reduce_motion)?;
+        let accent = match column {
+            FlashColumn::Cpu => CPU_ACCENT,
+            FlashColumn::Ram => RAM_ACCENT,
+        };
+        Some(Color {
+            a: FLASH_ALPHA * strength,
+            ..accent
+        })
+    }
+
+    /// Redraws the table while highlights fade. With reduced motion nothing fades, so the
+    /// refreshes alone redraw it.
+    pub(super) fn value_flash_subscription() -> Subscription<Message> {
+        Subscription::run(|| {
+            iced_futures::stream::channel(1, |mut emitter| async move {
+                let mut interval = tokio::time::interval(FLASH_FRAME_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    _ = emitter.send(Message::FadeValueFlashes).await;
+                }
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FLASH_DURATION, flash_strength, is_cpu_jump, is_ram_jump};
+    use std::time::Duration;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn spots_significant_jumps_only() {
+        assert!(is_cpu_jump(2.0, 40.0));
+        assert!(!is_cpu_jump(40.0, 45.0));
+        assert!(!is_cpu_jump(40.0, 2.0));
+
+        assert!(is_ram_jump(200 * MIB, 300 * MIB));
+        // A large app growing by a small share, and a small app doubling.
+        assert!(!is_ram_jump(4096 * MIB, 4200 * MIB));
+        assert!(!is_ram_jump(10 * MIB, 20 * MIB));
+    }
+
+    #[test]
+    fn fades_unless_motion_is_reduced() {
+        let halfway = FLASH_DURATION / 2;
+        assert_eq!(flash_strength(Duration::ZERO, false), Some(1.0));
+        assert!(flash_strength(halfway, false).is_some_and(|strength| strength < 0.6));
+        assert_eq!(flash_strength(halfway, true), Some(1.0));
+        assert_eq!(flash_strength(FLASH_DURATION, true), None);
+    }
+}
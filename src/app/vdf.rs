@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Steam's binary VDF, the key-value format of `shortcuts.vdf` in which Steam keeps the
+//! non-Steam games added to its library. Each value starts with a type byte and a
+//! NUL-terminated key; maps nest until an end byte.
+
+const TYPE_MAP: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0a;
+const TYPE_END_ALT: u8 = 0x0b;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum VdfValue {
+    Map(Vec<(String, VdfValue)>),
+    String(String),
+    Int(i64),
+    Float(f32),
+}
+
+/// A non-Steam game from `shortcuts.vdf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Shortcut {
+    /// The id Steam launches the shortcut with, as in `AppId=` on the launch command line.
+    pub(super) app_id: u32,
+    pub(super) name: String,
+    /// Path of the icon picked in the shortcut's properties; empty if none was.
+    pub(super) icon: String,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position.checked_add(len)?)?;
+        self.position += len;
+        Some(bytes)
+    }
+
+    fn byte(&mut self) -> Option<u8> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self
+            .data
+            .get(self.position..)?
+            .iter()
+            .position(|byte| *byte == 0)?;
+        let bytes = self.take(len)?;
+        self.position += 1;
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    /// Entries up to the map's end byte, or to the end of the data at the top level.
+    fn map(&mut self, nested: bool) -> Option<Vec<(String, VdfValue)>> {
+        let mut entries = Vec::new();
+        loop {
+            let Some(kind) = self.byte() else {
+                return (!nested).then_some(entries);
+            };
+            if kind == TYPE_END || kind == TYPE_END_ALT {
+                return Some(entries);
+            }
+            let key = self.string()?;
+            let value = match kind {
+                TYPE_MAP => VdfValue::Map(self.map(true)?),
+                TYPE_STRING => VdfValue::String(self.string()?),
+                TYPE_INT32 | TYPE_POINTER | TYPE_COLOR => {
+                    VdfValue::Int(i32::from_le_bytes(self.array()?).into())
+                }
+                TYPE_FLOAT32 => VdfValue::Float(f32::from_le_bytes(self.array()?)),
+                TYPE_UINT64 => VdfValue::Int(u64::from_le_bytes(self.array()?) as i64),
+                TYPE_INT64 => VdfValue::Int(i64::from_le_bytes(self.array()?)),
+                _ => return None,
+            };
+            entries.push((key, value));
+        }
+    }
+}
+
+/// The top-level entries of a binary VDF file, or `None` if it is malformed.
+pub(super) fn parse_binary_vdf(data: &[u8]) -> Option<Vec<(String, VdfValue)>> {
+    Reader { data, position: 0 }.map(false)
+}
+
+/// The value under `key` in a map, ignoring case, since Steam has written `AppName` and
+/// `appname` in different versions.
+fn get<'a>(entries: &'a [(String, VdfValue)], key: &str) -> Option<&'a VdfValue> {
+    entries
+        .iter()
+        .find(|(entry_key, _)| entry_key.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value)
+}
+
+/// The shortcuts in a `shortcuts.vdf`, whose root map `shortcuts` holds one map per game
+/// under the keys `0`, `1`, and so on.
+pub(super) fn parse_shortcuts(data: &[u8]) -> Vec<Shortcut> {
+    let Some(root) = parse_binary_vdf(data) else {
+        return Vec::new();
+    };
+    let Some(VdfValue::Map(shortcuts)) = get(&root, "shortcuts") else {
+        return Vec::new();
+    };
+    shortcuts
+        .iter()
+        .filter_map(|(_, shortcut)| {
+            let VdfValue::Map(fields) = shortcut else {
+                return None;
+            };
+            let string = |key| match get(fields, key) {
+                Some(VdfValue::String(value)) => value.clone(),
+                _ => String::new(),
+            };
+            // Stored signed, while launch command lines print it unsigned.
+            let app_id = match get(fields, "appid")? {
+                VdfValue::Int(app_id) => *app_id as u32,
+                _ => return None,
+            };
+            Some(Shortcut {
+                app_id,
+                name: string("AppName"),
+                icon: string("icon"),
+            })
+        })
+        .filter(|shortcut| !shortcut.name.trim().is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Shortcut, VdfValue, parse_binary_vdf, parse_shortcuts};
+
+    fn key(kind: u8, key: &str) -> Vec<u8> {
+        let mut bytes = vec![kind];
+        bytes.extend_from_slice(key.as_bytes());
+        bytes.push(0);
+        bytes
+    }
+
+    fn string(name: &str, value: &str) -> Vec<u8> {
+        let mut bytes = key(0x01, name);
+        bytes.extend_from_slice(value.as_bytes());
+        bytes.push(0);
+        bytes
+    }
+
+    fn int(name: &str, value: i32) -> Vec<u8> {
+        let mut bytes = key(0x02, name);
+        bytes.extend_from_slice(&value.to_le_bytes());
+        bytes
+    }
+
+    fn shortcut(index: &str, app_id: i32, name_key: &str, name: &str) -> Vec<u8> {
+        let mut bytes = key(0x00, index);
+        bytes.extend(int("appid", app_id));
+        bytes.extend(string(name_key, name));
+        bytes.extend(string("Exe", "\"/usr/bin/game\""));
+        bytes.extend(string("icon", "/home/user/icons/game.png"));
+        bytes.extend(key(0x00, "tags"));
+        bytes.extend(string("0", "Favorite"));
+        bytes.push(0x08);
+        bytes.push(0x08);
+        bytes
+    }
+
+    #[test]
+    fn parses_nested_maps_and_values() {
+        let mut data = key(0x00, "root");
+        data.extend(string("name", "Game"));
+        data.extend(int("count", -2));
+        data.extend(key(0x03, "scale"));
+        data.extend_from_slice(&1.5f32.to_le_bytes());
+        data.extend(key(0x07, "big"));
+        data.extend_from_slice(&(1u64 << 40).to_le_bytes());
+        data.push(0x08);
+
+        assert_eq!(
+            parse_binary_vdf(&data),
+            Some(vec![(
+                "root".to_string(),
+                VdfValue::Map(vec![
+                    ("name".to_string(), VdfValue::String("Game".to_string())),
+                    ("count".to_string(), VdfValue::Int(-2)),
+                    ("scale".to_string(), VdfValue::Float(1.5)),
+                    ("big".to_string(), VdfValue::Int(1 << 40)),
+                ]),
+            )])
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let mut data = key(0x00, "root");
+        data.extend(string("name", "Game"));
+        assert_eq!(parse_binary_vdf(&data), None);
+        assert_eq!(parse_binary_vdf(&[0x02, b'a', 0, 1, 2]), None);
+        assert_eq!(parse_binary_vdf(&[0x05, b'a', 0]), None);
+    }
+
+    #[test]
+    fn parses_shortcuts_with_either_name_key() {
+        let mut data = key(0x00, "shortcuts");
+        data.extend(shortcut("0", -1066818878, "AppName", "Celeste"));
+        data.extend(shortcut("1", 123456, "appname", "Itch Game"));
+        data.extend(shortcut("2", 42, "AppName", ""));
+        data.extend([0x08, 0x08]);
+
+        assert_eq!(
+            parse_shortcuts(&data),
+            vec![
+                Shortcut {
+                    app_id: 3228148418,
+                    name: "Celeste".to_string(),
+                    icon: "/home/user/icons/game.png".to_string(),
+                },
+                Shortcut {
+                    app_id: 123456,
+                    name: "Itch Game".to_string(),
+                    icon: "/home/user/icons/game.png".to_string(),
+                },
+            ]
+        );
+        assert!(parse_shortcuts(b"not a vdf").is_empty());
+    }
+}
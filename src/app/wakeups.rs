@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Wakeups per second per app, from how fast the voluntary context-switch counters of its
+//! threads grow between refreshes. An app whose timers fire constantly keeps the CPU out of
+//! its idle states even when its CPU use looks low.
+
+use super::*;
+
+#[derive(Debug, Default)]
+pub(super) struct WakeupMonitor {
+    /// Voluntary context switches per pid from the previous sample.
+    previous_switches: HashMap<u32, u64>,
+    sampled_at: Option<Instant>,
+}
+
+/// Voluntary context switches from a status file, e.g. `voluntary_ctxt_switches:\t1520`. A
+/// thread switches voluntarily when it blocks and is woken later; involuntary switches are
+/// preemptions while it was running, not wakeups.
+fn parse_context_switches(status: &str) -> Option<u64> {
+    status.lines().find_map(|line| {
+        line.strip_prefix("voluntary_ctxt_switches:")?
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
+/// Voluntary context switches of all of a process's threads, from its `/proc` directory.
+/// The process's own status file only counts its main thread.
+fn read_context_switches(proc_dir: &Path) -> Option<u64> {
+    let tasks = fs::read_dir(proc_dir.join("task")).ok()?;
+    let total = tasks
+        .flatten()
+        .filter_map(|task| fs::read_to_string(task.path().join("status")).ok())
+        .filter_map(|status| parse_context_switches(&status))
+        .sum();
+    Some(total)
+}
+
+impl WakeupMonitor {
+    /// Wakeups per second per pid since the previous sample, for the processes whose
+    /// threads the source can read.
+    fn sample(&mut self, source: &dyn SystemSource, now: Instant) -> HashMap<u32, f32> {
+        let switches = source
+            .processes()
+            .values()
+            .filter_map(|process| {
+                let switches = read_context_switches(&source.proc_dir(process.pid)?)?;
+                Some((process.pid, switches))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let elapsed_secs = self
+            .sampled_at
+            .map(|sampled_at| now.duration_since(sampled_at).as_secs_f32())
+            .filter(|elapsed| *elapsed > 0.0);
+        let mut wakeups = HashMap::new();
+        if let Some(elapsed_secs) = elapsed_secs {
+            for (pid, total) in &switches {
+                // A pid seen for the first time, or reused, has no meaningful delta yet.
+                let Some(previous) = self.previous_switches.get(pid) else {
+                    continue;
+                };
+                let delta = total.saturating_sub(*previous);
+                wakeups.insert(*pid, delta as f32 / elapsed_secs);
+            }
+        }
+        self.previous_switches = switches;
+        self.sampled_at = Some(now);
+        wakeups
+    }
+}
+
+impl AppModel {
+    pub(super) fn toggle_wakeups_column(&mut self) {
+        let enabled = !self.config.show_wakeups_column;
+        if let Some(handler) = self.config_handler.as_ref() {
+            if let Err(err) = self.config.set_show_wakeups_column(handler, enabled) {
+                eprintln!("failed to save wakeups column setting: {err}");
+            }
+        } else {
+            self.config.show_wakeups_column = enabled;
+        }
+    }
+
+    /// Wakeups per second per pid since the previous refresh. Nothing is sampled while the
+    /// Wakeups column is hidden, since it reads every thread's status file.
+    pub(super) fn sample_wakeups(&mut self) -> HashMap<u32, f32> {
+        if !self.config.show_wakeups_column {
+            self.wakeup_monitor = WakeupMonitor::default();
+            return HashMap::new();
+        }
+        self.wakeup_monitor
+            .sample(self.process_source.as_ref(), Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::fixture::FixtureSource;
+
+    const DESKTOP_SESSION: &str = include_str!("../../tests/fixtures/desktop-session.json");
+
+    #[test]
+    fn parses_context_switches() {
+        let status = "Name:\tfirefox\nThreads:\t1\nvoluntary_ctxt_switches:\t1520\n\
+                      nonvoluntary_ctxt_switches:\t14\n";
+        assert_eq!(parse_context_switches(status), Some(1520));
+        assert_eq!(parse_context_switches("Name:\tkthreadd\n"), None);
+    }
+
+    #[test]
+    fn samples_wakeups_of_every_thread() {
+        let root = tempfile::tempdir().unwrap();
+        let write_switches = |tid: u32, switches: u64| {
+            let task = root.path().join(format!("4210/task/{tid}"));
+            fs::create_dir_all(&task).unwrap();
+            let status = format!("voluntary_ctxt_switches:\t{switches}\n");
+            fs::write(task.join("status"), status).unwrap();
+        };
+        let source = FixtureSource::from_json(DESKTOP_SESSION)
+            .expect("fixture parses")
+            .with_proc_root(root.path());
+        let mut monitor = WakeupMonitor::default();
+        let start = Instant::now();
+
+        write_switches(4210, 100);
+        write_switches(4211, 50);
+        assert!(monitor.sample(&source, start).is_empty());
+
+        write_switches(4210, 160);
+        write_switches(4211, 70);
+        let wakeups = monitor.sample(&source, start + Duration::from_secs(2));
+        assert_eq!(wakeups, HashMap::from([(4210, 40.0)]));
+    }
+}
@@ -1,9 +1,473 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
+mod migration;
+
+#[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq, Serialize, Deserialize)]
 #[version = 1]
+#[serde(default)]
 pub struct Config {
     demo: String,
+    pub show_tray_icon: bool,
+    pub background_collector: bool,
+    pub remote_mode: bool,
+    /// Listens on every interface instead of loopback; requests need `remote_token`.
+    pub remote_lan: bool,
+    pub remote_token: String,
+    pub category_filter: Vec<String>,
+    pub threshold_alerts: bool,
+    pub density: Density,
+    /// Shows highlights of changed values without fading them.
+    pub reduce_motion: bool,
+    pub cpu_percent_mode: CpuPercentMode,
+    pub cpu_smoothing: CpuSmoothing,
+    pub sort_damping: SortDamping,
+    pub history_retention: HistoryRetention,
+    pub show_encoder_column: bool,
+    pub show_wakeups_column: bool,
+    pub show_fd_column: bool,
+    pub double_click_action: DoubleClickAction,
+    /// App ids that need a typed confirmation to stop or kill.
+    pub protected_apps: Vec<String>,
+    pub startup_page: Page,
+    pub custom_columns: Vec<CustomColumn>,
+    pub highlight_rules: Vec<HighlightRule>,
+    pub view_profiles: Vec<ViewProfile>,
+    pub split_view: bool,
+    /// Share of the width, in percent, taken by the split view's detail pane; 0 for default.
+    pub detail_pane_percent: u16,
+    pub game_playtime: Vec<GamePlaytime>,
+    /// Debugger command line with `{pid}` for the process id; empty for gdb.
+    pub debugger_command: String,
+    pub background_filter: BackgroundFilter,
+    /// Process names or app ids listed even when they look like background processes.
+    pub always_visible: Vec<String>,
+    pub process_backend: ProcessBackend,
+    pub snapshot_interval: SnapshotInterval,
+    /// Counts the hidden helpers an app spawned towards its CPU and memory.
+    pub include_helpers: bool,
+    pub memory_growth_alert: MemoryGrowthAlert,
+    pub launch_overrides: Vec<LaunchOverride>,
+    pub automation_hooks: Vec<AutomationHook>,
+}
+
+impl Config {
+    /// Loads the stored config, upgrading older versions first. Keys that fail to parse
+    /// keep their defaults and are returned as errors, so the user can be warned.
+    pub fn load(
+        app_id: &str,
+    ) -> (
+        Option<cosmic_config::Config>,
+        Self,
+        Vec<cosmic_config::Error>,
+    ) {
+        migration::migrate(app_id, Self::VERSION);
+        let Ok(handler) = cosmic_config::Config::new(app_id, Self::VERSION) else {
+            return (None, Self::default(), Vec::new());
+        };
+
+        match Self::get_entry(&handler) {
+            Ok(config) => (Some(handler), config, Vec::new()),
+            Err((errors, config)) => {
+                // Keys that were never saved are reported too, but they are not a problem.
+                let errors = errors
+                    .into_iter()
+                    .filter(|err| match err {
+                        cosmic_config::Error::GetKey(_, err) => {
+                            err.kind() != std::io::ErrorKind::NotFound
+                        }
+                        err => err.is_err(),
+                    })
+                    .collect();
+                (Some(handler), config, errors)
+            }
+        }
+    }
+}
+
+/// A user-defined column computed from an expression over per-app fields.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CustomColumn {
+    pub name: String,
+    pub expression: String,
+}
+
+/// Kinds of background processes shown on the Apps page, toggled from the Filter menu.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundFilter {
+    pub services: bool,
+    pub helpers: bool,
+    pub shells: bool,
+    pub portals: bool,
+}
+
+impl Default for BackgroundFilter {
+    /// Shells were always listed; the other kinds were always hidden.
+    fn default() -> Self {
+        Self {
+            services: false,
+            helpers: false,
+            shells: true,
+            portals: false,
+        }
+    }
+}
+
+/// Time spent in a detected game, kept across sessions.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GamePlaytime {
+    pub app_id: String,
+    /// The game's name when it last ran, shown while it is not running.
+    #[serde(default)]
+    pub name: String,
+    pub total_secs: u64,
+    /// Local day, counted from the Unix epoch, that `today_secs` was played on.
+    pub day: i64,
+    pub today_secs: u64,
+}
+
+/// A named layout of the Apps page, restored from the View menu.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ViewProfile {
+    pub name: String,
+    pub view_mode: AppsViewMode,
+    pub group_by: GroupBy,
+    pub sort_column: SortColumn,
+    pub sort_descending: bool,
+    pub category_filter: Vec<String>,
+    pub custom_columns: Vec<CustomColumn>,
+}
+
+/// Environment variables and extra arguments an app is restarted with.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LaunchOverride {
+    pub app_id: String,
+    /// `KEY=value` pairs separated by spaces, e.g. `MANGOHUD=1 PROTON_LOG=1`.
+    pub env: String,
+    /// Arguments added after the app's own, separated by spaces.
+    pub args: String,
+}
+
+/// A shell command run when an app whose name matches a case-insensitive regex starts or
+/// crosses a threshold.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AutomationHook {
+    pub pattern: String,
+    pub trigger: HookTrigger,
+    /// Run with `sh -c`, with `{app_id}`, `{pid}`, and `{name}` replaced by the app's.
+    pub command: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HookTrigger {
+    #[default]
+    Start,
+    /// CPU usage rising to this percentage or above.
+    CpuAbove(u8),
+    /// Share of the RAM, in percent, rising to this or above.
+    RamAbove(u8),
+}
+
+/// Tints apps whose name, or command line, matches a case-insensitive regex.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HighlightRule {
+    pub pattern: String,
+    pub match_command_line: bool,
+    pub color: HighlightColor,
+    /// Shown as a badge next to the app name when not empty.
+    pub badge: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HighlightColor {
+    #[default]
+    Purple,
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+}
+
+impl HighlightColor {
+    pub const ALL: [HighlightColor; 6] = [
+        HighlightColor::Purple,
+        HighlightColor::Red,
+        HighlightColor::Orange,
+        HighlightColor::Yellow,
+        HighlightColor::Green,
+        HighlightColor::Blue,
+    ];
+}
+
+/// What an app's CPU percentage is relative to.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CpuPercentMode {
+    /// Share of all cores together, so a busy app tops out at 100%.
+    #[default]
+    Machine,
+    /// Share of a single core, like htop, so a busy app can exceed 100%.
+    Core,
+}
+
+impl CpuPercentMode {
+    pub const ALL: [CpuPercentMode; 2] = [CpuPercentMode::Machine, CpuPercentMode::Core];
+}
+
+/// What double-clicking an app row or tile does, on top of selecting it.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DoubleClickAction {
+    #[default]
+    Details,
+    FocusWindow,
+    OpenFileLocation,
+    Nothing,
+}
+
+impl DoubleClickAction {
+    pub const ALL: [DoubleClickAction; 4] = [
+        DoubleClickAction::Details,
+        DoubleClickAction::FocusWindow,
+        DoubleClickAction::OpenFileLocation,
+        DoubleClickAction::Nothing,
+    ];
+}
+
+/// Where the process table is read from.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ProcessBackend {
+    #[default]
+    Sysinfo,
+    /// Reads only the `/proc` files the table needs, which is lighter on busy systems.
+    Procfs,
+}
+
+impl ProcessBackend {
+    pub const ALL: [ProcessBackend; 2] = [ProcessBackend::Sysinfo, ProcessBackend::Procfs];
+}
+
+/// Moving average applied to app CPU usage, so values and sorting settle down.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CpuSmoothing {
+    #[default]
+    Off,
+    Light,
+    Strong,
+}
+
+impl CpuSmoothing {
+    pub const ALL: [CpuSmoothing; 3] =
+        [CpuSmoothing::Off, CpuSmoothing::Light, CpuSmoothing::Strong];
+
+    /// Number of refreshes the average roughly spans.
+    pub fn samples(self) -> u8 {
+        match self {
+            CpuSmoothing::Off => 1,
+            CpuSmoothing::Light => 3,
+            CpuSmoothing::Strong => 5,
+        }
+    }
+}
+
+/// How far an app's CPU or memory share must pull ahead of its neighbour before the rows
+/// swap places in a table sorted by that column.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SortDamping {
+    #[default]
+    Off,
+    Light,
+    Strong,
+}
+
+impl SortDamping {
+    pub const ALL: [SortDamping; 3] = [SortDamping::Off, SortDamping::Light, SortDamping::Strong];
+
+    /// Difference in percentage points of the machine needed to reorder two rows.
+    pub fn threshold(self) -> f32 {
+        match self {
+            SortDamping::Off => 0.0,
+            SortDamping::Light => 1.0,
+            SortDamping::Strong => 5.0,
+        }
+    }
+}
+
+/// How much time the performance and detail graphs cover.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HistoryRetention {
+    #[default]
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+    OneHour,
+}
+
+impl HistoryRetention {
+    pub const ALL: [HistoryRetention; 4] = [
+        HistoryRetention::OneMinute,
+        HistoryRetention::FiveMinutes,
+        HistoryRetention::FifteenMinutes,
+        HistoryRetention::OneHour,
+    ];
+
+    pub fn seconds(self) -> u64 {
+        match self {
+            HistoryRetention::OneMinute => 60,
+            HistoryRetention::FiveMinutes => 5 * 60,
+            HistoryRetention::FifteenMinutes => 15 * 60,
+            HistoryRetention::OneHour => 60 * 60,
+        }
+    }
+}
+
+/// How often a snapshot of the process table is written to disk.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SnapshotInterval {
+    #[default]
+    Off,
+    FiveMinutes,
+    FifteenMinutes,
+    OneHour,
+}
+
+impl SnapshotInterval {
+    pub const ALL: [SnapshotInterval; 4] = [
+        SnapshotInterval::Off,
+        SnapshotInterval::FiveMinutes,
+        SnapshotInterval::FifteenMinutes,
+        SnapshotInterval::OneHour,
+    ];
+
+    pub fn seconds(self) -> Option<u64> {
+        match self {
+            SnapshotInterval::Off => None,
+            SnapshotInterval::FiveMinutes => Some(5 * 60),
+            SnapshotInterval::FifteenMinutes => Some(15 * 60),
+            SnapshotInterval::OneHour => Some(60 * 60),
+        }
+    }
+}
+
+/// How fast an app's memory has to grow before a memory growth alert is raised.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MemoryGrowthAlert {
+    #[default]
+    Off,
+    HundredMegabytesPerHour,
+    FiveHundredMegabytesPerHour,
+    GigabytePerHour,
+}
+
+impl MemoryGrowthAlert {
+    pub const ALL: [MemoryGrowthAlert; 4] = [
+        MemoryGrowthAlert::Off,
+        MemoryGrowthAlert::HundredMegabytesPerHour,
+        MemoryGrowthAlert::FiveHundredMegabytesPerHour,
+        MemoryGrowthAlert::GigabytePerHour,
+    ];
+
+    pub fn bytes_per_hour(self) -> Option<u64> {
+        const MIB: u64 = 1024 * 1024;
+        match self {
+            MemoryGrowthAlert::Off => None,
+            MemoryGrowthAlert::HundredMegabytesPerHour => Some(100 * MIB),
+            MemoryGrowthAlert::FiveHundredMegabytesPerHour => Some(500 * MIB),
+            MemoryGrowthAlert::GigabytePerHour => Some(1024 * MIB),
+        }
+    }
+}
+
+/// How tightly table rows and tiles are packed.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Density {
+    Compact,
+    #[default]
+    Comfortable,
+    Spacious,
+}
+
+impl Density {
+    pub const ALL: [Density; 3] = [Density::Compact, Density::Comfortable, Density::Spacious];
+
+    pub fn cell_padding(self) -> u16 {
+        match self {
+            Density::Compact => 6,
+            Density::Comfortable => 10,
+            Density::Spacious => 14,
+        }
+    }
+
+    pub fn row_icon_size(self) -> u16 {
+        match self {
+            Density::Compact => 16,
+            Density::Comfortable => 18,
+            Density::Spacious => 24,
+        }
+    }
+
+    pub fn tile_icon_size(self) -> u16 {
+        match self {
+            Density::Compact => 40,
+            Density::Comfortable => 56,
+            Density::Spacious => 72,
+        }
+    }
+}
+
+/// The page shown at startup.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Page {
+    #[default]
+    Processes,
+    Autostart,
+    Games,
+    Resources,
+    Cgroups,
+    Events,
+    Info,
+    Settings,
+}
+
+impl Page {
+    pub const ALL: [Page; 8] = [
+        Page::Processes,
+        Page::Autostart,
+        Page::Games,
+        Page::Resources,
+        Page::Cgroups,
+        Page::Events,
+        Page::Info,
+        Page::Settings,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SortColumn {
+    Name,
+    Cpu,
+    Pid,
+    Ram,
+    Swap,
+    Threads,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AppsViewMode {
+    List,
+    Tile,
+}
+
+/// What the rows of the Apps table stand for.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum GroupBy {
+    App,
+    User,
+    Category,
+}
+
+impl GroupBy {
+    pub const ALL: [GroupBy; 3] = [GroupBy::App, GroupBy::User, GroupBy::Category];
 }
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Upgrades configs stored by older versions of [`Config`](super::Config).
+//!
+//! cosmic-config keeps every version in its own `v<N>` directory with one RON file per key,
+//! and it already replaces key files atomically when settings change. A migration copies
+//! the newest older directory forward, renaming or dropping keys on the way, into a staging
+//! directory that is only renamed into place once every key is written.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Key changes made when `#[version]` on `Config` was bumped to `to`.
+struct Migration {
+    to: u64,
+    renamed: &'static [(&'static str, &'static str)],
+    removed: &'static [&'static str],
+}
+
+/// Add an entry here whenever `#[version]` on `Config` is bumped. Keys that were only
+/// added need no entry, since missing keys fall back to their defaults.
+const MIGRATIONS: &[Migration] = &[];
+
+fn config_root(app_id: &str) -> PathBuf {
+    let config_home = env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|_| PathBuf::from(".config"));
+    config_home.join("cosmic").join(app_id)
+}
+
+/// The newest stored version older than `version`, if any.
+fn previous_version(root: &Path, version: u64) -> Option<u64> {
+    fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.strip_prefix('v')?.parse().ok())
+        .filter(|stored| *stored < version)
+        .max()
+}
+
+fn apply_migrations(
+    mut keys: HashMap<String, String>,
+    from: u64,
+    to: u64,
+    migrations: &[Migration],
+) -> HashMap<String, String> {
+    for migration in migrations
+        .iter()
+        .filter(|migration| migration.to > from && migration.to <= to)
+    {
+        for (old, new) in migration.renamed {
+            if let Some(value) = keys.remove(*old) {
+                keys.insert((*new).to_string(), value);
+            }
+        }
+        for key in migration.removed {
+            keys.remove(*key);
+        }
+    }
+    keys
+}
+
+fn read_keys(dir: &Path) -> io::Result<HashMap<String, String>> {
+    let mut keys = HashMap::new();
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let Some(key) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if path.is_file() {
+            keys.insert(key.to_string(), fs::read_to_string(&path)?);
+        }
+    }
+    Ok(keys)
+}
+
+fn write_version(root: &Path, version: u64, keys: &HashMap<String, String>) -> io::Result<()> {
+    let staging = root.join(format!("v{version}.migrating"));
+    // A staging directory left by an interrupted migration is incomplete, so start over.
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    fs::create_dir_all(&staging)?;
+    for (key, value) in keys {
+        let mut file = fs::File::create(staging.join(key))?;
+        file.write_all(value.as_bytes())?;
+        file.sync_all()?;
+    }
+    // The keys and their directory entries are on disk before the rename makes the version
+    // visible, and the rename is on disk before the app goes on to use it.
+    fs::File::open(&staging)?.sync_all()?;
+    fs::rename(staging, root.join(format!("v{version}")))?;
+    fs::File::open(root)?.sync_all()
+}
+
+/// Copies the newest older config forward to `version`, unless that version already exists.
+pub fn migrate(app_id: &str, version: u64) {
+    let root = config_root(app_id);
+    if root.join(format!("v{version}")).exists() {
+        return;
+    }
+    let Some(from) = previous_version(&root, version) else {
+        return;
+    };
+
+    let result = read_keys(&root.join(format!("v{from}")))
+        .map(|keys| apply_migrations(keys, from, version, MIGRATIONS))
+        .and_then(|keys| write_version(&root, version, &keys));
+    if let Err(err) = result {
+        eprintln!("failed to migrate config from v{from} to v{version}: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Migration, apply_migrations};
+    use std::collections::HashMap;
+
+    #[test]
+    fn applies_only_migrations_in_range() {
+        let migrations = [
+            Migration {
+                to: 2,
+                renamed: &[("show_tray", "show_tray_icon")],
+                removed: &["demo"],
+            },
+            Migration {
+                to: 3,
+                renamed: &[("show_tray_icon", "tray_icon")],
+                removed: &[],
+            },
+        ];
+        let keys = HashMap::from([
+            ("show_tray".to_string(), "true".to_string()),
+            ("demo".to_string(), "\"\"".to_string()),
+            ("density".to_string(), "Compact".to_string()),
+        ]);
+
+        let migrated = apply_migrations(keys, 1, 2, &migrations);
+        assert_eq!(migrated.len(), 2);
+        assert_eq!(migrated["show_tray_icon"], "true");
+        assert_eq!(migrated["density"], "Compact");
+    }
+}
@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The task monitor as a library, so the benchmarks in `benches/` can run the matching done
+//! on every refresh, which the `bench` feature exposes. `main.rs` parses the command line and
+//! starts it.
+
+mod app;
+mod config;
+pub mod i18n;
+
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub use app::benches;
+pub use app::{Flags, record_fixture, run, run_collector};
@@ -1,8 +1,6 @@
 // SPDX-License-Identifier: MPL-2.0
 
-mod app;
-mod config;
-mod i18n;
+use cosmic_task_monitor::{Flags, i18n, record_fixture, run, run_collector};
 
 fn main() -> cosmic::iced::Result {
     // Get the system's preferred languages.
@@ -11,6 +9,29 @@ fn main() -> cosmic::iced::Result {
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
+    // Run only the background collector when requested, without opening a window.
+    if std::env::args().skip(1).any(|arg| arg == "--collector") {
+        if let Err(err) = run_collector() {
+            eprintln!("collector failed: {err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Save one scan of the system as a test fixture, e.g. `--record-fixture scan.json`.
+    let mut args = std::env::args().skip_while(|arg| arg != "--record-fixture");
+    if args.next().is_some() {
+        let Some(path) = args.next() else {
+            eprintln!("--record-fixture needs the path of the file to write");
+            std::process::exit(2);
+        };
+        if let Err(err) = record_fixture(std::path::Path::new(&path)) {
+            eprintln!("failed to record fixture: {err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Settings for configuring the application window and iced runtime.
     let settings = cosmic::app::Settings::default().size_limits(
         cosmic::iced::Limits::NONE
@@ -18,6 +39,7 @@ fn main() -> cosmic::iced::Result {
             .min_height(180.0),
     );
 
-    // Starts the application's event loop with `()` as the application's flags.
-    cosmic::app::run::<app::AppModel>(settings, ())
+    // A deep link such as `--select-app firefox` is handed to a running instance if there is one.
+    let flags = Flags::from_args(std::env::args().skip(1));
+    run(settings, flags)
 }